@@ -200,6 +200,12 @@ pub const FIX_DELIMITER_STR: &str = "\x01";
 /// Default FIX heartbeat interval in seconds
 pub const FIX_HEARTBEAT_INTERVAL: u32 = 30;
 
+/// Default largest `BodyLength(9)` accepted by the FIX stream decoder, in bytes
+pub const FIX_MAX_BODY_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Largest `BeginString(8)` and `BodyLength(9)` prefix buffered by the FIX stream decoder
+pub const FIX_MAX_HEADER_LENGTH: usize = 64;
+
 // =============================================================================
 // ERROR HANDLING
 // =============================================================================
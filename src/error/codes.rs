@@ -453,6 +453,25 @@ impl DeribitErrorCode {
         )
     }
 
+    /// Check if this is a transient error that may succeed when retried
+    ///
+    /// Covers rate limits, overloaded or unavailable services and explicit
+    /// `retry` answers. Rejections of the request itself are not transient.
+    pub fn is_transient(&self) -> bool {
+        self.is_rate_limit_error()
+            || matches!(
+                self,
+                Self::Retry
+                    | Self::SettlementInProgress
+                    | Self::MatchingEngineQueueFull
+                    | Self::SystemMaintenance
+                    | Self::InternalServerError
+                    | Self::TemporarilyUnavailable
+                    | Self::Unavailable
+                    | Self::MovePositionsOverLimit
+            )
+    }
+
     /// Check if this is a validation error
     pub fn is_validation_error(&self) -> bool {
         matches!(
@@ -817,6 +836,17 @@ mod tests {
         assert!(!validation_error.is_rate_limit_error());
     }
 
+    #[test]
+    fn test_transient_errors() {
+        assert!(DeribitErrorCode::TooManyRequests.is_transient());
+        assert!(DeribitErrorCode::Retry.is_transient());
+        assert!(DeribitErrorCode::TemporarilyUnavailable.is_transient());
+        assert!(DeribitErrorCode::MovePositionsOverLimit.is_transient());
+        assert!(!DeribitErrorCode::NotEnoughFunds.is_transient());
+        assert!(!DeribitErrorCode::Unauthorized.is_transient());
+        assert!(!DeribitErrorCode::Unknown(99999).is_transient());
+    }
+
     #[test]
     fn test_serde_serialization() {
         let error = DeribitErrorCode::AuthorizationRequired;
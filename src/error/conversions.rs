@@ -5,15 +5,23 @@
 ******************************************************************************/
 
 use crate::error::codes::DeribitErrorCode;
-use crate::error::types::DeribitError;
+use crate::error::types::{ApiError, DeribitError};
+use crate::fix::{FixError, LogonError};
+use crate::model::response::JsonRpcError;
+use crate::model::subscription::NotificationError;
 
 // Conversion from DeribitErrorCode to DeribitError
 impl From<DeribitErrorCode> for DeribitError {
     fn from(error_code: DeribitErrorCode) -> Self {
-        DeribitError::Api {
-            code: error_code.code(),
-            message: error_code.message().to_string(),
-        }
+        ApiError::from(error_code).into()
+    }
+}
+
+// Conversion from DeribitErrorCode to ApiError, using the documented message
+impl From<DeribitErrorCode> for ApiError {
+    fn from(error_code: DeribitErrorCode) -> Self {
+        let message = error_code.message();
+        ApiError::new(error_code, message)
     }
 }
 
@@ -53,6 +61,47 @@ impl From<serde_json::Error> for DeribitError {
     }
 }
 
+// Conversion from a JSON-RPC error object to DeribitError
+impl From<JsonRpcError> for DeribitError {
+    fn from(error: JsonRpcError) -> Self {
+        ApiError::from(error).into()
+    }
+}
+
+// Conversion from a JSON-RPC error object to ApiError, keeping its data
+impl From<JsonRpcError> for ApiError {
+    fn from(error: JsonRpcError) -> Self {
+        ApiError {
+            code: error.code.into(),
+            message: error.message,
+            data: error.data,
+            method: None,
+            request_id: None,
+        }
+    }
+}
+
+// Conversion from a subscription notification decoding error to DeribitError
+impl From<NotificationError> for DeribitError {
+    fn from(error: NotificationError) -> Self {
+        DeribitError::Notification(error)
+    }
+}
+
+// Conversion from a FIX codec error to DeribitError
+impl From<FixError> for DeribitError {
+    fn from(error: FixError) -> Self {
+        DeribitError::Fix(error)
+    }
+}
+
+// Conversion from a FIX Logon authentication error to DeribitError
+impl From<LogonError> for DeribitError {
+    fn from(error: LogonError) -> Self {
+        DeribitError::Authentication(error.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,14 +112,28 @@ mod tests {
         let deribit_error: DeribitError = error_code.into();
 
         match deribit_error {
-            DeribitError::Api { code, message } => {
-                assert_eq!(code, 10000);
-                assert_eq!(message, "authorization_required");
+            DeribitError::Api(error) => {
+                assert_eq!(error.code(), 10000);
+                assert_eq!(error.message, "authorization_required");
             }
             _ => panic!("Expected Api error"),
         }
     }
 
+    #[test]
+    fn test_json_rpc_error_conversion_keeps_data() {
+        let error = JsonRpcError {
+            code: 13668,
+            message: "security_key_authorization_error".to_string(),
+            data: Some(serde_json::json!({"reason": "tfa_code_not_matched"})),
+        };
+        let deribit_error: DeribitError = error.into();
+        let api = deribit_error.api_error().expect("Expected Api error");
+        assert_eq!(api.code, DeribitErrorCode::from(13668));
+        assert_eq!(api.reason(), Some("tfa_code_not_matched"));
+        assert_eq!(api.method, None);
+    }
+
     #[test]
     fn test_http_status_to_error_code_conversion() {
         // Test HTTP status to DeribitErrorCode conversion
@@ -156,6 +219,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fix_error_conversion_keeps_source() {
+        use std::error::Error;
+
+        let deribit_error: DeribitError = FixError::MissingField(35).into();
+        assert!(matches!(
+            deribit_error,
+            DeribitError::Fix(FixError::MissingField(35))
+        ));
+        assert_eq!(
+            deribit_error.to_string(),
+            "FIX error: missing required field MsgType(35)"
+        );
+        let source = deribit_error.source().expect("Expected a source");
+        assert_eq!(
+            source.downcast_ref::<FixError>(),
+            Some(&FixError::MissingField(35))
+        );
+    }
+
+    #[test]
+    fn test_notification_error_conversion_keeps_source_chain() {
+        use crate::model::channel::ChannelError;
+        use std::error::Error;
+
+        let channel_error = ChannelError::UnknownChannel("foo.bar".to_string());
+        let deribit_error: DeribitError = NotificationError::Channel(channel_error.clone()).into();
+        assert!(matches!(deribit_error, DeribitError::Notification(_)));
+        let source = deribit_error.source().expect("Expected a source");
+        assert!(source.downcast_ref::<NotificationError>().is_some());
+        let root = source.source().expect("Expected the channel error");
+        assert_eq!(root.downcast_ref::<ChannelError>(), Some(&channel_error));
+    }
+
+    #[test]
+    fn test_logon_error_conversion() {
+        let deribit_error: DeribitError = LogonError::InvalidPassword.into();
+        match deribit_error {
+            DeribitError::Authentication(message) => assert_eq!(message, "invalid password"),
+            _ => panic!("Expected Authentication error"),
+        }
+    }
+
     #[test]
     fn test_comprehensive_error_code_conversions() {
         // Test various error codes to ensure they convert properly
@@ -172,9 +278,9 @@ mod tests {
         for error_code in error_codes {
             let deribit_error: DeribitError = error_code.clone().into();
             match deribit_error {
-                DeribitError::Api { code, message } => {
-                    assert_eq!(code, error_code.code());
-                    assert_eq!(message, error_code.message());
+                DeribitError::Api(error) => {
+                    assert_eq!(error.code, error_code);
+                    assert_eq!(error.message, error_code.message());
                 }
                 _ => panic!("Expected Api error for {:?}", error_code),
             }
@@ -189,9 +295,9 @@ mod tests {
         let deribit_error: DeribitError = error_code.into();
 
         match deribit_error {
-            DeribitError::Api { code, message } => {
-                assert_eq!(code, 13009); // Unauthorized code
-                assert_eq!(message, "unauthorized");
+            DeribitError::Api(error) => {
+                assert_eq!(error.code(), 13009); // Unauthorized code
+                assert_eq!(error.message, "unauthorized");
             }
             _ => panic!("Expected Api error"),
         }
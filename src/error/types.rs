@@ -1,6 +1,121 @@
 //! Common error types for Deribit clients
 
+use crate::error::codes::DeribitErrorCode;
+use crate::fix::FixError;
+use crate::model::subscription::NotificationError;
+use serde_json::Value;
 use std::fmt;
+use std::time::Duration;
+
+/// Error returned by the Deribit API for a JSON-RPC request
+///
+/// Keeps the typed error code, the optional `data` member of the JSON-RPC
+/// error object (e.g., `param` and `reason` for invalid parameters, or
+/// `wait` for over-limit errors) and, when known, the request it answered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiError {
+    /// Error code returned by the API
+    pub code: DeribitErrorCode,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional error details sent by the server
+    pub data: Option<Value>,
+    /// Method of the request that failed
+    pub method: Option<String>,
+    /// ID of the request that failed
+    pub request_id: Option<u64>,
+}
+
+impl ApiError {
+    /// Create an API error without request context
+    pub fn new(code: impl Into<DeribitErrorCode>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            data: None,
+            method: None,
+            request_id: None,
+        }
+    }
+
+    /// Attach the `data` member of the JSON-RPC error object
+    #[must_use]
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Attach the method of the request that failed
+    #[must_use]
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    /// Attach the ID of the request that failed
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: u64) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
+
+    /// Numeric error code
+    pub fn code(&self) -> i32 {
+        self.code.code()
+    }
+
+    /// Parameter the server rejected (`data.param`)
+    pub fn param(&self) -> Option<&str> {
+        self.data_str("param")
+    }
+
+    /// Reason given by the server (`data.reason`)
+    pub fn reason(&self) -> Option<&str> {
+        self.data_str("reason")
+    }
+
+    /// Time the server asked the client to wait before retrying (`data.wait`)
+    pub fn retry_after(&self) -> Option<Duration> {
+        let wait = self.data.as_ref()?.get("wait")?.as_f64()?;
+        Duration::try_from_secs_f64(wait).ok()
+    }
+
+    /// Whether the same request may succeed when sent again
+    ///
+    /// True for transient codes (see [`DeribitErrorCode::is_transient`]);
+    /// whether a retry is safe for a non-idempotent method is up to the
+    /// caller.
+    pub fn is_retryable(&self) -> bool {
+        self.code.is_transient()
+    }
+
+    /// Whether the session must authenticate again before retrying
+    pub fn requires_reauth(&self) -> bool {
+        self.code.is_authorization_error()
+    }
+
+    fn data_str(&self, key: &str) -> Option<&str> {
+        self.data.as_ref()?.get(key)?.as_str()
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "API error {}: {}", self.code(), self.message)?;
+        match (&self.method, self.request_id) {
+            (Some(method), Some(id)) => write!(f, " ({method} #{id})"),
+            (Some(method), None) => write!(f, " ({method})"),
+            (None, Some(id)) => write!(f, " (#{id})"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.code)
+    }
+}
 
 /// Common error type for all Deribit clients
 #[derive(Debug, Clone)]
@@ -9,15 +124,14 @@ pub enum DeribitError {
     Connection(String),
     /// Authentication error
     Authentication(String),
-    /// API error with code and message
-    Api {
-        /// Error code returned by the API
-        code: i32,
-        /// Human-readable error message
-        message: String,
-    },
+    /// API error with code, JSON-RPC data and request context
+    Api(Box<ApiError>),
     /// Serialization/deserialization error
     Serialization(String),
+    /// FIX codec or message mapping error
+    Fix(FixError),
+    /// Subscription notification decoding error
+    Notification(NotificationError),
     /// Network timeout
     Timeout,
     /// Invalid configuration
@@ -26,13 +140,53 @@ pub enum DeribitError {
     Other(String),
 }
 
+impl DeribitError {
+    /// API error details, if the server answered with an error
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            DeribitError::Api(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Typed API error code, if the server answered with an error
+    pub fn code(&self) -> Option<&DeribitErrorCode> {
+        self.api_error().map(|error| &error.code)
+    }
+
+    /// Whether the request may succeed when sent again
+    ///
+    /// True for connection failures, timeouts and transient API errors.
+    /// A timed-out or disconnected request may still have reached the
+    /// server, so non-idempotent methods need a duplicate check first.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DeribitError::Connection(_) | DeribitError::Timeout => true,
+            DeribitError::Api(error) => error.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Time the server asked the client to wait before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.api_error().and_then(ApiError::retry_after)
+    }
+
+    /// Whether the session must authenticate again before retrying
+    pub fn requires_reauth(&self) -> bool {
+        self.api_error().is_some_and(ApiError::requires_reauth)
+    }
+}
+
 impl fmt::Display for DeribitError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DeribitError::Connection(msg) => write!(f, "Connection error: {msg}"),
             DeribitError::Authentication(msg) => write!(f, "Authentication error: {msg}"),
-            DeribitError::Api { code, message } => write!(f, "API error {code}: {message}"),
+            DeribitError::Api(error) => write!(f, "{error}"),
             DeribitError::Serialization(msg) => write!(f, "Serialization error: {msg}"),
+            DeribitError::Fix(error) => write!(f, "FIX error: {error}"),
+            DeribitError::Notification(error) => write!(f, "Notification error: {error}"),
             DeribitError::Timeout => write!(f, "Request timeout"),
             DeribitError::InvalidConfig(msg) => write!(f, "Invalid configuration: {msg}"),
             DeribitError::Other(msg) => write!(f, "Error: {msg}"),
@@ -40,7 +194,22 @@ impl fmt::Display for DeribitError {
     }
 }
 
-impl std::error::Error for DeribitError {}
+impl std::error::Error for DeribitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeribitError::Api(error) => Some(error.as_ref()),
+            DeribitError::Fix(error) => Some(error),
+            DeribitError::Notification(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ApiError> for DeribitError {
+    fn from(error: ApiError) -> Self {
+        DeribitError::Api(Box::new(error))
+    }
+}
 
 /// Result type alias for Deribit operations
 pub type DeribitResult<T> = Result<T, DeribitError>;
@@ -66,10 +235,7 @@ mod tests {
 
     #[test]
     fn test_deribit_error_api() {
-        let error = DeribitError::Api {
-            code: 10009,
-            message: "Invalid request".to_string(),
-        };
+        let error = DeribitError::from(ApiError::new(10009, "Invalid request"));
         assert_eq!(error.to_string(), "API error 10009: Invalid request");
     }
 
@@ -107,10 +273,7 @@ mod tests {
 
     #[test]
     fn test_deribit_error_clone() {
-        let error = DeribitError::Api {
-            code: 123,
-            message: "test".to_string(),
-        };
+        let error = DeribitError::from(ApiError::new(123, "test"));
         let cloned = error.clone();
         assert_eq!(error.to_string(), cloned.to_string());
     }
@@ -132,4 +295,55 @@ mod tests {
         let error = DeribitError::Connection("test".to_string());
         let _: &dyn std::error::Error = &error;
     }
+
+    #[test]
+    fn test_api_error_context() {
+        let error = ApiError::new(-32602, "Invalid params")
+            .with_data(serde_json::json!({"param": "amount", "reason": "must be positive"}))
+            .with_method("private/buy")
+            .with_request_id(7);
+        assert_eq!(error.code(), -32602);
+        assert_eq!(error.param(), Some("amount"));
+        assert_eq!(error.reason(), Some("must be positive"));
+        assert_eq!(error.retry_after(), None);
+        assert_eq!(
+            error.to_string(),
+            "API error -32602: Invalid params (private/buy #7)"
+        );
+    }
+
+    #[test]
+    fn test_retry_hints() {
+        let over_limit: DeribitError = ApiError::new(13780, "move_positions_over_limit")
+            .with_data(serde_json::json!({"wait": 2.5}))
+            .into();
+        assert!(over_limit.is_retryable());
+        assert_eq!(over_limit.retry_after(), Some(Duration::from_millis(2_500)));
+        assert!(!over_limit.requires_reauth());
+
+        let expired: DeribitError = ApiError::new(13009, "unauthorized").into();
+        assert!(expired.requires_reauth());
+        assert!(!expired.is_retryable());
+
+        let rejected: DeribitError = ApiError::new(10009, "not_enough_funds").into();
+        assert!(!rejected.is_retryable());
+        assert_eq!(rejected.code(), Some(&DeribitErrorCode::NotEnoughFunds));
+
+        assert!(DeribitError::Timeout.is_retryable());
+        assert!(DeribitError::Connection("reset".to_string()).is_retryable());
+        assert!(!DeribitError::InvalidConfig("bad".to_string()).is_retryable());
+        assert_eq!(DeribitError::Timeout.code(), None);
+    }
+
+    #[test]
+    fn test_error_source_chain() {
+        use std::error::Error;
+
+        let error: DeribitError = ApiError::new(10028, "too_many_requests").into();
+        let api = error.source().unwrap();
+        assert_eq!(api.to_string(), "API error 10028: too_many_requests");
+        let code = api.source().unwrap();
+        assert!(code.downcast_ref::<DeribitErrorCode>().is_some());
+        assert!(DeribitError::Timeout.source().is_none());
+    }
 }
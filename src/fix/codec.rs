@@ -0,0 +1,952 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Tag-value encoding of FIX messages
+//!
+//! [`FixFrame`] validates a single SOH-delimited frame without copying it
+//! and iterates over its fields. [`FixMessage::decode`] and
+//! [`FixMessage::encode`] convert between frames and owned messages, and
+//! [`FixDecoder`] splits a byte stream into messages using `BodyLength(9)`.
+
+use crate::constants::{FIX_MAX_BODY_LENGTH, FIX_MAX_HEADER_LENGTH};
+use crate::fix::error::FixError;
+use crate::fix::message::{FieldMap, FixField, FixMessage};
+use crate::fix::tags::{
+    BEGIN_STRING, BODY_LENGTH, CHECK_SUM, GroupSpec, MSG_TYPE, MsgType, data_length_tag,
+    is_data_length_tag, is_header_tag,
+};
+use std::iter::Peekable;
+
+const SOH: u8 = b'\x01';
+
+/// Length of the trailing `10=NNN<SOH>` field
+const CHECKSUM_FIELD_LEN: usize = 7;
+
+/// Compute the FIX checksum of a byte slice
+#[must_use]
+pub fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// A field borrowed from a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawField<'a> {
+    /// Field tag
+    pub tag: u32,
+    /// Raw value bytes
+    pub value: &'a [u8],
+    /// Offset of the field from the start of the frame
+    pub offset: usize,
+}
+
+impl<'a> RawField<'a> {
+    /// Get the value as UTF-8 text
+    pub fn value_str(&self) -> Result<&'a str, FixError> {
+        std::str::from_utf8(self.value).map_err(|_| FixError::InvalidValue {
+            tag: self.tag,
+            value: String::from_utf8_lossy(self.value).into_owned(),
+        })
+    }
+}
+
+/// Read the field starting at `offset`
+///
+/// Returns `Ok(None)` when `buf` ends before the field is terminated.
+/// `data_len` is the length announced for a data field, if the preceding
+/// field was a data length field.
+fn read_field(
+    buf: &[u8],
+    offset: usize,
+    data_len: Option<(u32, usize)>,
+) -> Result<Option<(RawField<'_>, usize)>, FixError> {
+    let rest = &buf[offset..];
+    let Some(equals) = rest.iter().position(|byte| *byte == b'=' || *byte == SOH) else {
+        return Ok(None);
+    };
+    if rest[equals] == SOH {
+        return Err(FixError::MissingEquals { offset });
+    }
+    let tag = parse_tag(&rest[..equals]).ok_or(FixError::InvalidTag { offset })?;
+    let value_start = equals + 1;
+    let fixed_len = data_len.filter(|(length_tag, _)| data_length_tag(tag) == Some(*length_tag));
+    let value_end = match fixed_len {
+        Some((length_tag, len)) => {
+            let end = value_start
+                .checked_add(len)
+                .ok_or_else(|| FixError::InvalidValue {
+                    tag: length_tag,
+                    value: len.to_string(),
+                })?;
+            match rest.get(end) {
+                None => return Ok(None),
+                Some(&SOH) => end,
+                Some(_) => return Err(FixError::MissingDelimiter { offset }),
+            }
+        }
+        None => match rest[value_start..].iter().position(|byte| *byte == SOH) {
+            Some(len) => value_start + len,
+            None => return Ok(None),
+        },
+    };
+    if value_end == value_start {
+        return Err(FixError::EmptyValue { tag });
+    }
+    let field = RawField {
+        tag,
+        value: &rest[value_start..value_end],
+        offset,
+    };
+    Ok(Some((field, offset + value_end + 1)))
+}
+
+fn parse_tag(bytes: &[u8]) -> Option<u32> {
+    if bytes.is_empty() || bytes[0] == b'0' || !bytes.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()?.parse().ok()
+}
+
+/// Read a field that must be present and have the given tag
+fn expect_field(
+    buf: &[u8],
+    offset: usize,
+    expected: u32,
+) -> Result<Option<(RawField<'_>, usize)>, FixError> {
+    match read_field(buf, offset, None)? {
+        Some((field, _)) if field.tag != expected => Err(FixError::UnexpectedField {
+            expected,
+            found: field.tag,
+            offset,
+        }),
+        other => Ok(other),
+    }
+}
+
+/// Get the total length of the frame at the start of `buf`
+///
+/// Only `BeginString(8)` and `BodyLength(9)` are read. Returns `Ok(None)`
+/// while those fields are incomplete.
+pub fn frame_length(buf: &[u8]) -> Result<Option<usize>, FixError> {
+    match read_header(buf)? {
+        Some((body_start, body_length)) => {
+            frame_end(body_start, body_length).map(|(_, total)| Some(total))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Read `BeginString(8)` and `BodyLength(9)`, returning the body start and length
+fn read_header(buf: &[u8]) -> Result<Option<(usize, usize)>, FixError> {
+    let Some((_, next)) = expect_field(buf, 0, BEGIN_STRING)? else {
+        return Ok(None);
+    };
+    let Some((length, body_start)) = expect_field(buf, next, BODY_LENGTH)? else {
+        return Ok(None);
+    };
+    Ok(Some((body_start, parse_body_length(&length)?)))
+}
+
+/// Get the end of the body and of the frame, rejecting lengths that overflow
+fn frame_end(body_start: usize, body_length: usize) -> Result<(usize, usize), FixError> {
+    body_start
+        .checked_add(body_length)
+        .and_then(|body_end| Some((body_end, body_end.checked_add(CHECKSUM_FIELD_LEN)?)))
+        .ok_or_else(|| FixError::InvalidValue {
+            tag: BODY_LENGTH,
+            value: body_length.to_string(),
+        })
+}
+
+fn parse_body_length(field: &RawField<'_>) -> Result<usize, FixError> {
+    field
+        .value_str()?
+        .parse()
+        .ok()
+        .filter(|_| field.value.iter().all(u8::is_ascii_digit))
+        .ok_or_else(|| FixError::InvalidValue {
+            tag: BODY_LENGTH,
+            value: String::from_utf8_lossy(field.value).into_owned(),
+        })
+}
+
+/// A validated frame borrowed from an input buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixFrame<'a> {
+    bytes: &'a [u8],
+    begin_string: &'a str,
+    msg_type: &'a str,
+    body_start: usize,
+    fields_start: usize,
+    body_end: usize,
+}
+
+impl<'a> FixFrame<'a> {
+    /// Parse the frame at the start of `buf`
+    ///
+    /// Checks the order of `BeginString(8)`, `BodyLength(9)` and
+    /// `MsgType(35)`, the declared body length and the checksum. Bytes after
+    /// the frame are ignored; use [`FixFrame::len`] to find where it ends.
+    pub fn parse(buf: &'a [u8]) -> Result<Self, FixError> {
+        let (begin, next) = expect_field(buf, 0, BEGIN_STRING)?.ok_or(FixError::Incomplete)?;
+        let (length, body_start) =
+            expect_field(buf, next, BODY_LENGTH)?.ok_or(FixError::Incomplete)?;
+        let declared = parse_body_length(&length)?;
+        let (body_end, total) = frame_end(body_start, declared)?;
+
+        if buf.len() < total {
+            return match find_checksum(buf, body_start) {
+                Some(position) if buf[position..].contains(&SOH) => {
+                    Err(FixError::BodyLengthMismatch {
+                        declared,
+                        actual: Some(position - body_start),
+                    })
+                }
+                _ => Err(FixError::Incomplete),
+            };
+        }
+        if declared == 0 {
+            return Err(FixError::MissingField(MSG_TYPE));
+        }
+        if !buf[body_end..].starts_with(b"10=") || buf[body_end - 1] != SOH {
+            let actual = find_checksum(buf, body_start).map(|position| position - body_start);
+            return Err(FixError::BodyLengthMismatch { declared, actual });
+        }
+
+        let (trailer, _) = read_field(buf, body_end, None)?.ok_or(FixError::Incomplete)?;
+        if trailer.value.len() != 3 || buf[total - 1] != SOH {
+            return Err(FixError::InvalidChecksum(
+                String::from_utf8_lossy(trailer.value).into_owned(),
+            ));
+        }
+        let declared_checksum: u8 = trailer
+            .value_str()?
+            .parse()
+            .ok()
+            .filter(|_| trailer.value.iter().all(u8::is_ascii_digit))
+            .ok_or_else(|| {
+                FixError::InvalidChecksum(String::from_utf8_lossy(trailer.value).into_owned())
+            })?;
+        let computed = checksum(&buf[..body_end]);
+        if declared_checksum != computed {
+            return Err(FixError::ChecksumMismatch {
+                declared: declared_checksum,
+                computed,
+            });
+        }
+
+        let bytes = &buf[..total];
+        let (msg_type, fields_start) = expect_field(&bytes[..body_end], body_start, MSG_TYPE)?
+            .ok_or(FixError::MissingDelimiter { offset: body_start })?;
+        Ok(Self {
+            bytes,
+            begin_string: begin.value_str()?,
+            msg_type: msg_type.value_str()?,
+            body_start,
+            fields_start,
+            body_end,
+        })
+    }
+
+    /// Get the frame bytes, from `BeginString(8)` to the trailing SOH
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Get the total frame length in bytes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Always `false`; a valid frame holds at least its standard fields
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Get the value of `BeginString(8)`
+    #[must_use]
+    pub fn begin_string(&self) -> &'a str {
+        self.begin_string
+    }
+
+    /// Get the value of `MsgType(35)`
+    #[must_use]
+    pub fn msg_type(&self) -> &'a str {
+        self.msg_type
+    }
+
+    /// Get the value of `BodyLength(9)`
+    #[must_use]
+    pub fn body_length(&self) -> usize {
+        self.body_end - self.body_start
+    }
+
+    /// Iterate over the fields between `MsgType(35)` and `CheckSum(10)`
+    #[must_use]
+    pub fn fields(&self) -> FieldIter<'a> {
+        FieldIter {
+            buf: &self.bytes[..self.body_end],
+            offset: self.fields_start,
+            data_len: None,
+            failed: false,
+        }
+    }
+
+    /// Convert the frame into an owned message, building repeating groups
+    pub fn to_message(&self) -> Result<FixMessage, FixError> {
+        let msg_type = MsgType::from(self.msg_type);
+        let mut message = FixMessage::new(msg_type.clone());
+        message.begin_string = self.begin_string.to_string();
+        let mut fields = self.fields().peekable();
+        while let Some(field) = fields.next() {
+            let field = field?;
+            if is_header_tag(field.tag) {
+                message.header.push(field.tag, field.value_str()?);
+            } else if let Some(spec) = msg_type.group_spec(field.tag) {
+                let entries = read_group(&mut fields, spec, &field)?;
+                message.body.push_field(FixField::Group {
+                    count_tag: field.tag,
+                    entries,
+                });
+            } else {
+                message.body.push(field.tag, field.value_str()?);
+            }
+        }
+        Ok(message)
+    }
+}
+
+fn find_checksum(buf: &[u8], from: usize) -> Option<usize> {
+    buf[from..]
+        .windows(4)
+        .position(|window| window == b"\x0110=")
+        .map(|position| from + position + 1)
+}
+
+fn read_group<'a>(
+    fields: &mut Peekable<FieldIter<'a>>,
+    spec: &GroupSpec,
+    count: &RawField<'a>,
+) -> Result<Vec<FieldMap>, FixError> {
+    let declared: usize = count
+        .value_str()?
+        .parse()
+        .map_err(|_| FixError::InvalidValue {
+            tag: count.tag,
+            value: String::from_utf8_lossy(count.value).into_owned(),
+        })?;
+    let mut entries: Vec<FieldMap> = Vec::new();
+    loop {
+        let (tag, offset) = match fields.peek() {
+            None => break,
+            Some(Err(err)) => return Err(err.clone()),
+            Some(Ok(field)) => (field.tag, field.offset),
+        };
+        if tag == spec.delimiter {
+            entries.push(FieldMap::new());
+        } else if entries.is_empty() {
+            if declared == 0 {
+                break;
+            }
+            return Err(FixError::UnexpectedField {
+                expected: spec.delimiter,
+                found: tag,
+                offset,
+            });
+        } else if !spec.contains(tag) {
+            break;
+        }
+        let Some(Ok(field)) = fields.next() else {
+            break;
+        };
+        let Some(entry) = entries.last_mut() else {
+            break;
+        };
+        match spec.nested_spec(tag) {
+            Some(nested) => {
+                let nested_entries = read_group(fields, nested, &field)?;
+                entry.push_field(FixField::Group {
+                    count_tag: tag,
+                    entries: nested_entries,
+                });
+            }
+            None => {
+                entry.push(tag, field.value_str()?);
+            }
+        }
+    }
+    if entries.len() != declared {
+        return Err(FixError::GroupCountMismatch {
+            count_tag: count.tag,
+            declared,
+            actual: entries.len(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Iterator over the body fields of a [`FixFrame`]
+///
+/// Stops after the first error.
+#[derive(Debug, Clone)]
+pub struct FieldIter<'a> {
+    buf: &'a [u8],
+    offset: usize,
+    data_len: Option<(u32, usize)>,
+    failed: bool,
+}
+
+impl<'a> Iterator for FieldIter<'a> {
+    type Item = Result<RawField<'a>, FixError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset >= self.buf.len() {
+            return None;
+        }
+        let result = read_field(self.buf, self.offset, self.data_len.take()).and_then(|field| {
+            field.ok_or(FixError::MissingDelimiter {
+                offset: self.offset,
+            })
+        });
+        match result {
+            Ok((field, next)) => {
+                self.offset = next;
+                if is_data_length_tag(field.tag) {
+                    match field.value_str().ok().and_then(|value| value.parse().ok()) {
+                        Some(len) => self.data_len = Some((field.tag, len)),
+                        None => {
+                            self.failed = true;
+                            return Some(Err(FixError::InvalidValue {
+                                tag: field.tag,
+                                value: String::from_utf8_lossy(field.value).into_owned(),
+                            }));
+                        }
+                    }
+                }
+                Some(Ok(field))
+            }
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl FixMessage {
+    /// Decode the frame at the start of `buf`
+    pub fn decode(buf: &[u8]) -> Result<Self, FixError> {
+        FixFrame::parse(buf)?.to_message()
+    }
+
+    /// Encode the message, computing `BodyLength(9)` and `CheckSum(10)`
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_to(&mut buf);
+        buf
+    }
+
+    /// Append the encoded message to `buf`
+    pub fn encode_to(&self, buf: &mut Vec<u8>) {
+        let mut body = Vec::new();
+        write_field(&mut body, MSG_TYPE, self.msg_type.as_str().as_bytes());
+        write_fields(&mut body, &self.header);
+        write_fields(&mut body, &self.body);
+
+        let start = buf.len();
+        write_field(buf, BEGIN_STRING, self.begin_string.as_bytes());
+        write_field(buf, BODY_LENGTH, body.len().to_string().as_bytes());
+        buf.extend_from_slice(&body);
+        let sum = checksum(&buf[start..]);
+        write_field(buf, CHECK_SUM, format!("{sum:03}").as_bytes());
+    }
+}
+
+fn write_field(buf: &mut Vec<u8>, tag: u32, value: &[u8]) {
+    buf.extend_from_slice(tag.to_string().as_bytes());
+    buf.push(b'=');
+    buf.extend_from_slice(value);
+    buf.push(SOH);
+}
+
+fn write_fields(buf: &mut Vec<u8>, fields: &FieldMap) {
+    for field in fields {
+        match field {
+            FixField::Value { tag, value } => write_field(buf, *tag, value.as_bytes()),
+            FixField::Group { count_tag, entries } => {
+                write_field(buf, *count_tag, entries.len().to_string().as_bytes());
+                for entry in entries {
+                    write_fields(buf, entry);
+                }
+            }
+        }
+    }
+}
+
+/// Splits a byte stream into FIX messages
+///
+/// Bytes are appended with [`FixDecoder::extend`] as they arrive and
+/// complete frames are taken with [`FixDecoder::next_message`]. A malformed
+/// frame is reported once and dropped, so decoding resumes with the next
+/// frame. Frames announcing a `BodyLength(9)` above the configured maximum
+/// are rejected before their body is buffered, and so are headers that stay
+/// unterminated past [`FIX_MAX_HEADER_LENGTH`] bytes.
+#[derive(Debug, Clone)]
+pub struct FixDecoder {
+    buffer: Vec<u8>,
+    max_body_length: usize,
+}
+
+impl Default for FixDecoder {
+    fn default() -> Self {
+        Self {
+            buffer: Vec::new(),
+            max_body_length: FIX_MAX_BODY_LENGTH,
+        }
+    }
+}
+
+impl FixDecoder {
+    /// Create an empty decoder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the largest accepted `BodyLength(9)`
+    #[must_use]
+    pub fn with_max_body_length(mut self, max_body_length: usize) -> Self {
+        self.max_body_length = max_body_length;
+        self
+    }
+
+    /// Get the largest accepted `BodyLength(9)`
+    #[must_use]
+    pub fn max_body_length(&self) -> usize {
+        self.max_body_length
+    }
+
+    /// Append received bytes
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Get the number of buffered bytes not yet decoded
+    #[must_use]
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Decode the next complete message, if one is buffered
+    pub fn next_message(&mut self) -> Result<Option<FixMessage>, FixError> {
+        let total = match read_header(&self.buffer).and_then(|header| {
+            header
+                .map(|(body_start, body_length)| self.checked_frame_end(body_start, body_length))
+                .transpose()
+        }) {
+            Ok(Some(total)) => total,
+            Ok(None) if self.buffer.len() > FIX_MAX_HEADER_LENGTH => {
+                let offset = match expect_field(&self.buffer, 0, BEGIN_STRING) {
+                    Ok(Some((_, next))) => next,
+                    _ => 0,
+                };
+                self.resync();
+                return Err(FixError::MissingDelimiter { offset });
+            }
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                self.resync();
+                return Err(err);
+            }
+        };
+        if self.buffer.len() < total {
+            return Ok(None);
+        }
+        match FixFrame::parse(&self.buffer).and_then(|frame| frame.to_message()) {
+            Ok(message) => {
+                self.buffer.drain(..total);
+                Ok(Some(message))
+            }
+            Err(err @ FixError::BodyLengthMismatch { .. }) => {
+                self.resync();
+                Err(err)
+            }
+            Err(err) => {
+                self.buffer.drain(..total);
+                Err(err)
+            }
+        }
+    }
+
+    fn checked_frame_end(&self, body_start: usize, body_length: usize) -> Result<usize, FixError> {
+        if body_length > self.max_body_length {
+            return Err(FixError::InvalidValue {
+                tag: BODY_LENGTH,
+                value: body_length.to_string(),
+            });
+        }
+        frame_end(body_start, body_length).map(|(_, total)| total)
+    }
+
+    /// Drop bytes up to the start of the next frame
+    fn resync(&mut self) {
+        let next = self
+            .buffer
+            .windows(3)
+            .position(|window| window == b"\x018=")
+            .map_or(self.buffer.len(), |position| position + 1);
+        self.buffer.drain(..next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::tags::{
+        DERIBIT_APP_ID, HEART_BT_INT, MD_ENTRY_PX, MD_ENTRY_TYPE, MD_UPDATE_ACTION, MSG_SEQ_NUM,
+        NO_MD_ENTRIES, NO_QUOTE_ENTRIES, NO_QUOTE_SETS, QUOTE_ENTRY_ID, QUOTE_SET_ID, RAW_DATA,
+        RAW_DATA_LENGTH, SYMBOL, TEXT,
+    };
+
+    fn frame(body: &str) -> Vec<u8> {
+        let body = body.replace('|', "\x01");
+        let head = format!("8=FIX.4.4\x019={}\x01", body.len());
+        let sum = checksum(format!("{head}{body}").as_bytes());
+        format!("{head}{body}10={sum:03}\x01").into_bytes()
+    }
+
+    fn logon() -> FixMessage {
+        FixMessage::new(MsgType::Logon)
+            .with_header("CLIENT", "DERIBITSERVER", 1, "20261018-10:00:00.000")
+            .with_field(HEART_BT_INT, "30")
+            .with_field(DERIBIT_APP_ID, "app")
+    }
+
+    #[test]
+    fn test_checksum_matches_utils() {
+        let message = "8=FIX.4.4\x019=5\x0135=0\x01";
+        assert_eq!(
+            checksum(message.as_bytes()),
+            crate::utils::tools::calculate_checksum(message)
+        );
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let message = logon();
+        let encoded = message.encode();
+        let text = String::from_utf8(encoded.clone()).unwrap();
+        assert!(text.starts_with("8=FIX.4.4\x019="));
+        assert!(text.contains("\x0135=A\x0149=CLIENT\x0156=DERIBITSERVER\x0134=1\x01"));
+        assert!(crate::utils::tools::validate_checksum(&text));
+
+        let frame = FixFrame::parse(&encoded).unwrap();
+        assert_eq!(frame.len(), encoded.len());
+        assert_eq!(frame.msg_type(), "A");
+        assert_eq!(frame.begin_string(), "FIX.4.4");
+        assert_eq!(FixMessage::decode(&encoded).unwrap(), message);
+        assert_eq!(
+            message.to_string(),
+            text.replace('\x01', "|"),
+            "display renders SOH as |"
+        );
+    }
+
+    #[test]
+    fn test_body_length_and_checksum_values() {
+        let encoded = FixMessage::new(MsgType::Heartbeat).encode();
+        assert_eq!(encoded, frame("35=0|"));
+        let frame = FixFrame::parse(&encoded).unwrap();
+        assert_eq!(frame.body_length(), 5);
+        assert_eq!(frame.fields().count(), 0);
+    }
+
+    #[test]
+    fn test_zero_copy_fields() {
+        let encoded = frame("35=0|34=2|112=ping|");
+        let frame = FixFrame::parse(&encoded).unwrap();
+        let fields: Vec<_> = frame.fields().map(Result::unwrap).collect();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].tag, MSG_SEQ_NUM);
+        assert_eq!(fields[1].value, b"ping");
+        assert_eq!(fields[1].value_str().unwrap(), "ping");
+        assert!(std::ptr::eq(
+            fields[1].value.as_ptr(),
+            &encoded[fields[1].offset + 4]
+        ));
+    }
+
+    #[test]
+    fn test_raw_data_may_contain_soh() {
+        let message = FixMessage::new(MsgType::Logon)
+            .with_field(RAW_DATA_LENGTH, "7")
+            .with_field(RAW_DATA, "123\x01abc");
+        let decoded = FixMessage::decode(&message.encode()).unwrap();
+        assert_eq!(decoded.body.get(RAW_DATA), Some("123\x01abc"));
+    }
+
+    #[test]
+    fn test_repeating_groups() {
+        let encoded = frame(
+            "35=X|34=3|262=1|268=2|279=0|269=0|270=100.5|271=10|279=2|269=1|270=101|271=0|58=done|",
+        );
+        let message = FixMessage::decode(&encoded).unwrap();
+        assert_eq!(message.msg_type, MsgType::MarketDataIncrementalRefresh);
+        assert_eq!(message.seq_num(), Ok(3));
+        let entries = message.body.group(NO_MD_ENTRIES).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].get(MD_UPDATE_ACTION), Some("0"));
+        assert_eq!(entries[1].get(MD_ENTRY_PX), Some("101"));
+        assert_eq!(entries[1].get(MD_ENTRY_TYPE), Some("1"));
+        assert_eq!(entries[1].get(TEXT), Some("done"));
+        assert_eq!(message.encode(), encoded);
+    }
+
+    #[test]
+    fn test_nested_groups() {
+        let encoded = frame(
+            "35=i|117=q1|296=1|302=1|295=2|299=1|55=BTC-PERPETUAL|132=41000|299=2|55=BTC-29DEC23|132=41500|",
+        );
+        let message = FixMessage::decode(&encoded).unwrap();
+        let sets = message.body.group(NO_QUOTE_SETS).unwrap();
+        assert_eq!(sets[0].get(QUOTE_SET_ID), Some("1"));
+        let entries = sets[0].group(NO_QUOTE_ENTRIES).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].get(QUOTE_ENTRY_ID), Some("2"));
+        assert_eq!(entries[1].get(SYMBOL), Some("BTC-29DEC23"));
+        assert_eq!(message.encode(), encoded);
+    }
+
+    #[test]
+    fn test_group_errors() {
+        let encoded = frame("35=X|268=2|279=0|269=0|");
+        assert_eq!(
+            FixMessage::decode(&encoded),
+            Err(FixError::GroupCountMismatch {
+                count_tag: NO_MD_ENTRIES,
+                declared: 2,
+                actual: 1
+            })
+        );
+        let encoded = frame("35=X|268=1|269=0|");
+        assert!(matches!(
+            FixMessage::decode(&encoded),
+            Err(FixError::UnexpectedField {
+                expected: MD_UPDATE_ACTION,
+                found: MD_ENTRY_TYPE,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_frames() {
+        let mut bad_checksum = frame("35=0|");
+        let len = bad_checksum.len();
+        bad_checksum[len - 2] = if bad_checksum[len - 2] == b'0' {
+            b'1'
+        } else {
+            b'0'
+        };
+        assert!(matches!(
+            FixFrame::parse(&bad_checksum),
+            Err(FixError::ChecksumMismatch { .. })
+        ));
+
+        let bad_length = b"8=FIX.4.4\x019=9\x0135=0\x0110=000\x01";
+        assert_eq!(
+            FixFrame::parse(bad_length),
+            Err(FixError::BodyLengthMismatch {
+                declared: 9,
+                actual: Some(5)
+            })
+        );
+
+        assert_eq!(
+            FixFrame::parse(b"9=5\x0135=0\x01"),
+            Err(FixError::UnexpectedField {
+                expected: BEGIN_STRING,
+                found: BODY_LENGTH,
+                offset: 0
+            })
+        );
+        assert_eq!(
+            FixFrame::parse(b"8=FIX.4.4\x019=abc\x01"),
+            Err(FixError::InvalidValue {
+                tag: BODY_LENGTH,
+                value: "abc".to_string()
+            })
+        );
+        assert_eq!(
+            FixFrame::parse(b"8=FIX.4.4\x019=5\x0135"),
+            Err(FixError::Incomplete)
+        );
+        assert_eq!(
+            FixFrame::parse(&frame("34=1|35=0|")),
+            Err(FixError::UnexpectedField {
+                expected: MSG_TYPE,
+                found: MSG_SEQ_NUM,
+                offset: 15
+            })
+        );
+        assert_eq!(
+            FixMessage::decode(&frame("35=0|34|")),
+            Err(FixError::MissingEquals { offset: 19 })
+        );
+        assert_eq!(
+            FixMessage::decode(&frame("35=0|3x=1|")),
+            Err(FixError::InvalidTag { offset: 20 })
+        );
+        assert_eq!(
+            FixMessage::decode(&frame("35=0|58=|")),
+            Err(FixError::EmptyValue { tag: TEXT })
+        );
+        assert_eq!(
+            FixMessage::decode(b"8=FIX.4.4\x019=5\x0135=0\x0110=12\x01x"),
+            Err(FixError::InvalidChecksum("12".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_overflowing_lengths_are_rejected() {
+        let huge = b"8=FIX.4.4\x019=18446744073709551615\x01";
+        let overflow = FixError::InvalidValue {
+            tag: BODY_LENGTH,
+            value: "18446744073709551615".to_string(),
+        };
+        assert_eq!(frame_length(huge), Err(overflow.clone()));
+        assert_eq!(FixFrame::parse(huge), Err(overflow.clone()));
+
+        let mut decoder = FixDecoder::new().with_max_body_length(usize::MAX);
+        decoder.extend(huge);
+        assert_eq!(decoder.next_message(), Err(overflow));
+        assert_eq!(decoder.buffered_len(), 0);
+
+        let data = frame("35=0|95=18446744073709551615|96=x|");
+        assert_eq!(
+            FixMessage::decode(&data),
+            Err(FixError::InvalidValue {
+                tag: RAW_DATA_LENGTH,
+                value: "18446744073709551615".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_stream_decoder_limits_body_length() {
+        let mut decoder = FixDecoder::new().with_max_body_length(16);
+        assert_eq!(decoder.max_body_length(), 16);
+        decoder.extend(b"8=FIX.4.4\x019=17\x01");
+        assert_eq!(
+            decoder.next_message(),
+            Err(FixError::InvalidValue {
+                tag: BODY_LENGTH,
+                value: "17".to_string()
+            })
+        );
+        assert_eq!(decoder.buffered_len(), 0);
+
+        decoder.extend(&frame("35=0|34=2|"));
+        assert_eq!(decoder.next_message().unwrap().unwrap().seq_num(), Ok(2));
+    }
+
+    #[test]
+    fn test_stream_decoder_limits_header_length() {
+        let mut decoder = FixDecoder::new();
+        decoder.extend(b"8=FIX.4.4\x019=");
+        assert_eq!(decoder.next_message(), Ok(None));
+        decoder.extend(&[b'1'; FIX_MAX_HEADER_LENGTH]);
+        assert_eq!(
+            decoder.next_message(),
+            Err(FixError::MissingDelimiter { offset: 10 })
+        );
+        assert_eq!(decoder.buffered_len(), 0);
+
+        decoder.extend(b"8=");
+        decoder.extend(&[b'F'; FIX_MAX_HEADER_LENGTH]);
+        assert_eq!(
+            decoder.next_message(),
+            Err(FixError::MissingDelimiter { offset: 0 })
+        );
+        assert_eq!(decoder.buffered_len(), 0);
+
+        decoder.extend(&frame("35=0|34=2|"));
+        assert_eq!(decoder.next_message().unwrap().unwrap().seq_num(), Ok(2));
+    }
+
+    #[test]
+    fn test_error_display_uses_tag_names() {
+        let err = FixError::UnexpectedField {
+            expected: MSG_TYPE,
+            found: MSG_SEQ_NUM,
+            offset: 15,
+        };
+        assert_eq!(
+            err.to_string(),
+            "expected MsgType(35) at offset 15, found MsgSeqNum(34)"
+        );
+    }
+
+    #[test]
+    fn test_frame_length() {
+        let encoded = frame("35=0|");
+        assert_eq!(frame_length(&encoded), Ok(Some(encoded.len())));
+        assert_eq!(frame_length(&encoded[..12]), Ok(None));
+        assert_eq!(frame_length(b"8=FIX"), Ok(None));
+    }
+
+    #[test]
+    fn test_stream_decoder() {
+        let mut stream = logon().encode();
+        stream.extend(frame("35=0|34=2|"));
+        stream.extend(frame("35=1|34=3|112=t|"));
+
+        let mut decoder = FixDecoder::new();
+        let mut messages = Vec::new();
+        for chunk in stream.chunks(7) {
+            decoder.extend(chunk);
+            while let Some(message) = decoder.next_message().unwrap() {
+                messages.push(message);
+            }
+        }
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0], logon());
+        assert_eq!(messages[2].msg_type, MsgType::TestRequest);
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+
+    #[test]
+    fn test_stream_decoder_recovers_from_bad_frames() {
+        let mut bad = frame("35=0|34=2|");
+        let len = bad.len();
+        bad[len - 3] = b'9';
+        bad[len - 2] = b'9';
+        let mut decoder = FixDecoder::new();
+        decoder.extend(b"garbage\x01");
+        decoder.extend(&bad);
+        decoder.extend(b"8=FIX.4.4\x019=20\x0135=0\x0110=000\x01");
+        decoder.extend(&frame("35=0|34=3|"));
+
+        assert_eq!(
+            decoder.next_message(),
+            Err(FixError::MissingEquals { offset: 0 })
+        );
+        assert!(matches!(
+            decoder.next_message(),
+            Err(FixError::ChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            decoder.next_message(),
+            Err(FixError::BodyLengthMismatch { .. })
+        ));
+        let message = decoder.next_message().unwrap().unwrap();
+        assert_eq!(message.seq_num(), Ok(3));
+        assert_eq!(decoder.next_message(), Ok(None));
+    }
+}
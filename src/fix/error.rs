@@ -0,0 +1,134 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Errors raised while parsing or building FIX messages
+
+use crate::fix::tags::describe_tag;
+use std::fmt;
+
+/// Error raised by the FIX codec
+///
+/// Offsets are byte positions from the start of the frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixError {
+    /// Not enough bytes to hold a complete frame
+    Incomplete,
+    /// Field has no `=` between tag and value
+    MissingEquals {
+        /// Offset of the field
+        offset: usize,
+    },
+    /// Field is not terminated by the SOH delimiter
+    MissingDelimiter {
+        /// Offset of the field
+        offset: usize,
+    },
+    /// Tag is not a positive integer
+    InvalidTag {
+        /// Offset of the field
+        offset: usize,
+    },
+    /// Field has an empty value
+    EmptyValue {
+        /// Tag of the field
+        tag: u32,
+    },
+    /// Field appears where another tag is required
+    UnexpectedField {
+        /// Tag required at this position
+        expected: u32,
+        /// Tag found instead
+        found: u32,
+        /// Offset of the field
+        offset: usize,
+    },
+    /// `CheckSum(10)` does not start where `BodyLength(9)` says it does
+    BodyLengthMismatch {
+        /// Length declared in `BodyLength(9)`
+        declared: usize,
+        /// Length up to the `CheckSum(10)` field, when one was found
+        actual: Option<usize>,
+    },
+    /// `CheckSum(10)` value is not three digits
+    InvalidChecksum(String),
+    /// `CheckSum(10)` does not match the frame contents
+    ChecksumMismatch {
+        /// Checksum sent in the frame
+        declared: u8,
+        /// Checksum computed over the frame
+        computed: u8,
+    },
+    /// Required field is missing
+    MissingField(u32),
+    /// Field value cannot be interpreted
+    InvalidValue {
+        /// Tag of the field
+        tag: u32,
+        /// Raw value
+        value: String,
+    },
+    /// Number of group entries differs from the `NumInGroup` field
+    GroupCountMismatch {
+        /// `NumInGroup` tag of the group
+        count_tag: u32,
+        /// Entries announced by the count field
+        declared: usize,
+        /// Entries found in the frame
+        actual: usize,
+    },
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Incomplete => write!(f, "incomplete FIX frame"),
+            Self::MissingEquals { offset } => {
+                write!(f, "field at offset {offset} has no '=' separator")
+            }
+            Self::MissingDelimiter { offset } => {
+                write!(f, "field at offset {offset} is not terminated by SOH")
+            }
+            Self::InvalidTag { offset } => write!(f, "invalid tag at offset {offset}"),
+            Self::EmptyValue { tag } => write!(f, "empty value for {}", describe_tag(*tag)),
+            Self::UnexpectedField {
+                expected,
+                found,
+                offset,
+            } => write!(
+                f,
+                "expected {} at offset {offset}, found {}",
+                describe_tag(*expected),
+                describe_tag(*found)
+            ),
+            Self::BodyLengthMismatch { declared, actual } => match actual {
+                Some(actual) => write!(f, "BodyLength(9) is {declared}, body has {actual} bytes"),
+                None => write!(
+                    f,
+                    "BodyLength(9) is {declared}, no CheckSum(10) found after the body"
+                ),
+            },
+            Self::InvalidChecksum(value) => write!(f, "invalid CheckSum(10) value {value:?}"),
+            Self::ChecksumMismatch { declared, computed } => {
+                write!(f, "CheckSum(10) is {declared:03}, computed {computed:03}")
+            }
+            Self::MissingField(tag) => write!(f, "missing required field {}", describe_tag(*tag)),
+            Self::InvalidValue { tag, value } => {
+                write!(f, "invalid value {value:?} for {}", describe_tag(*tag))
+            }
+            Self::GroupCountMismatch {
+                count_tag,
+                declared,
+                actual,
+            } => write!(
+                f,
+                "{} announces {declared} entries, found {actual}",
+                describe_tag(*count_tag)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FixError {}
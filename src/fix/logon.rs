@@ -0,0 +1,551 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Deribit FIX Logon authentication
+//!
+//! The Logon body carries `RawData(96)` as `timestamp.nonce` and
+//! `Password(554)` as `base64(sha256(RawData ++ client_secret))`. Registered
+//! applications add `DeribitAppSig(9005)`, computed the same way with the
+//! application secret. [`LogonBuilder`] produces these fields for
+//! [`crate::fix::FixSession::logon`] and [`LogonVerifier`] checks them on a
+//! local test acceptor.
+
+use crate::fix::message::{FieldMap, FixMessage};
+use crate::fix::tags::{
+    CANCEL_ON_DISCONNECT, CONNECTION_ONLY_EXECUTION_REPORTS, DERIBIT_APP_ID, DERIBIT_APP_SIG,
+    DERIBIT_SEQUENTIAL, DISPLAY_INCREMENT_STEPS, MsgType, PASSWORD, RAW_DATA, RAW_DATA_LENGTH,
+    REPORT_FILLS_AS_EXEC_REPORTS, UNSUBSCRIBE_EXECUTION_REPORTS, USE_WORDSAFE_TAGS, USERNAME,
+};
+use crate::model::config::DeribitConfig;
+use crate::utils::tools::{generate_nonce, generate_timestamp};
+use base64::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of random bytes in a generated nonce
+pub const LOGON_NONCE_BYTES: usize = 32;
+
+/// Maximum number of random bytes accepted in a nonce
+pub const LOGON_NONCE_MAX_BYTES: usize = 512;
+
+static LAST_LOGON_TIMESTAMP: AtomicU64 = AtomicU64::new(0);
+
+/// Get a millisecond timestamp greater than any previously returned one
+fn next_logon_timestamp() -> u64 {
+    let now = generate_timestamp();
+    let previous = LAST_LOGON_TIMESTAMP
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap_or(now);
+    now.max(previous + 1)
+}
+
+/// Compute `base64(sha256(raw_data ++ secret))`
+///
+/// Used for `Password(554)` with the client secret and for
+/// `DeribitAppSig(9005)` with the application secret.
+#[must_use]
+pub fn logon_signature(raw_data: &str, secret: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(raw_data.as_bytes());
+    hasher.update(secret.as_bytes());
+    BASE64_STANDARD.encode(hasher.finalize())
+}
+
+/// Builder of the authentication fields of a Deribit FIX Logon
+#[derive(Clone, PartialEq, Eq)]
+pub struct LogonBuilder {
+    client_id: String,
+    client_secret: String,
+    app: Option<(String, String)>,
+    flags: Vec<(u32, bool)>,
+}
+
+impl LogonBuilder {
+    /// Create a builder for the given API credentials
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            app: None,
+            flags: Vec::new(),
+        }
+    }
+
+    /// Create a builder from the credentials of a configuration
+    #[must_use]
+    pub fn from_config(config: &DeribitConfig) -> Self {
+        Self::new(config.client_id.as_str(), config.client_secret.as_str())
+    }
+
+    /// Sign as a registered application with `DeribitAppId(9004)` and `DeribitAppSig(9005)`
+    #[must_use]
+    pub fn with_app(mut self, app_id: impl Into<String>, app_secret: impl Into<String>) -> Self {
+        self.app = Some((app_id.into(), app_secret.into()));
+        self
+    }
+
+    /// Set `CancelOnDisconnect(9001)`; the account default applies when unset
+    #[must_use]
+    pub fn with_cancel_on_disconnect(self, enabled: bool) -> Self {
+        self.with_flag(CANCEL_ON_DISCONNECT, enabled)
+    }
+
+    /// Set `UseWordsafeTags(9002)`
+    #[must_use]
+    pub fn with_wordsafe_tags(self, enabled: bool) -> Self {
+        self.with_flag(USE_WORDSAFE_TAGS, enabled)
+    }
+
+    /// Set `DeribitSequential(9007)`
+    #[must_use]
+    pub fn with_sequential(self, enabled: bool) -> Self {
+        self.with_flag(DERIBIT_SEQUENTIAL, enabled)
+    }
+
+    /// Set `UnsubscribeExecutionReports(9009)`
+    #[must_use]
+    pub fn with_unsubscribe_execution_reports(self, enabled: bool) -> Self {
+        self.with_flag(UNSUBSCRIBE_EXECUTION_REPORTS, enabled)
+    }
+
+    /// Set `ConnectionOnlyExecutionReports(9010)`
+    #[must_use]
+    pub fn with_connection_only_execution_reports(self, enabled: bool) -> Self {
+        self.with_flag(CONNECTION_ONLY_EXECUTION_REPORTS, enabled)
+    }
+
+    /// Set `ReportFillsAsExecReports(9015)`
+    #[must_use]
+    pub fn with_report_fills_as_exec_reports(self, enabled: bool) -> Self {
+        self.with_flag(REPORT_FILLS_AS_EXEC_REPORTS, enabled)
+    }
+
+    /// Set `DisplayIncrementSteps(9018)`
+    #[must_use]
+    pub fn with_display_increment_steps(self, enabled: bool) -> Self {
+        self.with_flag(DISPLAY_INCREMENT_STEPS, enabled)
+    }
+
+    /// Get the client id sent in `Username(553)`
+    #[must_use]
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Build the Logon fields with a fresh timestamp and nonce
+    ///
+    /// Timestamps are kept strictly increasing within the process, as the
+    /// server rejects a Logon that does not advance the previous one.
+    #[must_use]
+    pub fn build(&self) -> FieldMap {
+        self.build_with_nonce(next_logon_timestamp(), &generate_nonce(LOGON_NONCE_BYTES))
+    }
+
+    /// Build the Logon fields with an explicit timestamp and base64 nonce
+    #[must_use]
+    pub fn build_with_nonce(&self, timestamp: u64, nonce: &str) -> FieldMap {
+        let raw_data = format!("{timestamp}.{nonce}");
+        let mut fields = FieldMap::new()
+            .with(RAW_DATA_LENGTH, raw_data.len().to_string())
+            .with(RAW_DATA, raw_data.as_str())
+            .with(USERNAME, self.client_id.as_str())
+            .with(PASSWORD, logon_signature(&raw_data, &self.client_secret));
+        if let Some((app_id, app_secret)) = &self.app {
+            fields
+                .set(DERIBIT_APP_ID, app_id.as_str())
+                .set(DERIBIT_APP_SIG, logon_signature(&raw_data, app_secret));
+        }
+        for (tag, enabled) in &self.flags {
+            fields.set(*tag, if *enabled { "Y" } else { "N" });
+        }
+        fields
+    }
+
+    fn with_flag(mut self, tag: u32, enabled: bool) -> Self {
+        self.flags.retain(|(existing, _)| *existing != tag);
+        self.flags.push((tag, enabled));
+        self
+    }
+}
+
+impl fmt::Debug for LogonBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogonBuilder")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"***")
+            .field("app_id", &self.app.as_ref().map(|(app_id, _)| app_id))
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+/// Reason a Logon was refused by [`LogonVerifier`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogonError {
+    /// Message is not a Logon
+    NotALogon(MsgType),
+    /// Required authentication field is missing
+    MissingField(u32),
+    /// `RawData(96)` is not `timestamp.nonce` with a base64 nonce
+    MalformedRawData(String),
+    /// Nonce is longer than [`LOGON_NONCE_MAX_BYTES`]
+    NonceTooLong(usize),
+    /// `Username(553)` is not a known client id
+    UnknownClient(String),
+    /// `Password(554)` does not match the client secret
+    InvalidPassword,
+    /// `DeribitAppId(9004)` is not a known application
+    UnknownApp(String),
+    /// `DeribitAppSig(9005)` is missing or does not match the application secret
+    InvalidAppSignature,
+    /// Timestamp is not greater than the one of the previous Logon
+    StaleTimestamp {
+        /// Timestamp of the previous accepted Logon
+        last: u64,
+        /// Timestamp received
+        received: u64,
+    },
+}
+
+impl fmt::Display for LogonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotALogon(msg_type) => write!(f, "expected Logon, received MsgType {msg_type}"),
+            Self::MissingField(tag) => write!(f, "missing Logon field {tag}"),
+            Self::MalformedRawData(raw_data) => write!(f, "malformed RawData {raw_data:?}"),
+            Self::NonceTooLong(len) => write!(f, "nonce of {len} bytes is too long"),
+            Self::UnknownClient(client_id) => write!(f, "unknown client id {client_id}"),
+            Self::InvalidPassword => write!(f, "invalid password"),
+            Self::UnknownApp(app_id) => write!(f, "unknown application id {app_id}"),
+            Self::InvalidAppSignature => write!(f, "invalid application signature"),
+            Self::StaleTimestamp { last, received } => write!(
+                f,
+                "timestamp {received} is not greater than previous timestamp {last}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LogonError {}
+
+/// A Logon accepted by [`LogonVerifier`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedLogon {
+    /// Client id from `Username(553)`
+    pub client_id: String,
+    /// Timestamp from `RawData(96)`
+    pub timestamp: u64,
+    /// Registered application id, if the Logon was signed by one
+    pub app_id: Option<String>,
+    /// `CancelOnDisconnect(9001)`, if sent
+    pub cancel_on_disconnect: Option<bool>,
+    /// Whether `UseWordsafeTags(9002)` was enabled
+    pub use_wordsafe_tags: bool,
+}
+
+/// Checks Logon authentication on a local test acceptor
+#[derive(Clone, Default)]
+pub struct LogonVerifier {
+    clients: HashMap<String, String>,
+    apps: HashMap<String, String>,
+    last_timestamps: HashMap<String, u64>,
+}
+
+impl LogonVerifier {
+    /// Create a verifier without known clients
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept Logons from a client id with the given secret
+    #[must_use]
+    pub fn with_client(mut self, client_id: impl Into<String>, secret: impl Into<String>) -> Self {
+        self.clients.insert(client_id.into(), secret.into());
+        self
+    }
+
+    /// Accept signatures from a registered application
+    #[must_use]
+    pub fn with_app(mut self, app_id: impl Into<String>, secret: impl Into<String>) -> Self {
+        self.apps.insert(app_id.into(), secret.into());
+        self
+    }
+
+    /// Verify a Logon and remember its timestamp
+    pub fn verify(&mut self, logon: &FixMessage) -> Result<VerifiedLogon, LogonError> {
+        if logon.msg_type != MsgType::Logon {
+            return Err(LogonError::NotALogon(logon.msg_type.clone()));
+        }
+        let body = &logon.body;
+        let raw_data = body
+            .get(RAW_DATA)
+            .ok_or(LogonError::MissingField(RAW_DATA))?;
+        let client_id = body
+            .get(USERNAME)
+            .ok_or(LogonError::MissingField(USERNAME))?;
+        let password = body
+            .get(PASSWORD)
+            .ok_or(LogonError::MissingField(PASSWORD))?;
+
+        let malformed = || LogonError::MalformedRawData(raw_data.to_string());
+        let (timestamp, nonce) = raw_data.split_once('.').ok_or_else(malformed)?;
+        let timestamp: u64 = timestamp.parse().map_err(|_| malformed())?;
+        let nonce = BASE64_STANDARD.decode(nonce).map_err(|_| malformed())?;
+        if nonce.is_empty() {
+            return Err(malformed());
+        }
+        if nonce.len() > LOGON_NONCE_MAX_BYTES {
+            return Err(LogonError::NonceTooLong(nonce.len()));
+        }
+
+        let secret = self
+            .clients
+            .get(client_id)
+            .ok_or_else(|| LogonError::UnknownClient(client_id.to_string()))?;
+        if logon_signature(raw_data, secret) != password {
+            return Err(LogonError::InvalidPassword);
+        }
+        let app_id = match body.get(DERIBIT_APP_ID) {
+            Some(app_id) => {
+                let secret = self
+                    .apps
+                    .get(app_id)
+                    .ok_or_else(|| LogonError::UnknownApp(app_id.to_string()))?;
+                if body.get(DERIBIT_APP_SIG) != Some(logon_signature(raw_data, secret).as_str()) {
+                    return Err(LogonError::InvalidAppSignature);
+                }
+                Some(app_id.to_string())
+            }
+            None => None,
+        };
+        if let Some(last) = self.last_timestamps.get(client_id)
+            && timestamp <= *last
+        {
+            return Err(LogonError::StaleTimestamp {
+                last: *last,
+                received: timestamp,
+            });
+        }
+        self.last_timestamps
+            .insert(client_id.to_string(), timestamp);
+
+        Ok(VerifiedLogon {
+            client_id: client_id.to_string(),
+            timestamp,
+            app_id,
+            cancel_on_disconnect: body.get_bool(CANCEL_ON_DISCONNECT).ok().flatten(),
+            use_wordsafe_tags: body.get_bool(USE_WORDSAFE_TAGS) == Ok(Some(true)),
+        })
+    }
+}
+
+impl fmt::Debug for LogonVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogonVerifier")
+            .field("clients", &self.clients.keys().collect::<Vec<_>>())
+            .field("apps", &self.apps.keys().collect::<Vec<_>>())
+            .field("last_timestamps", &self.last_timestamps)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::codec::FixDecoder;
+    use crate::fix::session::{FixSession, SessionAction, SessionConfig};
+
+    const NONCE: &str = "c2VjcmV0LW5vbmNlLWJ5dGVzLTAxMjM0NTY3ODlhYmNkZWY=";
+
+    fn logon(fields: FieldMap) -> FixMessage {
+        let mut message = FixMessage::new(MsgType::Logon);
+        message.body = fields;
+        message
+    }
+
+    #[test]
+    fn test_logon_signature() {
+        // echo -n "1700000000000.nonceSECRET" | openssl dgst -sha256 -binary | base64
+        assert_eq!(
+            logon_signature("1700000000000.nonce", "SECRET"),
+            "ooHQEzDbdAFUN200d+XFMAJLUfXTFVCzGXlwXJ6z3aQ="
+        );
+    }
+
+    #[test]
+    fn test_build_fields() {
+        let config = DeribitConfig::new("client".to_string(), "secret".to_string());
+        let fields = LogonBuilder::from_config(&config)
+            .with_app("app", "app-secret")
+            .with_cancel_on_disconnect(true)
+            .with_wordsafe_tags(false)
+            .with_cancel_on_disconnect(false)
+            .build_with_nonce(1_700_000_000_000, NONCE);
+
+        let raw_data = format!("1700000000000.{NONCE}");
+        assert_eq!(fields.get(RAW_DATA), Some(raw_data.as_str()));
+        assert_eq!(
+            fields.get_parsed::<usize>(RAW_DATA_LENGTH),
+            Ok(Some(raw_data.len()))
+        );
+        assert_eq!(fields.get(USERNAME), Some("client"));
+        assert_eq!(
+            fields.get(PASSWORD),
+            Some(logon_signature(&raw_data, "secret").as_str())
+        );
+        assert_eq!(fields.get(DERIBIT_APP_ID), Some("app"));
+        assert_eq!(
+            fields.get(DERIBIT_APP_SIG),
+            Some(logon_signature(&raw_data, "app-secret").as_str())
+        );
+        assert_eq!(fields.get(CANCEL_ON_DISCONNECT), Some("N"));
+        assert_eq!(fields.get(USE_WORDSAFE_TAGS), Some("N"));
+        assert!(!fields.contains(DERIBIT_SEQUENTIAL));
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let builder = LogonBuilder::new("client", "hunter2").with_app("app", "hunter3");
+        let verifier = LogonVerifier::new()
+            .with_client("client", "hunter2")
+            .with_app("app", "hunter3");
+        for debug in [format!("{builder:?}"), format!("{verifier:?}")] {
+            assert!(debug.contains("client"));
+            assert!(debug.contains("app"));
+            assert!(!debug.contains("hunter"), "{debug}");
+        }
+    }
+
+    #[test]
+    fn test_generated_nonce() {
+        let fields = LogonBuilder::new("client", "secret").build();
+        let raw_data = fields.get(RAW_DATA).unwrap();
+        let (timestamp, nonce) = raw_data.split_once('.').unwrap();
+        assert!(timestamp.parse::<u64>().is_ok());
+        assert_eq!(
+            BASE64_STANDARD.decode(nonce).unwrap().len(),
+            LOGON_NONCE_BYTES
+        );
+    }
+
+    #[test]
+    fn test_build_timestamps_increase() {
+        let builder = LogonBuilder::new("client", "secret");
+        let mut verifier = LogonVerifier::new().with_client("client", "secret");
+        for _ in 0..5 {
+            assert!(verifier.verify(&logon(builder.build())).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_verifier_accepts_valid_logon() {
+        let mut verifier = LogonVerifier::new()
+            .with_client("client", "secret")
+            .with_app("app", "app-secret");
+        let builder = LogonBuilder::new("client", "secret")
+            .with_app("app", "app-secret")
+            .with_cancel_on_disconnect(true)
+            .with_wordsafe_tags(true);
+        let verified = verifier
+            .verify(&logon(builder.build_with_nonce(1, NONCE)))
+            .unwrap();
+        assert_eq!(
+            verified,
+            VerifiedLogon {
+                client_id: "client".to_string(),
+                timestamp: 1,
+                app_id: Some("app".to_string()),
+                cancel_on_disconnect: Some(true),
+                use_wordsafe_tags: true,
+            }
+        );
+        assert_eq!(
+            verifier.verify(&logon(builder.build_with_nonce(1, NONCE))),
+            Err(LogonError::StaleTimestamp {
+                last: 1,
+                received: 1
+            })
+        );
+        assert!(
+            verifier
+                .verify(&logon(builder.build_with_nonce(2, NONCE)))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_verifier_rejections() {
+        let mut verifier = LogonVerifier::new()
+            .with_client("client", "secret")
+            .with_app("app", "app-secret");
+
+        let wrong_secret = LogonBuilder::new("client", "other").build_with_nonce(1, NONCE);
+        assert_eq!(
+            verifier.verify(&logon(wrong_secret)),
+            Err(LogonError::InvalidPassword)
+        );
+
+        let unknown = LogonBuilder::new("nobody", "secret").build_with_nonce(1, NONCE);
+        assert_eq!(
+            verifier.verify(&logon(unknown)),
+            Err(LogonError::UnknownClient("nobody".to_string()))
+        );
+
+        let wrong_app = LogonBuilder::new("client", "secret")
+            .with_app("app", "other")
+            .build_with_nonce(1, NONCE);
+        assert_eq!(
+            verifier.verify(&logon(wrong_app)),
+            Err(LogonError::InvalidAppSignature)
+        );
+
+        let malformed = LogonBuilder::new("client", "secret").build_with_nonce(1, "!!");
+        assert_eq!(
+            verifier.verify(&logon(malformed)),
+            Err(LogonError::MalformedRawData("1.!!".to_string()))
+        );
+
+        let long_nonce = BASE64_STANDARD.encode([0u8; LOGON_NONCE_MAX_BYTES + 1]);
+        let long = LogonBuilder::new("client", "secret").build_with_nonce(1, &long_nonce);
+        assert_eq!(
+            verifier.verify(&logon(long)),
+            Err(LogonError::NonceTooLong(LOGON_NONCE_MAX_BYTES + 1))
+        );
+
+        let mut missing = LogonBuilder::new("client", "secret").build_with_nonce(1, NONCE);
+        missing.remove(PASSWORD);
+        assert_eq!(
+            verifier.verify(&logon(missing)),
+            Err(LogonError::MissingField(PASSWORD))
+        );
+        assert_eq!(
+            verifier.verify(&FixMessage::new(MsgType::Heartbeat)),
+            Err(LogonError::NotALogon(MsgType::Heartbeat))
+        );
+    }
+
+    #[test]
+    fn test_logon_through_session_and_codec() {
+        let mut client = FixSession::in_memory(SessionConfig::new("CLIENT", "DERIBITSERVER"));
+        let mut server = FixSession::in_memory(SessionConfig::new("DERIBITSERVER", "CLIENT"));
+        let mut verifier = LogonVerifier::new().with_client("client", "secret");
+
+        let fields = LogonBuilder::new("client", "secret").build();
+        let frame = client.logon(fields, 0).unwrap().encode();
+        let mut decoder = FixDecoder::new();
+        decoder.extend(&frame);
+        let received = decoder.next_message().unwrap().unwrap();
+
+        assert_eq!(verifier.verify(&received).unwrap().client_id, "client");
+        let actions = server.handle(received, 0);
+        assert!(actions.contains(&SessionAction::LoggedOn));
+    }
+}
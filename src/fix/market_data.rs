@@ -0,0 +1,1123 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Market data and security definition mapping
+//!
+//! Builds `MarketDataRequest(V)` and `SecurityListRequest(x)`, and parses
+//! `MarketDataSnapshotFullRefresh(W)`, `MarketDataIncrementalRefresh(X)` and
+//! `SecurityList(y)` into the [`OrderBook`], [`TickerData`], [`Trade`] and
+//! [`Instrument`] models shared with JSON-RPC.
+//!
+//! Book and trade sizes are reported in contracts and are scaled by
+//! `ContractMultiplier(231)`, or by the contract size passed to
+//! `from_message` when the message omits it, matching the JSON-RPC amounts.
+
+use crate::fix::error::FixError;
+use crate::fix::message::{FieldMap, FixMessage};
+use crate::fix::orders::{contract_multiplier, invalid, parse_side};
+use crate::fix::tags::{
+    CONTRACT_MULTIPLIER, CURRENCY, CURRENT_FUNDING, DERIBIT_LABEL, DERIBIT_SINCE_TIMESTAMP,
+    DERIBIT_SKIP_BLOCK_TRADES, DERIBIT_TRADE_AMOUNT, DERIBIT_TRADE_ID, DISPLAY_INCREMENT_STEPS,
+    FUNDING_8H, ISSUE_DATE, MARK_PRICE, MARKET_DEPTH, MATURITY_DATE, MATURITY_TIME, MD_ENTRY_DATE,
+    MD_ENTRY_PX, MD_ENTRY_SIZE, MD_ENTRY_TYPE, MD_REQ_ID, MD_UPDATE_ACTION, MD_UPDATE_TYPE,
+    MIN_PRICE_INCREMENT, MIN_TRADE_VOL, MSG_TYPE, MsgType, NO_MD_ENTRIES, NO_MD_ENTRY_TYPES,
+    NO_RELATED_SYM, NO_TICK_RULES, OPEN_INTEREST, ORDER_ID, PRICE, PRICE_QUOTE_CURRENCY,
+    PUT_OR_CALL, SECONDARY_CURRENCY, SECURITY_LIST_REQUEST_TYPE, SECURITY_REQ_ID,
+    SECURITY_RESPONSE_ID, SECURITY_STATUS, SECURITY_TYPE, SENDING_TIME, SETTL_CURRENCY, SETTL_TYPE,
+    SIDE, START_TICK_PRICE_RANGE, STRIKE_PRICE, SUBSCRIPTION_REQUEST_TYPE, SYMBOL, TEXT,
+    TICK_INCREMENT, TRADE_VOLUME_24H, TRD_MATCH_ID, UNDERLYING_PX, UNDERLYING_SYMBOL,
+};
+use crate::model::instrument::{Instrument, InstrumentKind, OptionType, TickSizeStep};
+use crate::model::market_data::{OrderBook, OrderBookEntry};
+use crate::model::ticker::{TickerData, TickerStats};
+use crate::model::trade::{Liquidity, Trade};
+use chrono::{NaiveDate, NaiveTime};
+use std::fmt;
+
+/// `SubscriptionRequestType(263)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionRequestType {
+    /// Single snapshot
+    Snapshot,
+    /// Snapshot followed by updates
+    Subscribe,
+    /// Cancel a previous subscription
+    Unsubscribe,
+}
+
+impl SubscriptionRequestType {
+    /// Returns the FIX value
+    #[must_use]
+    pub fn as_fix(&self) -> &'static str {
+        match self {
+            Self::Snapshot => "0",
+            Self::Subscribe => "1",
+            Self::Unsubscribe => "2",
+        }
+    }
+}
+
+/// `MDEntryType(269)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MdEntryType {
+    /// Bid side of the order book
+    Bid,
+    /// Ask side of the order book
+    Offer,
+    /// Recent trade
+    Trade,
+    /// Index value of an index instrument
+    IndexValue,
+    /// Estimated delivery price of an index instrument
+    SettlementPrice,
+}
+
+impl MdEntryType {
+    /// Returns the FIX value
+    #[must_use]
+    pub fn as_fix(&self) -> &'static str {
+        match self {
+            Self::Bid => "0",
+            Self::Offer => "1",
+            Self::Trade => "2",
+            Self::IndexValue => "3",
+            Self::SettlementPrice => "6",
+        }
+    }
+
+    /// Parses a FIX value
+    #[must_use]
+    pub fn from_fix(value: &str) -> Option<Self> {
+        match value {
+            "0" => Some(Self::Bid),
+            "1" => Some(Self::Offer),
+            "2" => Some(Self::Trade),
+            "3" => Some(Self::IndexValue),
+            "6" => Some(Self::SettlementPrice),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MdEntryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_fix())
+    }
+}
+
+/// `MDUpdateAction(279)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MdUpdateAction {
+    /// New price level
+    New,
+    /// Changed price level
+    Change,
+    /// Removed price level
+    Delete,
+}
+
+impl MdUpdateAction {
+    /// Parses a FIX value
+    #[must_use]
+    pub fn from_fix(value: &str) -> Option<Self> {
+        match value {
+            "0" => Some(Self::New),
+            "1" => Some(Self::Change),
+            "2" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Builder of a `MarketDataRequest(V)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketDataRequest {
+    md_req_id: String,
+    subscription: SubscriptionRequestType,
+    market_depth: Option<u32>,
+    incremental: Option<bool>,
+    entry_types: Vec<MdEntryType>,
+    symbols: Vec<String>,
+    skip_block_trades: Option<bool>,
+    trade_amount: Option<u32>,
+    since_timestamp: Option<u64>,
+}
+
+impl MarketDataRequest {
+    /// Request a single snapshot of the books of `symbols`
+    pub fn snapshot(md_req_id: impl Into<String>, symbols: Vec<String>) -> Self {
+        Self::new(md_req_id, SubscriptionRequestType::Snapshot, symbols)
+    }
+
+    /// Subscribe to a full book snapshot followed by incremental refreshes
+    pub fn incremental(md_req_id: impl Into<String>, symbols: Vec<String>) -> Self {
+        let mut request = Self::new(md_req_id, SubscriptionRequestType::Subscribe, symbols);
+        request.market_depth = Some(0);
+        request.incremental = Some(true);
+        request
+    }
+
+    /// Subscribe to full refreshes of the top `depth` levels (1, 10 or 20)
+    pub fn full_refresh(md_req_id: impl Into<String>, symbols: Vec<String>, depth: u32) -> Self {
+        let mut request = Self::new(md_req_id, SubscriptionRequestType::Subscribe, symbols);
+        request.market_depth = Some(depth);
+        request.incremental = Some(false);
+        request
+    }
+
+    /// Cancel the subscription opened with `md_req_id`
+    pub fn unsubscribe(md_req_id: impl Into<String>, symbols: Vec<String>) -> Self {
+        Self::new(md_req_id, SubscriptionRequestType::Unsubscribe, symbols)
+    }
+
+    /// Replace the requested entry types, bids and offers by default
+    #[must_use]
+    pub fn with_entry_types(mut self, entry_types: Vec<MdEntryType>) -> Self {
+        self.entry_types = entry_types;
+        self
+    }
+
+    /// Also request recent trades, optionally limited in number or by time
+    #[must_use]
+    pub fn with_trades(mut self, amount: Option<u32>, since_timestamp: Option<u64>) -> Self {
+        if !self.entry_types.contains(&MdEntryType::Trade) {
+            self.entry_types.push(MdEntryType::Trade);
+        }
+        self.trade_amount = amount;
+        self.since_timestamp = since_timestamp;
+        self
+    }
+
+    /// Set `DeribitSkipBlockTrades(9011)`
+    #[must_use]
+    pub fn with_skip_block_trades(mut self, skip: bool) -> Self {
+        self.skip_block_trades = Some(skip);
+        self
+    }
+
+    /// Get the request id
+    #[must_use]
+    pub fn md_req_id(&self) -> &str {
+        &self.md_req_id
+    }
+
+    /// Build the FIX message
+    #[must_use]
+    pub fn to_message(&self) -> FixMessage {
+        let entry_types = self
+            .entry_types
+            .iter()
+            .map(|entry_type| FieldMap::new().with(MD_ENTRY_TYPE, entry_type.as_fix()))
+            .collect();
+        let symbols = self
+            .symbols
+            .iter()
+            .map(|symbol| FieldMap::new().with(SYMBOL, symbol.as_str()))
+            .collect();
+        let mut message = FixMessage::new(MsgType::MarketDataRequest)
+            .with_field(MD_REQ_ID, self.md_req_id.as_str())
+            .with_field(SUBSCRIPTION_REQUEST_TYPE, self.subscription.as_fix());
+        let body = &mut message.body;
+        if let Some(depth) = self.market_depth {
+            body.set(MARKET_DEPTH, depth.to_string());
+        }
+        if let Some(incremental) = self.incremental {
+            body.set(MD_UPDATE_TYPE, if incremental { "1" } else { "0" });
+        }
+        if let Some(skip) = self.skip_block_trades {
+            body.set(DERIBIT_SKIP_BLOCK_TRADES, if skip { "Y" } else { "N" });
+        }
+        if let Some(amount) = self.trade_amount {
+            body.set(DERIBIT_TRADE_AMOUNT, amount.to_string());
+        }
+        if let Some(since) = self.since_timestamp {
+            body.set(DERIBIT_SINCE_TIMESTAMP, since.to_string());
+        }
+        body.set_group(NO_MD_ENTRY_TYPES, entry_types)
+            .set_group(NO_RELATED_SYM, symbols);
+        message
+    }
+
+    fn new(
+        md_req_id: impl Into<String>,
+        subscription: SubscriptionRequestType,
+        symbols: Vec<String>,
+    ) -> Self {
+        Self {
+            md_req_id: md_req_id.into(),
+            subscription,
+            market_depth: None,
+            incremental: None,
+            entry_types: vec![MdEntryType::Bid, MdEntryType::Offer],
+            symbols,
+            skip_block_trades: None,
+            trade_amount: None,
+            since_timestamp: None,
+        }
+    }
+}
+
+/// A parsed `MarketDataSnapshotFullRefresh(W)`
+#[derive(Debug, Clone)]
+pub struct MarketDataSnapshot {
+    /// `MDReqID(262)` of the request, if applicable
+    pub md_req_id: Option<String>,
+    /// Book levels, timestamped with `SendingTime(52)`
+    pub order_book: OrderBook,
+    /// Ticker built from the book and the instrument statistics
+    ///
+    /// `volume` is `TradeVolume24h(100087)` in contracts.
+    pub ticker: TickerData,
+    /// Recent trades
+    pub trades: Vec<Trade>,
+}
+
+impl TryFrom<&FixMessage> for MarketDataSnapshot {
+    type Error = FixError;
+
+    /// Fails with a missing `ContractMultiplier(231)`; use
+    /// [`MarketDataSnapshot::from_message`] to supply the instrument's
+    /// contract size instead.
+    fn try_from(message: &FixMessage) -> Result<Self, Self::Error> {
+        Self::parse(message, None)
+    }
+}
+
+impl MarketDataSnapshot {
+    /// Parse a snapshot, scaling sizes by `contract_size` when it has no
+    /// `ContractMultiplier(231)`
+    pub fn from_message(message: &FixMessage, contract_size: f64) -> Result<Self, FixError> {
+        Self::parse(message, Some(contract_size))
+    }
+
+    fn parse(message: &FixMessage, contract_size: Option<f64>) -> Result<Self, FixError> {
+        expect_msg_type(message, MsgType::MarketDataSnapshotFullRefresh)?;
+        let body = &message.body;
+        let symbol = body.require(SYMBOL)?;
+        let multiplier = contract_multiplier(body, contract_size)?;
+        let mark_price = body.get_parsed::<f64>(MARK_PRICE)?;
+        let timestamp = message.header.get_timestamp(SENDING_TIME)?.unwrap_or(0);
+
+        let mut order_book = OrderBook::new(symbol.to_string(), timestamp as i64, 0);
+        let mut trades = Vec::new();
+        let mut index_price = None;
+        let mut estimated_delivery_price = None;
+        for entry in body.group(NO_MD_ENTRIES).unwrap_or_default() {
+            match entry_type(entry)? {
+                Some(MdEntryType::Bid) => order_book.bids.push(level(entry, multiplier)?),
+                Some(MdEntryType::Offer) => order_book.asks.push(level(entry, multiplier)?),
+                Some(MdEntryType::Trade) => {
+                    trades.push(trade(entry, symbol, multiplier, mark_price)?);
+                }
+                Some(MdEntryType::IndexValue) => index_price = entry.get_parsed(MD_ENTRY_PX)?,
+                Some(MdEntryType::SettlementPrice) => {
+                    estimated_delivery_price = entry.get_parsed(MD_ENTRY_PX)?;
+                }
+                None => {}
+            }
+        }
+        order_book.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        order_book.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        let volume = body.get_parsed(TRADE_VOLUME_24H)?;
+        let ticker = TickerData {
+            instrument_name: symbol.to_string(),
+            last_price: trades.last().map(|trade| trade.price),
+            mark_price: mark_price.or(index_price).unwrap_or(0.0),
+            best_bid_price: order_book.best_bid(),
+            best_ask_price: order_book.best_ask(),
+            best_bid_amount: order_book.bids.first().map_or(0.0, |level| level.amount),
+            best_ask_amount: order_book.asks.first().map_or(0.0, |level| level.amount),
+            volume,
+            volume_usd: None,
+            open_interest: body.get_parsed(OPEN_INTEREST)?,
+            high: None,
+            low: None,
+            price_change: None,
+            price_change_percentage: None,
+            bid_iv: None,
+            ask_iv: None,
+            mark_iv: None,
+            timestamp,
+            state: "open".to_string(),
+            settlement_price: None,
+            stats: TickerStats {
+                volume: volume.unwrap_or(0.0),
+                volume_usd: None,
+                price_change: None,
+                high: None,
+                low: None,
+            },
+            greeks: None,
+            index_price,
+            min_price: None,
+            max_price: None,
+            interest_rate: None,
+            underlying_price: body.get_parsed(UNDERLYING_PX)?,
+            underlying_index: body.get(UNDERLYING_SYMBOL).map(str::to_string),
+            estimated_delivery_price,
+            current_funding: body.get_parsed(CURRENT_FUNDING)?,
+            funding_8h: body.get_parsed(FUNDING_8H)?,
+            interest_value: None,
+        };
+
+        Ok(Self {
+            md_req_id: body.get(MD_REQ_ID).map(str::to_string),
+            order_book,
+            ticker,
+            trades,
+        })
+    }
+}
+
+/// A single book change of a `MarketDataIncrementalRefresh(X)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MdBookChange {
+    /// Action applied to the price level
+    pub action: MdUpdateAction,
+    /// Whether the level is on the bid side
+    pub is_bid: bool,
+    /// Level price
+    pub price: f64,
+    /// New level amount, scaled by the contract multiplier
+    pub amount: f64,
+}
+
+/// A parsed `MarketDataIncrementalRefresh(X)`
+#[derive(Debug, Clone)]
+pub struct MarketDataIncrement {
+    /// Instrument name
+    pub instrument_name: String,
+    /// `MDReqID(262)` of the request, if applicable
+    pub md_req_id: Option<String>,
+    /// `SendingTime(52)` in milliseconds
+    pub timestamp: u64,
+    /// Book changes, in message order
+    pub changes: Vec<MdBookChange>,
+    /// New trades
+    pub trades: Vec<Trade>,
+    /// Mark price, if sent
+    pub mark_price: Option<f64>,
+    /// Open interest, if sent
+    pub open_interest: Option<f64>,
+    /// 24h volume in contracts, if sent
+    pub volume_24h: Option<f64>,
+}
+
+impl MarketDataIncrement {
+    /// Apply the book changes to a snapshot of the same instrument
+    pub fn apply_to(&self, book: &mut OrderBook) {
+        for change in &self.changes {
+            let (levels, descending) = if change.is_bid {
+                (&mut book.bids, true)
+            } else {
+                (&mut book.asks, false)
+            };
+            let position = levels.iter().position(|level| level.price == change.price);
+            match (change.action, position) {
+                (MdUpdateAction::Delete, Some(index)) => {
+                    levels.remove(index);
+                }
+                (MdUpdateAction::Delete, None) => {}
+                (_, Some(index)) => levels[index].amount = change.amount,
+                (_, None) => {
+                    let index = levels.partition_point(|level| {
+                        if descending {
+                            level.price > change.price
+                        } else {
+                            level.price < change.price
+                        }
+                    });
+                    levels.insert(index, OrderBookEntry::new(change.price, change.amount));
+                }
+            }
+        }
+        book.timestamp = self.timestamp as i64;
+    }
+
+    /// Update a ticker with the statistics, trades and the resulting book
+    pub fn update_ticker(&self, ticker: &mut TickerData, book: &OrderBook) {
+        if let Some(mark_price) = self.mark_price {
+            ticker.mark_price = mark_price;
+        }
+        if let Some(open_interest) = self.open_interest {
+            ticker.open_interest = Some(open_interest);
+        }
+        if let Some(volume) = self.volume_24h {
+            ticker.volume = Some(volume);
+            ticker.stats.volume = volume;
+        }
+        if let Some(trade) = self.trades.last() {
+            ticker.last_price = Some(trade.price);
+        }
+        ticker.best_bid_price = book.best_bid();
+        ticker.best_ask_price = book.best_ask();
+        ticker.best_bid_amount = book.bids.first().map_or(0.0, |level| level.amount);
+        ticker.best_ask_amount = book.asks.first().map_or(0.0, |level| level.amount);
+        ticker.timestamp = self.timestamp;
+    }
+}
+
+impl TryFrom<&FixMessage> for MarketDataIncrement {
+    type Error = FixError;
+
+    /// Fails with a missing `ContractMultiplier(231)`; use
+    /// [`MarketDataIncrement::from_message`] to supply the instrument's
+    /// contract size instead.
+    fn try_from(message: &FixMessage) -> Result<Self, Self::Error> {
+        Self::parse(message, None)
+    }
+}
+
+impl MarketDataIncrement {
+    /// Parse an incremental refresh, scaling sizes by `contract_size` when it
+    /// has no `ContractMultiplier(231)`
+    pub fn from_message(message: &FixMessage, contract_size: f64) -> Result<Self, FixError> {
+        Self::parse(message, Some(contract_size))
+    }
+
+    fn parse(message: &FixMessage, contract_size: Option<f64>) -> Result<Self, FixError> {
+        expect_msg_type(message, MsgType::MarketDataIncrementalRefresh)?;
+        let body = &message.body;
+        let symbol = body.require(SYMBOL)?;
+        let multiplier = contract_multiplier(body, contract_size)?;
+        let mark_price = body.get_parsed::<f64>(MARK_PRICE)?;
+
+        let mut changes = Vec::new();
+        let mut trades = Vec::new();
+        for entry in body.group(NO_MD_ENTRIES).unwrap_or_default() {
+            let value = entry.require(MD_UPDATE_ACTION)?;
+            let action =
+                MdUpdateAction::from_fix(value).ok_or_else(|| invalid(MD_UPDATE_ACTION, value))?;
+            let is_bid = match entry_type(entry)? {
+                Some(MdEntryType::Bid) => true,
+                Some(MdEntryType::Offer) => false,
+                Some(MdEntryType::Trade) => {
+                    trades.push(trade(entry, symbol, multiplier, mark_price)?);
+                    continue;
+                }
+                _ => continue,
+            };
+            changes.push(MdBookChange {
+                action,
+                is_bid,
+                price: entry.require_parsed(MD_ENTRY_PX)?,
+                amount: entry.get_parsed::<f64>(MD_ENTRY_SIZE)?.unwrap_or(0.0) * multiplier,
+            });
+        }
+
+        Ok(Self {
+            instrument_name: symbol.to_string(),
+            md_req_id: body.get(MD_REQ_ID).map(str::to_string),
+            timestamp: message.header.get_timestamp(SENDING_TIME)?.unwrap_or(0),
+            changes,
+            trades,
+            mark_price,
+            open_interest: body.get_parsed(OPEN_INTEREST)?,
+            volume_24h: body.get_parsed(TRADE_VOLUME_24H)?,
+        })
+    }
+}
+
+/// Builder of a `SecurityListRequest(x)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityListRequest {
+    security_req_id: String,
+    subscription: Option<SubscriptionRequestType>,
+    currency: Option<String>,
+    secondary_currency: Option<String>,
+    kind: Option<InstrumentKind>,
+    display_increment_steps: bool,
+}
+
+impl SecurityListRequest {
+    /// Request the list of all instruments
+    pub fn new(security_req_id: impl Into<String>) -> Self {
+        Self {
+            security_req_id: security_req_id.into(),
+            subscription: None,
+            currency: None,
+            secondary_currency: None,
+            kind: None,
+            display_increment_steps: false,
+        }
+    }
+
+    /// Limit the list to a currency pair; the quote currency defaults to USD
+    #[must_use]
+    pub fn with_currency(mut self, currency: impl Into<String>, quote: Option<String>) -> Self {
+        self.currency = Some(currency.into());
+        self.secondary_currency = quote;
+        self
+    }
+
+    /// Limit the list to an instrument kind
+    #[must_use]
+    pub fn with_kind(mut self, kind: InstrumentKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Include price increment steps with `DisplayIncrementSteps(9018)`
+    #[must_use]
+    pub fn with_increment_steps(mut self) -> Self {
+        self.display_increment_steps = true;
+        self
+    }
+
+    /// Set `SubscriptionRequestType(263)` to follow new and terminated instruments
+    #[must_use]
+    pub fn with_subscription(mut self, subscription: SubscriptionRequestType) -> Self {
+        self.subscription = Some(subscription);
+        self
+    }
+
+    /// Build the FIX message
+    #[must_use]
+    pub fn to_message(&self) -> FixMessage {
+        let mut message = FixMessage::new(MsgType::SecurityListRequest)
+            .with_field(SECURITY_REQ_ID, self.security_req_id.as_str())
+            .with_field(SECURITY_LIST_REQUEST_TYPE, "0");
+        let body = &mut message.body;
+        if let Some(subscription) = self.subscription {
+            body.set(SUBSCRIPTION_REQUEST_TYPE, subscription.as_fix());
+        }
+        if self.display_increment_steps {
+            body.set(DISPLAY_INCREMENT_STEPS, "Y");
+        }
+        if let Some(currency) = &self.currency {
+            body.set(CURRENCY, currency.as_str());
+        }
+        if let Some(quote) = &self.secondary_currency {
+            body.set(SECONDARY_CURRENCY, quote.as_str());
+        }
+        if let Some(kind) = &self.kind {
+            body.set(SECURITY_TYPE, security_type(kind));
+        }
+        message
+    }
+}
+
+/// A parsed `SecurityList(y)`
+#[derive(Debug, Clone)]
+pub struct SecurityList {
+    /// `SecurityReqID(320)` of the request
+    pub security_req_id: String,
+    /// `SecurityResponseID(322)`
+    pub security_response_id: Option<String>,
+    /// Listed instruments
+    ///
+    /// Status notifications for closed or settled instruments only carry the
+    /// name and `is_active`.
+    pub instruments: Vec<Instrument>,
+}
+
+impl TryFrom<&FixMessage> for SecurityList {
+    type Error = FixError;
+
+    fn try_from(message: &FixMessage) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MsgType::SecurityList)?;
+        let body = &message.body;
+        let instruments = body
+            .group(NO_RELATED_SYM)
+            .unwrap_or_default()
+            .iter()
+            .map(instrument)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            security_req_id: body.require(SECURITY_REQ_ID)?.to_string(),
+            security_response_id: body.get(SECURITY_RESPONSE_ID).map(str::to_string),
+            instruments,
+        })
+    }
+}
+
+/// Deribit expiries settle at 08:00 UTC when `MaturityTime(1079)` is absent
+const DEFAULT_EXPIRY_TIME: NaiveTime = match NaiveTime::from_hms_opt(8, 0, 0) {
+    Some(time) => time,
+    None => panic!("valid time"),
+};
+
+fn instrument(entry: &FieldMap) -> Result<Instrument, FixError> {
+    let kind = match entry.get(SECURITY_TYPE) {
+        Some("FUT") => Some(InstrumentKind::Future),
+        Some("OPT") => Some(InstrumentKind::Option),
+        Some("FUTCO") => Some(InstrumentKind::FutureCombo),
+        Some("OPTCO") => Some(InstrumentKind::OptionCombo),
+        Some("FXSPOT") => Some(InstrumentKind::Spot),
+        _ => None,
+    };
+    let option_type = match entry.get(PUT_OR_CALL) {
+        Some("0") => Some(OptionType::Put),
+        Some("1") => Some(OptionType::Call),
+        Some(value) => return Err(invalid(PUT_OR_CALL, value)),
+        None => None,
+    };
+    let is_active = match entry.get(SECURITY_STATUS) {
+        Some("1") => Some(true),
+        Some("2" | "4" | "10" | "12") => Some(false),
+        Some(value) => return Err(invalid(SECURITY_STATUS, value)),
+        None => None,
+    };
+    let tick_size_steps = entry
+        .group(NO_TICK_RULES)
+        .map(|rules| {
+            rules
+                .iter()
+                .map(|rule| {
+                    Ok(TickSizeStep {
+                        above_price: rule.require_parsed(START_TICK_PRICE_RANGE)?,
+                        tick_size: rule.require_parsed(TICK_INCREMENT)?,
+                    })
+                })
+                .collect::<Result<Vec<_>, FixError>>()
+        })
+        .transpose()?;
+    let settlement_period = entry.get(SETTL_TYPE).map(|value| {
+        match value.chars().next() {
+            Some('M') => "month",
+            Some('W') => "week",
+            Some('D') => "day",
+            _ => value,
+        }
+        .to_string()
+    });
+    let currency = entry.get(CURRENCY).map(str::to_string);
+    let quote_currency = entry.get(PRICE_QUOTE_CURRENCY).map(str::to_string);
+
+    Ok(Instrument {
+        instrument_name: entry.require(SYMBOL)?.to_string(),
+        price_index: None,
+        kind,
+        currency: currency.clone(),
+        is_active,
+        expiration_timestamp: expiration(entry)?,
+        strike: entry.get_parsed(STRIKE_PRICE)?,
+        option_type,
+        tick_size: entry.get_parsed(MIN_PRICE_INCREMENT)?,
+        tick_size_steps,
+        min_trade_amount: entry.get_parsed(MIN_TRADE_VOL)?,
+        contract_size: entry.get_parsed(CONTRACT_MULTIPLIER)?,
+        settlement_period,
+        instrument_type: None,
+        quote_currency: quote_currency.clone(),
+        settlement_currency: entry.get(SETTL_CURRENCY).map(str::to_string),
+        creation_timestamp: entry.get_timestamp(ISSUE_DATE)?.map(|ms| ms as i64),
+        max_leverage: None,
+        maker_commission: None,
+        taker_commission: None,
+        instrument_id: None,
+        base_currency: currency,
+        counter_currency: quote_currency,
+        block_trade_commission: None,
+        block_trade_min_trade_amount: None,
+        block_trade_tick_size: None,
+        max_liquidation_commission: None,
+        rfq: None,
+    })
+}
+
+/// Expiration from `MaturityDate(541)` and the optional `MaturityTime(1079)`
+fn expiration(entry: &FieldMap) -> Result<Option<i64>, FixError> {
+    let Some(date) = entry.get(MATURITY_DATE) else {
+        return Ok(None);
+    };
+    let date =
+        NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| invalid(MATURITY_DATE, date))?;
+    let (time, offset_seconds) = match entry.get(MATURITY_TIME) {
+        Some(value) => parse_tz_time(value).ok_or_else(|| invalid(MATURITY_TIME, value))?,
+        None => (DEFAULT_EXPIRY_TIME, 0),
+    };
+    let utc = date.and_time(time).and_utc().timestamp_millis() - offset_seconds * 1000;
+    Ok(Some(utc))
+}
+
+/// Parse a `TZTimeOnly` such as `08:00:00Z` or `10:00:00+02:00`
+fn parse_tz_time(value: &str) -> Option<(NaiveTime, i64)> {
+    let (time, offset) = match value.find(['Z', '+', '-']) {
+        Some(index) => value.split_at(index),
+        None => (value, "Z"),
+    };
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok()?;
+    let offset_seconds = match offset {
+        "Z" => 0,
+        _ => {
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (hours, minutes) = offset[1..].split_once(':').unwrap_or((&offset[1..], "0"));
+            sign * (hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60)
+        }
+    };
+    Some((time, offset_seconds))
+}
+
+fn security_type(kind: &InstrumentKind) -> &'static str {
+    match kind {
+        InstrumentKind::Future => "FUT",
+        InstrumentKind::Option => "OPT",
+        InstrumentKind::FutureCombo => "FUTCO",
+        InstrumentKind::OptionCombo => "OPTCO",
+        InstrumentKind::Spot => "FXSPOT",
+    }
+}
+
+/// Entry type of a group entry; unknown types are skipped
+fn entry_type(entry: &FieldMap) -> Result<Option<MdEntryType>, FixError> {
+    Ok(entry.get(MD_ENTRY_TYPE).and_then(MdEntryType::from_fix))
+}
+
+fn level(entry: &FieldMap, multiplier: f64) -> Result<OrderBookEntry, FixError> {
+    Ok(OrderBookEntry::new(
+        entry.require_parsed(MD_ENTRY_PX)?,
+        entry.require_parsed::<f64>(MD_ENTRY_SIZE)? * multiplier,
+    ))
+}
+
+/// Public trade as seen by the taker, whose order is `OrderID(37)`
+fn trade(
+    entry: &FieldMap,
+    symbol: &str,
+    multiplier: f64,
+    mark_price: Option<f64>,
+) -> Result<Trade, FixError> {
+    Ok(Trade {
+        trade_id: entry.get(DERIBIT_TRADE_ID).unwrap_or_default().to_string(),
+        instrument_name: symbol.to_string(),
+        order_id: entry.get(ORDER_ID).unwrap_or_default().to_string(),
+        direction: parse_side(entry.require(SIDE)?)?,
+        amount: entry.require_parsed::<f64>(MD_ENTRY_SIZE)? * multiplier,
+        price: entry.require_parsed(MD_ENTRY_PX)?,
+        timestamp: entry.get_timestamp(MD_ENTRY_DATE)?.unwrap_or(0) as i64,
+        fee: 0.0,
+        fee_currency: String::new(),
+        liquidity: Liquidity::Taker,
+        mark_price: mark_price.unwrap_or(0.0),
+        index_price: entry.get_parsed(PRICE)?.unwrap_or(0.0),
+        instrument_kind: None,
+        trade_seq: entry.get_parsed(TEXT)?,
+        user_role: None,
+        block_trade: entry.get(TRD_MATCH_ID).map(|_| true),
+        underlying_price: None,
+        iv: None,
+        label: entry.get(DERIBIT_LABEL).map(str::to_string),
+        profit_loss: None,
+        tick_direction: None,
+        self_trade: None,
+        contracts: None,
+        liquidation: None,
+        block_trade_id: None,
+        block_trade_leg_count: None,
+        block_rfq_id: None,
+        combo_id: None,
+        combo_trade_id: None,
+    })
+}
+
+fn expect_msg_type(message: &FixMessage, expected: MsgType) -> Result<(), FixError> {
+    if message.msg_type == expected {
+        Ok(())
+    } else {
+        Err(invalid(MSG_TYPE, message.msg_type.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::tags::SECURITY_DESC;
+
+    fn book_entry(entry_type: MdEntryType, price: &str, size: &str) -> FieldMap {
+        FieldMap::new()
+            .with(MD_ENTRY_TYPE, entry_type.as_fix())
+            .with(MD_ENTRY_PX, price)
+            .with(MD_ENTRY_SIZE, size)
+    }
+
+    fn incremental_entry(
+        action: &str,
+        entry_type: MdEntryType,
+        price: &str,
+        size: &str,
+    ) -> FieldMap {
+        FieldMap::new()
+            .with(MD_UPDATE_ACTION, action)
+            .with(MD_ENTRY_TYPE, entry_type.as_fix())
+            .with(MD_ENTRY_PX, price)
+            .with(MD_ENTRY_SIZE, size)
+    }
+
+    fn snapshot() -> FixMessage {
+        let trade = book_entry(MdEntryType::Trade, "65000.5", "3")
+            .with(MD_ENTRY_DATE, "20261018-10:00:00.250")
+            .with(DERIBIT_TRADE_ID, "BTC-1001")
+            .with(SIDE, "1")
+            .with(PRICE, "64990.1")
+            .with(TEXT, "77")
+            .with(ORDER_ID, "BTC-5");
+        FixMessage::new(MsgType::MarketDataSnapshotFullRefresh)
+            .with_header("DERIBITSERVER", "CLIENT", 5, "20261018-10:00:01.000")
+            .with_field(SYMBOL, "BTC-PERPETUAL")
+            .with_field(MD_REQ_ID, "md-1")
+            .with_field(CONTRACT_MULTIPLIER, "10")
+            .with_field(TRADE_VOLUME_24H, "12345")
+            .with_field(MARK_PRICE, "65001")
+            .with_field(OPEN_INTEREST, "900")
+            .with_field(CURRENT_FUNDING, "0.0001")
+            .with_field(FUNDING_8H, "0.0003")
+            .with_group(
+                NO_MD_ENTRIES,
+                vec![
+                    book_entry(MdEntryType::Bid, "64999", "2"),
+                    book_entry(MdEntryType::Bid, "65000", "1"),
+                    book_entry(MdEntryType::Offer, "65002", "4"),
+                    book_entry(MdEntryType::Offer, "65001.5", "5"),
+                    trade,
+                ],
+            )
+    }
+
+    #[test]
+    fn test_market_data_request() {
+        let message = MarketDataRequest::incremental("md-1", vec!["BTC-PERPETUAL".to_string()])
+            .with_trades(Some(50), None)
+            .with_skip_block_trades(true)
+            .to_message();
+        assert_eq!(message.msg_type, MsgType::MarketDataRequest);
+        assert_eq!(message.get(MD_REQ_ID), Some("md-1"));
+        assert_eq!(message.get(SUBSCRIPTION_REQUEST_TYPE), Some("1"));
+        assert_eq!(message.get(MARKET_DEPTH), Some("0"));
+        assert_eq!(message.get(MD_UPDATE_TYPE), Some("1"));
+        assert_eq!(message.get(DERIBIT_SKIP_BLOCK_TRADES), Some("Y"));
+        assert_eq!(message.get(DERIBIT_TRADE_AMOUNT), Some("50"));
+        let types = message.body.group(NO_MD_ENTRY_TYPES).unwrap();
+        assert_eq!(
+            types
+                .iter()
+                .map(|e| e.get(MD_ENTRY_TYPE))
+                .collect::<Vec<_>>(),
+            vec![Some("0"), Some("1"), Some("2")]
+        );
+
+        let decoded = FixMessage::decode(&message.encode()).unwrap();
+        let symbols = decoded.body.group(NO_RELATED_SYM).unwrap();
+        assert_eq!(symbols[0].get(SYMBOL), Some("BTC-PERPETUAL"));
+
+        let snapshot =
+            MarketDataRequest::full_refresh("md-2", vec!["ETH-PERPETUAL".into()], 10).to_message();
+        assert_eq!(snapshot.get(MARKET_DEPTH), Some("10"));
+        assert_eq!(snapshot.get(MD_UPDATE_TYPE), Some("0"));
+    }
+
+    #[test]
+    fn test_snapshot_into_models() {
+        let decoded = FixMessage::decode(&snapshot().encode()).unwrap();
+        let snapshot = MarketDataSnapshot::try_from(&decoded).unwrap();
+
+        assert_eq!(snapshot.md_req_id.as_deref(), Some("md-1"));
+        let book = &snapshot.order_book;
+        assert_eq!(book.instrument_name, "BTC-PERPETUAL");
+        assert_eq!(book.timestamp, 1_792_317_601_000);
+        assert_eq!(book.best_bid(), Some(65000.0));
+        assert_eq!(book.best_ask(), Some(65001.5));
+        assert_eq!(book.bids[0].amount, 10.0);
+        assert_eq!(book.asks[1].amount, 40.0);
+
+        assert_eq!(snapshot.trades.len(), 1);
+        let trade = &snapshot.trades[0];
+        assert_eq!(trade.trade_id, "BTC-1001");
+        assert_eq!(trade.direction, crate::model::order::OrderSide::Buy);
+        assert_eq!(trade.amount, 30.0);
+        assert_eq!(trade.index_price, 64990.1);
+        assert_eq!(trade.trade_seq, Some(77));
+        assert_eq!(trade.timestamp, 1_792_317_600_250);
+
+        let ticker = &snapshot.ticker;
+        assert_eq!(ticker.mark_price, 65001.0);
+        assert_eq!(ticker.last_price, Some(65000.5));
+        assert_eq!(ticker.best_bid_amount, 10.0);
+        assert_eq!(ticker.best_ask_price, Some(65001.5));
+        assert_eq!(ticker.open_interest, Some(900.0));
+        assert_eq!(ticker.volume, Some(12345.0));
+        assert_eq!(ticker.funding_8h, Some(0.0003));
+    }
+
+    #[test]
+    fn test_incremental_refresh_updates_book() {
+        let mut snapshot = MarketDataSnapshot::try_from(&snapshot()).unwrap();
+        let message = FixMessage::new(MsgType::MarketDataIncrementalRefresh)
+            .with_header("DERIBITSERVER", "CLIENT", 6, "20261018-10:00:02.000")
+            .with_field(SYMBOL, "BTC-PERPETUAL")
+            .with_field(CONTRACT_MULTIPLIER, "10")
+            .with_field(MARK_PRICE, "65003")
+            .with_group(
+                NO_MD_ENTRIES,
+                vec![
+                    incremental_entry("2", MdEntryType::Bid, "65000", "0"),
+                    incremental_entry("0", MdEntryType::Bid, "64999.5", "3"),
+                    incremental_entry("1", MdEntryType::Offer, "65002", "1"),
+                    incremental_entry("0", MdEntryType::Offer, "65001", "2"),
+                    incremental_entry("0", MdEntryType::Trade, "65001", "1")
+                        .with(SIDE, "2")
+                        .with(DERIBIT_TRADE_ID, "BTC-1002"),
+                ],
+            );
+        let decoded = FixMessage::decode(&message.encode()).unwrap();
+        let increment = MarketDataIncrement::try_from(&decoded).unwrap();
+        assert_eq!(increment.changes.len(), 4);
+        assert_eq!(increment.trades.len(), 1);
+
+        increment.apply_to(&mut snapshot.order_book);
+        let book = &snapshot.order_book;
+        let bids: Vec<_> = book.bids.iter().map(|l| (l.price, l.amount)).collect();
+        let asks: Vec<_> = book.asks.iter().map(|l| (l.price, l.amount)).collect();
+        assert_eq!(bids, vec![(64999.5, 30.0), (64999.0, 20.0)]);
+        assert_eq!(
+            asks,
+            vec![(65001.0, 20.0), (65001.5, 50.0), (65002.0, 10.0)]
+        );
+        assert_eq!(book.timestamp, 1_792_317_602_000);
+
+        increment.update_ticker(&mut snapshot.ticker, book);
+        assert_eq!(snapshot.ticker.mark_price, 65003.0);
+        assert_eq!(snapshot.ticker.last_price, Some(65001.0));
+        assert_eq!(snapshot.ticker.best_bid_price, Some(64999.5));
+        assert_eq!(snapshot.ticker.best_ask_amount, 20.0);
+    }
+
+    #[test]
+    fn test_market_data_without_contract_multiplier() {
+        let mut message = snapshot();
+        message.body.remove(CONTRACT_MULTIPLIER);
+        assert!(matches!(
+            MarketDataSnapshot::try_from(&message),
+            Err(FixError::MissingField(CONTRACT_MULTIPLIER))
+        ));
+        let snapshot = MarketDataSnapshot::from_message(&message, 10.0).unwrap();
+        assert_eq!(snapshot.order_book.bids[0].amount, 10.0);
+        assert_eq!(snapshot.trades[0].amount, 30.0);
+
+        let message = FixMessage::new(MsgType::MarketDataIncrementalRefresh)
+            .with_header("DERIBITSERVER", "CLIENT", 6, "20261018-10:00:02.000")
+            .with_field(SYMBOL, "BTC-PERPETUAL")
+            .with_group(
+                NO_MD_ENTRIES,
+                vec![incremental_entry("0", MdEntryType::Bid, "64999.5", "3")],
+            );
+        assert!(matches!(
+            MarketDataIncrement::try_from(&message),
+            Err(FixError::MissingField(CONTRACT_MULTIPLIER))
+        ));
+        let increment = MarketDataIncrement::from_message(&message, 10.0).unwrap();
+        assert_eq!(increment.changes[0].amount, 30.0);
+    }
+
+    #[test]
+    fn test_security_list_request() {
+        let message = SecurityListRequest::new("sec-1")
+            .with_currency("BTC", None)
+            .with_kind(InstrumentKind::Option)
+            .with_increment_steps()
+            .with_subscription(SubscriptionRequestType::Subscribe)
+            .to_message();
+        assert_eq!(message.msg_type, MsgType::SecurityListRequest);
+        assert_eq!(message.get(SECURITY_REQ_ID), Some("sec-1"));
+        assert_eq!(message.get(SECURITY_LIST_REQUEST_TYPE), Some("0"));
+        assert_eq!(message.get(SUBSCRIPTION_REQUEST_TYPE), Some("1"));
+        assert_eq!(message.get(DISPLAY_INCREMENT_STEPS), Some("Y"));
+        assert_eq!(message.get(CURRENCY), Some("BTC"));
+        assert_eq!(message.get(SECURITY_TYPE), Some("OPT"));
+        assert!(!message.body.contains(SECONDARY_CURRENCY));
+    }
+
+    #[test]
+    fn test_security_list_into_instruments() {
+        let option = FieldMap::new()
+            .with(SYMBOL, "BTC-27NOV26-70000-C")
+            .with(SECURITY_DESC, "option")
+            .with(SECURITY_TYPE, "OPT")
+            .with(PUT_OR_CALL, "1")
+            .with(STRIKE_PRICE, "70000")
+            .with(CURRENCY, "BTC")
+            .with(PRICE_QUOTE_CURRENCY, "BTC")
+            .with(MIN_PRICE_INCREMENT, "0.0001")
+            .with(ISSUE_DATE, "20261001-08:00:00.000")
+            .with(MATURITY_DATE, "20261127")
+            .with(MATURITY_TIME, "08:00:00Z")
+            .with(MIN_TRADE_VOL, "0.1")
+            .with(SETTL_TYPE, "M1")
+            .with(SETTL_CURRENCY, "BTC")
+            .with(CONTRACT_MULTIPLIER, "1")
+            .with_group(
+                NO_TICK_RULES,
+                vec![
+                    FieldMap::new()
+                        .with(START_TICK_PRICE_RANGE, "0.005")
+                        .with(TICK_INCREMENT, "0.0005"),
+                ],
+            );
+        let perpetual = FieldMap::new()
+            .with(SYMBOL, "ETH-PERPETUAL")
+            .with(SECURITY_TYPE, "FUT")
+            .with(CURRENCY, "ETH")
+            .with(PRICE_QUOTE_CURRENCY, "USD")
+            .with(MIN_PRICE_INCREMENT, "0.05")
+            .with(CONTRACT_MULTIPLIER, "1")
+            .with(SECURITY_STATUS, "1");
+        let settled = FieldMap::new()
+            .with(SYMBOL, "BTC-16OCT26")
+            .with(SECURITY_STATUS, "12");
+        let message = FixMessage::new(MsgType::SecurityList)
+            .with_field(SECURITY_REQ_ID, "sec-1")
+            .with_field(SECURITY_RESPONSE_ID, "resp-1")
+            .with_group(NO_RELATED_SYM, vec![option, perpetual, settled]);
+        let decoded = FixMessage::decode(&message.encode()).unwrap();
+        let list = SecurityList::try_from(&decoded).unwrap();
+
+        assert_eq!(list.security_req_id, "sec-1");
+        assert_eq!(list.instruments.len(), 3);
+        let option = &list.instruments[0];
+        assert!(option.is_option());
+        assert_eq!(option.option_type, Some(OptionType::Call));
+        assert_eq!(option.strike, Some(70000.0));
+        assert_eq!(option.expiration_timestamp, Some(1_795_766_400_000));
+        assert_eq!(option.creation_timestamp, Some(1_790_841_600_000));
+        assert_eq!(option.settlement_period.as_deref(), Some("month"));
+        assert_eq!(option.tick_size_for_price(0.01), Some(0.0005));
+        assert_eq!(option.contract_size, Some(1.0));
+        assert_eq!(option.min_trade_amount, Some(0.1));
+
+        let perpetual = &list.instruments[1];
+        assert!(perpetual.is_perpetual());
+        assert_eq!(perpetual.is_active, Some(true));
+        assert_eq!(perpetual.quote_currency.as_deref(), Some("USD"));
+
+        assert_eq!(list.instruments[2].is_active, Some(false));
+        assert_eq!(list.instruments[2].kind, None);
+    }
+
+    #[test]
+    fn test_maturity_time_offsets() {
+        let entry = FieldMap::new()
+            .with(MATURITY_DATE, "20261127")
+            .with(MATURITY_TIME, "10:00:00+02:00");
+        assert_eq!(expiration(&entry), Ok(Some(1_795_766_400_000)));
+        let entry = FieldMap::new().with(MATURITY_DATE, "20261127");
+        assert_eq!(expiration(&entry), Ok(Some(1_795_766_400_000)));
+        let entry = FieldMap::new()
+            .with(MATURITY_DATE, "2026-11-27")
+            .with(MATURITY_TIME, "08:00:00Z");
+        assert!(expiration(&entry).is_err());
+    }
+
+    #[test]
+    fn test_wrong_message_type() {
+        let heartbeat = FixMessage::new(MsgType::Heartbeat);
+        assert!(MarketDataSnapshot::try_from(&heartbeat).is_err());
+        assert!(MarketDataIncrement::try_from(&heartbeat).is_err());
+        assert!(SecurityList::try_from(&heartbeat).is_err());
+    }
+}
@@ -0,0 +1,417 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! FIX messages with ordered fields and repeating groups
+
+use crate::constants::FIX_VERSION;
+use crate::fix::error::FixError;
+use crate::fix::tags::{MSG_SEQ_NUM, MsgType, SENDER_COMP_ID, SENDING_TIME, TARGET_COMP_ID};
+use crate::utils::tools::parse_fix_time;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single entry of a [`FieldMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixField {
+    /// Plain `tag=value` field
+    Value {
+        /// Field tag
+        tag: u32,
+        /// Field value
+        value: String,
+    },
+    /// Repeating group with its `NumInGroup` tag
+    Group {
+        /// `NumInGroup` tag announcing the entries
+        count_tag: u32,
+        /// Group entries, in order
+        entries: Vec<FieldMap>,
+    },
+}
+
+impl FixField {
+    /// Get the tag of the field, or the count tag of a group
+    #[must_use]
+    pub fn tag(&self) -> u32 {
+        match self {
+            Self::Value { tag, .. } => *tag,
+            Self::Group { count_tag, .. } => *count_tag,
+        }
+    }
+}
+
+/// Ordered collection of fields, preserving the wire order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMap {
+    fields: Vec<FixField>,
+}
+
+impl FieldMap {
+    /// Create an empty field map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a field, replacing the first field with the same tag
+    pub fn set(&mut self, tag: u32, value: impl Into<String>) -> &mut Self {
+        let value = value.into();
+        match self.position(tag) {
+            Some(index) => self.fields[index] = FixField::Value { tag, value },
+            None => self.fields.push(FixField::Value { tag, value }),
+        }
+        self
+    }
+
+    /// Append a field, keeping fields with the same tag
+    pub fn push(&mut self, tag: u32, value: impl Into<String>) -> &mut Self {
+        self.fields.push(FixField::Value {
+            tag,
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Set a repeating group, replacing an existing group with the same count tag
+    pub fn set_group(&mut self, count_tag: u32, entries: Vec<FieldMap>) -> &mut Self {
+        let group = FixField::Group { count_tag, entries };
+        match self.position(count_tag) {
+            Some(index) => self.fields[index] = group,
+            None => self.fields.push(group),
+        }
+        self
+    }
+
+    /// Builder-style variant of [`FieldMap::set`]
+    #[must_use]
+    pub fn with(mut self, tag: u32, value: impl Into<String>) -> Self {
+        self.set(tag, value);
+        self
+    }
+
+    /// Builder-style variant of [`FieldMap::set_group`]
+    #[must_use]
+    pub fn with_group(mut self, count_tag: u32, entries: Vec<FieldMap>) -> Self {
+        self.set_group(count_tag, entries);
+        self
+    }
+
+    /// Get the value of the first field with the given tag
+    #[must_use]
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.fields.iter().find_map(|field| match field {
+            FixField::Value { tag: t, value } if *t == tag => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Get every value of a tag, in order
+    #[must_use]
+    pub fn get_all(&self, tag: u32) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter_map(|field| match field {
+                FixField::Value { tag: t, value } if *t == tag => Some(value.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the value of a required field
+    pub fn require(&self, tag: u32) -> Result<&str, FixError> {
+        self.get(tag).ok_or(FixError::MissingField(tag))
+    }
+
+    /// Parse the value of an optional field
+    pub fn get_parsed<T: FromStr>(&self, tag: u32) -> Result<Option<T>, FixError> {
+        self.get(tag)
+            .map(|value| {
+                value.parse().map_err(|_| FixError::InvalidValue {
+                    tag,
+                    value: value.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Parse the value of a required field
+    pub fn require_parsed<T: FromStr>(&self, tag: u32) -> Result<T, FixError> {
+        self.get_parsed(tag)?.ok_or(FixError::MissingField(tag))
+    }
+
+    /// Parse a `Y`/`N` boolean field
+    pub fn get_bool(&self, tag: u32) -> Result<Option<bool>, FixError> {
+        match self.get(tag) {
+            None => Ok(None),
+            Some("Y") => Ok(Some(true)),
+            Some("N") => Ok(Some(false)),
+            Some(value) => Err(FixError::InvalidValue {
+                tag,
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    /// Parse a `UTCTimestamp` field into milliseconds since the epoch
+    pub fn get_timestamp(&self, tag: u32) -> Result<Option<u64>, FixError> {
+        self.get(tag)
+            .map(|value| {
+                parse_fix_time(value)
+                    .map(|time| time.timestamp_millis() as u64)
+                    .map_err(|_| FixError::InvalidValue {
+                        tag,
+                        value: value.to_string(),
+                    })
+            })
+            .transpose()
+    }
+
+    /// Get the entries of a repeating group
+    #[must_use]
+    pub fn group(&self, count_tag: u32) -> Option<&[FieldMap]> {
+        self.fields.iter().find_map(|field| match field {
+            FixField::Group {
+                count_tag: t,
+                entries,
+            } if *t == count_tag => Some(entries.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// Remove every field and group with the given tag
+    ///
+    /// Returns the value of the first removed plain field.
+    pub fn remove(&mut self, tag: u32) -> Option<String> {
+        let mut removed = None;
+        self.fields.retain(|field| {
+            if field.tag() != tag {
+                return true;
+            }
+            if let (None, FixField::Value { value, .. }) = (&removed, field) {
+                removed = Some(value.clone());
+            }
+            false
+        });
+        removed
+    }
+
+    /// Check whether a field or group with the given tag is present
+    #[must_use]
+    pub fn contains(&self, tag: u32) -> bool {
+        self.position(tag).is_some()
+    }
+
+    /// Iterate over the fields in wire order
+    pub fn iter(&self) -> std::slice::Iter<'_, FixField> {
+        self.fields.iter()
+    }
+
+    /// Get the number of top-level fields and groups
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Check whether the map has no fields
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Renumber every tag, including tags inside groups
+    pub fn remap_tags(&mut self, map: &impl Fn(u32) -> u32) {
+        for field in &mut self.fields {
+            match field {
+                FixField::Value { tag, .. } => *tag = map(*tag),
+                FixField::Group { count_tag, entries } => {
+                    *count_tag = map(*count_tag);
+                    for entry in entries {
+                        entry.remap_tags(map);
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn push_field(&mut self, field: FixField) {
+        self.fields.push(field);
+    }
+
+    fn position(&self, tag: u32) -> Option<usize> {
+        self.fields.iter().position(|field| field.tag() == tag)
+    }
+}
+
+impl<'a> IntoIterator for &'a FieldMap {
+    type Item = &'a FixField;
+    type IntoIter = std::slice::Iter<'a, FixField>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+/// A FIX message split into standard header and body
+///
+/// `BeginString(8)`, `BodyLength(9)`, `MsgType(35)` and `CheckSum(10)` are
+/// not stored in the field maps; they are written by the encoder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessage {
+    /// Protocol version sent in `BeginString(8)`
+    pub begin_string: String,
+    /// Message type sent in `MsgType(35)`
+    pub msg_type: MsgType,
+    /// Standard header fields following `MsgType(35)`
+    pub header: FieldMap,
+    /// Body fields
+    pub body: FieldMap,
+}
+
+impl FixMessage {
+    /// Create an empty FIX 4.4 message of the given type
+    #[must_use]
+    pub fn new(msg_type: MsgType) -> Self {
+        Self {
+            begin_string: FIX_VERSION.to_string(),
+            msg_type,
+            header: FieldMap::new(),
+            body: FieldMap::new(),
+        }
+    }
+
+    /// Set a body field
+    #[must_use]
+    pub fn with_field(mut self, tag: u32, value: impl Into<String>) -> Self {
+        self.body.set(tag, value);
+        self
+    }
+
+    /// Set a repeating group in the body
+    #[must_use]
+    pub fn with_group(mut self, count_tag: u32, entries: Vec<FieldMap>) -> Self {
+        self.body.set_group(count_tag, entries);
+        self
+    }
+
+    /// Set the session header fields
+    #[must_use]
+    pub fn with_header(
+        mut self,
+        sender_comp_id: impl Into<String>,
+        target_comp_id: impl Into<String>,
+        seq_num: u64,
+        sending_time: impl Into<String>,
+    ) -> Self {
+        self.header
+            .set(SENDER_COMP_ID, sender_comp_id)
+            .set(TARGET_COMP_ID, target_comp_id)
+            .set(MSG_SEQ_NUM, seq_num.to_string())
+            .set(SENDING_TIME, sending_time);
+        self
+    }
+
+    /// Get a field from the header or, failing that, the body
+    #[must_use]
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.header.get(tag).or_else(|| self.body.get(tag))
+    }
+
+    /// Get the value of `MsgSeqNum(34)`
+    pub fn seq_num(&self) -> Result<u64, FixError> {
+        self.header.require_parsed(MSG_SEQ_NUM)
+    }
+
+    /// Renumber every header and body tag
+    ///
+    /// Combined with [`crate::fix::tags::to_wordsafe_tag`] and
+    /// [`crate::fix::tags::from_wordsafe_tag`], this converts messages of a
+    /// session that enabled `UseWordsafeTags`.
+    pub fn remap_tags(&mut self, map: impl Fn(u32) -> u32) {
+        self.header.remap_tags(&map);
+        self.body.remap_tags(&map);
+    }
+}
+
+impl fmt::Display for FixMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = self.encode();
+        let text = String::from_utf8_lossy(&encoded);
+        write!(f, "{}", text.replace('\x01', "|"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::tags::{
+        DERIBIT_LABEL, MD_ENTRY_TYPE, NO_MD_ENTRY_TYPES, SYMBOL, TEXT, to_wordsafe_tag,
+    };
+
+    #[test]
+    fn test_field_map_set_and_push() {
+        let mut fields = FieldMap::new();
+        fields
+            .set(SYMBOL, "BTC-PERPETUAL")
+            .push(TEXT, "a")
+            .push(TEXT, "b");
+        fields.set(SYMBOL, "ETH-PERPETUAL");
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields.get(SYMBOL), Some("ETH-PERPETUAL"));
+        assert_eq!(fields.get_all(TEXT), vec!["a", "b"]);
+        assert_eq!(fields.remove(TEXT), Some("a".to_string()));
+        assert!(!fields.contains(TEXT));
+    }
+
+    #[test]
+    fn test_field_map_parsing() {
+        let fields = FieldMap::new()
+            .with(34, "12")
+            .with(43, "Y")
+            .with(44, "abc")
+            .with(60, "20261018-10:00:00.250");
+        assert_eq!(fields.require_parsed::<u64>(34), Ok(12));
+        assert_eq!(fields.get_parsed::<u64>(7), Ok(None));
+        assert_eq!(fields.get_bool(43), Ok(Some(true)));
+        assert_eq!(fields.get_timestamp(60), Ok(Some(1_792_317_600_250)));
+        assert!(fields.get_timestamp(44).is_err());
+        assert_eq!(fields.require(7), Err(FixError::MissingField(7)));
+        assert_eq!(
+            fields.get_parsed::<f64>(44),
+            Err(FixError::InvalidValue {
+                tag: 44,
+                value: "abc".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_groups_and_remap() {
+        let entries = vec![
+            FieldMap::new().with(MD_ENTRY_TYPE, "0"),
+            FieldMap::new().with(MD_ENTRY_TYPE, "1"),
+        ];
+        let mut message = FixMessage::new(MsgType::MarketDataRequest)
+            .with_group(NO_MD_ENTRY_TYPES, entries)
+            .with_field(DERIBIT_LABEL, "label");
+        assert_eq!(message.body.group(NO_MD_ENTRY_TYPES).unwrap().len(), 2);
+        message.remap_tags(to_wordsafe_tag);
+        assert_eq!(message.get(5010), Some("label"));
+        assert_eq!(message.get(DERIBIT_LABEL), None);
+    }
+
+    #[test]
+    fn test_header_fields() {
+        let message = FixMessage::new(MsgType::Heartbeat).with_header(
+            "CLIENT",
+            "DERIBITSERVER",
+            7,
+            "20261018-10:00:00.000",
+        );
+        assert_eq!(message.seq_num(), Ok(7));
+        assert_eq!(message.get(SENDER_COMP_ID), Some("CLIENT"));
+        assert!(message.body.is_empty());
+    }
+}
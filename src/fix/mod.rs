@@ -0,0 +1,54 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! FIX 4.4 protocol support
+//!
+//! Messages are built as [`FixMessage`] values holding ordered fields and
+//! repeating groups, and converted to and from SOH-delimited frames:
+//!
+//! ```rust
+//! use deribit_base::fix::{FixMessage, MsgType, tags};
+//!
+//! let message = FixMessage::new(MsgType::TestRequest)
+//!     .with_header("CLIENT", "DERIBITSERVER", 2, "20261018-10:00:00.000")
+//!     .with_field(tags::TEST_REQ_ID, "ping");
+//! let frame = message.encode();
+//!
+//! let decoded = FixMessage::decode(&frame).unwrap();
+//! assert_eq!(decoded.body.get(tags::TEST_REQ_ID), Some("ping"));
+//! assert_eq!(decoded.seq_num(), Ok(2));
+//! ```
+
+pub mod codec;
+pub mod error;
+pub mod logon;
+pub mod market_data;
+pub mod message;
+pub mod orders;
+pub mod session;
+pub mod store;
+pub mod tags;
+
+pub use codec::{FieldIter, FixDecoder, FixFrame, RawField, checksum, frame_length};
+pub use error::FixError;
+pub use logon::{
+    LOGON_NONCE_BYTES, LOGON_NONCE_MAX_BYTES, LogonBuilder, LogonError, LogonVerifier,
+    VerifiedLogon, logon_signature,
+};
+pub use market_data::{
+    MarketDataIncrement, MarketDataRequest, MarketDataSnapshot, MdBookChange, MdEntryType,
+    MdUpdateAction, SecurityList, SecurityListRequest, SubscriptionRequestType,
+};
+pub use message::{FieldMap, FixField, FixMessage};
+pub use orders::{
+    ExecutionReport, OrderCancelReject, new_order_single, order_cancel_replace_request,
+    order_cancel_request, order_mass_cancel_request, order_status,
+};
+pub use session::{
+    FixSession, SessionAction, SessionConfig, SessionError, SessionRejectReason, SessionState,
+};
+pub use store::{MemorySequenceStore, SequenceStore};
+pub use tags::{GroupSpec, MsgType};
@@ -0,0 +1,844 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Order entry message mapping
+//!
+//! Builds `NewOrderSingle(D)`, `OrderCancelRequest(F)`,
+//! `OrderCancelReplaceRequest(G)` and `OrderMassCancelRequest(q)` from the
+//! JSON-RPC order requests, and parses `ExecutionReport(8)` and
+//! `OrderCancelReject(9)` back into [`OrderInfo`] and [`TradeExecution`].
+//!
+//! Outbound quantities are sent with `QtyType(854)=0` (units), which Deribit
+//! interprets like the JSON-RPC `amount`. Inbound quantities are reported in
+//! contracts and are scaled by `ContractMultiplier(231)`, or by the contract
+//! size passed to [`ExecutionReport::from_message`] when the report omits it.
+
+use crate::fix::error::FixError;
+use crate::fix::message::{FieldMap, FixMessage};
+use crate::fix::tags::{
+    AVG_PX, CL_ORD_ID, CONDITION_TRIGGER_METHOD, CONTRACT_MULTIPLIER, CUM_QTY, CURRENCY,
+    DERIBIT_ADV_ORDER_TYPE, DERIBIT_LABEL, DISPLAY_QTY, EXEC_INST, EXEC_TYPE, FILL_EXEC_ID,
+    FILL_LIQUIDITY_IND, FILL_PX, FILL_QTY, IS_LIQUIDATION, IS_RISK_REDUCING, LAST_PX, LAST_QTY,
+    MASS_CANCEL_REQUEST_TYPE, MSG_TYPE, MsgType, NO_FILLS, ORD_REJ_REASON, ORD_STATUS, ORD_TYPE,
+    ORDER_ID, ORDER_QTY, ORIG_CL_ORD_ID, PRICE, QTY_TYPE, SECONDARY_EXEC_ID, SECURITY_TYPE, SIDE,
+    STOP_PX, SYMBOL, TEXT, TIME_IN_FORCE, TRADE_ID, TRANSACT_TIME, VALID_UNTIL_TIME,
+};
+use crate::model::order::{OrderInfo, OrderPrice, OrderSide, OrderStatus, OrderType, TimeInForce};
+use crate::model::request::{
+    AdvancedOrderType, CancelAllOrdersRequest, CancelOrderRequest, ModifyOrderRequest,
+    NewOrderRequest, TriggerType,
+};
+use crate::model::trade::TradeExecution;
+use crate::utils::tools::{
+    format_fix_time, generate_client_order_id, side_to_fix, time_in_force_to_fix,
+};
+use chrono::DateTime;
+
+/// `QtyType(854)` value for amounts in JSON-RPC units
+const QTY_TYPE_UNITS: &str = "0";
+
+/// `MassCancelRequestType(530)` value cancelling all orders
+const MASS_CANCEL_ALL: &str = "7";
+
+/// `MassCancelRequestType(530)` value cancelling orders by security type
+const MASS_CANCEL_BY_SECURITY_TYPE: &str = "5";
+
+/// Build a `NewOrderSingle(D)` from an order request
+///
+/// A client order id is generated when the request has none. Trailing stops
+/// are rejected because the request carries no trail offset.
+pub fn new_order_single(order: &NewOrderRequest) -> Result<FixMessage, FixError> {
+    let cl_ord_id = order
+        .client_order_id
+        .clone()
+        .unwrap_or_else(|| generate_client_order_id("fix"));
+    let mut message = FixMessage::new(MsgType::NewOrderSingle)
+        .with_field(CL_ORD_ID, cl_ord_id)
+        .with_field(SIDE, side_to_fix(order.side))
+        .with_field(ORDER_QTY, order.amount.to_string())
+        .with_field(QTY_TYPE, QTY_TYPE_UNITS)
+        .with_field(SYMBOL, order.instrument_name.as_str())
+        .with_field(ORD_TYPE, ord_type(order.order_type)?)
+        .with_field(TIME_IN_FORCE, time_in_force_to_fix(order.time_in_force));
+    let body = &mut message.body;
+
+    match (order.price, requires_price(order.order_type)) {
+        (Some(price), _) => {
+            body.set(PRICE, price.to_string());
+        }
+        (None, true) => return Err(FixError::MissingField(PRICE)),
+        (None, false) => {}
+    }
+    match (order.stop_price, requires_stop_price(order.order_type)) {
+        (Some(stop_price), true) => {
+            body.set(STOP_PX, stop_price.to_string());
+        }
+        (None, true) => return Err(FixError::MissingField(STOP_PX)),
+        _ => {}
+    }
+    if let Some(exec_inst) = exec_inst(order.post_only, order.reject_post_only, order.reduce_only) {
+        body.set(EXEC_INST, exec_inst);
+    }
+    if let Some(max_show) = order.max_show {
+        body.set(DISPLAY_QTY, max_show.to_string());
+    }
+    if let Some(valid_until) = order.valid_until {
+        let time = DateTime::from_timestamp_millis(valid_until)
+            .ok_or_else(|| invalid(VALID_UNTIL_TIME, &valid_until.to_string()))?;
+        body.set(VALID_UNTIL_TIME, format_fix_time(time));
+    }
+    if let Some(label) = &order.label {
+        body.set(DERIBIT_LABEL, label.as_str());
+    }
+    if let Some(advanced) = &order.advanced {
+        body.set(DERIBIT_ADV_ORDER_TYPE, adv_order_type(advanced));
+    }
+    if let Some(trigger) = &order.trigger {
+        body.set(CONDITION_TRIGGER_METHOD, trigger_method(trigger));
+    }
+    Ok(message)
+}
+
+/// Build an `OrderCancelRequest(F)` cancelling an order by its Deribit id
+#[must_use]
+pub fn order_cancel_request(request: &CancelOrderRequest) -> FixMessage {
+    FixMessage::new(MsgType::OrderCancelRequest)
+        .with_field(ORIG_CL_ORD_ID, request.order_id.as_str())
+}
+
+/// Build an `OrderCancelReplaceRequest(G)` amending `order`
+///
+/// The instrument and side come from the current order, as do the amount,
+/// price and execution flags the request leaves unset.
+pub fn order_cancel_replace_request(
+    request: &ModifyOrderRequest,
+    order: &OrderInfo,
+) -> Result<FixMessage, FixError> {
+    let mut message = FixMessage::new(MsgType::OrderCancelReplaceRequest)
+        .with_field(ORIG_CL_ORD_ID, request.order_id.as_str())
+        .with_field(SYMBOL, order.instrument_name.as_str())
+        .with_field(SIDE, side_to_fix(order.direction))
+        .with_field(
+            ORDER_QTY,
+            request.amount.unwrap_or(order.amount).to_string(),
+        )
+        .with_field(QTY_TYPE, QTY_TYPE_UNITS);
+    let body = &mut message.body;
+
+    if let Some(price) = request.price.or(order.limit_price()) {
+        body.set(PRICE, price.to_string());
+    }
+
+    if let Some(stop_price) = request.stop_price {
+        body.set(STOP_PX, stop_price.to_string());
+    }
+    if let Some(exec_inst) = exec_inst(
+        Some(request.post_only.unwrap_or(order.post_only)),
+        request.reject_post_only,
+        Some(request.reduce_only.unwrap_or(order.reduce_only)),
+    ) {
+        body.set(EXEC_INST, exec_inst);
+    }
+    if let Some(advanced) = &request.advanced {
+        body.set(DERIBIT_ADV_ORDER_TYPE, adv_order_type(advanced));
+    }
+    Ok(message)
+}
+
+/// Build an `OrderMassCancelRequest(q)` with a generated `ClOrdID(11)`
+///
+/// `kind` selects the security type; `instrument_type` has no FIX
+/// equivalent and is ignored.
+pub fn order_mass_cancel_request(request: &CancelAllOrdersRequest) -> Result<FixMessage, FixError> {
+    let mut message = FixMessage::new(MsgType::OrderMassCancelRequest)
+        .with_field(CL_ORD_ID, generate_client_order_id("fix_mass_cancel"));
+    let body = &mut message.body;
+
+    match request.kind.as_deref() {
+        None | Some("any") => {
+            body.set(MASS_CANCEL_REQUEST_TYPE, MASS_CANCEL_ALL);
+        }
+        Some(kind) => {
+            let security_type = match kind {
+                "future" => "FUT",
+                "option" => "OPT",
+                "future_combo" => "FUTCO",
+                "option_combo" => "OPTCO",
+                _ => return Err(invalid(SECURITY_TYPE, kind)),
+            };
+            body.set(MASS_CANCEL_REQUEST_TYPE, MASS_CANCEL_BY_SECURITY_TYPE)
+                .set(SECURITY_TYPE, security_type);
+        }
+    }
+    if let Some(currency) = &request.currency {
+        body.set(CURRENCY, currency.as_str());
+    }
+    Ok(message)
+}
+
+/// Order status from `OrdStatus(39)`, refined by `ExecType(150)`
+///
+/// Deribit reports triggered, expired and day orders through `ExecType(150)`
+/// while `OrdStatus(39)` keeps the generic state.
+pub fn order_status(fields: &FieldMap) -> Result<OrderStatus, FixError> {
+    let refined = match fields.get(EXEC_TYPE) {
+        Some("3") => Some(OrderStatus::DoneForDay),
+        Some("6") => Some(OrderStatus::PendingCancel),
+        Some("7") => Some(OrderStatus::Stopped),
+        Some("8") => Some(OrderStatus::Rejected),
+        Some("C") => Some(OrderStatus::Expired),
+        Some("E") => Some(OrderStatus::PendingReplace),
+        _ => None,
+    };
+    if let Some(status) = refined {
+        return Ok(status);
+    }
+    let value = fields.require(ORD_STATUS)?;
+    OrderStatus::from_fix(value).ok_or_else(|| invalid(ORD_STATUS, value))
+}
+
+/// A parsed `ExecutionReport(8)`
+#[derive(Debug, Clone)]
+pub struct ExecutionReport {
+    /// Order state after the reported change
+    pub order: OrderInfo,
+    /// Status mapped from `OrdStatus(39)` and `ExecType(150)`
+    pub status: OrderStatus,
+    /// Raw `ExecType(150)`, if sent
+    pub exec_type: Option<String>,
+    /// `ClOrdID(11)`, replaced by Deribit with its own value
+    pub cl_ord_id: Option<String>,
+    /// `OrigClOrdID(41)`, the client order id of the `NewOrderSingle(D)`
+    pub orig_cl_ord_id: Option<String>,
+    /// `SecondaryExecID(527)` identifying the order change
+    pub secondary_exec_id: Option<String>,
+    /// `OrdRejReason(103)`, `0` when accepted
+    pub reject_reason: Option<u32>,
+    /// Free text, such as `notification` for order state updates
+    pub text: Option<String>,
+    /// Fills carried by the report
+    pub fills: Vec<TradeExecution>,
+}
+
+impl TryFrom<&FixMessage> for ExecutionReport {
+    type Error = FixError;
+
+    /// Fields the report does not carry, such as fees and index prices,
+    /// are left at zero or empty. Fails with a missing
+    /// `ContractMultiplier(231)`; use [`ExecutionReport::from_message`] to
+    /// supply the instrument's contract size instead.
+    fn try_from(message: &FixMessage) -> Result<Self, Self::Error> {
+        Self::parse(message, None)
+    }
+}
+
+impl ExecutionReport {
+    /// Parse a report, scaling quantities by `contract_size` when it has no
+    /// `ContractMultiplier(231)`
+    pub fn from_message(message: &FixMessage, contract_size: f64) -> Result<Self, FixError> {
+        Self::parse(message, Some(contract_size))
+    }
+
+    fn parse(message: &FixMessage, contract_size: Option<f64>) -> Result<Self, FixError> {
+        expect_msg_type(message, MsgType::ExecutionReport)?;
+        let body = &message.body;
+        let status = order_status(body)?;
+        let multiplier = contract_multiplier(body, contract_size)?;
+        let side = parse_side(body.require(SIDE)?)?;
+        let order_type = match body.get(ORD_TYPE) {
+            Some(value) => parse_ord_type(value)?,
+            None => OrderType::Limit,
+        };
+        let time_in_force = match body.get(TIME_IN_FORCE) {
+            Some(value) => parse_time_in_force(value)?,
+            None => TimeInForce::GoodTilCancelled,
+        };
+        let timestamp = body.get_timestamp(TRANSACT_TIME)?.unwrap_or(0);
+        let exec_inst = body.get(EXEC_INST).unwrap_or_default();
+        let order_id = body.get(ORDER_ID).unwrap_or_default().to_string();
+        let label = body.get(DERIBIT_LABEL).unwrap_or_default().to_string();
+
+        let order = OrderInfo {
+            amount: body.require_parsed::<f64>(ORDER_QTY)? * multiplier,
+            api: true,
+            average_price: body.get_parsed(AVG_PX)?.unwrap_or(0.0),
+            creation_timestamp: timestamp,
+            direction: side,
+            filled_amount: body.get_parsed::<f64>(CUM_QTY)?.unwrap_or(0.0) * multiplier,
+            instrument_name: body.require(SYMBOL)?.to_string(),
+            is_liquidation: body.get_bool(IS_LIQUIDATION)?.unwrap_or(false),
+            label: label.clone(),
+            last_update_timestamp: timestamp,
+            max_show: body
+                .get_parsed::<f64>(DISPLAY_QTY)?
+                .map(|qty| qty * multiplier),
+            order_id: order_id.clone(),
+            order_state: status,
+            order_type,
+            original_order_type: None,
+            post_only: exec_inst.contains('6'),
+            price: body.get_parsed(PRICE)?.map(OrderPrice::Value),
+            profit_loss: None,
+            reduce_only: exec_inst.contains('E'),
+            replaced: status == OrderStatus::Replaced,
+            risk_reducing: body.get_bool(IS_RISK_REDUCING)?.unwrap_or(false),
+            time_in_force,
+            triggered: (status == OrderStatus::Stopped).then_some(true),
+            trigger: body
+                .get(CONDITION_TRIGGER_METHOD)
+                .map(parse_trigger_method)
+                .transpose()?,
+            usd: None,
+            web: false,
+            trigger_price: body.get_parsed(STOP_PX)?,
+            implv: None,
+            advanced: None,
+            contracts: None,
+            is_rebalance: None,
+            cancel_reason: None,
+            quote: None,
+            quote_id: None,
+            quote_set_id: None,
+            mmp: None,
+            mmp_group: None,
+            mmp_cancelled: None,
+        };
+
+        let fill = |trade_id: String, price: f64, amount: f64, liquidity: &str| TradeExecution {
+            amount: amount * multiplier,
+            direction: order.direction.as_str().to_string(),
+            fee: 0.0,
+            fee_currency: String::new(),
+            index_price: 0.0,
+            instrument_name: order.instrument_name.clone(),
+            iv: None,
+            label: label.clone(),
+            liquidity: liquidity.to_string(),
+            mark_price: 0.0,
+            matching_id: None,
+            order_id: order_id.clone(),
+            order_type: order.order_type.as_str().to_string(),
+            original_order_type: None,
+            post_only: None,
+            price,
+            reduce_only: None,
+            self_trade: false,
+            state: order.order_state.as_str().to_string(),
+            tick_direction: 0,
+            timestamp,
+            trade_seq: trade_seq(&trade_id),
+            trade_id,
+            underlying_price: None,
+        };
+        let fills = match body.group(NO_FILLS) {
+            Some(entries) => entries
+                .iter()
+                .map(|entry| {
+                    let liquidity = match entry.get(FILL_LIQUIDITY_IND) {
+                        Some("1") => "M",
+                        Some("2") => "T",
+                        _ => "",
+                    };
+                    Ok(fill(
+                        entry.require(FILL_EXEC_ID)?.to_string(),
+                        entry.require_parsed(FILL_PX)?,
+                        entry.require_parsed(FILL_QTY)?,
+                        liquidity,
+                    ))
+                })
+                .collect::<Result<Vec<_>, FixError>>()?,
+            None => match body.get_parsed::<f64>(LAST_QTY)? {
+                Some(qty) if qty > 0.0 => {
+                    let trade_id = body
+                        .get(TRADE_ID)
+                        .or(body.get(SECONDARY_EXEC_ID))
+                        .unwrap_or_default()
+                        .to_string();
+                    vec![fill(trade_id, body.require_parsed(LAST_PX)?, qty, "")]
+                }
+                _ => Vec::new(),
+            },
+        };
+
+        Ok(Self {
+            status,
+            exec_type: body.get(EXEC_TYPE).map(str::to_string),
+            cl_ord_id: body.get(CL_ORD_ID).map(str::to_string),
+            orig_cl_ord_id: body.get(ORIG_CL_ORD_ID).map(str::to_string),
+            secondary_exec_id: body.get(SECONDARY_EXEC_ID).map(str::to_string),
+            reject_reason: body.get_parsed(ORD_REJ_REASON)?,
+            text: body.get(TEXT).map(str::to_string),
+            fills,
+            order,
+        })
+    }
+}
+
+/// A parsed `OrderCancelReject(9)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderCancelReject {
+    /// Current order status, if reported
+    pub status: Option<OrderStatus>,
+    /// `ClOrdID(11)` echoed from the request
+    pub cl_ord_id: Option<String>,
+    /// `OrigClOrdID(41)` echoed from the request
+    pub orig_cl_ord_id: Option<String>,
+    /// `DeribitLabel(100010)` echoed from the request
+    pub label: Option<String>,
+    /// Reason for the rejection
+    pub text: Option<String>,
+}
+
+impl TryFrom<&FixMessage> for OrderCancelReject {
+    type Error = FixError;
+
+    fn try_from(message: &FixMessage) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MsgType::OrderCancelReject)?;
+        let body = &message.body;
+        let status = match body.get(ORD_STATUS) {
+            Some(_) => Some(order_status(body)?),
+            None => None,
+        };
+        Ok(Self {
+            status,
+            cl_ord_id: body.get(CL_ORD_ID).map(str::to_string),
+            orig_cl_ord_id: body.get(ORIG_CL_ORD_ID).map(str::to_string),
+            label: body.get(DERIBIT_LABEL).map(str::to_string),
+            text: body.get(TEXT).map(str::to_string),
+        })
+    }
+}
+
+fn expect_msg_type(message: &FixMessage, expected: MsgType) -> Result<(), FixError> {
+    if message.msg_type == expected {
+        Ok(())
+    } else {
+        Err(invalid(MSG_TYPE, message.msg_type.as_str()))
+    }
+}
+
+/// `OrdType(40)` value of an order type
+///
+/// Deribit derives the trigger direction itself, so take orders share the
+/// values of the matching stop orders.
+fn ord_type(order_type: OrderType) -> Result<&'static str, FixError> {
+    match order_type {
+        OrderType::Market => Ok("1"),
+        OrderType::Limit => Ok("2"),
+        OrderType::MarketLimit => Ok("K"),
+        OrderType::StopLimit | OrderType::TakeLimit => Ok("J"),
+        OrderType::StopMarket | OrderType::TakeMarket => Ok("S"),
+        OrderType::TrailingStop => Err(invalid(ORD_TYPE, order_type.as_str())),
+    }
+}
+
+fn parse_ord_type(value: &str) -> Result<OrderType, FixError> {
+    match value {
+        "1" => Ok(OrderType::Market),
+        "2" => Ok(OrderType::Limit),
+        "K" => Ok(OrderType::MarketLimit),
+        "4" => Ok(OrderType::TrailingStop),
+        "J" => Ok(OrderType::StopLimit),
+        "S" => Ok(OrderType::StopMarket),
+        _ => Err(invalid(ORD_TYPE, value)),
+    }
+}
+
+fn requires_price(order_type: OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::Limit | OrderType::StopLimit | OrderType::TakeLimit
+    )
+}
+
+fn requires_stop_price(order_type: OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::StopLimit | OrderType::StopMarket | OrderType::TakeLimit | OrderType::TakeMarket
+    )
+}
+
+pub(super) fn parse_side(value: &str) -> Result<OrderSide, FixError> {
+    match value {
+        "1" => Ok(OrderSide::Buy),
+        "2" => Ok(OrderSide::Sell),
+        _ => Err(invalid(SIDE, value)),
+    }
+}
+
+fn parse_time_in_force(value: &str) -> Result<TimeInForce, FixError> {
+    match value {
+        "0" => Ok(TimeInForce::GoodTilDay),
+        "1" => Ok(TimeInForce::GoodTilCancelled),
+        "3" => Ok(TimeInForce::ImmediateOrCancel),
+        "4" => Ok(TimeInForce::FillOrKill),
+        _ => Err(invalid(TIME_IN_FORCE, value)),
+    }
+}
+
+fn trigger_method(trigger: &TriggerType) -> &'static str {
+    match trigger {
+        TriggerType::MarkPrice => "1",
+        TriggerType::LastPrice => "2",
+        TriggerType::IndexPrice => "3",
+    }
+}
+
+fn parse_trigger_method(value: &str) -> Result<TriggerType, FixError> {
+    match value {
+        "1" => Ok(TriggerType::MarkPrice),
+        "2" => Ok(TriggerType::LastPrice),
+        "3" => Ok(TriggerType::IndexPrice),
+        _ => Err(invalid(CONDITION_TRIGGER_METHOD, value)),
+    }
+}
+
+fn adv_order_type(advanced: &AdvancedOrderType) -> &'static str {
+    match advanced {
+        AdvancedOrderType::Implv => "0",
+        AdvancedOrderType::Usd => "1",
+    }
+}
+
+/// `ExecInst(18)` from the post-only, reject post-only and reduce-only flags
+fn exec_inst(
+    post_only: Option<bool>,
+    reject_post_only: Option<bool>,
+    reduce_only: Option<bool>,
+) -> Option<String> {
+    let mut value = String::new();
+    if post_only == Some(true) {
+        value.push('6');
+        if reject_post_only == Some(true) {
+            value.push('A');
+        }
+    }
+    if reduce_only == Some(true) {
+        value.push('E');
+    }
+    (!value.is_empty()).then_some(value)
+}
+
+/// Trade sequence number from a `FillExecID(1363)` such as `BTC-28SEP18#38`
+fn trade_seq(exec_id: &str) -> u64 {
+    exec_id
+        .rsplit_once('#')
+        .and_then(|(_, seq)| seq.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Multiplier converting reported contracts into JSON-RPC amounts
+///
+/// `ContractMultiplier(231)` takes precedence over the caller's contract
+/// size; with neither the field is reported missing.
+pub(super) fn contract_multiplier(
+    fields: &FieldMap,
+    contract_size: Option<f64>,
+) -> Result<f64, FixError> {
+    fields
+        .get_parsed::<f64>(CONTRACT_MULTIPLIER)?
+        .or(contract_size)
+        .ok_or(FixError::MissingField(CONTRACT_MULTIPLIER))
+}
+
+pub(super) fn invalid(tag: u32, value: &str) -> FixError {
+    FixError::InvalidValue {
+        tag,
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::tags::LEAVES_QTY;
+
+    fn execution_report() -> FixMessage {
+        FixMessage::new(MsgType::ExecutionReport)
+            .with_field(SECONDARY_EXEC_ID, "4711")
+            .with_field(ORDER_ID, "ETH-349280")
+            .with_field(CL_ORD_ID, "srv-1")
+            .with_field(ORIG_CL_ORD_ID, "my-order")
+            .with_field(ORD_STATUS, "1")
+            .with_field(SIDE, "2")
+            .with_field(TRANSACT_TIME, "20261018-10:00:00.250")
+            .with_field(LEAVES_QTY, "3")
+            .with_field(CUM_QTY, "2")
+            .with_field(ORDER_QTY, "5")
+            .with_field(ORD_TYPE, "2")
+            .with_field(PRICE, "2500.5")
+            .with_field(EXEC_TYPE, "I")
+            .with_field(EXEC_INST, "6E")
+            .with_field(ORD_REJ_REASON, "0")
+            .with_field(SYMBOL, "ETH-PERPETUAL")
+            .with_field(CONTRACT_MULTIPLIER, "10")
+            .with_field(AVG_PX, "2500.25")
+            .with_field(DERIBIT_LABEL, "grid")
+            .with_group(
+                NO_FILLS,
+                vec![
+                    FieldMap::new()
+                        .with(FILL_EXEC_ID, "ETH-PERPETUAL#41")
+                        .with(FILL_PX, "2500")
+                        .with(FILL_QTY, "1")
+                        .with(FILL_LIQUIDITY_IND, "2"),
+                    FieldMap::new()
+                        .with(FILL_EXEC_ID, "ETH-PERPETUAL#42")
+                        .with(FILL_PX, "2500.5")
+                        .with(FILL_QTY, "1")
+                        .with(FILL_LIQUIDITY_IND, "1"),
+                ],
+            )
+    }
+
+    #[test]
+    fn test_new_order_single() {
+        let order = NewOrderRequest::limit_buy("BTC-PERPETUAL".to_string(), 100.0, 65000.5)
+            .with_post_only(true)
+            .with_label("grid".to_string());
+        let order = NewOrderRequest {
+            client_order_id: Some("c-1".to_string()),
+            max_show: Some(10.0),
+            valid_until: Some(1_792_324_800_000),
+            ..order
+        };
+        let message = new_order_single(&order).unwrap();
+
+        assert_eq!(message.msg_type, MsgType::NewOrderSingle);
+        assert_eq!(message.get(CL_ORD_ID), Some("c-1"));
+        assert_eq!(message.get(SIDE), Some("1"));
+        assert_eq!(message.get(ORDER_QTY), Some("100"));
+        assert_eq!(message.get(QTY_TYPE), Some("0"));
+        assert_eq!(message.get(ORD_TYPE), Some("2"));
+        assert_eq!(message.get(PRICE), Some("65000.5"));
+        assert_eq!(message.get(TIME_IN_FORCE), Some("1"));
+        assert_eq!(message.get(EXEC_INST), Some("6"));
+        assert_eq!(message.get(DISPLAY_QTY), Some("10"));
+        assert_eq!(message.get(VALID_UNTIL_TIME), Some("20261018-12:00:00.000"));
+        assert_eq!(message.get(DERIBIT_LABEL), Some("grid"));
+        assert!(!message.body.contains(STOP_PX));
+        assert!(FixMessage::decode(&message.encode()).is_ok());
+    }
+
+    #[test]
+    fn test_new_order_single_order_types() {
+        let market = NewOrderRequest::market_sell("BTC-PERPETUAL".to_string(), 10.0);
+        let message = new_order_single(&market).unwrap();
+        assert_eq!(message.get(ORD_TYPE), Some("1"));
+        assert_eq!(message.get(TIME_IN_FORCE), Some("3"));
+        assert!(!message.body.contains(PRICE));
+        assert!(message.get(CL_ORD_ID).unwrap().starts_with("fix_"));
+
+        let stop = NewOrderRequest {
+            order_type: OrderType::StopMarket,
+            trigger: Some(TriggerType::MarkPrice),
+            ..market.clone()
+        };
+        assert!(matches!(
+            new_order_single(&stop),
+            Err(FixError::MissingField(STOP_PX))
+        ));
+        let stop = NewOrderRequest {
+            stop_price: Some(60000.0),
+            ..stop
+        };
+        let message = new_order_single(&stop).unwrap();
+        assert_eq!(message.get(ORD_TYPE), Some("S"));
+        assert_eq!(message.get(STOP_PX), Some("60000"));
+        assert_eq!(message.get(CONDITION_TRIGGER_METHOD), Some("1"));
+
+        let limit = NewOrderRequest {
+            order_type: OrderType::Limit,
+            ..market.clone()
+        };
+        assert!(matches!(
+            new_order_single(&limit),
+            Err(FixError::MissingField(PRICE))
+        ));
+        let trailing = NewOrderRequest {
+            order_type: OrderType::TrailingStop,
+            ..market
+        };
+        assert!(matches!(
+            new_order_single(&trailing),
+            Err(FixError::InvalidValue { tag: ORD_TYPE, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cancel_and_mass_cancel() {
+        let cancel = order_cancel_request(&CancelOrderRequest {
+            order_id: "ETH-349280".to_string(),
+        });
+        assert_eq!(cancel.msg_type, MsgType::OrderCancelRequest);
+        assert_eq!(cancel.get(ORIG_CL_ORD_ID), Some("ETH-349280"));
+
+        let all = order_mass_cancel_request(&CancelAllOrdersRequest {
+            currency: Some("BTC".to_string()),
+            kind: None,
+            instrument_type: None,
+        })
+        .unwrap();
+        assert_eq!(all.msg_type, MsgType::OrderMassCancelRequest);
+        assert_eq!(all.get(MASS_CANCEL_REQUEST_TYPE), Some("7"));
+        assert_eq!(all.get(CURRENCY), Some("BTC"));
+        assert!(all.get(CL_ORD_ID).is_some());
+
+        let options = order_mass_cancel_request(&CancelAllOrdersRequest {
+            currency: None,
+            kind: Some("option".to_string()),
+            instrument_type: None,
+        })
+        .unwrap();
+        assert_eq!(options.get(MASS_CANCEL_REQUEST_TYPE), Some("5"));
+        assert_eq!(options.get(SECURITY_TYPE), Some("OPT"));
+
+        let unknown = order_mass_cancel_request(&CancelAllOrdersRequest {
+            currency: None,
+            kind: Some("spot".to_string()),
+            instrument_type: None,
+        });
+        assert!(matches!(
+            unknown,
+            Err(FixError::InvalidValue {
+                tag: SECURITY_TYPE,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_execution_report_round_trip_into_cancel_replace() {
+        let frame = execution_report().encode();
+        let report = ExecutionReport::try_from(&FixMessage::decode(&frame).unwrap()).unwrap();
+
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.exec_type.as_deref(), Some("I"));
+        assert_eq!(report.orig_cl_ord_id.as_deref(), Some("my-order"));
+        assert_eq!(report.reject_reason, Some(0));
+        let order = &report.order;
+        assert_eq!(order.order_id, "ETH-349280");
+        assert_eq!(order.instrument_name, "ETH-PERPETUAL");
+        assert_eq!(order.direction, OrderSide::Sell);
+        assert_eq!(order.amount, 50.0);
+        assert_eq!(order.filled_amount, 20.0);
+        assert_eq!(order.average_price, 2500.25);
+        assert_eq!(order.order_state, OrderStatus::PartiallyFilled);
+        assert_eq!(order.order_state.as_str(), "open");
+        assert_eq!(order.order_type, OrderType::Limit);
+        assert_eq!(order.time_in_force, TimeInForce::GoodTilCancelled);
+        assert_eq!(order.last_update_timestamp, 1_792_317_600_250);
+        assert!(order.post_only && order.reduce_only);
+        assert_eq!(order.label, "grid");
+
+        assert_eq!(report.fills.len(), 2);
+        assert_eq!(report.fills[0].trade_id, "ETH-PERPETUAL#41");
+        assert_eq!(report.fills[0].trade_seq, 41);
+        assert_eq!(report.fills[0].amount, 10.0);
+        assert_eq!(report.fills[0].liquidity, "T");
+        assert_eq!(report.fills[1].liquidity, "M");
+        assert_eq!(report.fills[1].price, 2500.5);
+
+        let modify = ModifyOrderRequest {
+            order_id: order.order_id.clone(),
+            amount: None,
+            price: Some(2501.0),
+            stop_price: None,
+            post_only: Some(false),
+            reduce_only: None,
+            reject_post_only: None,
+            advanced: None,
+        };
+        let replace = order_cancel_replace_request(&modify, order).unwrap();
+        assert_eq!(replace.msg_type, MsgType::OrderCancelReplaceRequest);
+        assert_eq!(replace.get(ORIG_CL_ORD_ID), Some("ETH-349280"));
+        assert_eq!(replace.get(SYMBOL), Some("ETH-PERPETUAL"));
+        assert_eq!(replace.get(SIDE), Some("2"));
+        assert_eq!(replace.get(ORDER_QTY), Some("50"));
+        assert_eq!(replace.get(PRICE), Some("2501"));
+        assert_eq!(replace.get(EXEC_INST), Some("E"));
+    }
+
+    #[test]
+    fn test_execution_report_status_and_last_fill() {
+        let mut message = execution_report();
+        message.body.remove(NO_FILLS);
+        message
+            .body
+            .set(EXEC_TYPE, "7")
+            .set(LAST_QTY, "1")
+            .set(LAST_PX, "2499")
+            .set(TRADE_ID, "ETH-99");
+        let report = ExecutionReport::try_from(&message).unwrap();
+        assert_eq!(report.status, OrderStatus::Stopped);
+        assert_eq!(report.order.order_state.as_str(), "triggered");
+        assert_eq!(report.order.triggered, Some(true));
+        assert_eq!(report.fills.len(), 1);
+        assert_eq!(report.fills[0].trade_id, "ETH-99");
+        assert_eq!(report.fills[0].price, 2499.0);
+
+        message.body.set(EXEC_TYPE, "C").set(ORD_STATUS, "4");
+        let report = ExecutionReport::try_from(&message).unwrap();
+        assert_eq!(report.status, OrderStatus::Expired);
+        assert_eq!(report.order.order_state.as_str(), "cancelled");
+
+        message.body.set(EXEC_TYPE, "I").set(ORD_STATUS, "X");
+        assert!(matches!(
+            ExecutionReport::try_from(&message),
+            Err(FixError::InvalidValue {
+                tag: ORD_STATUS,
+                ..
+            })
+        ));
+        assert!(matches!(
+            ExecutionReport::try_from(&FixMessage::new(MsgType::Heartbeat)),
+            Err(FixError::InvalidValue { tag: MSG_TYPE, .. })
+        ));
+    }
+
+    #[test]
+    fn test_execution_report_without_contract_multiplier() {
+        let mut message = execution_report();
+        message.body.remove(CONTRACT_MULTIPLIER);
+        assert!(matches!(
+            ExecutionReport::try_from(&message),
+            Err(FixError::MissingField(CONTRACT_MULTIPLIER))
+        ));
+
+        let report = ExecutionReport::from_message(&message, 10.0).unwrap();
+        assert_eq!(report.order.amount, 50.0);
+        assert_eq!(report.order.filled_amount, 20.0);
+        assert_eq!(report.fills[0].amount, 10.0);
+
+        message.body.set(CONTRACT_MULTIPLIER, "1");
+        let report = ExecutionReport::from_message(&message, 10.0).unwrap();
+        assert_eq!(report.order.amount, 5.0);
+    }
+
+    #[test]
+    fn test_order_cancel_reject() {
+        let message = FixMessage::new(MsgType::OrderCancelReject)
+            .with_field(ORD_STATUS, "8")
+            .with_field(TEXT, "order_not_found")
+            .with_field(ORIG_CL_ORD_ID, "ETH-1");
+        let reject = OrderCancelReject::try_from(&message).unwrap();
+        assert_eq!(
+            reject,
+            OrderCancelReject {
+                status: Some(OrderStatus::Rejected),
+                cl_ord_id: None,
+                orig_cl_ord_id: Some("ETH-1".to_string()),
+                label: None,
+                text: Some("order_not_found".to_string()),
+            }
+        );
+
+        let message = FixMessage::new(MsgType::OrderCancelReject).with_field(DERIBIT_LABEL, "grid");
+        let reject = OrderCancelReject::try_from(&message).unwrap();
+        assert_eq!(reject.status, None);
+        assert_eq!(reject.label.as_deref(), Some("grid"));
+    }
+}
@@ -0,0 +1,1139 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Sans-IO FIX session layer
+//!
+//! [`FixSession`] implements the FIX 4.4 session protocol: Logon and
+//! Logout, heartbeats and test requests, sequence number checks, gap
+//! recovery with `ResendRequest` and `SequenceReset`, and session level
+//! rejects. It never touches a socket; inbound messages are passed to
+//! [`FixSession::handle`], timers are driven by [`FixSession::poll`], and
+//! every call returns the [`SessionAction`]s the transport has to carry
+//! out. All times are Unix timestamps in milliseconds.
+
+use crate::constants::FIX_HEARTBEAT_INTERVAL;
+use crate::fix::message::{FieldMap, FixMessage};
+use crate::fix::store::{MemorySequenceStore, SequenceStore};
+use crate::fix::tags::{
+    BEGIN_SEQ_NO, END_SEQ_NO, GAP_FILL_FLAG, HEART_BT_INT, MSG_SEQ_NUM, MsgType, NEW_SEQ_NO,
+    ORIG_SENDING_TIME, POSS_DUP_FLAG, REF_MSG_TYPE, REF_SEQ_NUM, RESET_SEQ_NUM_FLAG,
+    SENDER_COMP_ID, SENDING_TIME, SESSION_REJECT_REASON, TARGET_COMP_ID, TEST_REQ_ID, TEXT,
+};
+use crate::utils::tools::format_fix_time;
+use chrono::DateTime;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Lifecycle state of a FIX session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionState {
+    /// No session is established
+    Disconnected,
+    /// Logon sent, waiting for the counterparty's Logon
+    LogonSent,
+    /// Logged on; application messages may be exchanged
+    Active,
+    /// Logout sent, waiting for the counterparty's Logout
+    LogoutSent,
+}
+
+impl SessionState {
+    /// Get the string representation of the state
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disconnected => "disconnected",
+            Self::LogonSent => "logon_sent",
+            Self::Active => "active",
+            Self::LogoutSent => "logout_sent",
+        }
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Reason sent in `SessionRejectReason(373)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionRejectReason {
+    /// Required tag missing (1)
+    RequiredTagMissing,
+    /// Value is incorrect (out of range) for this tag (5)
+    ValueIncorrect,
+    /// CompID problem (9)
+    CompIdProblem,
+    /// Invalid MsgType (11)
+    InvalidMsgType,
+    /// Other (99)
+    Other,
+}
+
+impl SessionRejectReason {
+    /// Get the numeric code sent on the wire
+    #[must_use]
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::RequiredTagMissing => 1,
+            Self::ValueIncorrect => 5,
+            Self::CompIdProblem => 9,
+            Self::InvalidMsgType => 11,
+            Self::Other => 99,
+        }
+    }
+}
+
+/// Error raised when an operation is not allowed in the current state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionError {
+    /// State of the session when the operation was attempted
+    pub state: SessionState,
+    /// Operation that was attempted
+    pub operation: &'static str,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot {} while session is {}",
+            self.operation, self.state
+        )
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// Something the transport must do on behalf of the session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionAction {
+    /// Encode and send the message
+    Send(FixMessage),
+    /// Pass an in-sequence application message or session Reject to the application
+    Deliver(FixMessage),
+    /// The session is logged on
+    LoggedOn,
+    /// The session is logged out, with the counterparty's text if any
+    LoggedOut(Option<String>),
+    /// Close the connection
+    Disconnect(String),
+}
+
+/// Static settings of a FIX session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// Our `SenderCompID(49)`
+    pub sender_comp_id: String,
+    /// The counterparty's `SenderCompID(49)`, sent as `TargetCompID(56)`
+    pub target_comp_id: String,
+    /// Heartbeat interval in seconds sent in `HeartBtInt(108)`
+    pub heartbeat_interval: u32,
+    /// Send `ResetSeqNumFlag(141)=Y` on Logon and restart both sequences at 1
+    pub reset_on_logon: bool,
+    /// Time to wait for the Logon response, in milliseconds
+    pub logon_timeout_ms: u64,
+    /// Time to wait for the Logout response, in milliseconds
+    pub logout_timeout_ms: u64,
+}
+
+impl SessionConfig {
+    /// Create a configuration with the default heartbeat interval
+    pub fn new(sender_comp_id: impl Into<String>, target_comp_id: impl Into<String>) -> Self {
+        Self {
+            sender_comp_id: sender_comp_id.into(),
+            target_comp_id: target_comp_id.into(),
+            heartbeat_interval: FIX_HEARTBEAT_INTERVAL,
+            reset_on_logon: false,
+            logon_timeout_ms: 10_000,
+            logout_timeout_ms: 10_000,
+        }
+    }
+
+    /// Set the heartbeat interval in seconds
+    #[must_use]
+    pub fn with_heartbeat_interval(mut self, seconds: u32) -> Self {
+        self.heartbeat_interval = seconds;
+        self
+    }
+
+    /// Reset sequence numbers on every Logon
+    #[must_use]
+    pub fn with_reset_on_logon(mut self, reset: bool) -> Self {
+        self.reset_on_logon = reset;
+        self
+    }
+
+    /// Set the Logon and Logout timeouts in milliseconds
+    #[must_use]
+    pub fn with_timeouts(mut self, logon_timeout_ms: u64, logout_timeout_ms: u64) -> Self {
+        self.logon_timeout_ms = logon_timeout_ms;
+        self.logout_timeout_ms = logout_timeout_ms;
+        self
+    }
+}
+
+/// Inbound message received ahead of a sequence gap
+#[derive(Debug, Clone)]
+struct QueuedMessage {
+    message: FixMessage,
+    processed: bool,
+}
+
+/// Sans-IO FIX session engine
+///
+/// The session acts as initiator after [`FixSession::logon`] and as
+/// acceptor when a Logon arrives while disconnected.
+#[derive(Debug, Clone)]
+pub struct FixSession<S: SequenceStore = MemorySequenceStore> {
+    config: SessionConfig,
+    store: S,
+    state: SessionState,
+    heartbeat_ms: u64,
+    last_sent_ms: u64,
+    last_received_ms: u64,
+    state_since_ms: u64,
+    test_request: Option<(String, u64)>,
+    test_request_counter: u64,
+    resend_requested: bool,
+    queue: BTreeMap<u64, QueuedMessage>,
+}
+
+impl FixSession<MemorySequenceStore> {
+    /// Create a session keeping sequence numbers in memory
+    #[must_use]
+    pub fn in_memory(config: SessionConfig) -> Self {
+        Self::new(config, MemorySequenceStore::new())
+    }
+}
+
+impl<S: SequenceStore> FixSession<S> {
+    /// Create a session using the given sequence store
+    pub fn new(config: SessionConfig, store: S) -> Self {
+        let heartbeat_ms = u64::from(config.heartbeat_interval) * 1000;
+        Self {
+            config,
+            store,
+            state: SessionState::Disconnected,
+            heartbeat_ms,
+            last_sent_ms: 0,
+            last_received_ms: 0,
+            state_since_ms: 0,
+            test_request: None,
+            test_request_counter: 0,
+            resend_requested: false,
+            queue: BTreeMap::new(),
+        }
+    }
+
+    /// Get the session configuration
+    #[must_use]
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// Get the current state
+    #[must_use]
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Check whether application messages may be sent
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.state == SessionState::Active
+    }
+
+    /// Get the sequence store
+    #[must_use]
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Get the sequence number of the next outbound message
+    #[must_use]
+    pub fn next_sender_seq(&self) -> u64 {
+        self.store.next_sender_seq()
+    }
+
+    /// Get the sequence number expected on the next inbound message
+    #[must_use]
+    pub fn next_target_seq(&self) -> u64 {
+        self.store.next_target_seq()
+    }
+
+    /// Build the Logon message starting the session
+    ///
+    /// `fields` carries the authentication fields of the Logon body;
+    /// `HeartBtInt(108)` and `ResetSeqNumFlag(141)` are added by the session.
+    pub fn logon(&mut self, fields: FieldMap, now_ms: u64) -> Result<FixMessage, SessionError> {
+        self.ensure(SessionState::Disconnected, "log on")?;
+        let mut message = FixMessage::new(MsgType::Logon);
+        message.body = fields;
+        message
+            .body
+            .set(HEART_BT_INT, self.config.heartbeat_interval.to_string());
+        if self.config.reset_on_logon {
+            self.store.reset();
+            message.body.set(RESET_SEQ_NUM_FLAG, "Y");
+        }
+        self.heartbeat_ms = u64::from(self.config.heartbeat_interval) * 1000;
+        self.last_received_ms = now_ms;
+        self.set_state(SessionState::LogonSent, now_ms);
+        Ok(self.stamp(message, now_ms))
+    }
+
+    /// Build the Logout message ending the session
+    pub fn logout(&mut self, text: Option<&str>, now_ms: u64) -> Result<FixMessage, SessionError> {
+        self.ensure(SessionState::Active, "log out")?;
+        self.set_state(SessionState::LogoutSent, now_ms);
+        Ok(self.logout_message(text, now_ms))
+    }
+
+    /// Stamp an application message with the session header and sequence number
+    pub fn send(&mut self, message: FixMessage, now_ms: u64) -> Result<FixMessage, SessionError> {
+        self.ensure(SessionState::Active, "send application messages")?;
+        Ok(self.stamp(message, now_ms))
+    }
+
+    /// Record that the transport was closed
+    ///
+    /// Sequence numbers are kept in the store for the next Logon.
+    pub fn disconnected(&mut self, now_ms: u64) {
+        self.set_state(SessionState::Disconnected, now_ms);
+    }
+
+    /// Process an inbound message
+    pub fn handle(&mut self, message: FixMessage, now_ms: u64) -> Vec<SessionAction> {
+        let mut actions = Vec::new();
+        self.last_received_ms = now_ms;
+
+        if self.state == SessionState::Disconnected && message.msg_type != MsgType::Logon {
+            actions.push(SessionAction::Disconnect(format!(
+                "received {} before Logon",
+                message.msg_type
+            )));
+            return actions;
+        }
+        let Ok(seq) = message.seq_num() else {
+            self.fail("MsgSeqNum(34) missing or invalid", now_ms, &mut actions);
+            return actions;
+        };
+        let comp_ids_match = message.header.get(SENDER_COMP_ID)
+            == Some(self.config.target_comp_id.as_str())
+            && message.header.get(TARGET_COMP_ID) == Some(self.config.sender_comp_id.as_str());
+        if !comp_ids_match {
+            if self.state != SessionState::Disconnected {
+                let reject = self.reject_message(
+                    &message,
+                    seq,
+                    SessionRejectReason::CompIdProblem,
+                    "CompID problem",
+                    now_ms,
+                );
+                actions.push(SessionAction::Send(reject));
+            }
+            self.fail("CompID problem", now_ms, &mut actions);
+            return actions;
+        }
+        if message.msg_type == MsgType::Logon
+            && message.body.get_bool(RESET_SEQ_NUM_FLAG) == Ok(Some(true))
+            && self.state == SessionState::Disconnected
+        {
+            self.store.reset();
+        }
+
+        let is_reset = message.msg_type == MsgType::SequenceReset
+            && message.body.get_bool(GAP_FILL_FLAG) != Ok(Some(true));
+        if is_reset {
+            self.on_sequence_reset(&message, seq, now_ms, &mut actions);
+            self.drain_queue(now_ms, &mut actions);
+            return actions;
+        }
+
+        let expected = self.store.next_target_seq();
+        if seq > expected {
+            let processed = matches!(message.msg_type, MsgType::Logon | MsgType::ResendRequest);
+            if processed {
+                self.process(&message, seq, now_ms, &mut actions);
+                self.store.set_next_target_seq(expected);
+            }
+            self.queue.insert(seq, QueuedMessage { message, processed });
+            if !self.resend_requested && self.state != SessionState::Disconnected {
+                self.resend_requested = true;
+                let request = FixMessage::new(MsgType::ResendRequest)
+                    .with_field(BEGIN_SEQ_NO, expected.to_string())
+                    .with_field(END_SEQ_NO, "0");
+                actions.push(SessionAction::Send(self.stamp(request, now_ms)));
+            }
+            return actions;
+        }
+        if seq < expected {
+            if message.header.get_bool(POSS_DUP_FLAG) != Ok(Some(true)) {
+                let text = format!("MsgSeqNum too low, expecting {expected} but received {seq}");
+                self.fail(&text, now_ms, &mut actions);
+            }
+            return actions;
+        }
+
+        self.process(&message, seq, now_ms, &mut actions);
+        self.drain_queue(now_ms, &mut actions);
+        actions
+    }
+
+    /// Drive heartbeat, test request and timeout timers
+    pub fn poll(&mut self, now_ms: u64) -> Vec<SessionAction> {
+        let mut actions = Vec::new();
+        match self.state {
+            SessionState::Disconnected => {}
+            SessionState::LogonSent => {
+                if now_ms.saturating_sub(self.state_since_ms) >= self.config.logon_timeout_ms {
+                    self.set_state(SessionState::Disconnected, now_ms);
+                    actions.push(SessionAction::Disconnect("Logon timed out".to_string()));
+                }
+            }
+            SessionState::LogoutSent => {
+                if now_ms.saturating_sub(self.state_since_ms) >= self.config.logout_timeout_ms {
+                    self.set_state(SessionState::Disconnected, now_ms);
+                    actions.push(SessionAction::LoggedOut(None));
+                    actions.push(SessionAction::Disconnect("Logout timed out".to_string()));
+                }
+            }
+            SessionState::Active => {
+                if let Some((_, sent_ms)) = &self.test_request
+                    && now_ms.saturating_sub(*sent_ms) >= self.heartbeat_ms
+                {
+                    self.set_state(SessionState::Disconnected, now_ms);
+                    actions.push(SessionAction::Disconnect(
+                        "TestRequest not answered".to_string(),
+                    ));
+                    return actions;
+                }
+                if self.test_request.is_none()
+                    && now_ms.saturating_sub(self.last_received_ms) >= self.receive_timeout_ms()
+                {
+                    self.test_request_counter += 1;
+                    let id = format!("TEST-{}", self.test_request_counter);
+                    self.test_request = Some((id.clone(), now_ms));
+                    let request = FixMessage::new(MsgType::TestRequest).with_field(TEST_REQ_ID, id);
+                    actions.push(SessionAction::Send(self.stamp(request, now_ms)));
+                }
+                if now_ms.saturating_sub(self.last_sent_ms) >= self.heartbeat_ms {
+                    let heartbeat = FixMessage::new(MsgType::Heartbeat);
+                    actions.push(SessionAction::Send(self.stamp(heartbeat, now_ms)));
+                }
+            }
+        }
+        actions
+    }
+
+    /// Get the time of the next timer event, for the transport's sleep
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<u64> {
+        match self.state {
+            SessionState::Disconnected => None,
+            SessionState::LogonSent => Some(self.state_since_ms + self.config.logon_timeout_ms),
+            SessionState::LogoutSent => Some(self.state_since_ms + self.config.logout_timeout_ms),
+            SessionState::Active => {
+                let heartbeat = self.last_sent_ms + self.heartbeat_ms;
+                let check = match &self.test_request {
+                    Some((_, sent_ms)) => sent_ms + self.heartbeat_ms,
+                    None => self.last_received_ms + self.receive_timeout_ms(),
+                };
+                Some(heartbeat.min(check))
+            }
+        }
+    }
+
+    /// Silence after which a TestRequest is sent: the interval plus 20%
+    fn receive_timeout_ms(&self) -> u64 {
+        self.heartbeat_ms + self.heartbeat_ms / 5
+    }
+
+    fn ensure(&self, state: SessionState, operation: &'static str) -> Result<(), SessionError> {
+        if self.state == state {
+            Ok(())
+        } else {
+            Err(SessionError {
+                state: self.state,
+                operation,
+            })
+        }
+    }
+
+    fn set_state(&mut self, state: SessionState, now_ms: u64) {
+        self.state = state;
+        self.state_since_ms = now_ms;
+        if state == SessionState::Disconnected {
+            self.test_request = None;
+            self.resend_requested = false;
+            self.queue.clear();
+        }
+    }
+
+    /// Add the header, assign the next sequence number and keep the message
+    fn stamp(&mut self, mut message: FixMessage, now_ms: u64) -> FixMessage {
+        let seq = self.store.next_sender_seq();
+        message.header = FieldMap::new()
+            .with(SENDER_COMP_ID, self.config.sender_comp_id.as_str())
+            .with(TARGET_COMP_ID, self.config.target_comp_id.as_str())
+            .with(MSG_SEQ_NUM, seq.to_string())
+            .with(SENDING_TIME, sending_time(now_ms));
+        self.store.save_sent(seq, &message);
+        self.store.set_next_sender_seq(seq + 1);
+        self.last_sent_ms = now_ms;
+        message
+    }
+
+    /// Process an in-sequence message and advance the inbound sequence
+    fn process(
+        &mut self,
+        message: &FixMessage,
+        seq: u64,
+        now_ms: u64,
+        actions: &mut Vec<SessionAction>,
+    ) {
+        self.store.set_next_target_seq(seq + 1);
+        match &message.msg_type {
+            MsgType::Logon => self.on_logon(message, seq, now_ms, actions),
+            MsgType::Heartbeat => {
+                let answered = match (&self.test_request, message.body.get(TEST_REQ_ID)) {
+                    (Some((id, _)), Some(received)) => id == received,
+                    _ => false,
+                };
+                if answered {
+                    self.test_request = None;
+                }
+            }
+            MsgType::TestRequest => {
+                let mut heartbeat = FixMessage::new(MsgType::Heartbeat);
+                if let Some(id) = message.body.get(TEST_REQ_ID) {
+                    heartbeat.body.set(TEST_REQ_ID, id);
+                }
+                actions.push(SessionAction::Send(self.stamp(heartbeat, now_ms)));
+            }
+            MsgType::ResendRequest => self.on_resend_request(message, seq, now_ms, actions),
+            MsgType::SequenceReset => self.on_sequence_reset(message, seq, now_ms, actions),
+            MsgType::Logout => {
+                let text = message.body.get(TEXT).map(str::to_string);
+                if self.state != SessionState::LogoutSent {
+                    let reply = self.logout_message(None, now_ms);
+                    actions.push(SessionAction::Send(reply));
+                }
+                self.set_state(SessionState::Disconnected, now_ms);
+                actions.push(SessionAction::LoggedOut(text));
+                actions.push(SessionAction::Disconnect("Logout completed".to_string()));
+            }
+            MsgType::Reject => actions.push(SessionAction::Deliver(message.clone())),
+            MsgType::Other(_) => {
+                let reject = self.reject_message(
+                    message,
+                    seq,
+                    SessionRejectReason::InvalidMsgType,
+                    "Invalid MsgType",
+                    now_ms,
+                );
+                actions.push(SessionAction::Send(reject));
+            }
+            _ => actions.push(SessionAction::Deliver(message.clone())),
+        }
+    }
+
+    fn on_logon(
+        &mut self,
+        message: &FixMessage,
+        seq: u64,
+        now_ms: u64,
+        actions: &mut Vec<SessionAction>,
+    ) {
+        let interval = match message.body.get_parsed::<u32>(HEART_BT_INT) {
+            Ok(Some(interval)) => interval,
+            _ => {
+                self.fail("HeartBtInt(108) missing or invalid", now_ms, actions);
+                return;
+            }
+        };
+        match self.state {
+            SessionState::LogonSent => {
+                self.set_state(SessionState::Active, now_ms);
+                actions.push(SessionAction::LoggedOn);
+            }
+            SessionState::Disconnected => {
+                self.heartbeat_ms = u64::from(interval) * 1000;
+                let mut reply =
+                    FixMessage::new(MsgType::Logon).with_field(HEART_BT_INT, interval.to_string());
+                if message.body.get_bool(RESET_SEQ_NUM_FLAG) == Ok(Some(true)) {
+                    reply.body.set(RESET_SEQ_NUM_FLAG, "Y");
+                }
+                self.set_state(SessionState::Active, now_ms);
+                actions.push(SessionAction::Send(self.stamp(reply, now_ms)));
+                actions.push(SessionAction::LoggedOn);
+            }
+            SessionState::Active | SessionState::LogoutSent => {
+                let reject = self.reject_message(
+                    message,
+                    seq,
+                    SessionRejectReason::Other,
+                    "Logon received on an active session",
+                    now_ms,
+                );
+                actions.push(SessionAction::Send(reject));
+            }
+        }
+    }
+
+    fn on_resend_request(
+        &mut self,
+        message: &FixMessage,
+        seq: u64,
+        now_ms: u64,
+        actions: &mut Vec<SessionAction>,
+    ) {
+        let range = (
+            message.body.require_parsed::<u64>(BEGIN_SEQ_NO),
+            message.body.require_parsed::<u64>(END_SEQ_NO),
+        );
+        let (Ok(begin), Ok(end)) = range else {
+            let reject = self.reject_message(
+                message,
+                seq,
+                SessionRejectReason::RequiredTagMissing,
+                "BeginSeqNo(7) and EndSeqNo(16) are required",
+                now_ms,
+            );
+            actions.push(SessionAction::Send(reject));
+            return;
+        };
+        let last = self.store.next_sender_seq().saturating_sub(1);
+        let end = if end == 0 || end > last { last } else { end };
+        if begin == 0 || begin > end {
+            return;
+        }
+        let sent: BTreeMap<u64, FixMessage> =
+            self.store.sent_messages(begin, end).into_iter().collect();
+        let mut gap_start = None;
+        for resend_seq in begin..=end {
+            match sent
+                .get(&resend_seq)
+                .filter(|sent| !sent.msg_type.is_admin())
+            {
+                Some(original) => {
+                    if let Some(start) = gap_start.take() {
+                        actions.push(SessionAction::Send(
+                            self.gap_fill(start, resend_seq, now_ms),
+                        ));
+                    }
+                    actions.push(SessionAction::Send(possible_duplicate(original, now_ms)));
+                }
+                None => {
+                    gap_start.get_or_insert(resend_seq);
+                }
+            }
+        }
+        if let Some(start) = gap_start {
+            actions.push(SessionAction::Send(self.gap_fill(start, end + 1, now_ms)));
+        }
+        self.last_sent_ms = now_ms;
+    }
+
+    fn on_sequence_reset(
+        &mut self,
+        message: &FixMessage,
+        seq: u64,
+        now_ms: u64,
+        actions: &mut Vec<SessionAction>,
+    ) {
+        let expected = self.store.next_target_seq();
+        match message.body.get_parsed::<u64>(NEW_SEQ_NO) {
+            Ok(Some(new_seq)) if new_seq >= expected => {
+                self.store.set_next_target_seq(new_seq);
+            }
+            _ => {
+                let text = format!("NewSeqNo(36) must not be lower than {expected}");
+                let reject = self.reject_message(
+                    message,
+                    seq,
+                    SessionRejectReason::ValueIncorrect,
+                    &text,
+                    now_ms,
+                );
+                actions.push(SessionAction::Send(reject));
+            }
+        }
+    }
+
+    /// Process queued messages that became in sequence
+    fn drain_queue(&mut self, now_ms: u64, actions: &mut Vec<SessionAction>) {
+        loop {
+            let expected = self.store.next_target_seq();
+            self.queue.retain(|seq, _| *seq >= expected);
+            let Some(queued) = self.queue.remove(&expected) else {
+                break;
+            };
+            if queued.processed {
+                self.store.set_next_target_seq(expected + 1);
+            } else {
+                self.process(&queued.message, expected, now_ms, actions);
+            }
+            if self.state == SessionState::Disconnected {
+                return;
+            }
+        }
+        if self.queue.is_empty() {
+            self.resend_requested = false;
+        }
+    }
+
+    /// Build a `SequenceReset` in gap fill mode covering `seq..new_seq`
+    fn gap_fill(&self, seq: u64, new_seq: u64, now_ms: u64) -> FixMessage {
+        let mut message = FixMessage::new(MsgType::SequenceReset)
+            .with_field(GAP_FILL_FLAG, "Y")
+            .with_field(NEW_SEQ_NO, new_seq.to_string());
+        message.header = FieldMap::new()
+            .with(SENDER_COMP_ID, self.config.sender_comp_id.as_str())
+            .with(TARGET_COMP_ID, self.config.target_comp_id.as_str())
+            .with(MSG_SEQ_NUM, seq.to_string())
+            .with(SENDING_TIME, sending_time(now_ms))
+            .with(POSS_DUP_FLAG, "Y");
+        message
+    }
+
+    fn reject_message(
+        &mut self,
+        message: &FixMessage,
+        seq: u64,
+        reason: SessionRejectReason,
+        text: &str,
+        now_ms: u64,
+    ) -> FixMessage {
+        let reject = FixMessage::new(MsgType::Reject)
+            .with_field(REF_SEQ_NUM, seq.to_string())
+            .with_field(REF_MSG_TYPE, message.msg_type.as_str())
+            .with_field(SESSION_REJECT_REASON, reason.code().to_string())
+            .with_field(TEXT, text);
+        self.stamp(reject, now_ms)
+    }
+
+    fn logout_message(&mut self, text: Option<&str>, now_ms: u64) -> FixMessage {
+        let mut logout = FixMessage::new(MsgType::Logout);
+        if let Some(text) = text {
+            logout.body.set(TEXT, text);
+        }
+        self.stamp(logout, now_ms)
+    }
+
+    /// Send a Logout explaining a protocol violation and disconnect
+    fn fail(&mut self, text: &str, now_ms: u64, actions: &mut Vec<SessionAction>) {
+        if self.state != SessionState::Disconnected {
+            let logout = self.logout_message(Some(text), now_ms);
+            actions.push(SessionAction::Send(logout));
+        }
+        self.set_state(SessionState::Disconnected, now_ms);
+        actions.push(SessionAction::Disconnect(text.to_string()));
+    }
+}
+
+fn sending_time(now_ms: u64) -> String {
+    let time = DateTime::from_timestamp_millis(now_ms as i64).unwrap_or_default();
+    format_fix_time(time)
+}
+
+/// Copy a sent message for retransmission with `PossDupFlag(43)=Y`
+fn possible_duplicate(original: &FixMessage, now_ms: u64) -> FixMessage {
+    let mut message = original.clone();
+    if let Some(sent) = original.header.get(SENDING_TIME) {
+        message.header.set(ORIG_SENDING_TIME, sent);
+    }
+    message
+        .header
+        .set(SENDING_TIME, sending_time(now_ms))
+        .set(POSS_DUP_FLAG, "Y");
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::codec::FixDecoder;
+    use crate::fix::tags::{CL_ORD_ID, SYMBOL};
+
+    const START: u64 = 1_760_000_000_000;
+
+    /// Two sessions connected by in-memory byte pipes
+    struct MemoryTransport {
+        client: FixSession,
+        server: FixSession,
+        to_server: FixDecoder,
+        to_client: FixDecoder,
+        client_events: Vec<SessionAction>,
+        server_events: Vec<SessionAction>,
+    }
+
+    impl MemoryTransport {
+        fn new(config: SessionConfig) -> Self {
+            let server_config =
+                SessionConfig::new(config.target_comp_id.clone(), config.sender_comp_id.clone());
+            Self {
+                client: FixSession::in_memory(config),
+                server: FixSession::in_memory(server_config),
+                to_server: FixDecoder::new(),
+                to_client: FixDecoder::new(),
+                client_events: Vec::new(),
+                server_events: Vec::new(),
+            }
+        }
+
+        fn connected() -> Self {
+            let mut transport = Self::new(SessionConfig::new("CLIENT", "DERIBITSERVER"));
+            let logon = transport.client.logon(FieldMap::new(), START).unwrap();
+            transport.to_server.extend(&logon.encode());
+            transport.pump(START);
+            transport.client_events.clear();
+            transport.server_events.clear();
+            transport
+        }
+
+        fn client_actions(&mut self, actions: Vec<SessionAction>) {
+            route(actions, &mut self.to_server, &mut self.client_events);
+        }
+
+        fn server_actions(&mut self, actions: Vec<SessionAction>) {
+            route(actions, &mut self.to_client, &mut self.server_events);
+        }
+
+        /// Deliver buffered bytes in both directions until both pipes are empty
+        fn pump(&mut self, now_ms: u64) {
+            loop {
+                if let Some(message) = self.to_server.next_message().unwrap() {
+                    let actions = self.server.handle(message, now_ms);
+                    self.server_actions(actions);
+                } else if let Some(message) = self.to_client.next_message().unwrap() {
+                    let actions = self.client.handle(message, now_ms);
+                    self.client_actions(actions);
+                } else {
+                    break;
+                }
+            }
+        }
+
+        fn server_send(&mut self, message: FixMessage, now_ms: u64) {
+            let message = self.server.send(message, now_ms).unwrap();
+            self.to_client.extend(&message.encode());
+        }
+    }
+
+    fn route(actions: Vec<SessionAction>, pipe: &mut FixDecoder, events: &mut Vec<SessionAction>) {
+        for action in actions {
+            match action {
+                SessionAction::Send(message) => pipe.extend(&message.encode()),
+                other => events.push(other),
+            }
+        }
+    }
+
+    fn order(id: &str) -> FixMessage {
+        FixMessage::new(MsgType::NewOrderSingle)
+            .with_field(CL_ORD_ID, id)
+            .with_field(SYMBOL, "BTC-PERPETUAL")
+    }
+
+    fn delivered(events: &[SessionAction]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                SessionAction::Deliver(message) => message.body.get(CL_ORD_ID).map(str::to_string),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn sent_types(actions: &[SessionAction]) -> Vec<MsgType> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                SessionAction::Send(message) => Some(message.msg_type.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_logon_handshake() {
+        let mut transport = MemoryTransport::new(SessionConfig::new("CLIENT", "DERIBITSERVER"));
+        let logon = transport.client.logon(FieldMap::new(), START).unwrap();
+        assert_eq!(logon.body.get(HEART_BT_INT), Some("30"));
+        assert_eq!(logon.seq_num(), Ok(1));
+        assert_eq!(transport.client.state(), SessionState::LogonSent);
+        assert!(transport.client.send(order("1"), START).is_err());
+
+        transport.to_server.extend(&logon.encode());
+        transport.pump(START);
+        assert!(transport.client.is_active());
+        assert!(transport.server.is_active());
+        assert_eq!(transport.client_events, vec![SessionAction::LoggedOn]);
+        assert_eq!(transport.server_events, vec![SessionAction::LoggedOn]);
+        assert_eq!(transport.client.next_sender_seq(), 2);
+        assert_eq!(transport.client.next_target_seq(), 2);
+    }
+
+    #[test]
+    fn test_application_messages_are_sequenced() {
+        let mut transport = MemoryTransport::connected();
+        for id in ["1", "2"] {
+            let message = transport.client.send(order(id), START).unwrap();
+            assert_eq!(message.header.get(SENDER_COMP_ID), Some("CLIENT"));
+            assert_eq!(message.header.get(TARGET_COMP_ID), Some("DERIBITSERVER"));
+            transport.to_server.extend(&message.encode());
+        }
+        transport.pump(START);
+        assert_eq!(delivered(&transport.server_events), vec!["1", "2"]);
+        assert_eq!(transport.server.next_target_seq(), 4);
+    }
+
+    #[test]
+    fn test_heartbeat_and_test_request() {
+        let mut transport = MemoryTransport::connected();
+        assert_eq!(transport.client.next_deadline(), Some(START + 30_000));
+        assert!(transport.client.poll(START + 29_999).is_empty());
+
+        let actions = transport.client.poll(START + 30_000);
+        assert_eq!(sent_types(&actions), vec![MsgType::Heartbeat]);
+        transport.client_actions(actions);
+        transport.pump(START + 30_000);
+
+        let actions = transport.client.poll(START + 36_000);
+        assert_eq!(sent_types(&actions), vec![MsgType::TestRequest]);
+        transport.client_actions(actions);
+        transport.pump(START + 36_500);
+        assert!(transport.client.test_request.is_none());
+
+        let actions = transport.client.poll(START + 72_500);
+        assert_eq!(sent_types(&actions), vec![MsgType::TestRequest]);
+        let actions = transport.client.poll(START + 102_500);
+        assert_eq!(
+            actions,
+            vec![SessionAction::Disconnect(
+                "TestRequest not answered".to_string()
+            )]
+        );
+        assert_eq!(transport.client.state(), SessionState::Disconnected);
+    }
+
+    #[test]
+    fn test_gap_triggers_resend_request() {
+        let mut transport = MemoryTransport::connected();
+        let lost = transport.server.send(order("1"), START).unwrap();
+        assert_eq!(lost.seq_num(), Ok(2));
+        transport.server_send(order("2"), START);
+
+        let message = transport.to_client.next_message().unwrap().unwrap();
+        let actions = transport.client.handle(message, START);
+        assert_eq!(sent_types(&actions), vec![MsgType::ResendRequest]);
+        let SessionAction::Send(request) = &actions[0] else {
+            panic!("expected a ResendRequest");
+        };
+        assert_eq!(request.body.get(BEGIN_SEQ_NO), Some("2"));
+        assert_eq!(request.body.get(END_SEQ_NO), Some("0"));
+        assert!(delivered(&transport.client_events).is_empty());
+
+        transport.client_actions(actions);
+        transport.pump(START + 10);
+        assert_eq!(delivered(&transport.client_events), vec!["1", "2"]);
+        assert_eq!(transport.client.next_target_seq(), 4);
+        assert!(transport.client.queue.is_empty());
+        assert!(transport.client.is_active());
+    }
+
+    #[test]
+    fn test_resend_fills_admin_messages_with_gap_fill() {
+        let mut transport = MemoryTransport::connected();
+        let mut order_message = None;
+        for now_ms in [START + 30_000, START + 60_000, START + 90_000] {
+            let actions = transport.client.poll(now_ms);
+            transport.client_actions(actions);
+            let actions = transport.server.poll(now_ms);
+            transport.server_actions(actions);
+            if now_ms == START + 60_000 {
+                let message = transport.server.send(order("1"), now_ms).unwrap();
+                transport.to_client.extend(&message.encode());
+                order_message = Some(message);
+            }
+            transport.pump(now_ms);
+        }
+        let order_message = order_message.unwrap();
+        assert_eq!(transport.server.next_sender_seq(), 6);
+
+        let request = FixMessage::new(MsgType::ResendRequest)
+            .with_field(BEGIN_SEQ_NO, "1")
+            .with_field(END_SEQ_NO, "0");
+        let request = transport.client.send(request, START + 90_000).unwrap();
+        let actions = transport.server.handle(request, START + 90_000);
+        let sent: Vec<FixMessage> = actions
+            .into_iter()
+            .filter_map(|action| match action {
+                SessionAction::Send(message) => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0].msg_type, MsgType::SequenceReset);
+        assert_eq!(sent[0].seq_num(), Ok(1));
+        assert_eq!(sent[0].body.get(NEW_SEQ_NO), Some("4"));
+        assert_eq!(sent[1].seq_num(), Ok(4));
+        assert_eq!(sent[1].header.get(POSS_DUP_FLAG), Some("Y"));
+        assert_eq!(
+            sent[1].header.get(ORIG_SENDING_TIME),
+            order_message.header.get(SENDING_TIME)
+        );
+        assert_eq!(sent[2].body.get(NEW_SEQ_NO), Some("6"));
+        assert_eq!(sent[2].body.get(GAP_FILL_FLAG), Some("Y"));
+    }
+
+    #[test]
+    fn test_sequence_too_low_disconnects() {
+        let mut transport = MemoryTransport::connected();
+        let mut stale = order("1");
+        stale.header = FieldMap::new()
+            .with(SENDER_COMP_ID, "DERIBITSERVER")
+            .with(TARGET_COMP_ID, "CLIENT")
+            .with(MSG_SEQ_NUM, "1")
+            .with(SENDING_TIME, sending_time(START));
+
+        let mut duplicate = stale.clone();
+        duplicate.header.set(POSS_DUP_FLAG, "Y");
+        assert!(transport.client.handle(duplicate, START).is_empty());
+
+        let actions = transport.client.handle(stale, START);
+        assert_eq!(sent_types(&actions), vec![MsgType::Logout]);
+        assert_eq!(
+            actions.last(),
+            Some(&SessionAction::Disconnect(
+                "MsgSeqNum too low, expecting 2 but received 1".to_string()
+            ))
+        );
+        assert_eq!(transport.client.state(), SessionState::Disconnected);
+    }
+
+    #[test]
+    fn test_sequence_reset() {
+        let mut transport = MemoryTransport::connected();
+        let reset = FixMessage::new(MsgType::SequenceReset).with_field(NEW_SEQ_NO, "10");
+        transport.server_send(reset, START);
+        transport.pump(START);
+        assert_eq!(transport.client.next_target_seq(), 10);
+
+        let lower = FixMessage::new(MsgType::SequenceReset).with_field(NEW_SEQ_NO, "5");
+        let lower = transport.server.send(lower, START).unwrap();
+        let actions = transport.client.handle(lower, START);
+        let SessionAction::Send(reject) = &actions[0] else {
+            panic!("expected a Reject");
+        };
+        assert_eq!(reject.msg_type, MsgType::Reject);
+        assert_eq!(reject.body.get(SESSION_REJECT_REASON), Some("5"));
+        assert_eq!(transport.client.next_target_seq(), 10);
+    }
+
+    #[test]
+    fn test_rejects() {
+        let mut transport = MemoryTransport::connected();
+        transport.server_send(FixMessage::new(MsgType::Other("UZ".to_string())), START);
+        let message = transport.to_client.next_message().unwrap().unwrap();
+        let actions = transport.client.handle(message, START);
+        let SessionAction::Send(reject) = &actions[0] else {
+            panic!("expected a Reject");
+        };
+        assert_eq!(reject.body.get(REF_MSG_TYPE), Some("UZ"));
+        assert_eq!(reject.body.get(REF_SEQ_NUM), Some("2"));
+        assert_eq!(reject.body.get(SESSION_REJECT_REASON), Some("11"));
+
+        transport.client_actions(actions);
+        transport.pump(START);
+        assert_eq!(transport.server_events.len(), 1);
+        assert!(matches!(
+            &transport.server_events[0],
+            SessionAction::Deliver(message) if message.msg_type == MsgType::Reject
+        ));
+
+        let mut foreign = order("1");
+        foreign.header = FieldMap::new()
+            .with(SENDER_COMP_ID, "OTHER")
+            .with(TARGET_COMP_ID, "CLIENT")
+            .with(MSG_SEQ_NUM, "3");
+        let actions = transport.client.handle(foreign, START);
+        assert_eq!(sent_types(&actions), vec![MsgType::Reject, MsgType::Logout]);
+        assert_eq!(transport.client.state(), SessionState::Disconnected);
+    }
+
+    #[test]
+    fn test_logout_handshake_and_resume() {
+        let mut transport = MemoryTransport::connected();
+        let logout = transport.client.logout(Some("bye"), START).unwrap();
+        assert_eq!(transport.client.state(), SessionState::LogoutSent);
+        transport.to_server.extend(&logout.encode());
+        transport.pump(START);
+
+        assert_eq!(transport.client.state(), SessionState::Disconnected);
+        assert_eq!(transport.server.state(), SessionState::Disconnected);
+        assert!(
+            transport
+                .server_events
+                .contains(&SessionAction::LoggedOut(Some("bye".to_string())))
+        );
+        assert!(
+            transport
+                .client_events
+                .contains(&SessionAction::LoggedOut(None))
+        );
+        assert_eq!(transport.client.next_sender_seq(), 3);
+
+        let logon = transport
+            .client
+            .logon(FieldMap::new(), START + 1_000)
+            .unwrap();
+        assert_eq!(logon.seq_num(), Ok(3));
+        transport.to_server.extend(&logon.encode());
+        transport.pump(START + 1_000);
+        assert!(transport.client.is_active());
+        assert!(transport.server.is_active());
+    }
+
+    #[test]
+    fn test_reset_on_logon() {
+        let config = SessionConfig::new("CLIENT", "DERIBITSERVER").with_reset_on_logon(true);
+        let store = MemorySequenceStore::with_sequences(40, 50);
+        let mut client = FixSession::new(config, store);
+        let logon = client.logon(FieldMap::new(), START).unwrap();
+        assert_eq!(logon.seq_num(), Ok(1));
+        assert_eq!(logon.body.get(RESET_SEQ_NUM_FLAG), Some("Y"));
+        assert_eq!(client.next_target_seq(), 1);
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let config = SessionConfig::new("CLIENT", "DERIBITSERVER").with_timeouts(1_000, 500);
+        let mut client = FixSession::in_memory(config);
+        client.logon(FieldMap::new(), START).unwrap();
+        assert_eq!(client.next_deadline(), Some(START + 1_000));
+        assert!(client.poll(START + 999).is_empty());
+        assert_eq!(
+            client.poll(START + 1_000),
+            vec![SessionAction::Disconnect("Logon timed out".to_string())]
+        );
+        assert_eq!(client.next_deadline(), None);
+        assert_eq!(
+            client.logout(None, START).unwrap_err().to_string(),
+            "cannot log out while session is disconnected"
+        );
+    }
+
+    #[test]
+    fn test_message_before_logon() {
+        let mut server = FixSession::in_memory(SessionConfig::new("DERIBITSERVER", "CLIENT"));
+        let actions = server.handle(order("1"), START);
+        assert!(matches!(&actions[..], [SessionAction::Disconnect(_)]));
+    }
+}
@@ -0,0 +1,129 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Sequence number persistence for FIX sessions
+
+use crate::fix::message::FixMessage;
+use std::collections::BTreeMap;
+
+/// Storage for the sequence numbers and sent messages of a FIX session
+///
+/// [`crate::fix::session::FixSession`] reads and updates the store as it
+/// sends and receives messages, so an implementation backed by a file or a
+/// database lets a session resume after a restart without resetting
+/// sequence numbers.
+pub trait SequenceStore {
+    /// Get the sequence number of the next outbound message
+    fn next_sender_seq(&self) -> u64;
+
+    /// Get the sequence number expected on the next inbound message
+    fn next_target_seq(&self) -> u64;
+
+    /// Set the sequence number of the next outbound message
+    fn set_next_sender_seq(&mut self, seq: u64);
+
+    /// Set the sequence number expected on the next inbound message
+    fn set_next_target_seq(&mut self, seq: u64);
+
+    /// Keep a sent message so it can be resent on a `ResendRequest`
+    fn save_sent(&mut self, seq: u64, message: &FixMessage);
+
+    /// Get the sent messages with sequence numbers in `begin..=end`
+    fn sent_messages(&self, begin: u64, end: u64) -> Vec<(u64, FixMessage)>;
+
+    /// Reset both sequence numbers to 1 and drop the sent messages
+    fn reset(&mut self);
+}
+
+/// In-memory [`SequenceStore`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySequenceStore {
+    next_sender_seq: u64,
+    next_target_seq: u64,
+    sent: BTreeMap<u64, FixMessage>,
+}
+
+impl MemorySequenceStore {
+    /// Create a store starting both sequences at 1
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store resuming from the given sequence numbers
+    #[must_use]
+    pub fn with_sequences(next_sender_seq: u64, next_target_seq: u64) -> Self {
+        Self {
+            next_sender_seq,
+            next_target_seq,
+            sent: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for MemorySequenceStore {
+    fn default() -> Self {
+        Self::with_sequences(1, 1)
+    }
+}
+
+impl SequenceStore for MemorySequenceStore {
+    fn next_sender_seq(&self) -> u64 {
+        self.next_sender_seq
+    }
+
+    fn next_target_seq(&self) -> u64 {
+        self.next_target_seq
+    }
+
+    fn set_next_sender_seq(&mut self, seq: u64) {
+        self.next_sender_seq = seq;
+    }
+
+    fn set_next_target_seq(&mut self, seq: u64) {
+        self.next_target_seq = seq;
+    }
+
+    fn save_sent(&mut self, seq: u64, message: &FixMessage) {
+        self.sent.insert(seq, message.clone());
+    }
+
+    fn sent_messages(&self, begin: u64, end: u64) -> Vec<(u64, FixMessage)> {
+        if begin > end {
+            return Vec::new();
+        }
+        self.sent
+            .range(begin..=end)
+            .map(|(seq, message)| (*seq, message.clone()))
+            .collect()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::tags::MsgType;
+
+    #[test]
+    fn test_memory_store() {
+        let mut store = MemorySequenceStore::with_sequences(5, 7);
+        assert_eq!(store.next_sender_seq(), 5);
+        assert_eq!(store.next_target_seq(), 7);
+
+        store.save_sent(5, &FixMessage::new(MsgType::Heartbeat));
+        store.save_sent(6, &FixMessage::new(MsgType::NewOrderSingle));
+        assert_eq!(store.sent_messages(5, 5).len(), 1);
+        assert_eq!(store.sent_messages(1, 10).len(), 2);
+        assert!(store.sent_messages(7, 6).is_empty());
+
+        store.reset();
+        assert_eq!(store, MemorySequenceStore::new());
+    }
+}
@@ -0,0 +1,840 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! FIX tag dictionary for the Deribit FIX API
+//!
+//! Tag numbers are exposed as constants named after the FIX field (e.g.,
+//! [`MSG_SEQ_NUM`] for `MsgSeqNum(34)`), together with the message types
+//! and repeating group layouts used by Deribit. Custom Deribit tags above
+//! 100000 can be converted to and from their `UseWordsafeTags` form.
+
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! fix_tags {
+    ($($konst:ident = $tag:literal, $name:literal;)*) => {
+        $(
+            #[doc = concat!("`", $name, "` (", stringify!($tag), ")")]
+            pub const $konst: u32 = $tag;
+        )*
+
+        /// Get the FIX field name of a tag, if it is part of the dictionary
+        #[must_use]
+        pub fn tag_name(tag: u32) -> Option<&'static str> {
+            match tag {
+                $($tag => Some($name),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+fix_tags! {
+    AVG_PX = 6, "AvgPx";
+    BEGIN_SEQ_NO = 7, "BeginSeqNo";
+    BEGIN_STRING = 8, "BeginString";
+    BODY_LENGTH = 9, "BodyLength";
+    CHECK_SUM = 10, "CheckSum";
+    CL_ORD_ID = 11, "ClOrdID";
+    COMMISSION = 12, "Commission";
+    CUM_QTY = 14, "CumQty";
+    CURRENCY = 15, "Currency";
+    END_SEQ_NO = 16, "EndSeqNo";
+    EXEC_ID = 17, "ExecID";
+    EXEC_INST = 18, "ExecInst";
+    SECURITY_ID_SOURCE = 22, "SecurityIDSource";
+    LAST_PX = 31, "LastPx";
+    LAST_QTY = 32, "LastQty";
+    MSG_SEQ_NUM = 34, "MsgSeqNum";
+    MSG_TYPE = 35, "MsgType";
+    NEW_SEQ_NO = 36, "NewSeqNo";
+    ORDER_ID = 37, "OrderID";
+    ORDER_QTY = 38, "OrderQty";
+    ORD_STATUS = 39, "OrdStatus";
+    ORD_TYPE = 40, "OrdType";
+    ORIG_CL_ORD_ID = 41, "OrigClOrdID";
+    POSS_DUP_FLAG = 43, "PossDupFlag";
+    PRICE = 44, "Price";
+    REF_SEQ_NUM = 45, "RefSeqNum";
+    SECURITY_ID = 48, "SecurityID";
+    SENDER_COMP_ID = 49, "SenderCompID";
+    SENDING_TIME = 52, "SendingTime";
+    SIDE = 54, "Side";
+    SYMBOL = 55, "Symbol";
+    TARGET_COMP_ID = 56, "TargetCompID";
+    TEXT = 58, "Text";
+    TIME_IN_FORCE = 59, "TimeInForce";
+    TRANSACT_TIME = 60, "TransactTime";
+    VALID_UNTIL_TIME = 62, "ValidUntilTime";
+    SETTL_TYPE = 63, "SettlType";
+    TRADE_DATE = 75, "TradeDate";
+    RAW_DATA_LENGTH = 95, "RawDataLength";
+    RAW_DATA = 96, "RawData";
+    POSS_RESEND = 97, "PossResend";
+    ENCRYPT_METHOD = 98, "EncryptMethod";
+    STOP_PX = 99, "StopPx";
+    ORD_REJ_REASON = 103, "OrdRejReason";
+    SECURITY_DESC = 107, "SecurityDesc";
+    HEART_BT_INT = 108, "HeartBtInt";
+    TEST_REQ_ID = 112, "TestReqID";
+    QUOTE_ID = 117, "QuoteID";
+    SETTL_CURRENCY = 120, "SettlCurrency";
+    ORIG_SENDING_TIME = 122, "OrigSendingTime";
+    GAP_FILL_FLAG = 123, "GapFillFlag";
+    QUOTE_REQ_ID = 131, "QuoteReqID";
+    BID_PX = 132, "BidPx";
+    OFFER_PX = 133, "OfferPx";
+    BID_SIZE = 134, "BidSize";
+    OFFER_SIZE = 135, "OfferSize";
+    RESET_SEQ_NUM_FLAG = 141, "ResetSeqNumFlag";
+    NO_RELATED_SYM = 146, "NoRelatedSym";
+    EXEC_TYPE = 150, "ExecType";
+    LEAVES_QTY = 151, "LeavesQty";
+    SECURITY_TYPE = 167, "SecurityType";
+    ORDER_QTY_2 = 192, "OrderQty2";
+    SECONDARY_ORDER_ID = 198, "SecondaryOrderID";
+    PUT_OR_CALL = 201, "PutOrCall";
+    STRIKE_PRICE = 202, "StrikePrice";
+    SECURITY_EXCHANGE = 207, "SecurityExchange";
+    PEG_OFFSET_VALUE = 211, "PegOffsetValue";
+    ISSUE_DATE = 225, "IssueDate";
+    CONTRACT_MULTIPLIER = 231, "ContractMultiplier";
+    MD_REQ_ID = 262, "MDReqID";
+    SUBSCRIPTION_REQUEST_TYPE = 263, "SubscriptionRequestType";
+    MARKET_DEPTH = 264, "MarketDepth";
+    MD_UPDATE_TYPE = 265, "MDUpdateType";
+    NO_MD_ENTRY_TYPES = 267, "NoMDEntryTypes";
+    NO_MD_ENTRIES = 268, "NoMDEntries";
+    MD_ENTRY_TYPE = 269, "MDEntryType";
+    MD_ENTRY_PX = 270, "MDEntryPx";
+    MD_ENTRY_SIZE = 271, "MDEntrySize";
+    MD_ENTRY_DATE = 272, "MDEntryDate";
+    MD_UPDATE_ACTION = 279, "MDUpdateAction";
+    MD_REQ_REJ_REASON = 281, "MDReqRejReason";
+    NO_QUOTE_ENTRIES = 295, "NoQuoteEntries";
+    NO_QUOTE_SETS = 296, "NoQuoteSets";
+    QUOTE_STATUS = 297, "QuoteStatus";
+    QUOTE_CANCEL_TYPE = 298, "QuoteCancelType";
+    QUOTE_ENTRY_ID = 299, "QuoteEntryID";
+    QUOTE_REJECT_REASON = 300, "QuoteRejectReason";
+    QUOTE_SET_ID = 302, "QuoteSetID";
+    TOT_NO_QUOTE_ENTRIES = 304, "TotNoQuoteEntries";
+    UNDERLYING_SYMBOL = 311, "UnderlyingSymbol";
+    SECURITY_REQ_ID = 320, "SecurityReqID";
+    SECURITY_REQUEST_TYPE = 321, "SecurityRequestType";
+    SECURITY_RESPONSE_ID = 322, "SecurityResponseID";
+    SECURITY_RESPONSE_TYPE = 323, "SecurityResponseType";
+    SECURITY_STATUS_REQ_ID = 324, "SecurityStatusReqID";
+    SECURITY_TRADING_STATUS = 326, "SecurityTradingStatus";
+    BUY_VOLUME = 330, "BuyVolume";
+    SELL_VOLUME = 331, "SellVolume";
+    HIGH_PX = 332, "HighPx";
+    LOW_PX = 333, "LowPx";
+    QUOTE_ENTRY_REJECT_REASON = 368, "QuoteEntryRejectReason";
+    REF_MSG_TYPE = 372, "RefMsgType";
+    SESSION_REJECT_REASON = 373, "SessionRejectReason";
+    TOTAL_VOLUME_TRADED = 387, "TotalVolumeTraded";
+    NO_SECURITY_ALT_ID = 454, "NoSecurityAltID";
+    SECURITY_ALT_ID = 455, "SecurityAltID";
+    SECURITY_ALT_ID_SOURCE = 456, "SecurityAltIDSource";
+    COMM_CURRENCY = 479, "CommCurrency";
+    SECONDARY_EXEC_ID = 527, "SecondaryExecID";
+    MASS_CANCEL_REQUEST_TYPE = 530, "MassCancelRequestType";
+    MASS_CANCEL_RESPONSE = 531, "MassCancelResponse";
+    MASS_CANCEL_REJECT_REASON = 532, "MassCancelRejectReason";
+    TOTAL_AFFECTED_ORDERS = 533, "TotalAffectedOrders";
+    NO_AFFECTED_ORDERS = 534, "NoAffectedOrders";
+    MATURITY_DATE = 541, "MaturityDate";
+    NO_SIDES = 552, "NoSides";
+    USERNAME = 553, "Username";
+    PASSWORD = 554, "Password";
+    NO_LEGS = 555, "NoLegs";
+    SECURITY_LIST_REQUEST_TYPE = 559, "SecurityListRequestType";
+    SECURITY_REQUEST_RESULT = 560, "SecurityRequestResult";
+    MIN_TRADE_VOL = 562, "MinTradeVol";
+    LEG_PRICE = 566, "LegPrice";
+    TRADE_REQUEST_ID = 568, "TradeRequestID";
+    TRADE_REQUEST_TYPE = 569, "TradeRequestType";
+    PREVIOUSLY_REPORTED = 570, "PreviouslyReported";
+    TRADE_REQUEST_RESULT = 571, "TradeRequestResult";
+    MASS_STATUS_REQ_ID = 584, "MassStatusReqID";
+    MASS_STATUS_REQ_TYPE = 585, "MassStatusReqType";
+    LEG_SYMBOL = 600, "LegSymbol";
+    LEG_RATIO_QTY = 623, "LegRatioQty";
+    LEG_SIDE = 624, "LegSide";
+    RFQ_REQ_ID = 644, "RFQReqID";
+    QUOTE_REQUEST_REJECT_REASON = 658, "QuoteRequestRejectReason";
+    LEG_QTY = 687, "LegQty";
+    NO_POSITIONS = 702, "NoPositions";
+    POS_TYPE = 703, "PosType";
+    LONG_QTY = 704, "LongQty";
+    SHORT_QTY = 705, "ShortQty";
+    POS_REQ_ID = 710, "PosReqID";
+    NO_UNDERLYINGS = 711, "NoUnderlyings";
+    POS_MAINT_RPT_ID = 721, "PosMaintRptID";
+    POS_REQ_TYPE = 724, "PosReqType";
+    POS_REQ_RESULT = 728, "PosReqResult";
+    SETTL_PRICE = 730, "SettlPrice";
+    OPEN_INTEREST = 746, "OpenInterest";
+    TRADE_REQUEST_STATUS = 750, "TradeRequestStatus";
+    NEXT_EXPECTED_MSG_SEQ_NUM = 789, "NextExpectedMsgSeqNum";
+    UNDERLYING_PX = 810, "UnderlyingPx";
+    PEGGED_PRICE = 839, "PeggedPrice";
+    QTY_TYPE = 854, "QtyType";
+    DATED_DATE = 873, "DatedDate";
+    TRD_MATCH_ID = 880, "TrdMatchID";
+    UNDERLYING_END_PRICE = 883, "UnderlyingEndPrice";
+    TOT_NUM_REPORTS = 911, "TotNumReports";
+    USER_REQUEST_ID = 923, "UserRequestID";
+    USER_REQUEST_TYPE = 924, "UserRequestType";
+    USER_STATUS = 926, "UserStatus";
+    STRIKE_CURRENCY = 947, "StrikeCurrency";
+    SECURITY_STATUS = 965, "SecurityStatus";
+    MIN_PRICE_INCREMENT = 969, "MinPriceIncrement";
+    TRADE_ID = 1003, "TradeID";
+    SECONDARY_TRADE_ID = 1040, "SecondaryTradeID";
+    MATURITY_TIME = 1079, "MaturityTime";
+    REFRESH_QTY = 1088, "RefreshQty";
+    PEG_PRICE_TYPE = 1094, "PegPriceType";
+    DISPLAY_QTY = 1138, "DisplayQty";
+    QUOTE_MSG_ID = 1166, "QuoteMsgID";
+    QUOTE_ENTRY_STATUS = 1167, "QuoteEntryStatus";
+    VOLATILITY = 1188, "Volatility";
+    NO_TICK_RULES = 1205, "NoTickRules";
+    START_TICK_PRICE_RANGE = 1206, "StartTickPriceRange";
+    TICK_INCREMENT = 1208, "TickIncrement";
+    NO_FILLS = 1362, "NoFills";
+    FILL_EXEC_ID = 1363, "FillExecID";
+    FILL_PX = 1364, "FillPx";
+    FILL_QTY = 1365, "FillQty";
+    FILL_LIQUIDITY_IND = 1443, "FillLiquidityInd";
+    PRICE_QUOTE_CURRENCY = 1524, "PriceQuoteCurrency";
+    INSTRUMENT_PRICE_PRECISION = 2576, "InstrumentPricePrecision";
+    CONDITION_TRIGGER_METHOD = 5127, "ConditionTriggerMethod";
+    SECONDARY_CURRENCY = 5544, "SecondaryCurrency";
+    CANCEL_ON_DISCONNECT = 9001, "CancelOnDisconnect";
+    USE_WORDSAFE_TAGS = 9002, "UseWordsafeTags";
+    DONT_CANCEL_ON_DISCONNECT = 9003, "DontCancelOnDisconnect";
+    DERIBIT_APP_ID = 9004, "DeribitAppId";
+    DERIBIT_APP_SIG = 9005, "DeribitAppSig";
+    DERIBIT_SEQUENTIAL = 9007, "DeribitSequential";
+    DERIBIT_MM_PROTECTION = 9008, "DeribitMMProtection";
+    UNSUBSCRIBE_EXECUTION_REPORTS = 9009, "UnsubscribeExecutionReports";
+    CONNECTION_ONLY_EXECUTION_REPORTS = 9010, "ConnectionOnlyExecutionReports";
+    DERIBIT_SKIP_BLOCK_TRADES = 9011, "DeribitSkipBlockTrades";
+    DERIBIT_SHOW_BLOCK_TRADE_ID = 9012, "DeribitShowBlockTradeId";
+    DISPLAY_MULTICAST_INSTRUMENT_ID = 9013, "DisplayMulticastInstrumentID";
+    MASS_STATUS_REQ_ID_TYPE = 9014, "MassStatusReqIDType";
+    REPORT_FILLS_AS_EXEC_REPORTS = 9015, "ReportFillsAsExecReports";
+    DISPLAY_INCREMENT_STEPS = 9018, "DisplayIncrementSteps";
+    MMP_GROUP = 9019, "MMPGroup";
+    QUOTE_ENTRY_TYPE = 9020, "QuoteEntryType";
+    FREEZE_QUOTES = 9031, "FreezeQuotes";
+    MIN_DELTA = 9032, "MinDelta";
+    MAX_DELTA = 9033, "MaxDelta";
+    IS_LIQUIDATION = 9034, "IsLiquidation";
+    IS_REBALANCE = 9035, "IsRebalance";
+    IS_RISK_REDUCING = 9036, "IsRiskReducing";
+    ORDER_HISTORY_INCLUDE_UNFILLED = 9039, "OrderHistoryIncludeUnfilled";
+    ORDER_HISTORY_OFFSET = 9040, "OrderHistoryOffset";
+    PROTECTION_QTY_LIMIT = 20110, "ProtectionQtyLimit";
+    PROTECTION_DELTA_LIMIT = 20111, "ProtectionDeltaLimit";
+    FROZEN_LIMIT = 20112, "FrozenLimit";
+    PROTECTION_REQUEST_ID = 20114, "ProtectionRequestID";
+    INTERVAL_LENGTH = 20116, "IntervalLength";
+    PROTECTION_REQUEST_RESULT = 20117, "ProtectionRequestResult";
+    PROTECTION_VEGA_LIMIT = 20118, "ProtectionVegaLimit";
+    DERIBIT_USER_EQUITY = 100001, "DeribitUserEquity";
+    DERIBIT_USER_BALANCE = 100002, "DeribitUserBalance";
+    DERIBIT_USER_INITIAL_MARGIN = 100003, "DeribitUserInitialMargin";
+    DERIBIT_USER_MAINTENANCE_MARGIN = 100004, "DeribitUserMaintenanceMargin";
+    DERIBIT_UNREALIZED_PL = 100005, "DeribitUnrealizedPl";
+    DERIBIT_REALIZED_PL = 100006, "DeribitRealizedPl";
+    DERIBIT_TRADE_AMOUNT = 100007, "DeribitTradeAmount";
+    DERIBIT_SINCE_TIMESTAMP = 100008, "DeribitSinceTimestamp";
+    DERIBIT_TRADE_ID = 100009, "DeribitTradeId";
+    DERIBIT_LABEL = 100010, "DeribitLabel";
+    DERIBIT_TOTAL_PL = 100011, "DeribitTotalPl";
+    DERIBIT_ADV_ORDER_TYPE = 100012, "DeribitAdvOrderType";
+    DERIBIT_MARGIN_BALANCE = 100013, "DeribitMarginBalance";
+    TRADE_VOLUME_24H = 100087, "TradeVolume24h";
+    DERIBIT_LIQUIDATION_PRICE = 100088, "DeribitLiquidationPrice";
+    DERIBIT_SIZE_IN_CURRENCY = 100089, "DeribitSizeInCurrency";
+    MARK_PRICE = 100090, "MarkPrice";
+    DERIBIT_LIQUIDATION = 100091, "DeribitLiquidation";
+    CURRENT_FUNDING = 100092, "CurrentFunding";
+    FUNDING_8H = 100093, "Funding8h";
+}
+
+/// First tag of the Deribit custom tag range
+pub const DERIBIT_CUSTOM_TAG_BASE: u32 = 100_000;
+
+/// First tag of the custom tag range when `UseWordsafeTags` is enabled
+pub const WORDSAFE_CUSTOM_TAG_BASE: u32 = 5_000;
+
+/// Tags carried in the standard header after `MsgType(35)`
+pub const HEADER_TAGS: &[u32] = &[
+    SENDER_COMP_ID,
+    TARGET_COMP_ID,
+    MSG_SEQ_NUM,
+    SENDING_TIME,
+    POSS_DUP_FLAG,
+    POSS_RESEND,
+    ORIG_SENDING_TIME,
+];
+
+/// Check whether a tag belongs to the standard header
+#[must_use]
+pub fn is_header_tag(tag: u32) -> bool {
+    HEADER_TAGS.contains(&tag)
+}
+
+/// Check whether a tag is outside the range defined by the FIX specification
+#[must_use]
+pub fn is_custom_tag(tag: u32) -> bool {
+    tag >= WORDSAFE_CUSTOM_TAG_BASE
+}
+
+/// Data fields as `(length tag, data tag)` pairs
+///
+/// Data fields may contain the SOH delimiter, so their length is always
+/// sent in the preceding length field.
+pub const DATA_FIELDS: &[(u32, u32)] = &[(RAW_DATA_LENGTH, RAW_DATA)];
+
+/// Get the length tag preceding a data field, if the tag holds raw data
+#[must_use]
+pub fn data_length_tag(tag: u32) -> Option<u32> {
+    DATA_FIELDS
+        .iter()
+        .find(|(_, data)| *data == tag)
+        .map(|(length, _)| *length)
+}
+
+/// Check whether a tag carries the length of a data field
+#[must_use]
+pub fn is_data_length_tag(tag: u32) -> bool {
+    DATA_FIELDS.iter().any(|(length, _)| *length == tag)
+}
+
+/// Convert a Deribit custom tag to its `UseWordsafeTags` number
+///
+/// Tags above 100000 move down to the range starting at 5000 (e.g.,
+/// `DeribitLabel(100010)` becomes 5010). Other tags are returned as is.
+/// The API documentation maps `100087` to `5078`, which does not follow its
+/// own rule; this function yields `5087`.
+#[must_use]
+pub fn to_wordsafe_tag(tag: u32) -> u32 {
+    if tag > DERIBIT_CUSTOM_TAG_BASE && tag_name(tag).is_some() {
+        tag - DERIBIT_CUSTOM_TAG_BASE + WORDSAFE_CUSTOM_TAG_BASE
+    } else {
+        tag
+    }
+}
+
+/// Convert a `UseWordsafeTags` number back to the Deribit custom tag
+///
+/// Only numbers that map to a known custom tag are converted, so standard
+/// tags in the 5000 range such as `ConditionTriggerMethod(5127)` are kept.
+#[must_use]
+pub fn from_wordsafe_tag(tag: u32) -> u32 {
+    if tag > WORDSAFE_CUSTOM_TAG_BASE && tag < WORDSAFE_CUSTOM_TAG_BASE * 2 {
+        let custom = tag - WORDSAFE_CUSTOM_TAG_BASE + DERIBIT_CUSTOM_TAG_BASE;
+        if tag_name(custom).is_some() {
+            return custom;
+        }
+    }
+    tag
+}
+
+/// Describe a tag for error messages, as `Name(tag)` when known
+#[must_use]
+pub fn describe_tag(tag: u32) -> String {
+    match tag_name(tag) {
+        Some(name) => format!("{name}({tag})"),
+        None => tag.to_string(),
+    }
+}
+
+macro_rules! msg_types {
+    ($($variant:ident => $value:literal, $doc:literal;)*) => {
+        /// FIX message type carried in `MsgType(35)`
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum MsgType {
+            $(
+                #[doc = concat!($doc, " (`", $value, "`)")]
+                $variant,
+            )*
+            /// Message type not used by the Deribit FIX API
+            Other(String),
+        }
+
+        impl MsgType {
+            /// Get the value sent in `MsgType(35)`
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for MsgType {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)*
+                    other => Self::Other(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+msg_types! {
+    Heartbeat => "0", "Heartbeat";
+    TestRequest => "1", "Test Request";
+    ResendRequest => "2", "Resend Request";
+    Reject => "3", "Session level Reject";
+    SequenceReset => "4", "Sequence Reset";
+    Logout => "5", "Logout";
+    ExecutionReport => "8", "Execution Report";
+    OrderCancelReject => "9", "Order Cancel Reject";
+    Logon => "A", "Logon";
+    NewOrderSingle => "D", "New Order Single";
+    OrderCancelRequest => "F", "Order Cancel Request";
+    OrderCancelReplaceRequest => "G", "Order Cancel/Replace Request";
+    QuoteRequest => "R", "Quote Request";
+    MarketDataRequest => "V", "Market Data Request";
+    MarketDataSnapshotFullRefresh => "W", "Market Data Snapshot/Full Refresh";
+    MarketDataIncrementalRefresh => "X", "Market Data Incremental Refresh";
+    MarketDataRequestReject => "Y", "Market Data Request Reject";
+    QuoteCancel => "Z", "Quote Cancel";
+    MassQuoteAcknowledgement => "b", "Mass Quote Acknowledgement";
+    SecurityDefinitionRequest => "c", "Security Definition Request";
+    SecurityDefinition => "d", "Security Definition";
+    SecurityStatusRequest => "e", "Security Status Request";
+    SecurityStatus => "f", "Security Status";
+    MassQuote => "i", "Mass Quote";
+    OrderMassCancelRequest => "q", "Order Mass Cancel Request";
+    OrderMassCancelReport => "r", "Order Mass Cancel Report";
+    SecurityListRequest => "x", "Security List Request";
+    SecurityList => "y", "Security List";
+    TradeCaptureReportRequest => "AD", "Trade Capture Report Request";
+    TradeCaptureReport => "AE", "Trade Capture Report";
+    OrderMassStatusRequest => "AF", "Order Mass Status Request";
+    QuoteRequestReject => "AG", "Quote Request Reject";
+    RfqRequest => "AH", "RFQ Request";
+    QuoteStatusReport => "AI", "Quote Status Report";
+    RequestForPositions => "AN", "Request For Positions";
+    PositionReport => "AP", "Position Report";
+    TradeCaptureReportRequestAck => "AQ", "Trade Capture Report Request Ack";
+    UserRequest => "BE", "User Request";
+    UserResponse => "BF", "User Response";
+    MmProtectionLimits => "MM", "MMProtection Limits";
+    MmProtectionLimitsResult => "MR", "MMProtection Limits Result/Reject";
+    MmProtectionReset => "MZ", "MMProtection Reset";
+}
+
+impl MsgType {
+    /// Check whether the message belongs to the session layer
+    #[must_use]
+    pub fn is_admin(&self) -> bool {
+        matches!(
+            self,
+            Self::Heartbeat
+                | Self::TestRequest
+                | Self::ResendRequest
+                | Self::Reject
+                | Self::SequenceReset
+                | Self::Logout
+                | Self::Logon
+        )
+    }
+
+    /// Get the repeating group introduced by `count_tag` in this message type
+    #[must_use]
+    pub fn group_spec(&self, count_tag: u32) -> Option<&'static GroupSpec> {
+        match (self, count_tag) {
+            (Self::SecurityList, NO_RELATED_SYM) => Some(&SECURITY_LIST_SYMBOLS),
+            (Self::MarketDataRequest, NO_MD_ENTRY_TYPES) => Some(&MD_ENTRY_TYPES),
+            (Self::MarketDataRequest | Self::QuoteRequestReject, NO_RELATED_SYM) => {
+                Some(&RELATED_SYMBOLS)
+            }
+            (Self::MarketDataSnapshotFullRefresh, NO_MD_ENTRIES) => Some(&MD_FULL_ENTRIES),
+            (Self::MarketDataIncrementalRefresh, NO_MD_ENTRIES) => Some(&MD_INCREMENTAL_ENTRIES),
+            (Self::ExecutionReport, NO_FILLS) => Some(&FILLS),
+            (Self::OrderMassCancelReport, NO_AFFECTED_ORDERS) => Some(&AFFECTED_ORDERS),
+            (Self::PositionReport, NO_POSITIONS) => Some(&POSITIONS),
+            (Self::SecurityDefinitionRequest, NO_LEGS) => Some(&DEFINITION_REQUEST_LEGS),
+            (Self::SecurityDefinition, NO_LEGS) => Some(&DEFINITION_LEGS),
+            (Self::SecurityDefinition, NO_UNDERLYINGS) => Some(&UNDERLYINGS),
+            (Self::QuoteRequest, NO_RELATED_SYM) => Some(&QUOTE_REQUEST_SYMBOLS),
+            (Self::TradeCaptureReport, NO_LEGS) => Some(&TRADE_CAPTURE_LEGS),
+            (Self::TradeCaptureReport, NO_SIDES) => Some(&TRADE_CAPTURE_SIDES),
+            (Self::MassQuote, NO_QUOTE_SETS) => Some(&QUOTE_SETS),
+            (Self::MassQuoteAcknowledgement, NO_QUOTE_ENTRIES) => Some(&QUOTE_ACK_ENTRIES),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MsgType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for MsgType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+/// Layout of a repeating group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupSpec {
+    /// `NumInGroup` tag announcing the number of entries
+    pub count_tag: u32,
+    /// Tag opening every entry
+    pub delimiter: u32,
+    /// Tags allowed inside an entry, including the delimiter
+    pub members: &'static [u32],
+    /// Groups nested inside an entry
+    pub nested: &'static [GroupSpec],
+}
+
+impl GroupSpec {
+    /// Check whether a tag may appear inside an entry of this group
+    #[must_use]
+    pub fn contains(&self, tag: u32) -> bool {
+        self.members.contains(&tag) || self.nested_spec(tag).is_some()
+    }
+
+    /// Get the nested group introduced by `count_tag`
+    #[must_use]
+    pub fn nested_spec(&self, count_tag: u32) -> Option<&'static GroupSpec> {
+        self.nested.iter().find(|spec| spec.count_tag == count_tag)
+    }
+}
+
+const SECURITY_ALT_IDS: GroupSpec = GroupSpec {
+    count_tag: NO_SECURITY_ALT_ID,
+    delimiter: SECURITY_ALT_ID,
+    members: &[SECURITY_ALT_ID, SECURITY_ALT_ID_SOURCE],
+    nested: &[],
+};
+
+const TICK_RULES: GroupSpec = GroupSpec {
+    count_tag: NO_TICK_RULES,
+    delimiter: START_TICK_PRICE_RANGE,
+    members: &[START_TICK_PRICE_RANGE, TICK_INCREMENT],
+    nested: &[],
+};
+
+static SECURITY_LIST_SYMBOLS: GroupSpec = GroupSpec {
+    count_tag: NO_RELATED_SYM,
+    delimiter: SYMBOL,
+    members: &[
+        SYMBOL,
+        SECURITY_DESC,
+        SECURITY_TYPE,
+        PUT_OR_CALL,
+        STRIKE_PRICE,
+        STRIKE_CURRENCY,
+        CURRENCY,
+        PRICE_QUOTE_CURRENCY,
+        INSTRUMENT_PRICE_PRECISION,
+        MIN_PRICE_INCREMENT,
+        UNDERLYING_SYMBOL,
+        ISSUE_DATE,
+        MATURITY_DATE,
+        MATURITY_TIME,
+        MIN_TRADE_VOL,
+        SETTL_TYPE,
+        SETTL_CURRENCY,
+        COMM_CURRENCY,
+        CONTRACT_MULTIPLIER,
+        SECURITY_STATUS,
+    ],
+    nested: &[SECURITY_ALT_IDS, TICK_RULES],
+};
+
+static MD_ENTRY_TYPES: GroupSpec = GroupSpec {
+    count_tag: NO_MD_ENTRY_TYPES,
+    delimiter: MD_ENTRY_TYPE,
+    members: &[MD_ENTRY_TYPE],
+    nested: &[],
+};
+
+static RELATED_SYMBOLS: GroupSpec = GroupSpec {
+    count_tag: NO_RELATED_SYM,
+    delimiter: SYMBOL,
+    members: &[SYMBOL],
+    nested: &[],
+};
+
+static MD_FULL_ENTRIES: GroupSpec = GroupSpec {
+    count_tag: NO_MD_ENTRIES,
+    delimiter: MD_ENTRY_TYPE,
+    members: &[
+        MD_ENTRY_TYPE,
+        MD_ENTRY_PX,
+        MD_ENTRY_SIZE,
+        MD_ENTRY_DATE,
+        DERIBIT_TRADE_ID,
+        SIDE,
+        PRICE,
+        TEXT,
+        ORDER_ID,
+        SECONDARY_ORDER_ID,
+        ORD_STATUS,
+        DERIBIT_LABEL,
+        DERIBIT_LIQUIDATION,
+        TRD_MATCH_ID,
+    ],
+    nested: &[],
+};
+
+static MD_INCREMENTAL_ENTRIES: GroupSpec = GroupSpec {
+    count_tag: NO_MD_ENTRIES,
+    delimiter: MD_UPDATE_ACTION,
+    members: &[
+        MD_UPDATE_ACTION,
+        MD_ENTRY_TYPE,
+        MD_ENTRY_PX,
+        MD_ENTRY_SIZE,
+        MD_ENTRY_DATE,
+        DERIBIT_TRADE_ID,
+        SIDE,
+        ORDER_ID,
+        SECONDARY_ORDER_ID,
+        ORD_STATUS,
+        DERIBIT_LABEL,
+        PRICE,
+        TEXT,
+        DERIBIT_LIQUIDATION,
+        TRD_MATCH_ID,
+    ],
+    nested: &[],
+};
+
+static FILLS: GroupSpec = GroupSpec {
+    count_tag: NO_FILLS,
+    delimiter: FILL_EXEC_ID,
+    members: &[FILL_EXEC_ID, FILL_PX, FILL_QTY, FILL_LIQUIDITY_IND],
+    nested: &[],
+};
+
+static AFFECTED_ORDERS: GroupSpec = GroupSpec {
+    count_tag: NO_AFFECTED_ORDERS,
+    delimiter: ORIG_CL_ORD_ID,
+    members: &[ORIG_CL_ORD_ID],
+    nested: &[],
+};
+
+static POSITIONS: GroupSpec = GroupSpec {
+    count_tag: NO_POSITIONS,
+    delimiter: POS_TYPE,
+    members: &[
+        POS_TYPE,
+        LONG_QTY,
+        SHORT_QTY,
+        SYMBOL,
+        QTY_TYPE,
+        CONTRACT_MULTIPLIER,
+        UNDERLYING_END_PRICE,
+        SIDE,
+        SETTL_PRICE,
+        AVG_PX,
+        DERIBIT_LIQUIDATION_PRICE,
+        DERIBIT_SIZE_IN_CURRENCY,
+    ],
+    nested: &[],
+};
+
+static DEFINITION_REQUEST_LEGS: GroupSpec = GroupSpec {
+    count_tag: NO_LEGS,
+    delimiter: LEG_SYMBOL,
+    members: &[LEG_SYMBOL, LEG_SIDE, LEG_RATIO_QTY],
+    nested: &[],
+};
+
+static DEFINITION_LEGS: GroupSpec = GroupSpec {
+    count_tag: NO_LEGS,
+    delimiter: LEG_SYMBOL,
+    members: &[LEG_SYMBOL, LEG_RATIO_QTY],
+    nested: &[],
+};
+
+static UNDERLYINGS: GroupSpec = GroupSpec {
+    count_tag: NO_UNDERLYINGS,
+    delimiter: UNDERLYING_SYMBOL,
+    members: &[UNDERLYING_SYMBOL],
+    nested: &[],
+};
+
+static QUOTE_REQUEST_SYMBOLS: GroupSpec = GroupSpec {
+    count_tag: NO_RELATED_SYM,
+    delimiter: SYMBOL,
+    members: &[SYMBOL, SECURITY_TYPE, SIDE, ORDER_QTY],
+    nested: &[],
+};
+
+static TRADE_CAPTURE_LEGS: GroupSpec = GroupSpec {
+    count_tag: NO_LEGS,
+    delimiter: LEG_SYMBOL,
+    members: &[LEG_SYMBOL, LEG_QTY, LEG_PRICE, LEG_SIDE],
+    nested: &[],
+};
+
+static TRADE_CAPTURE_SIDES: GroupSpec = GroupSpec {
+    count_tag: NO_SIDES,
+    delimiter: SIDE,
+    members: &[SIDE, ORDER_ID, COMMISSION, COMM_CURRENCY],
+    nested: &[],
+};
+
+const QUOTE_ENTRIES: GroupSpec = GroupSpec {
+    count_tag: NO_QUOTE_ENTRIES,
+    delimiter: QUOTE_ENTRY_ID,
+    members: &[
+        QUOTE_ENTRY_ID,
+        SYMBOL,
+        BID_PX,
+        OFFER_PX,
+        BID_SIZE,
+        OFFER_SIZE,
+        EXEC_INST,
+    ],
+    nested: &[],
+};
+
+static QUOTE_SETS: GroupSpec = GroupSpec {
+    count_tag: NO_QUOTE_SETS,
+    delimiter: QUOTE_SET_ID,
+    members: &[QUOTE_SET_ID, TOT_NO_QUOTE_ENTRIES],
+    nested: &[QUOTE_ENTRIES],
+};
+
+static QUOTE_ACK_ENTRIES: GroupSpec = GroupSpec {
+    count_tag: NO_QUOTE_ENTRIES,
+    delimiter: QUOTE_ENTRY_ID,
+    members: &[
+        QUOTE_ENTRY_ID,
+        QUOTE_ENTRY_TYPE,
+        QUOTE_SET_ID,
+        QUOTE_ENTRY_STATUS,
+        SYMBOL,
+        SIDE,
+        ORDER_QTY_2,
+        ORDER_ID,
+        TRANSACT_TIME,
+        BID_PX,
+        BID_SIZE,
+        OFFER_PX,
+        OFFER_SIZE,
+        QUOTE_ENTRY_REJECT_REASON,
+        TEXT,
+    ],
+    nested: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_names() {
+        assert_eq!(tag_name(MSG_SEQ_NUM), Some("MsgSeqNum"));
+        assert_eq!(tag_name(DERIBIT_LABEL), Some("DeribitLabel"));
+        assert_eq!(tag_name(4), None);
+        assert_eq!(describe_tag(CHECK_SUM), "CheckSum(10)");
+        assert_eq!(describe_tag(4), "4");
+    }
+
+    #[test]
+    fn test_documented_tags_are_known() {
+        let doc =
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/doc/DeribitAPI.md"))
+                .unwrap();
+        let start = doc.find("## FIX API").unwrap();
+        let end = start + doc[start..].find("## Changes Log").unwrap();
+        let lines: Vec<&str> = doc[start..end]
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let mut checked = 0;
+        for pair in lines.windows(2) {
+            let number = pair[0].trim_start_matches(['\\', '=', '>', ' ']);
+            let is_name = pair[1].starts_with('`') && pair[1].ends_with('`');
+            if let (Ok(tag), true) = (number.parse::<u32>(), is_name) {
+                assert!(tag_name(tag).is_some(), "tag {tag} missing");
+                checked += 1;
+            }
+        }
+        assert!(checked > 200);
+    }
+
+    #[test]
+    fn test_header_and_custom_tags() {
+        assert!(is_header_tag(MSG_SEQ_NUM));
+        assert!(!is_header_tag(SYMBOL));
+        assert!(is_custom_tag(DERIBIT_APP_ID));
+        assert!(is_custom_tag(DERIBIT_LABEL));
+        assert!(!is_custom_tag(HEART_BT_INT));
+        assert_eq!(data_length_tag(RAW_DATA), Some(RAW_DATA_LENGTH));
+        assert_eq!(data_length_tag(TEXT), None);
+        assert!(is_data_length_tag(RAW_DATA_LENGTH));
+    }
+
+    #[test]
+    fn test_wordsafe_tags() {
+        assert_eq!(to_wordsafe_tag(DERIBIT_LABEL), 5010);
+        assert_eq!(to_wordsafe_tag(TRADE_VOLUME_24H), 5087);
+        assert_eq!(to_wordsafe_tag(SYMBOL), SYMBOL);
+        assert_eq!(from_wordsafe_tag(5010), DERIBIT_LABEL);
+        assert_eq!(
+            from_wordsafe_tag(CONDITION_TRIGGER_METHOD),
+            CONDITION_TRIGGER_METHOD
+        );
+        assert_eq!(from_wordsafe_tag(SECONDARY_CURRENCY), SECONDARY_CURRENCY);
+        assert_eq!(from_wordsafe_tag(SYMBOL), SYMBOL);
+    }
+
+    #[test]
+    fn test_msg_type_round_trip() {
+        for value in ["0", "A", "D", "W", "X", "AE", "BF", "MZ", "i", "y"] {
+            let msg_type: MsgType = value.parse().unwrap();
+            assert!(!matches!(msg_type, MsgType::Other(_)));
+            assert_eq!(msg_type.to_string(), value);
+        }
+        assert_eq!(MsgType::from("UZ"), MsgType::Other("UZ".to_string()));
+        assert!(MsgType::Logon.is_admin());
+        assert!(!MsgType::NewOrderSingle.is_admin());
+    }
+
+    #[test]
+    fn test_group_specs() {
+        let spec = MsgType::MarketDataIncrementalRefresh
+            .group_spec(NO_MD_ENTRIES)
+            .unwrap();
+        assert_eq!(spec.delimiter, MD_UPDATE_ACTION);
+        let spec = MsgType::MarketDataSnapshotFullRefresh
+            .group_spec(NO_MD_ENTRIES)
+            .unwrap();
+        assert_eq!(spec.delimiter, MD_ENTRY_TYPE);
+        let spec = MsgType::SecurityList.group_spec(NO_RELATED_SYM).unwrap();
+        assert!(spec.contains(NO_TICK_RULES));
+        assert_eq!(
+            spec.nested_spec(NO_TICK_RULES).unwrap().delimiter,
+            START_TICK_PRICE_RANGE
+        );
+        assert!(MsgType::Logon.group_spec(NO_RELATED_SYM).is_none());
+    }
+}
@@ -73,6 +73,13 @@
 //! - Serde support for JSON serialization
 //! - Standard Error trait implementations
 //!
+//! ### [`fix`]
+//! FIX 4.4 tag-value codec for the Deribit FIX API:
+//! - Messages with ordered fields and repeating groups
+//! - Zero-copy frame parsing and stream framing by `BodyLength(9)`
+//! - Serialization computing `BodyLength(9)` and `CheckSum(10)`
+//! - Tag dictionary including Deribit custom tags
+//!
 //! ### [`model`]
 //! Protocol-agnostic data structures for:
 //! - **Trading**: Orders, positions, trades, settlements
@@ -80,6 +87,12 @@
 //! - **Account**: Portfolios, balances, transfers, subaccounts
 //! - **Configuration**: Request/response wrappers, authentication
 //!
+//! ### `testkit`
+//! With the `testkit` feature, an in-process fake exchange for offline tests:
+//! - Answers authentication, instruments, tickers, orders and positions
+//! - Injects errors built from `DeribitErrorCode`
+//! - Pushes scripted market data as subscription notifications
+//!
 //! ### [`utils`]
 //! Utility functions for:
 //! - **Cryptographic Operations**: Nonce generation, checksums, hashing
@@ -143,9 +156,12 @@
 pub mod constants;
 /// Error handling types and utilities
 pub mod error;
+pub mod fix;
 /// Data models for orders, positions, and other trading entities
 pub mod model;
 /// Re-export commonly used types for convenience
 pub mod prelude;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 /// Utility functions and helpers
 pub mod utils;
@@ -3,8 +3,12 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::order::OrderInfo;
+use crate::model::position::Position;
+use crate::model::subscription::AccessLogEntry;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Account summary information
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
@@ -19,13 +23,16 @@ pub struct AccountSummary {
     pub available_funds: f64,
     /// Margin balance
     pub margin_balance: f64,
-    /// Unrealized profit and loss
+    /// Unrealized profit and loss (not part of the API response)
+    #[serde(default)]
     pub unrealized_pnl: f64,
-    /// Realized profit and loss
+    /// Realized profit and loss (not part of the API response)
+    #[serde(default)]
     pub realized_pnl: f64,
     /// Total profit and loss
     pub total_pl: f64,
-    /// Session funding
+    /// Session funding (not part of the API response)
+    #[serde(default)]
     pub session_funding: f64,
     /// Session realized P&L
     pub session_rpl: f64,
@@ -61,6 +68,8 @@ pub struct AccountSummary {
     pub options_theta: Option<f64>,
     /// Options vega
     pub options_vega: Option<f64>,
+    /// Options value
+    pub options_value: Option<f64>,
     /// Portfolio margin enabled
     pub portfolio_margining_enabled: Option<bool>,
     /// Projected delta total
@@ -69,20 +78,152 @@ pub struct AccountSummary {
     pub projected_initial_margin: Option<f64>,
     /// Projected maintenance margin
     pub projected_maintenance_margin: Option<f64>,
+    /// Estimated liquidation ratio
+    pub estimated_liquidation_ratio: Option<f64>,
+    /// Balance reserved in other orders
+    pub additional_reserve: Option<f64>,
+    /// Balance reserved for spot orders
+    pub spot_reserve: Option<f64>,
+    /// Balance of the fee credit
+    pub fee_balance: Option<f64>,
+    /// Margin model (e.g., "segregated_sm", "cross_pm")
+    pub margin_model: Option<String>,
+    /// Whether the account holds equity outside the blockchain
+    pub has_non_block_chain_equity: Option<bool>,
+    /// Total equity of all currencies in USD (cross collateral only)
+    pub total_equity_usd: Option<f64>,
+    /// Total margin balance of all currencies in USD (cross collateral only)
+    pub total_margin_balance_usd: Option<f64>,
+    /// Total delta of all currencies in USD (cross collateral only)
+    pub total_delta_total_usd: Option<f64>,
+    /// Total initial margin of all currencies in USD (cross collateral only)
+    pub total_initial_margin_usd: Option<f64>,
+    /// Total maintenance margin of all currencies in USD (cross collateral only)
+    pub total_maintenance_margin_usd: Option<f64>,
+    /// Account ID, present with `extended`
+    pub id: Option<u64>,
+    /// Account email, present with `extended`
+    pub email: Option<String>,
+    /// Account name given by the user, present with `extended`
+    pub username: Option<String>,
     /// System name
     pub system_name: Option<String>,
-    /// Type of account
-    #[serde(rename = "type")]
+    /// Type of account, present with `extended`
+    #[serde(rename = "type", default)]
     pub account_type: String,
+    /// Account creation timestamp (milliseconds since Unix epoch)
+    pub creation_timestamp: Option<u64>,
+    /// Optional identifier of the referrer
+    pub referrer_id: Option<String>,
+    /// Whether account login is enabled
+    pub login_enabled: Option<bool>,
+    /// Whether security keys are enabled
+    pub security_keys_enabled: Option<bool>,
+    /// Whether market maker protection is enabled
+    pub mmp_enabled: Option<bool>,
+    /// Whether transfers to other users are enabled
+    pub interuser_transfers_enabled: Option<bool>,
+    /// Self trading rejection behavior
+    pub self_trading_reject_mode: Option<String>,
+    /// Whether self trading prevention covers subaccounts
+    pub self_trading_extended_to_subaccounts: Option<bool>,
+    /// Whether Block RFQ self match prevention is enabled
+    pub block_rfq_self_match_prevention: Option<bool>,
     // Additional fields from deribit-http types.rs
-    /// Delta total map (currency -> delta)
-    pub delta_total_map: std::collections::HashMap<String, f64>,
+    /// Delta total map (index -> delta)
+    #[serde(default)]
+    pub delta_total_map: HashMap<String, f64>,
+    /// Estimated liquidation ratio per index
+    #[serde(default)]
+    pub estimated_liquidation_ratio_map: HashMap<String, f64>,
+    /// Options gamma per index
+    #[serde(default)]
+    pub options_gamma_map: HashMap<String, f64>,
+    /// Options theta per index
+    #[serde(default)]
+    pub options_theta_map: HashMap<String, f64>,
+    /// Options vega per index
+    #[serde(default)]
+    pub options_vega_map: HashMap<String, f64>,
     /// Deposit address
+    #[serde(default)]
     pub deposit_address: String,
     /// Fees structure
-    pub fees: Vec<std::collections::HashMap<String, f64>>,
+    #[serde(default)]
+    pub fees: Vec<AccountFee>,
     /// Account limits
-    pub limits: std::collections::HashMap<String, f64>,
+    #[serde(default)]
+    pub limits: AccountLimits,
+}
+
+/// Fee rates of one instrument kind, from the account summary
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountFee {
+    /// Index the fees apply to (e.g., "btc_usd")
+    pub index_name: String,
+    /// Instrument kind (e.g., "future", "perpetual", "option")
+    pub kind: String,
+    /// Fee values
+    pub value: AccountFeeValue,
+}
+
+/// Fee values of one instrument kind
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountFeeValue {
+    /// Default maker and taker fees
+    pub default: FeeRates,
+    /// Block trade fee
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_trade: Option<f64>,
+    /// Settlement fee
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement: Option<f64>,
+}
+
+/// Maker and taker fee rates
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    /// Fee type: `fixed` or `relative`
+    #[serde(rename = "type")]
+    pub fee_type: String,
+    /// Maker fee
+    pub maker: f64,
+    /// Taker fee
+    pub taker: f64,
+}
+
+/// Rate limit of one group of requests
+#[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Maximum number of requests in a burst
+    pub burst: u32,
+    /// Requests per second
+    pub rate: u32,
+}
+
+/// Matching engine rate limits, per group of requests
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MatchingEngineLimits {
+    /// Trading limits, in total and per currency or kind
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub trading: HashMap<String, RateLimit>,
+    /// Limits of the other groups (e.g., "spot", "quotes", "cancel_all")
+    #[serde(flatten)]
+    pub groups: HashMap<String, RateLimit>,
+}
+
+/// Rate limits of the account
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AccountLimits {
+    /// Whether the limits are applied per currency
+    #[serde(default)]
+    pub limits_per_currency: bool,
+    /// Non matching engine limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_matching_engine: Option<RateLimit>,
+    /// Matching engine limits
+    #[serde(default)]
+    pub matching_engine: MatchingEngineLimits,
 }
 
 impl AccountSummary {
@@ -124,13 +265,28 @@ pub struct Subaccount {
     pub id: u64,
     /// Whether login is enabled
     pub login_enabled: bool,
-    /// Portfolio information (optional)
-    pub portfolio: Option<PortfolioInfo>,
+    /// Whether a password is set
+    pub is_password: Option<bool>,
+    /// Margin model (e.g., "segregated_sm", "cross_pm")
+    pub margin_model: Option<String>,
+    /// New email address awaiting confirmation
+    pub not_confirmed_email: Option<String>,
+    /// Whether security keys are enabled
+    pub security_keys_enabled: Option<bool>,
+    /// Security keys assigned to the subaccount
+    pub security_keys_assignments: Option<Vec<serde_json::Value>>,
+    /// Proof id of the subaccount
+    pub proof_id: Option<String>,
+    /// Signature of the proof id
+    pub proof_id_signature: Option<String>,
+    /// Balances by lowercase currency, present with `with_portfolio`
+    pub portfolio: Option<HashMap<String, SubaccountBalance>>,
     /// Whether to receive notifications
     pub receive_notifications: bool,
     /// System name
     pub system_name: String,
     /// Time in force (optional)
+    #[serde(default)]
     pub tif: Option<String>,
     /// Subaccount type
     #[serde(rename = "type")]
@@ -139,6 +295,110 @@ pub struct Subaccount {
     pub username: String,
 }
 
+impl Subaccount {
+    /// Whether this is the main account
+    #[must_use]
+    pub fn is_main(&self) -> bool {
+        self.subaccount_type == "main"
+    }
+}
+
+/// Balance of a subaccount in one currency
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubaccountBalance {
+    /// Currency, in lowercase
+    pub currency: String,
+    /// Balance
+    pub balance: f64,
+    /// Equity
+    pub equity: f64,
+    /// Available funds
+    pub available_funds: f64,
+    /// Available withdrawal funds
+    pub available_withdrawal_funds: f64,
+    /// Initial margin
+    pub initial_margin: f64,
+    /// Maintenance margin
+    pub maintenance_margin: f64,
+    /// Margin balance
+    pub margin_balance: f64,
+    /// Balance reserved in other orders
+    #[serde(default)]
+    pub additional_reserve: Option<f64>,
+    /// Balance reserved for spot orders
+    #[serde(default)]
+    pub spot_reserve: Option<f64>,
+}
+
+/// Positions and open orders of a subaccount, from `private/get_subaccounts_details`
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct SubaccountDetails {
+    /// Subaccount id
+    pub uid: u64,
+    /// Open positions
+    #[serde(default)]
+    pub positions: Vec<Position>,
+    /// Open orders, present with `with_open_orders`
+    #[serde(default)]
+    pub open_orders: Vec<OrderInfo>,
+}
+
+/// Login state of a subaccount
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubaccountLoginState {
+    /// Allow login
+    Enable,
+    /// Block login and terminate existing sessions
+    Disable,
+}
+
+impl SubaccountLoginState {
+    /// Returns the state as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Enable => "enable",
+            Self::Disable => "disable",
+        }
+    }
+}
+
+impl std::fmt::Display for SubaccountLoginState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Request of `private/toggle_subaccount_login`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToggleSubaccountLoginRequest {
+    /// Subaccount id
+    pub sid: u64,
+    /// New login state
+    pub state: SubaccountLoginState,
+}
+
+impl ToggleSubaccountLoginRequest {
+    /// Allow login to a subaccount
+    #[must_use]
+    pub fn enable(sid: u64) -> Self {
+        Self {
+            sid,
+            state: SubaccountLoginState::Enable,
+        }
+    }
+
+    /// Block login to a subaccount
+    #[must_use]
+    pub fn disable(sid: u64) -> Self {
+        Self {
+            sid,
+            state: SubaccountLoginState::Disable,
+        }
+    }
+}
+
 /// Portfolio information
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct PortfolioInfo {
@@ -169,16 +429,154 @@ pub struct PortfolioInfo {
 }
 
 /// Portfolio information
+///
+/// Also the payload of the `user.portfolio` channel, which sends the
+/// balances of one currency and no account summaries.
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct Portfolio {
     /// Currency of the portfolio
     pub currency: String,
     /// Account summaries for different currencies
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub accounts: Vec<AccountSummary>,
     /// Total portfolio value in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub total_usd_value: Option<f64>,
     /// Cross-currency margin enabled
+    #[serde(default)]
     pub cross_margin_enabled: bool,
+    /// Account balance
+    #[serde(default)]
+    pub balance: f64,
+    /// Account equity
+    #[serde(default)]
+    pub equity: f64,
+    /// Margin balance
+    #[serde(default)]
+    pub margin_balance: f64,
+    /// Available funds
+    #[serde(default)]
+    pub available_funds: f64,
+    /// Funds available for withdrawal
+    #[serde(default)]
+    pub available_withdrawal_funds: f64,
+    /// Initial margin
+    #[serde(default)]
+    pub initial_margin: f64,
+    /// Maintenance margin
+    #[serde(default)]
+    pub maintenance_margin: f64,
+    /// Projected initial margin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_initial_margin: Option<f64>,
+    /// Projected maintenance margin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_maintenance_margin: Option<f64>,
+    /// Total delta
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_total: Option<f64>,
+    /// Projected total delta
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_delta_total: Option<f64>,
+    /// Total delta in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_delta_total_usd: Option<f64>,
+    /// Total margin balance in USD (cross collateral only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_margin_balance_usd: Option<f64>,
+    /// Total equity in USD (cross collateral only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_equity_usd: Option<f64>,
+    /// Total initial margin in USD (cross collateral only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_initial_margin_usd: Option<f64>,
+    /// Total maintenance margin in USD (cross collateral only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_maintenance_margin_usd: Option<f64>,
+    /// Funds available for transfer to subaccounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_subaccount_transfer_funds: Option<f64>,
+    /// Balance locked by open orders and pending withdrawals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_balance: Option<f64>,
+    /// Session realized profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_rpl: Option<f64>,
+    /// Session unrealized profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_upl: Option<f64>,
+    /// Total profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pl: Option<f64>,
+    /// Futures profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub futures_pl: Option<f64>,
+    /// Futures session realized profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub futures_session_rpl: Option<f64>,
+    /// Futures session unrealized profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub futures_session_upl: Option<f64>,
+    /// Options profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_pl: Option<f64>,
+    /// Options session realized profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_session_rpl: Option<f64>,
+    /// Options session unrealized profit and loss
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_session_upl: Option<f64>,
+    /// Options value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_value: Option<f64>,
+    /// Options delta
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_delta: Option<f64>,
+    /// Options gamma
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_gamma: Option<f64>,
+    /// Options theta
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_theta: Option<f64>,
+    /// Options vega
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options_vega: Option<f64>,
+    /// Estimated liquidation ratio
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_liquidation_ratio: Option<f64>,
+    /// Fee balance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_balance: Option<f64>,
+    /// Additional reserve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_reserve: Option<f64>,
+    /// Spot reserve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spot_reserve: Option<f64>,
+    /// Margin model (e.g., `segregated_sm`, `cross_pm`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_model: Option<String>,
+    /// Whether portfolio margining is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portfolio_margining_enabled: Option<bool>,
+    /// Whether cross collateral is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cross_collateral_enabled: Option<bool>,
+    /// Total delta per index
+    #[serde(default)]
+    pub delta_total_map: HashMap<String, f64>,
+    /// Estimated liquidation ratio per index
+    #[serde(default)]
+    pub estimated_liquidation_ratio_map: HashMap<String, f64>,
+    /// Options gamma per index
+    #[serde(default)]
+    pub options_gamma_map: HashMap<String, f64>,
+    /// Options theta per index
+    #[serde(default)]
+    pub options_theta_map: HashMap<String, f64>,
+    /// Options vega per index
+    #[serde(default)]
+    pub options_vega_map: HashMap<String, f64>,
 }
 
 impl Portfolio {
@@ -189,6 +587,50 @@ impl Portfolio {
             accounts: Vec::new(),
             total_usd_value: None,
             cross_margin_enabled: false,
+            balance: 0.0,
+            equity: 0.0,
+            margin_balance: 0.0,
+            available_funds: 0.0,
+            available_withdrawal_funds: 0.0,
+            initial_margin: 0.0,
+            maintenance_margin: 0.0,
+            projected_initial_margin: None,
+            projected_maintenance_margin: None,
+            delta_total: None,
+            projected_delta_total: None,
+            total_delta_total_usd: None,
+            total_margin_balance_usd: None,
+            total_equity_usd: None,
+            total_initial_margin_usd: None,
+            total_maintenance_margin_usd: None,
+            available_subaccount_transfer_funds: None,
+            locked_balance: None,
+            session_rpl: None,
+            session_upl: None,
+            total_pl: None,
+            futures_pl: None,
+            futures_session_rpl: None,
+            futures_session_upl: None,
+            options_pl: None,
+            options_session_rpl: None,
+            options_session_upl: None,
+            options_value: None,
+            options_delta: None,
+            options_gamma: None,
+            options_theta: None,
+            options_vega: None,
+            estimated_liquidation_ratio: None,
+            fee_balance: None,
+            additional_reserve: None,
+            spot_reserve: None,
+            margin_model: None,
+            portfolio_margining_enabled: None,
+            cross_collateral_enabled: None,
+            delta_total_map: HashMap::new(),
+            estimated_liquidation_ratio_map: HashMap::new(),
+            options_gamma_map: HashMap::new(),
+            options_theta_map: HashMap::new(),
+            options_vega_map: HashMap::new(),
         }
     }
 
@@ -218,10 +660,109 @@ impl Portfolio {
     }
 }
 
+/// Account summaries returned by `private/get_account_summaries`
+///
+/// The account-level fields are only present when the request sets
+/// `extended`.
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct AccountSummaries {
+    /// Account ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// Account email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// System generated user nickname
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_name: Option<String>,
+    /// Account name given by the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Account type
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<String>,
+    /// Account creation timestamp (milliseconds since Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_timestamp: Option<i64>,
+    /// Optional identifier of the referrer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referrer_id: Option<String>,
+    /// Whether account login is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login_enabled: Option<bool>,
+    /// Whether security keys are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_keys_enabled: Option<bool>,
+    /// Whether market maker protection is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mmp_enabled: Option<bool>,
+    /// Whether transfers to other users are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interuser_transfers_enabled: Option<bool>,
+    /// Self trading rejection behavior
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_trading_reject_mode: Option<String>,
+    /// Whether self trading prevention covers subaccounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_trading_extended_to_subaccounts: Option<bool>,
+    /// Whether Block RFQ self match prevention is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_self_match_prevention: Option<bool>,
+    /// Per-currency account summaries
+    pub summaries: Vec<AccountSummary>,
+}
+
+/// Margin figures before or after a margin model change
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginState {
+    /// Maintenance margin rate
+    pub maintenance_margin_rate: f64,
+    /// Initial margin rate
+    pub initial_margin_rate: f64,
+    /// Available balance
+    pub available_balance: f64,
+}
+
+/// Per-currency outcome of `private/change_margin_model`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginModelChange {
+    /// Currency
+    pub currency: String,
+    /// Margin state under the previous model
+    pub old_state: MarginState,
+    /// Margin state under the new model
+    pub new_state: MarginState,
+}
+
+/// Page of the account access log
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessLogResponse {
+    /// Total number of records available
+    pub records_total: u32,
+    /// Records in this page
+    pub data: Vec<AccessLogEntry>,
+}
+
+/// Affiliate program status returned by `private/get_affiliate_program_info`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AffiliateProgramInfo {
+    /// Whether the affiliate program is enabled
+    pub is_enabled: bool,
+    /// Affiliate link
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Number of affiliates
+    #[serde(default)]
+    pub number_of_affiliates: u32,
+    /// Rewards received, keyed by lowercase currency
+    #[serde(default)]
+    pub received: HashMap<String, f64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::model::subscription::UserLock;
 
     fn create_test_account_summary() -> AccountSummary {
         AccountSummary {
@@ -251,16 +792,44 @@ mod tests {
             options_session_upl: Some(-0.06),
             options_theta: Some(-0.02),
             options_vega: Some(0.1),
+            options_value: None,
             portfolio_margining_enabled: Some(false),
             projected_delta_total: Some(0.6),
             projected_initial_margin: Some(0.25),
             projected_maintenance_margin: Some(0.12),
+            estimated_liquidation_ratio: None,
+            additional_reserve: None,
+            spot_reserve: None,
+            fee_balance: None,
+            margin_model: None,
+            has_non_block_chain_equity: None,
+            total_equity_usd: None,
+            total_margin_balance_usd: None,
+            total_delta_total_usd: None,
+            total_initial_margin_usd: None,
+            total_maintenance_margin_usd: None,
+            id: None,
+            email: None,
+            username: None,
             system_name: Some("deribit".to_string()),
             account_type: "main".to_string(),
+            creation_timestamp: None,
+            referrer_id: None,
+            login_enabled: None,
+            security_keys_enabled: None,
+            mmp_enabled: None,
+            interuser_transfers_enabled: None,
+            self_trading_reject_mode: None,
+            self_trading_extended_to_subaccounts: None,
+            block_rfq_self_match_prevention: None,
             delta_total_map: HashMap::new(),
+            estimated_liquidation_ratio_map: HashMap::new(),
+            options_gamma_map: HashMap::new(),
+            options_theta_map: HashMap::new(),
+            options_vega_map: HashMap::new(),
             deposit_address: "bc1qtest123".to_string(),
-            fees: vec![HashMap::new()],
-            limits: HashMap::new(),
+            fees: Vec::new(),
+            limits: AccountLimits::default(),
         }
     }
 
@@ -401,6 +970,13 @@ mod tests {
             email: "test@example.com".to_string(),
             id: 12345,
             login_enabled: true,
+            is_password: None,
+            margin_model: None,
+            not_confirmed_email: None,
+            security_keys_enabled: None,
+            security_keys_assignments: None,
+            proof_id: None,
+            proof_id_signature: None,
             portfolio: None,
             receive_notifications: false,
             system_name: "deribit".to_string(),
@@ -444,4 +1020,160 @@ mod tests {
         assert!(debug_str.contains("BTC"));
         assert!(display_str.contains("BTC"));
     }
+
+    #[test]
+    fn test_subaccount_with_portfolio() {
+        let json = r#"{
+            "email": "user_AAA@email.com",
+            "id": 13,
+            "is_password": false,
+            "login_enabled": false,
+            "portfolio": {
+                "btc": {
+                    "additional_reserve": 0,
+                    "spot_reserve": 0,
+                    "available_funds": 5.000413075,
+                    "available_withdrawal_funds": 5.000413075,
+                    "balance": 5.000593987,
+                    "currency": "btc",
+                    "equity": 5.000571846,
+                    "initial_margin": 0.000158771,
+                    "maintenance_margin": 0.000115715,
+                    "margin_balance": 5.000571846
+                }
+            },
+            "receive_notifications": false,
+            "system_name": "user_1_4",
+            "security_keys_enabled": false,
+            "type": "subaccount",
+            "username": "user_1_4"
+        }"#;
+        let subaccount: Subaccount = serde_json::from_str(json).unwrap();
+        assert!(!subaccount.is_main());
+        assert_eq!(subaccount.tif, None);
+        let btc = &subaccount.portfolio.unwrap()["btc"];
+        assert_eq!(btc.balance, 5.000593987);
+        assert_eq!(btc.spot_reserve, Some(0.0));
+    }
+
+    #[test]
+    fn test_subaccount_details() {
+        let json = r#"[{
+            "uid": 10,
+            "positions": [{
+                "total_profit_loss": 0.000037333,
+                "size_currency": -0.001308984,
+                "size": -60.0,
+                "settlement_price": 47886.98,
+                "realized_profit_loss": 0.0,
+                "open_orders_margin": 0.0,
+                "mark_price": 45837.07,
+                "maintenance_margin": 0.000028143,
+                "leverage": 34,
+                "kind": "future",
+                "instrument_name": "BTC-3SEP21",
+                "initial_margin": 0.000038615,
+                "index_price": 47897.12,
+                "floating_profit_loss": 0.000037333,
+                "estimated_liquidation_price": null,
+                "direction": "sell",
+                "delta": -0.001308984,
+                "average_price": 47182.76
+            }]
+        }]"#;
+        let details: Vec<SubaccountDetails> = serde_json::from_str(json).unwrap();
+        assert_eq!(details[0].uid, 10);
+        assert_eq!(details[0].positions[0].size, -60.0);
+        assert!(details[0].open_orders.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_subaccount_login_request() {
+        let request = ToggleSubaccountLoginRequest::enable(7);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({"sid": 7, "state": "enable"})
+        );
+        assert_eq!(
+            ToggleSubaccountLoginRequest::disable(7).state.to_string(),
+            "disable"
+        );
+    }
+
+    #[test]
+    fn test_account_endpoint_results_deserialization() {
+        let locks: Vec<UserLock> = serde_json::from_str(
+            r#"[{"message":"locked in one currency","locked":true,"currency":"BTC"},
+                {"enabled":false,"currency":"ETH"}]"#,
+        )
+        .unwrap();
+        assert!(locks[0].locked);
+        assert!(!locks[1].locked);
+        assert_eq!(locks[1].message, None);
+
+        let changes: Vec<MarginModelChange> = serde_json::from_str(
+            r#"[{"currency":"btc",
+                 "old_state":{"maintenance_margin_rate":0.03,"initial_margin_rate":0.45,"available_balance":0.55},
+                 "new_state":{"maintenance_margin_rate":0.02,"initial_margin_rate":0.03,"available_balance":0.98}}]"#,
+        )
+        .unwrap();
+        assert_eq!(changes[0].new_state.available_balance, 0.98);
+
+        let log: AccessLogResponse = serde_json::from_str(
+            r#"{"records_total":34,"data":[{"timestamp":1575876682576,"result":"success",
+                "ip":"127.0.0.1","id":45,"country":"Local Country","city":"Local Town"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(log.records_total, 34);
+        assert_eq!(log.data[0].result.as_deref(), Some("success"));
+        assert_eq!(log.data[0].action(), "success");
+        assert_eq!(log.data[0].data, None);
+    }
+
+    #[test]
+    fn test_affiliate_program_info_deserialization() {
+        let json = r#"{
+            "received": {"eth": 0.00004, "btc": 0.000001},
+            "number_of_affiliates": 1,
+            "link": "https://www.deribit.com/reg-xxx.zxyq",
+            "is_enabled": true
+        }"#;
+
+        let info: AffiliateProgramInfo = serde_json::from_str(json).unwrap();
+        assert!(info.is_enabled);
+        assert_eq!(info.number_of_affiliates, 1);
+        assert_eq!(info.received.get("btc"), Some(&0.000001));
+        assert_eq!(
+            info.link.as_deref(),
+            Some("https://www.deribit.com/reg-xxx.zxyq")
+        );
+    }
+
+    #[test]
+    fn test_simulated_portfolio_deserialization() {
+        let json = r#"{
+            "currency": "BTC",
+            "balance": 150076473.4995114,
+            "equity": 150075253.91354558,
+            "margin_balance": 153534213.79481918,
+            "available_funds": 115871741.76065847,
+            "available_withdrawal_funds": 115871741.76065847,
+            "available_subaccount_transfer_funds": 0,
+            "initial_margin": 37662472.03416069,
+            "maintenance_margin": 30129215.84817124,
+            "total_equity_usd": 13075634611389.318,
+            "total_initial_margin_usd": 3139528603778.822,
+            "total_maintenance_margin_usd": 2511559381417.215,
+            "locked_balance": 0,
+            "margin_model": "cross_pm",
+            "delta_total_map": {"btc_usd": 68024.519462366}
+        }"#;
+
+        let portfolio: Portfolio = serde_json::from_str(json).unwrap();
+        assert_eq!(portfolio.total_equity_usd, Some(13075634611389.318));
+        assert_eq!(portfolio.available_subaccount_transfer_funds, Some(0.0));
+        assert_eq!(portfolio.locked_balance, Some(0.0));
+        assert_eq!(portfolio.margin_model.as_deref(), Some("cross_pm"));
+        assert!(portfolio.accounts.is_empty());
+    }
 }
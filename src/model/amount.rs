@@ -0,0 +1,129 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Order amount units and conversions
+//!
+//! The meaning of the `amount` field depends on the instrument:
+//! - Inverse futures and perpetuals: USD, in multiples of `contract_size`
+//! - Options: base currency (coin)
+//! - Linear futures, perpetuals and spot: base currency
+//!
+//! The conversions themselves live on [`Instrument`](crate::model::instrument::Instrument);
+//! this module holds the unit and size types they work with.
+
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+
+/// Unit of the `amount` field for an instrument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountUnit {
+    /// Amount is a USD notional (inverse futures and perpetuals)
+    Usd,
+    /// Amount is in the instrument base currency
+    BaseCurrency,
+}
+
+impl AmountUnit {
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Usd => "usd",
+            Self::BaseCurrency => "base_currency",
+        }
+    }
+}
+
+impl std::fmt::Display for AmountUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Order size expressed in a caller-chosen unit
+///
+/// Converted to the instrument's native `amount` with
+/// [`Instrument::order_amount`](crate::model::instrument::Instrument::order_amount).
+#[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderSize {
+    /// Number of contracts
+    Contracts(f64),
+    /// Quantity of the base currency (e.g., 0.5 BTC)
+    Coin(f64),
+    /// USD notional
+    Usd(f64),
+    /// Native instrument amount, passed through unchanged apart from rounding
+    Amount(f64),
+}
+
+/// Error raised while converting amounts
+#[derive(Debug, Clone, PartialEq)]
+pub enum AmountError {
+    /// Instrument has no contract size
+    MissingContractSize(String),
+    /// Reference price is not a positive finite number
+    InvalidPrice(f64),
+    /// Requested size is not a positive finite number
+    InvalidSize(f64),
+    /// Converted amount is below the instrument minimum trade amount
+    BelowMinimum {
+        /// Converted and rounded amount
+        amount: f64,
+        /// Minimum trade amount of the instrument
+        min_trade_amount: f64,
+    },
+}
+
+impl std::fmt::Display for AmountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingContractSize(instrument) => {
+                write!(f, "instrument {instrument} has no contract size")
+            }
+            Self::InvalidPrice(price) => write!(f, "invalid reference price: {price}"),
+            Self::InvalidSize(size) => write!(f, "invalid order size: {size}"),
+            Self::BelowMinimum {
+                amount,
+                min_trade_amount,
+            } => write!(
+                f,
+                "amount {amount} is below minimum trade amount {min_trade_amount}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AmountError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_unit() {
+        assert_eq!(AmountUnit::Usd.as_str(), "usd");
+        assert_eq!(AmountUnit::BaseCurrency.to_string(), "base_currency");
+    }
+
+    #[test]
+    fn test_order_size_serialization() {
+        let json = serde_json::to_string(&OrderSize::Coin(0.5)).unwrap();
+        assert_eq!(json, r#"{"coin":0.5}"#);
+        let size: OrderSize = serde_json::from_str(r#"{"usd":1000.0}"#).unwrap();
+        assert_eq!(size, OrderSize::Usd(1000.0));
+    }
+
+    #[test]
+    fn test_amount_error_display() {
+        let error = AmountError::BelowMinimum {
+            amount: 0.0,
+            min_trade_amount: 10.0,
+        };
+        assert!(error.to_string().contains("below minimum"));
+    }
+}
@@ -0,0 +1,428 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! API key management
+//!
+//! Requests and results of `private/create_api_key`, `private/edit_api_key`,
+//! `private/change_scope_in_api_key` and `private/list_api_keys`, with the
+//! maximum scope of a key typed as an [`AccessScope`]. A key can only be
+//! managed with a token whose scope covers the key's maximum scope,
+//! otherwise the server answers `scope_exceeded` (13403).
+
+use crate::model::scope::AccessScope;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Maximum length of an API key name
+pub const MAX_API_KEY_NAME_LENGTH: usize = 16;
+
+/// Advanced feature enabled on an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyFeature {
+    /// Limit `block_trade:read` to block trades made with this key
+    RestrictedBlockTrades,
+    /// Block trades made with this key require approval
+    BlockTradeApproval,
+}
+
+impl ApiKeyFeature {
+    /// Returns the feature as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RestrictedBlockTrades => "restricted_block_trades",
+            Self::BlockTradeApproval => "block_trade_approval",
+        }
+    }
+}
+
+impl fmt::Display for ApiKeyFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Error raised while validating an API key request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyError {
+    /// Name is longer than [`MAX_API_KEY_NAME_LENGTH`] or contains characters
+    /// other than ASCII letters, digits and underscores
+    InvalidName(String),
+    /// Maximum scope contains items other than area access levels
+    InvalidMaxScope(String),
+}
+
+impl fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "invalid API key name: '{name}'"),
+            Self::InvalidMaxScope(scope) => write!(f, "invalid API key max scope: '{scope}'"),
+        }
+    }
+}
+
+impl std::error::Error for ApiKeyError {}
+
+/// API key as returned by the account administration methods
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Key identifier
+    pub id: u64,
+    /// Client id used for authentication
+    pub client_id: String,
+    /// Client secret, or MD5 fingerprint of the public key
+    pub client_secret: String,
+    /// Whether this is the default key (deprecated)
+    #[serde(default)]
+    pub default: bool,
+    /// Whether the key can be used for authentication
+    pub enabled: bool,
+    /// Enabled advanced features
+    #[serde(default)]
+    pub enabled_features: Vec<ApiKeyFeature>,
+    /// Whitelisted IP addresses
+    #[serde(default)]
+    pub ip_whitelist: Option<Vec<String>>,
+    /// Maximum scope of tokens issued with the key
+    pub max_scope: AccessScope,
+    /// Key name
+    #[serde(default)]
+    pub name: String,
+    /// PEM encoded public key of asymmetric keys
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// Creation time in milliseconds
+    pub timestamp: u64,
+}
+
+impl ApiKey {
+    /// Whether the key signs with a public key instead of a shared secret
+    #[must_use]
+    pub fn is_asymmetric(&self) -> bool {
+        self.public_key.is_some()
+    }
+}
+
+/// Request of `private/create_api_key`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// Maximum scope of tokens issued with the key
+    pub max_scope: AccessScope,
+    /// Key name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// ED25519 or RSA PEM encoded public key for asymmetric keys
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// Enabled advanced features
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_features: Option<Vec<ApiKeyFeature>>,
+}
+
+impl CreateApiKeyRequest {
+    /// Create a request for a key with the given maximum scope
+    #[must_use]
+    pub fn new(max_scope: AccessScope) -> Self {
+        Self {
+            max_scope,
+            name: None,
+            public_key: None,
+            enabled_features: None,
+        }
+    }
+
+    /// Set the key name
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Create an asymmetric key from a PEM encoded public key
+    #[must_use]
+    pub fn with_public_key(mut self, public_key: impl Into<String>) -> Self {
+        self.public_key = Some(public_key.into());
+        self
+    }
+
+    /// Enable advanced features
+    #[must_use]
+    pub fn with_features(mut self, features: Vec<ApiKeyFeature>) -> Self {
+        self.enabled_features = Some(features);
+        self
+    }
+
+    /// Validate the name and maximum scope before sending
+    pub fn validate(&self) -> Result<(), ApiKeyError> {
+        validate_key(self.name.as_deref(), &self.max_scope)
+    }
+}
+
+/// Request of `private/edit_api_key`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditApiKeyRequest {
+    /// Key identifier
+    pub id: u64,
+    /// Maximum scope of tokens issued with the key
+    pub max_scope: AccessScope,
+    /// Key name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Enable or disable the key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Enabled advanced features
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_features: Option<Vec<ApiKeyFeature>>,
+    /// Whitelisted IP addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_whitelist: Option<Vec<String>>,
+}
+
+impl EditApiKeyRequest {
+    /// Create a request setting the maximum scope of a key
+    #[must_use]
+    pub fn new(id: u64, max_scope: AccessScope) -> Self {
+        Self {
+            id,
+            max_scope,
+            name: None,
+            enabled: None,
+            enabled_features: None,
+            ip_whitelist: None,
+        }
+    }
+
+    /// Create a request keeping the current settings of a key
+    #[must_use]
+    pub fn from_key(key: &ApiKey) -> Self {
+        Self::new(key.id, key.max_scope.clone())
+    }
+
+    /// Set the key name
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Enable or disable the key
+    #[must_use]
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Set the enabled advanced features
+    #[must_use]
+    pub fn with_features(mut self, features: Vec<ApiKeyFeature>) -> Self {
+        self.enabled_features = Some(features);
+        self
+    }
+
+    /// Set the whitelisted IP addresses
+    #[must_use]
+    pub fn with_ip_whitelist(mut self, ips: Vec<String>) -> Self {
+        self.ip_whitelist = Some(ips);
+        self
+    }
+
+    /// Validate the name and maximum scope before sending
+    pub fn validate(&self) -> Result<(), ApiKeyError> {
+        validate_key(self.name.as_deref(), &self.max_scope)
+    }
+}
+
+/// Request of `private/change_scope_in_api_key`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeApiKeyScopeRequest {
+    /// Key identifier
+    pub id: u64,
+    /// New maximum scope
+    pub max_scope: AccessScope,
+}
+
+impl ChangeApiKeyScopeRequest {
+    /// Create a request changing the maximum scope of a key
+    #[must_use]
+    pub fn new(id: u64, max_scope: AccessScope) -> Self {
+        Self { id, max_scope }
+    }
+
+    /// Validate the maximum scope before sending
+    pub fn validate(&self) -> Result<(), ApiKeyError> {
+        validate_key(None, &self.max_scope)
+    }
+}
+
+/// Validate a key name and maximum scope
+fn validate_key(name: Option<&str>, max_scope: &AccessScope) -> Result<(), ApiKeyError> {
+    if let Some(name) = name
+        && (name.len() > MAX_API_KEY_NAME_LENGTH
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+    {
+        return Err(ApiKeyError::InvalidName(name.to_string()));
+    }
+    let areas_only = max_scope.session.is_none()
+        && !max_scope.connection
+        && !max_scope.mainaccount
+        && max_scope.expires.is_none()
+        && max_scope.ip.is_none();
+    if !areas_only {
+        return Err(ApiKeyError::InvalidMaxScope(max_scope.render()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::scope::{AccessLevel, ScopeArea};
+
+    #[test]
+    fn test_list_api_keys_result() {
+        let json = r#"[
+            {
+                "timestamp": 1560236001108,
+                "max_scope": "account:read block_trade:read trade:read_write wallet:read",
+                "id": 1,
+                "enabled": false,
+                "default": false,
+                "client_secret": "SjM57m1T2CfXZ4vZ76X1APjqRlJdtzHI8IwVXoQnfoM",
+                "client_id": "TiA4AyLPq3",
+                "name": "",
+                "enabled_features": []
+            },
+            {
+                "timestamp": 1560236287708,
+                "max_scope": "account:read_write block_trade:read_write trade:read_write wallet:read_write",
+                "id": 2,
+                "enabled": true,
+                "default": true,
+                "client_secret": "mwNOvbUVyQczytQ5IVM8CbzmgqNJ81WvLKfu6MXcJPs",
+                "client_id": "aD-KFx-H",
+                "name": "",
+                "enabled_features": ["block_trade_approval"]
+            }
+        ]"#;
+        let keys: Vec<ApiKey> = serde_json::from_str(json).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            keys[0].max_scope.level(ScopeArea::Trade),
+            AccessLevel::ReadWrite
+        );
+        assert!(keys[0].max_scope.permits("private/get_positions"));
+        assert!(!keys[0].max_scope.permits("private/withdraw"));
+        assert!(!keys[0].is_asymmetric());
+        assert_eq!(
+            keys[1].enabled_features,
+            vec![ApiKeyFeature::BlockTradeApproval]
+        );
+        assert_eq!(keys[1].ip_whitelist, None);
+        assert_eq!(
+            serde_json::to_value(&keys[1]).unwrap()["max_scope"],
+            "account:read_write block_trade:read_write trade:read_write wallet:read_write"
+        );
+    }
+
+    #[test]
+    fn test_api_key_with_unlisted_scope_items_decodes() {
+        let json = r#"{
+            "timestamp": 1560236287708,
+            "max_scope": "custody:read_write trade:read future_area:read",
+            "id": 3,
+            "enabled": true,
+            "default": false,
+            "client_secret": "secret",
+            "client_id": "client",
+            "name": "custody",
+            "enabled_features": []
+        }"#;
+        let key: ApiKey = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            key.max_scope.level(ScopeArea::Custody),
+            AccessLevel::ReadWrite
+        );
+        assert_eq!(key.max_scope.unknown, ["future_area:read"]);
+        assert_eq!(
+            key.max_scope.render(),
+            "custody:read_write trade:read future_area:read"
+        );
+    }
+
+    #[test]
+    fn test_create_api_key_request() {
+        let request = CreateApiKeyRequest::new(AccessScope::read_only())
+            .with_name("monitor_1")
+            .with_features(vec![ApiKeyFeature::RestrictedBlockTrades]);
+        assert_eq!(request.validate(), Ok(()));
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "max_scope": "account:read trade:read wallet:read",
+                "name": "monitor_1",
+                "enabled_features": ["restricted_block_trades"]
+            })
+        );
+
+        let long_name =
+            CreateApiKeyRequest::new(AccessScope::read_only()).with_name("a_name_that_is_too_long");
+        assert!(matches!(
+            long_name.validate(),
+            Err(ApiKeyError::InvalidName(_))
+        ));
+        let dashed = CreateApiKeyRequest::new(AccessScope::read_only()).with_name("my-key");
+        assert!(matches!(
+            dashed.validate(),
+            Err(ApiKeyError::InvalidName(_))
+        ));
+    }
+
+    #[test]
+    fn test_edit_and_change_scope_requests() {
+        let key: ApiKey = serde_json::from_value(serde_json::json!({
+            "timestamp": 1560236001108u64,
+            "max_scope": "account:read trade:read",
+            "id": 5,
+            "enabled": true,
+            "client_secret": "secret",
+            "client_id": "client",
+            "ip_whitelist": ["10.0.0.1"]
+        }))
+        .unwrap();
+        let request = EditApiKeyRequest::from_key(&key)
+            .with_enabled(false)
+            .with_ip_whitelist(vec!["10.0.0.2".to_string()]);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "id": 5,
+                "max_scope": "account:read trade:read",
+                "enabled": false,
+                "ip_whitelist": ["10.0.0.2"]
+            })
+        );
+
+        let widened = key
+            .max_scope
+            .clone()
+            .with_access(ScopeArea::Trade, AccessLevel::ReadWrite);
+        let change = ChangeApiKeyScopeRequest::new(key.id, widened);
+        assert_eq!(change.validate(), Ok(()));
+        assert_eq!(
+            serde_json::to_value(&change).unwrap()["max_scope"],
+            "account:read trade:read_write"
+        );
+
+        let session = ChangeApiKeyScopeRequest::new(5, AccessScope::read_only().with_session("x"));
+        assert!(matches!(
+            session.validate(),
+            Err(ApiKeyError::InvalidMaxScope(_))
+        ));
+    }
+}
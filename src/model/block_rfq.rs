@@ -0,0 +1,1532 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Block RFQ data structures and workflow
+//!
+//! A taker creates a Block RFQ (request for quote) for one or more legs,
+//! makers answer with quotes, and the taker accepts a price. This module
+//! contains the request and response models for the Block RFQ methods and
+//! the `block_rfq.taker.*` / `block_rfq.maker.*` channels, plus
+//! [`BlockRfqTaker`] and [`BlockRfqMaker`], which track RFQs and quotes
+//! through their lifecycle and reject actions that the server would refuse.
+
+use crate::model::block_trade::BlockTradeRole;
+use crate::model::order::OrderSide;
+use crate::model::subscription::BlockRfqPublicTrade;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Period after creation during which the taker cannot see quotes or trade
+pub const BLOCK_RFQ_GRACE_PERIOD_MS: u64 = 5_000;
+
+/// Lifecycle state of a Block RFQ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockRfqState {
+    /// Created, still in the grace period
+    Created,
+    /// Open for quotes and trading
+    Open,
+    /// Fully filled
+    Filled,
+    /// Partially traded
+    Traded,
+    /// Cancelled by the taker
+    Cancelled,
+    /// Expired without being filled
+    Expired,
+    /// Closed
+    Closed,
+}
+
+impl BlockRfqState {
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Open => "open",
+            Self::Filled => "filled",
+            Self::Traded => "traded",
+            Self::Cancelled => "cancelled",
+            Self::Expired => "expired",
+            Self::Closed => "closed",
+        }
+    }
+
+    /// Check if the RFQ can no longer change
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Filled | Self::Cancelled | Self::Expired | Self::Closed
+        )
+    }
+
+    /// Check if the RFQ can still be quoted or traded
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Open | Self::Traded)
+    }
+
+    /// Check if an update from this state to `next` is valid
+    ///
+    /// Repeating the current state is always valid, as every quote change
+    /// produces a new notification.
+    #[must_use]
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        if *self == next {
+            return true;
+        }
+        match self {
+            Self::Created => matches!(next, Self::Open | Self::Cancelled | Self::Expired),
+            Self::Open | Self::Traded => !matches!(next, Self::Created | Self::Open),
+            Self::Filled | Self::Cancelled | Self::Expired | Self::Closed => false,
+        }
+    }
+}
+
+impl std::fmt::Display for BlockRfqState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Lifecycle state of a maker quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockRfqQuoteState {
+    /// Live, possibly partially filled
+    Open,
+    /// Fully filled
+    Filled,
+    /// Cancelled
+    Cancelled,
+    /// Expired
+    Expired,
+}
+
+impl BlockRfqQuoteState {
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Filled => "filled",
+            Self::Cancelled => "cancelled",
+            Self::Expired => "expired",
+        }
+    }
+
+    /// Check if the quote can no longer change
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Open)
+    }
+
+    /// Check if an update from this state to `next` is valid
+    #[must_use]
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        *self == next || *self == Self::Open
+    }
+}
+
+impl std::fmt::Display for BlockRfqQuoteState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Execution instruction of a quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionInstruction {
+    /// Fill entirely or not at all
+    AllOrNone,
+    /// Fill partially or fully
+    #[default]
+    AnyPartOf,
+}
+
+impl ExecutionInstruction {
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AllOrNone => "all_or_none",
+            Self::AnyPartOf => "any_part_of",
+        }
+    }
+}
+
+impl std::fmt::Display for ExecutionInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Time in force when accepting a Block RFQ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockRfqTimeInForce {
+    /// Fill immediately or cancel
+    FillOrKill,
+    /// Remain active until cancelled
+    GoodTilCancelled,
+}
+
+impl BlockRfqTimeInForce {
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FillOrKill => "fill_or_kill",
+            Self::GoodTilCancelled => "good_til_cancelled",
+        }
+    }
+}
+
+impl std::fmt::Display for BlockRfqTimeInForce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Leg requested when creating a Block RFQ
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateBlockRfqLeg {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Trade size (USD for perpetuals/inverse futures, base currency otherwise)
+    pub amount: f64,
+    /// Direction of the leg
+    pub direction: OrderSide,
+}
+
+impl CreateBlockRfqLeg {
+    /// Create a new leg
+    #[must_use]
+    pub fn new(instrument_name: String, amount: f64, direction: OrderSide) -> Self {
+        Self {
+            instrument_name,
+            amount,
+            direction,
+        }
+    }
+}
+
+/// Leg of a Block RFQ, sized relative to the RFQ amount
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqLeg {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Direction of the leg, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<OrderSide>,
+    /// Ratio of amount between legs
+    pub ratio: u32,
+}
+
+/// Leg of a maker quote
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqQuoteLeg {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Direction, matching the corresponding RFQ leg
+    pub direction: OrderSide,
+    /// Price of the leg
+    pub price: f64,
+    /// Ratio of amount between legs
+    pub ratio: u32,
+}
+
+/// Hedge leg of a Block RFQ
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqHedge {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Direction of the hedge
+    pub direction: OrderSide,
+    /// Hedge price
+    pub price: f64,
+    /// Hedge size
+    pub amount: f64,
+}
+
+impl BlockRfqHedge {
+    /// Create a new hedge leg
+    #[must_use]
+    pub fn new(instrument_name: String, direction: OrderSide, price: f64, amount: f64) -> Self {
+        Self {
+            instrument_name,
+            direction,
+            price,
+            amount,
+        }
+    }
+}
+
+/// Aggregated quote level visible to the taker
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqQuoteLevel {
+    /// Amount, multiplied by the leg ratio for each leg
+    pub amount: f64,
+    /// Price of the level
+    pub price: f64,
+    /// Execution instruction
+    #[serde(default)]
+    pub execution_instruction: ExecutionInstruction,
+    /// Earliest expiry of the quotes at this level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Timestamp of the last update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update_timestamp: Option<u64>,
+    /// Makers quoting at this level
+    #[serde(default)]
+    pub makers: Vec<String>,
+}
+
+/// Trade executed on a Block RFQ
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqTrade {
+    /// Trade amount
+    pub amount: f64,
+    /// Trade direction
+    pub direction: OrderSide,
+    /// Trade price
+    pub price: f64,
+    /// Amount of the hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge_amount: Option<f64>,
+    /// Alias of the maker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maker: Option<String>,
+}
+
+/// Trade trigger attached to a Block RFQ
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqTradeTrigger {
+    /// Trigger state (`untriggered` or `cancelled`)
+    pub state: String,
+    /// Trigger direction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<OrderSide>,
+    /// Trigger price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Cancellation reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<String>,
+}
+
+/// Block RFQ as returned by the API and the `block_rfq.*` channels
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfq {
+    /// Block RFQ id
+    pub block_rfq_id: u64,
+    /// Lifecycle state
+    pub state: BlockRfqState,
+    /// Role of the user
+    pub role: BlockTradeRole,
+    /// Amount, multiplied by the leg ratio for each leg
+    pub amount: f64,
+    /// Legs of the RFQ
+    pub legs: Vec<BlockRfqLeg>,
+    /// Hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// Combo identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combo_id: Option<String>,
+    /// Creation timestamp in milliseconds
+    pub creation_timestamp: u64,
+    /// Expiration timestamp in milliseconds
+    pub expiration_timestamp: u64,
+    /// Bids visible to the taker
+    #[serde(default)]
+    pub bids: Vec<BlockRfqQuoteLevel>,
+    /// Asks visible to the taker
+    #[serde(default)]
+    pub asks: Vec<BlockRfqQuoteLevel>,
+    /// Targeted makers (taker only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub makers: Option<Vec<String>>,
+    /// User label (taker only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Whether taker and maker aliases are visible
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disclosed: Option<bool>,
+    /// Application that created the RFQ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_name: Option<String>,
+    /// Minimum trade amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_trade_amount: Option<f64>,
+    /// Mark price (filled RFQs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
+    /// Index prices at execution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_prices: Option<Vec<f64>>,
+    /// Rating of the taker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taker_rating: Option<String>,
+    /// Whether the RFQ counts towards the taker rating
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub included_in_taker_rating: Option<bool>,
+    /// Trade trigger state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_trigger: Option<BlockRfqTradeTrigger>,
+    /// Executed trades (filled RFQs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trades: Option<Vec<BlockRfqTrade>>,
+}
+
+impl BlockRfq {
+    /// Get the best (highest) bid
+    #[must_use]
+    pub fn best_bid(&self) -> Option<&BlockRfqQuoteLevel> {
+        self.bids.iter().max_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    /// Get the best (lowest) ask
+    #[must_use]
+    pub fn best_ask(&self) -> Option<&BlockRfqQuoteLevel> {
+        self.asks.iter().min_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    /// Check if the grace period is over at the given time
+    #[must_use]
+    pub fn is_past_grace_period(&self, now_ms: u64) -> bool {
+        now_ms >= self.creation_timestamp + BLOCK_RFQ_GRACE_PERIOD_MS
+    }
+
+    /// Check if the RFQ has expired at the given time
+    #[must_use]
+    pub fn is_expired_at(&self, now_ms: u64) -> bool {
+        self.state == BlockRfqState::Expired || now_ms >= self.expiration_timestamp
+    }
+}
+
+/// Maker quote on a Block RFQ
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqQuote {
+    /// Quote id
+    pub block_rfq_quote_id: u64,
+    /// Block RFQ id
+    pub block_rfq_id: u64,
+    /// Quote state
+    pub quote_state: BlockRfqQuoteState,
+    /// Direction from the maker perspective
+    pub direction: OrderSide,
+    /// Amount, multiplied by the leg ratio for each leg
+    pub amount: f64,
+    /// Filled amount
+    #[serde(default)]
+    pub filled_amount: f64,
+    /// Aggregated price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Quote legs
+    pub legs: Vec<BlockRfqQuoteLeg>,
+    /// Hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// User label
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Execution instruction
+    #[serde(default)]
+    pub execution_instruction: ExecutionInstruction,
+    /// Whether the quote was edited
+    #[serde(default)]
+    pub replaced: bool,
+    /// Creation timestamp in milliseconds
+    pub creation_timestamp: u64,
+    /// Last update timestamp in milliseconds
+    pub last_update_timestamp: u64,
+    /// Reason of cancellation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_state_reason: Option<String>,
+    /// Application that placed the quote
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_name: Option<String>,
+}
+
+impl BlockRfqQuote {
+    /// Get the amount still open
+    #[must_use]
+    pub fn remaining_amount(&self) -> f64 {
+        (self.amount - self.filled_amount).max(0.0)
+    }
+}
+
+/// Request for `private/create_block_rfq`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateBlockRfqRequest {
+    /// Legs of the RFQ
+    pub legs: Vec<CreateBlockRfqLeg>,
+    /// Hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// User label (maximum 64 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Targeted makers, all makers when empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub makers: Option<Vec<String>>,
+    /// Whether aliases are disclosed (anonymous mode needs at least 5 makers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disclosed: Option<bool>,
+}
+
+impl CreateBlockRfqRequest {
+    /// Create a new request
+    #[must_use]
+    pub fn new(legs: Vec<CreateBlockRfqLeg>) -> Self {
+        Self {
+            legs,
+            hedge: None,
+            label: None,
+            makers: None,
+            disclosed: None,
+        }
+    }
+
+    /// Set the hedge leg
+    #[must_use]
+    pub fn with_hedge(mut self, hedge: BlockRfqHedge) -> Self {
+        self.hedge = Some(hedge);
+        self
+    }
+
+    /// Set the label
+    #[must_use]
+    pub fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the targeted makers
+    #[must_use]
+    pub fn with_makers(mut self, makers: Vec<String>) -> Self {
+        self.makers = Some(makers);
+        self
+    }
+
+    /// Set the disclosed flag
+    #[must_use]
+    pub fn with_disclosed(mut self, disclosed: bool) -> Self {
+        self.disclosed = Some(disclosed);
+        self
+    }
+
+    /// Validate the request
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        let legs_valid = !self.legs.is_empty()
+            && self
+                .legs
+                .iter()
+                .all(|l| !l.instrument_name.is_empty() && l.amount > 0.0);
+        let label_valid = self
+            .label
+            .as_ref()
+            .is_none_or(|l| l.len() <= crate::model::label::MAX_LABEL_LENGTH);
+        let anonymity_valid =
+            self.disclosed != Some(false) || self.makers.as_ref().is_some_and(|m| m.len() >= 5);
+        legs_valid && label_valid && anonymity_valid
+    }
+}
+
+/// Request for `private/add_block_rfq_quote`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddBlockRfqQuoteRequest {
+    /// Block RFQ id
+    pub block_rfq_id: u64,
+    /// Amount, multiplied by the leg ratio for each leg
+    pub amount: f64,
+    /// Direction from the maker perspective
+    pub direction: OrderSide,
+    /// Quote legs
+    pub legs: Vec<BlockRfqQuoteLeg>,
+    /// Hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// User label
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Execution instruction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_instruction: Option<ExecutionInstruction>,
+    /// Aggregated price for future spreads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Quote expiry in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl AddBlockRfqQuoteRequest {
+    /// Set the hedge leg
+    #[must_use]
+    pub fn with_hedge(mut self, hedge: BlockRfqHedge) -> Self {
+        self.hedge = Some(hedge);
+        self
+    }
+
+    /// Set the label
+    #[must_use]
+    pub fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the execution instruction
+    #[must_use]
+    pub fn with_execution_instruction(mut self, instruction: ExecutionInstruction) -> Self {
+        self.execution_instruction = Some(instruction);
+        self
+    }
+
+    /// Set the quote expiry
+    #[must_use]
+    pub fn with_expires_at(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+/// Request for `private/edit_block_rfq_quote`
+///
+/// The quote is identified by `block_rfq_quote_id`, or by `block_rfq_id` and `label`.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditBlockRfqQuoteRequest {
+    /// Quote id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_quote_id: Option<u64>,
+    /// Block RFQ id, used with `label`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_id: Option<u64>,
+    /// Quote label, used with `block_rfq_id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// New amount
+    pub amount: f64,
+    /// New legs
+    pub legs: Vec<BlockRfqQuoteLeg>,
+    /// New hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// Aggregated price for future spreads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+}
+
+impl EditBlockRfqQuoteRequest {
+    /// Create an edit for a quote id
+    #[must_use]
+    pub fn by_id(block_rfq_quote_id: u64, amount: f64, legs: Vec<BlockRfqQuoteLeg>) -> Self {
+        Self {
+            block_rfq_quote_id: Some(block_rfq_quote_id),
+            block_rfq_id: None,
+            label: None,
+            amount,
+            legs,
+            hedge: None,
+            price: None,
+        }
+    }
+
+    /// Create an edit for a quote label within a Block RFQ
+    #[must_use]
+    pub fn by_label(
+        block_rfq_id: u64,
+        label: String,
+        amount: f64,
+        legs: Vec<BlockRfqQuoteLeg>,
+    ) -> Self {
+        Self {
+            block_rfq_quote_id: None,
+            block_rfq_id: Some(block_rfq_id),
+            label: Some(label),
+            amount,
+            legs,
+            hedge: None,
+            price: None,
+        }
+    }
+
+    /// Set the hedge leg
+    #[must_use]
+    pub fn with_hedge(mut self, hedge: BlockRfqHedge) -> Self {
+        self.hedge = Some(hedge);
+        self
+    }
+
+    /// Check that the quote is identified
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.block_rfq_quote_id.is_some() || (self.block_rfq_id.is_some() && self.label.is_some())
+    }
+}
+
+/// Request for `private/accept_block_rfq`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcceptBlockRfqRequest {
+    /// Block RFQ id
+    pub block_rfq_id: u64,
+    /// Maximum acceptable price
+    pub price: f64,
+    /// Amount, multiplied by the leg ratio for each leg
+    pub amount: f64,
+    /// Direction from the taker perspective
+    pub direction: OrderSide,
+    /// Legs of the RFQ
+    pub legs: Vec<BlockRfqLeg>,
+    /// Hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// Time in force
+    pub time_in_force: BlockRfqTimeInForce,
+}
+
+/// Role filter for `private/get_block_rfqs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockRfqRoleFilter {
+    /// Taker or maker
+    Any,
+    /// Taker only
+    Taker,
+    /// Maker only
+    Maker,
+}
+
+/// Request for `private/get_block_rfqs`
+#[derive(DebugPretty, DisplaySimple, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockRfqsRequest {
+    /// Number of RFQs to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// State filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<BlockRfqState>,
+    /// Role filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<BlockRfqRoleFilter>,
+    /// Fetch RFQs with lower ids than this
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<u64>,
+    /// Return only this RFQ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_id: Option<u64>,
+    /// Currency filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+/// Response of `private/get_block_rfqs`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockRfqsResponse {
+    /// Continuation id for the next page
+    pub continuation: Option<u64>,
+    /// Block RFQs
+    pub block_rfqs: Vec<BlockRfq>,
+}
+
+/// Response of `private/get_block_rfq_quotes`
+///
+/// The method is documented as returning a list of quotes, while its example
+/// returns a single quote; both shapes are kept as received.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetBlockRfqQuotesResponse {
+    /// List of quotes
+    List(Vec<BlockRfqQuote>),
+    /// Single quote
+    Single(Box<BlockRfqQuote>),
+}
+
+impl GetBlockRfqQuotesResponse {
+    /// Get the open quotes matching the request
+    #[must_use]
+    pub fn quotes(&self) -> &[BlockRfqQuote] {
+        match self {
+            Self::List(quotes) => quotes,
+            Self::Single(quote) => std::slice::from_ref(quote.as_ref()),
+        }
+    }
+}
+
+impl From<GetBlockRfqQuotesResponse> for Vec<BlockRfqQuote> {
+    fn from(response: GetBlockRfqQuotesResponse) -> Self {
+        match response {
+            GetBlockRfqQuotesResponse::List(quotes) => quotes,
+            GetBlockRfqQuotesResponse::Single(quote) => vec![*quote],
+        }
+    }
+}
+
+/// Leg prices returned by `private/get_leg_prices`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqLegPrices {
+    /// Amount of the structure
+    pub amount: f64,
+    /// Legs with their computed prices
+    pub legs: Vec<BlockRfqQuoteLeg>,
+}
+
+/// Response of `public/get_block_rfq_trades`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetBlockRfqTradesResponse {
+    /// Continuation token for the next page
+    pub continuation: Option<String>,
+    /// Executed Block RFQs
+    pub block_rfqs: Vec<BlockRfqPublicTrade>,
+}
+
+/// Block RFQ alias of the account group
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqIdentity {
+    /// Group-level alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    /// Whether the group has maker scope
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_maker: Option<bool>,
+}
+
+/// Block RFQ alias and rating of one (sub)account
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqUser {
+    /// User id
+    pub user_id: u64,
+    /// Account-level alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    /// Whether the account has maker scope
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_maker: Option<bool>,
+    /// Taker rating of the account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taker_rating: Option<f64>,
+}
+
+/// Response of `private/get_block_rfq_user_info`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqUserInfo {
+    /// Alias of the account group
+    pub parent: BlockRfqIdentity,
+    /// Main account and subaccounts
+    #[serde(default)]
+    pub users: Vec<BlockRfqUser>,
+}
+
+/// Error raised by the Block RFQ workflow
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockRfqError {
+    /// RFQ is not tracked
+    UnknownRfq(u64),
+    /// Quote is not tracked
+    UnknownQuote(u64),
+    /// RFQ belongs to the other side
+    RoleMismatch {
+        /// Block RFQ id
+        block_rfq_id: u64,
+        /// Role of the RFQ
+        role: BlockTradeRole,
+    },
+    /// Invalid RFQ state change
+    InvalidTransition {
+        /// Block RFQ id
+        block_rfq_id: u64,
+        /// Current state
+        from: BlockRfqState,
+        /// Requested state
+        to: BlockRfqState,
+    },
+    /// Invalid quote state change
+    InvalidQuoteTransition {
+        /// Quote id
+        block_rfq_quote_id: u64,
+        /// Current state
+        from: BlockRfqQuoteState,
+        /// Requested state
+        to: BlockRfqQuoteState,
+    },
+    /// RFQ cannot be quoted or traded in its current state
+    NotActive {
+        /// Block RFQ id
+        block_rfq_id: u64,
+        /// Current state
+        state: BlockRfqState,
+    },
+    /// Taker acted during the grace period
+    GracePeriod {
+        /// Block RFQ id
+        block_rfq_id: u64,
+        /// Milliseconds left
+        remaining_ms: u64,
+    },
+    /// RFQ has expired
+    Expired(u64),
+    /// No quote on the side the taker wants to trade against
+    NoQuotes {
+        /// Block RFQ id
+        block_rfq_id: u64,
+        /// Direction of the taker
+        direction: OrderSide,
+    },
+    /// Number of leg prices does not match the RFQ legs
+    LegCountMismatch {
+        /// Legs of the RFQ
+        expected: usize,
+        /// Prices given
+        actual: usize,
+    },
+    /// Amount is not positive
+    InvalidAmount(f64),
+    /// RFQ leg does not report its direction
+    MissingLegDirection {
+        /// Block RFQ id
+        block_rfq_id: u64,
+        /// Instrument of the leg
+        instrument_name: String,
+    },
+}
+
+impl std::fmt::Display for BlockRfqError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownRfq(id) => write!(f, "unknown block RFQ {id}"),
+            Self::UnknownQuote(id) => write!(f, "unknown block RFQ quote {id}"),
+            Self::RoleMismatch { block_rfq_id, role } => {
+                write!(f, "block RFQ {block_rfq_id} has role {role}")
+            }
+            Self::InvalidTransition {
+                block_rfq_id,
+                from,
+                to,
+            } => write!(f, "block RFQ {block_rfq_id} cannot go from {from} to {to}"),
+            Self::InvalidQuoteTransition {
+                block_rfq_quote_id,
+                from,
+                to,
+            } => write!(
+                f,
+                "block RFQ quote {block_rfq_quote_id} cannot go from {from} to {to}"
+            ),
+            Self::NotActive {
+                block_rfq_id,
+                state,
+            } => write!(f, "block RFQ {block_rfq_id} is {state}"),
+            Self::GracePeriod {
+                block_rfq_id,
+                remaining_ms,
+            } => write!(
+                f,
+                "block RFQ {block_rfq_id} is in its grace period for {remaining_ms} ms"
+            ),
+            Self::Expired(id) => write!(f, "block RFQ {id} has expired"),
+            Self::NoQuotes {
+                block_rfq_id,
+                direction,
+            } => write!(
+                f,
+                "block RFQ {block_rfq_id} has no quotes to {}",
+                direction.as_str()
+            ),
+            Self::LegCountMismatch { expected, actual } => {
+                write!(f, "expected {expected} leg prices, got {actual}")
+            }
+            Self::InvalidAmount(amount) => write!(f, "invalid amount: {amount}"),
+            Self::MissingLegDirection {
+                block_rfq_id,
+                instrument_name,
+            } => write!(
+                f,
+                "block RFQ {block_rfq_id} leg {instrument_name} has no direction"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BlockRfqError {}
+
+/// Replace a tracked RFQ after validating the state change
+fn apply_rfq_update(
+    rfqs: &mut HashMap<u64, BlockRfq>,
+    update: BlockRfq,
+    role: BlockTradeRole,
+) -> Result<BlockRfqState, BlockRfqError> {
+    if update.role != role {
+        return Err(BlockRfqError::RoleMismatch {
+            block_rfq_id: update.block_rfq_id,
+            role: update.role,
+        });
+    }
+    if let Some(current) = rfqs.get(&update.block_rfq_id)
+        && !current.state.can_transition_to(update.state)
+    {
+        return Err(BlockRfqError::InvalidTransition {
+            block_rfq_id: update.block_rfq_id,
+            from: current.state,
+            to: update.state,
+        });
+    }
+    let state = update.state;
+    rfqs.insert(update.block_rfq_id, update);
+    Ok(state)
+}
+
+/// Check that an RFQ can be traded or quoted at the given time
+fn ensure_active(rfq: &BlockRfq, now_ms: u64) -> Result<(), BlockRfqError> {
+    if rfq.is_expired_at(now_ms) {
+        return Err(BlockRfqError::Expired(rfq.block_rfq_id));
+    }
+    if !rfq.state.is_active() {
+        return Err(BlockRfqError::NotActive {
+            block_rfq_id: rfq.block_rfq_id,
+            state: rfq.state,
+        });
+    }
+    Ok(())
+}
+
+/// Taker side of the Block RFQ workflow
+#[derive(Debug, Clone, Default)]
+pub struct BlockRfqTaker {
+    rfqs: HashMap<u64, BlockRfq>,
+}
+
+impl BlockRfqTaker {
+    /// Create an empty taker workflow
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track or update an RFQ from a response or `block_rfq.taker.*` notification
+    pub fn apply(&mut self, rfq: BlockRfq) -> Result<BlockRfqState, BlockRfqError> {
+        apply_rfq_update(&mut self.rfqs, rfq, BlockTradeRole::Taker)
+    }
+
+    /// Get a tracked RFQ
+    #[must_use]
+    pub fn get(&self, block_rfq_id: u64) -> Option<&BlockRfq> {
+        self.rfqs.get(&block_rfq_id)
+    }
+
+    /// Get the RFQs that are not in a terminal state
+    pub fn active(&self) -> impl Iterator<Item = &BlockRfq> {
+        self.rfqs.values().filter(|r| !r.state.is_terminal())
+    }
+
+    /// Build a request accepting the best quote against `direction`
+    ///
+    /// A taker buying trades against the best ask, a taker selling against
+    /// the best bid. Fails if the RFQ is not tradeable at `now_ms`.
+    pub fn accept_best(
+        &self,
+        block_rfq_id: u64,
+        direction: OrderSide,
+        amount: f64,
+        time_in_force: BlockRfqTimeInForce,
+        now_ms: u64,
+    ) -> Result<AcceptBlockRfqRequest, BlockRfqError> {
+        let rfq = self
+            .rfqs
+            .get(&block_rfq_id)
+            .ok_or(BlockRfqError::UnknownRfq(block_rfq_id))?;
+        if amount <= 0.0 || !amount.is_finite() {
+            return Err(BlockRfqError::InvalidAmount(amount));
+        }
+        ensure_active(rfq, now_ms)?;
+        if !rfq.is_past_grace_period(now_ms) {
+            return Err(BlockRfqError::GracePeriod {
+                block_rfq_id,
+                remaining_ms: rfq.creation_timestamp + BLOCK_RFQ_GRACE_PERIOD_MS - now_ms,
+            });
+        }
+        let level = match direction {
+            OrderSide::Buy => rfq.best_ask(),
+            OrderSide::Sell => rfq.best_bid(),
+        }
+        .ok_or(BlockRfqError::NoQuotes {
+            block_rfq_id,
+            direction,
+        })?;
+        Ok(AcceptBlockRfqRequest {
+            block_rfq_id,
+            price: level.price,
+            amount,
+            direction,
+            legs: rfq.legs.clone(),
+            hedge: rfq.hedge.clone(),
+            time_in_force,
+        })
+    }
+
+    /// Check that an RFQ can be cancelled
+    pub fn can_cancel(&self, block_rfq_id: u64) -> Result<(), BlockRfqError> {
+        let rfq = self
+            .rfqs
+            .get(&block_rfq_id)
+            .ok_or(BlockRfqError::UnknownRfq(block_rfq_id))?;
+        if rfq.state.is_terminal() {
+            return Err(BlockRfqError::NotActive {
+                block_rfq_id,
+                state: rfq.state,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Maker side of the Block RFQ workflow
+#[derive(Debug, Clone, Default)]
+pub struct BlockRfqMaker {
+    rfqs: HashMap<u64, BlockRfq>,
+    quotes: HashMap<u64, BlockRfqQuote>,
+}
+
+impl BlockRfqMaker {
+    /// Create an empty maker workflow
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track or update an RFQ from a `block_rfq.maker.*` notification
+    pub fn apply_rfq(&mut self, rfq: BlockRfq) -> Result<BlockRfqState, BlockRfqError> {
+        apply_rfq_update(&mut self.rfqs, rfq, BlockTradeRole::Maker)
+    }
+
+    /// Track or update one of our quotes
+    pub fn apply_quote(
+        &mut self,
+        quote: BlockRfqQuote,
+    ) -> Result<BlockRfqQuoteState, BlockRfqError> {
+        if let Some(current) = self.quotes.get(&quote.block_rfq_quote_id)
+            && !current.quote_state.can_transition_to(quote.quote_state)
+        {
+            return Err(BlockRfqError::InvalidQuoteTransition {
+                block_rfq_quote_id: quote.block_rfq_quote_id,
+                from: current.quote_state,
+                to: quote.quote_state,
+            });
+        }
+        let state = quote.quote_state;
+        self.quotes.insert(quote.block_rfq_quote_id, quote);
+        Ok(state)
+    }
+
+    /// Get a tracked RFQ
+    #[must_use]
+    pub fn rfq(&self, block_rfq_id: u64) -> Option<&BlockRfq> {
+        self.rfqs.get(&block_rfq_id)
+    }
+
+    /// Get a tracked quote
+    #[must_use]
+    pub fn quote(&self, block_rfq_quote_id: u64) -> Option<&BlockRfqQuote> {
+        self.quotes.get(&block_rfq_quote_id)
+    }
+
+    /// Get our open quotes on an RFQ
+    pub fn open_quotes(&self, block_rfq_id: u64) -> impl Iterator<Item = &BlockRfqQuote> {
+        self.quotes.values().filter(move |q| {
+            q.block_rfq_id == block_rfq_id && q.quote_state == BlockRfqQuoteState::Open
+        })
+    }
+
+    /// Build a quote for an RFQ with one price per leg
+    ///
+    /// Leg directions and ratios are taken from the RFQ. Fails if the RFQ
+    /// cannot be quoted at `now_ms` or one of its legs has no direction.
+    pub fn quote_request(
+        &self,
+        block_rfq_id: u64,
+        direction: OrderSide,
+        amount: f64,
+        leg_prices: &[f64],
+        now_ms: u64,
+    ) -> Result<AddBlockRfqQuoteRequest, BlockRfqError> {
+        let rfq = self
+            .rfqs
+            .get(&block_rfq_id)
+            .ok_or(BlockRfqError::UnknownRfq(block_rfq_id))?;
+        if amount <= 0.0 || !amount.is_finite() {
+            return Err(BlockRfqError::InvalidAmount(amount));
+        }
+        ensure_active(rfq, now_ms)?;
+        if leg_prices.len() != rfq.legs.len() {
+            return Err(BlockRfqError::LegCountMismatch {
+                expected: rfq.legs.len(),
+                actual: leg_prices.len(),
+            });
+        }
+        let legs = rfq
+            .legs
+            .iter()
+            .zip(leg_prices)
+            .map(|(leg, price)| {
+                let direction =
+                    leg.direction
+                        .ok_or_else(|| BlockRfqError::MissingLegDirection {
+                            block_rfq_id,
+                            instrument_name: leg.instrument_name.clone(),
+                        })?;
+                Ok(BlockRfqQuoteLeg {
+                    instrument_name: leg.instrument_name.clone(),
+                    direction,
+                    price: *price,
+                    ratio: leg.ratio,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(AddBlockRfqQuoteRequest {
+            block_rfq_id,
+            amount,
+            direction,
+            legs,
+            hedge: rfq.hedge.clone(),
+            label: None,
+            execution_instruction: None,
+            price: None,
+            expires_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAKER_RFQ: &str = r#"{
+        "amount": 10000,
+        "asks": [],
+        "bids": [{"amount": 10000, "execution_instruction": "any_part_of",
+                  "last_update_timestamp": 1740047910507, "makers": ["ANONYMOUS"],
+                  "price": 291664.14}],
+        "block_rfq_id": 321,
+        "combo_id": null,
+        "creation_timestamp": 1740047910438,
+        "disclosed": false,
+        "expiration_timestamp": 1740048210438,
+        "label": "example",
+        "legs": [{"direction": "buy", "instrument_name": "BTC-21FEB25", "ratio": 1},
+                 {"direction": "buy", "instrument_name": "BTC-28FEB25", "ratio": 1}],
+        "makers": ["MAKER1", "MAKER2"],
+        "min_trade_amount": 10,
+        "role": "taker",
+        "state": "open",
+        "taker_rating": "1-2"
+    }"#;
+
+    const MAKER_RFQ: &str = r#"{
+        "amount": 25, "block_rfq_id": 722, "combo_id": "BTC-18NOV24-82000-C",
+        "creation_timestamp": 1731664676443, "expiration_timestamp": 1731664976443,
+        "legs": [{"direction": "buy", "instrument_name": "BTC-18NOV24-82000-C", "ratio": 1}],
+        "role": "maker", "state": "open", "taker_rating": "1-2"
+    }"#;
+
+    const QUOTE: &str = r#"{
+        "amount": 25, "block_rfq_id": 722, "block_rfq_quote_id": 1301,
+        "creation_timestamp": 1731665928291, "direction": "buy", "filled_amount": 0,
+        "label": "example_quote", "last_update_timestamp": 1731665928291,
+        "legs": [{"direction": "buy", "instrument_name": "BTC-18NOV24-82000-C", "price": 10, "ratio": 1}],
+        "price": 10, "quote_state": "open", "replaced": false
+    }"#;
+
+    #[test]
+    fn test_state_transitions() {
+        assert!(BlockRfqState::Created.can_transition_to(BlockRfqState::Open));
+        assert!(!BlockRfqState::Created.can_transition_to(BlockRfqState::Filled));
+        assert!(BlockRfqState::Open.can_transition_to(BlockRfqState::Traded));
+        assert!(BlockRfqState::Traded.can_transition_to(BlockRfqState::Filled));
+        assert!(!BlockRfqState::Open.can_transition_to(BlockRfqState::Created));
+        assert!(!BlockRfqState::Filled.can_transition_to(BlockRfqState::Open));
+        assert!(BlockRfqState::Cancelled.can_transition_to(BlockRfqState::Cancelled));
+        assert!(BlockRfqQuoteState::Open.can_transition_to(BlockRfqQuoteState::Filled));
+        assert!(!BlockRfqQuoteState::Cancelled.can_transition_to(BlockRfqQuoteState::Open));
+    }
+
+    #[test]
+    fn test_create_request_serialization() {
+        let request = CreateBlockRfqRequest::new(vec![CreateBlockRfqLeg::new(
+            "BTC-15NOV24".to_string(),
+            20000.0,
+            OrderSide::Sell,
+        )])
+        .with_hedge(BlockRfqHedge::new(
+            "BTC-PERPETUAL".to_string(),
+            OrderSide::Buy,
+            70000.0,
+            10.0,
+        ))
+        .with_label("example".to_string())
+        .with_makers(vec!["MAKER1".to_string()]);
+        assert!(request.is_valid());
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["legs"][0]["direction"], "sell");
+        assert_eq!(json["hedge"]["price"], 70000.0);
+        assert!(json.get("disclosed").is_none());
+
+        let anonymous = request.with_disclosed(false);
+        assert!(!anonymous.is_valid());
+    }
+
+    #[test]
+    fn test_taker_workflow() {
+        let mut taker = BlockRfqTaker::new();
+        let mut rfq: BlockRfq = serde_json::from_str(TAKER_RFQ).unwrap();
+        rfq.state = BlockRfqState::Created;
+        assert_eq!(taker.apply(rfq.clone()).unwrap(), BlockRfqState::Created);
+
+        let created = rfq.creation_timestamp;
+        assert!(matches!(
+            taker.accept_best(
+                321,
+                OrderSide::Sell,
+                10000.0,
+                BlockRfqTimeInForce::FillOrKill,
+                created + 1000
+            ),
+            Err(BlockRfqError::NotActive { .. })
+        ));
+
+        rfq.state = BlockRfqState::Open;
+        taker.apply(rfq.clone()).unwrap();
+        assert_eq!(
+            taker.accept_best(
+                321,
+                OrderSide::Sell,
+                10000.0,
+                BlockRfqTimeInForce::FillOrKill,
+                created + 1000
+            ),
+            Err(BlockRfqError::GracePeriod {
+                block_rfq_id: 321,
+                remaining_ms: 4000
+            })
+        );
+        let accept = taker
+            .accept_best(
+                321,
+                OrderSide::Sell,
+                10000.0,
+                BlockRfqTimeInForce::FillOrKill,
+                created + BLOCK_RFQ_GRACE_PERIOD_MS,
+            )
+            .unwrap();
+        assert_eq!(accept.price, 291664.14);
+        assert_eq!(accept.legs.len(), 2);
+        assert!(matches!(
+            taker.accept_best(
+                321,
+                OrderSide::Buy,
+                10000.0,
+                BlockRfqTimeInForce::FillOrKill,
+                created + 6000
+            ),
+            Err(BlockRfqError::NoQuotes { .. })
+        ));
+        assert_eq!(
+            taker.accept_best(
+                321,
+                OrderSide::Sell,
+                1.0,
+                BlockRfqTimeInForce::FillOrKill,
+                rfq.expiration_timestamp
+            ),
+            Err(BlockRfqError::Expired(321))
+        );
+
+        rfq.state = BlockRfqState::Filled;
+        taker.apply(rfq.clone()).unwrap();
+        assert!(taker.can_cancel(321).is_err());
+        rfq.state = BlockRfqState::Open;
+        assert!(matches!(
+            taker.apply(rfq),
+            Err(BlockRfqError::InvalidTransition { .. })
+        ));
+        assert_eq!(taker.active().count(), 0);
+    }
+
+    #[test]
+    fn test_maker_workflow() {
+        let mut maker = BlockRfqMaker::new();
+        let rfq: BlockRfq = serde_json::from_str(MAKER_RFQ).unwrap();
+        assert!(matches!(
+            BlockRfqTaker::new().apply(rfq.clone()),
+            Err(BlockRfqError::RoleMismatch { .. })
+        ));
+        maker.apply_rfq(rfq).unwrap();
+
+        let request = maker
+            .quote_request(722, OrderSide::Buy, 25.0, &[10.0], 1731664700000)
+            .unwrap()
+            .with_label("example_quote".to_string())
+            .with_execution_instruction(ExecutionInstruction::AllOrNone);
+        assert_eq!(request.legs[0].direction, OrderSide::Buy);
+        assert_eq!(request.legs[0].price, 10.0);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["execution_instruction"], "all_or_none");
+
+        assert_eq!(
+            maker.quote_request(722, OrderSide::Buy, 25.0, &[], 1731664700000),
+            Err(BlockRfqError::LegCountMismatch {
+                expected: 1,
+                actual: 0
+            })
+        );
+
+        let mut undirected: BlockRfq = serde_json::from_str(MAKER_RFQ).unwrap();
+        undirected.block_rfq_id = 723;
+        undirected.legs[0].direction = None;
+        maker.apply_rfq(undirected).unwrap();
+        assert!(matches!(
+            maker.quote_request(723, OrderSide::Buy, 25.0, &[10.0], 1731664700000),
+            Err(BlockRfqError::MissingLegDirection {
+                block_rfq_id: 723,
+                ..
+            })
+        ));
+
+        let mut quote: BlockRfqQuote = serde_json::from_str(QUOTE).unwrap();
+        assert_eq!(
+            maker.apply_quote(quote.clone()).unwrap(),
+            BlockRfqQuoteState::Open
+        );
+        assert_eq!(maker.open_quotes(722).count(), 1);
+        quote.quote_state = BlockRfqQuoteState::Cancelled;
+        maker.apply_quote(quote.clone()).unwrap();
+        assert_eq!(maker.open_quotes(722).count(), 0);
+        quote.quote_state = BlockRfqQuoteState::Open;
+        assert!(matches!(
+            maker.apply_quote(quote),
+            Err(BlockRfqError::InvalidQuoteTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_block_rfq_quotes_response() {
+        // Result of the documented `/private/get_block_rfq_quotes` example
+        let documented = r#"{
+            "label": "example_quote",
+            "amount": 20000,
+            "direction": "buy",
+            "price": 74600,
+            "legs": [
+                {"direction": "buy", "price": 74600, "instrument_name": "BTC-15NOV24", "ratio": 1}
+            ],
+            "creation_timestamp": 1731076586371,
+            "block_rfq_id": 1,
+            "replaced": false,
+            "filled_amount": 0,
+            "last_update_timestamp": 1731076638591,
+            "hedge": {
+                "amount": 10,
+                "direction": "buy",
+                "price": 70000,
+                "instrument_name": "BTC-PERPETUAL"
+            },
+            "block_rfq_quote_id": 8,
+            "quote_state": "open",
+            "execution_instruction": "all_or_none"
+        }"#;
+        let response: GetBlockRfqQuotesResponse = serde_json::from_str(documented).unwrap();
+        assert_eq!(response.quotes().len(), 1);
+        assert_eq!(response.quotes()[0].block_rfq_quote_id, 8);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap()["block_rfq_quote_id"],
+            8
+        );
+        assert_eq!(
+            response.quotes()[0].execution_instruction,
+            ExecutionInstruction::AllOrNone
+        );
+
+        let list: GetBlockRfqQuotesResponse =
+            serde_json::from_str(&format!("[{QUOTE}, {documented}]")).unwrap();
+        assert_eq!(list.quotes().len(), 2);
+        let value = serde_json::to_value(&list).unwrap();
+        assert_eq!(value[1]["block_rfq_quote_id"], 8);
+        assert_eq!(
+            serde_json::from_value::<GetBlockRfqQuotesResponse>(value).unwrap(),
+            list
+        );
+    }
+
+    #[test]
+    fn test_get_block_rfqs_response() {
+        let json = format!(r#"{{"continuation": null, "block_rfqs": [{MAKER_RFQ}]}}"#);
+        let response: GetBlockRfqsResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(response.block_rfqs[0].role, BlockTradeRole::Maker);
+        assert!(response.continuation.is_none());
+
+        let edit = EditBlockRfqQuoteRequest::by_label(722, "q".to_string(), 25.0, vec![]);
+        assert!(edit.is_valid());
+        let json = serde_json::to_value(&edit).unwrap();
+        assert!(json.get("block_rfq_quote_id").is_none());
+    }
+
+    #[test]
+    fn test_block_rfq_user_info_deserialization() {
+        let json = r#"{
+            "parent": {"identity": "MAKER1", "is_maker": true},
+            "users": [
+                {"user_id": 1, "taker_rating": 98.5, "identity": "TAKER1", "is_maker": false},
+                {"user_id": 2, "taker_rating": 97.0}
+            ]
+        }"#;
+
+        let info: BlockRfqUserInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.parent.identity.as_deref(), Some("MAKER1"));
+        assert_eq!(info.parent.is_maker, Some(true));
+        assert_eq!(info.users.len(), 2);
+        assert_eq!(info.users[1].identity, None);
+        assert_eq!(info.users[1].taker_rating, Some(97.0));
+    }
+
+    #[test]
+    fn test_block_rfq_trades_deserialization() {
+        let json = r#"{
+            "continuation": "1739739009234:6570",
+            "block_rfqs": [{
+                "id": 6579,
+                "timestamp": 1739743922308,
+                "combo_id": "BTC-CS-17FEB25-89000_90000",
+                "legs": [
+                    {"price": 0.08, "direction": "buy", "instrument_name": "BTC-17FEB25-89000-C", "ratio": 1},
+                    {"price": 0.075, "direction": "sell", "instrument_name": "BTC-17FEB25-90000-C", "ratio": 1}
+                ],
+                "amount": 12.5,
+                "direction": "sell",
+                "mark_price": 0.010314468,
+                "trades": [{"price": 0.005, "amount": 12.5, "direction": "sell"}]
+            }]
+        }"#;
+
+        let response: GetBlockRfqTradesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.continuation.as_deref(), Some("1739739009234:6570"));
+        assert_eq!(response.block_rfqs[0].legs.len(), 2);
+        assert_eq!(response.block_rfqs[0].trades[0].price, 0.005);
+        assert!(response.block_rfqs[0].hedge.is_none());
+
+        let prices: BlockRfqLegPrices = serde_json::from_str(
+            r#"{"legs": [{"ratio": 1, "instrument_name": "BTC-1NOV24-67000-C", "price": 0.6001, "direction": "buy"}], "amount": 2}"#,
+        )
+        .unwrap();
+        assert_eq!(prices.amount, 2.0);
+        assert_eq!(prices.legs[0].direction, OrderSide::Buy);
+    }
+}
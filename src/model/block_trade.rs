@@ -12,9 +12,17 @@
 //! Block trades allow large trades to be executed off the order book
 //! between two parties who have agreed on the terms.
 
+use crate::model::instrument::Instrument;
 use crate::model::order::OrderSide;
+use crate::model::trade::Trade;
+use crate::utils::ids::next_monotonic_id;
+use crate::utils::tools::generate_timestamp;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Validity window of a block trade signature on each side of its timestamp
+pub const BLOCK_TRADE_SIGNATURE_VALIDITY_MS: i64 = 5 * 60 * 1000;
 
 /// Role in a block trade
 ///
@@ -40,6 +48,17 @@ impl BlockTradeRole {
     }
 }
 
+impl BlockTradeRole {
+    /// Get the counterparty role
+    #[must_use]
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Maker => Self::Taker,
+            Self::Taker => Self::Maker,
+        }
+    }
+}
+
 impl std::fmt::Display for BlockTradeRole {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -74,6 +93,18 @@ impl BlockTradeLeg {
             direction,
         }
     }
+
+    /// Get the direction traded by the party with the given role
+    ///
+    /// Leg directions are always expressed from the maker's perspective,
+    /// so the taker trades the opposite side.
+    #[must_use]
+    pub fn direction_for(&self, role: BlockTradeRole) -> OrderSide {
+        match role {
+            BlockTradeRole::Maker => self.direction,
+            BlockTradeRole::Taker => opposite_side(self.direction),
+        }
+    }
 }
 
 /// Block trade verification request
@@ -213,7 +244,8 @@ pub struct BlockTradeExecution {
     /// Index price at execution
     pub index_price: f64,
     /// Mark price at execution
-    pub mark_price: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
     /// Block trade ID this execution belongs to
     pub block_trade_id: String,
     /// Execution timestamp in milliseconds
@@ -241,6 +273,32 @@ pub struct BlockTradeExecution {
     /// Trade label
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// Matching engine identifier
+    pub matching_id: Option<String>,
+    /// Trade size in contracts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contracts: Option<f64>,
+    /// Profit or loss of the trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profit_loss: Option<f64>,
+    /// Whether this was a self trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_trade: Option<bool>,
+    /// Whether the order was protected by MMP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mmp: Option<bool>,
+    /// Whether the trade only reduced risk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_reducing: Option<bool>,
+    /// Combo instrument, if the trade is a combo leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combo_id: Option<String>,
+    /// Block RFQ identifier, if the trade came from a block RFQ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_id: Option<u64>,
+    /// Number of legs in the block trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_trade_leg_count: Option<u32>,
 }
 
 /// Executed block trade
@@ -299,6 +357,484 @@ impl BlockTrade {
     }
 }
 
+/// Get the opposite order side
+fn opposite_side(side: OrderSide) -> OrderSide {
+    match side {
+        OrderSide::Buy => OrderSide::Sell,
+        OrderSide::Sell => OrderSide::Buy,
+    }
+}
+
+/// Error raised while preparing a block trade
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockTradeError {
+    /// No legs in the trade
+    NoLegs,
+    /// Same instrument appears in several legs
+    DuplicateInstrument(String),
+    /// No instrument definition for a leg
+    UnknownInstrument(String),
+    /// Instrument is not active
+    InactiveInstrument(String),
+    /// Leg price is not positive or not on a valid tick
+    InvalidPrice {
+        /// Instrument of the leg
+        instrument_name: String,
+        /// Leg price
+        price: f64,
+    },
+    /// Leg amount is missing, not positive, not a multiple of the step or below the minimum
+    InvalidAmount {
+        /// Instrument of the leg
+        instrument_name: String,
+        /// Leg amount
+        amount: Option<f64>,
+    },
+    /// Timestamp is outside the signature validity window
+    TimestampOutOfWindow {
+        /// Block trade timestamp
+        timestamp: i64,
+        /// Current time
+        now: i64,
+    },
+    /// Nonce is empty
+    EmptyNonce,
+    /// Nonce was already used within the validity window
+    NonceReused(String),
+}
+
+impl std::fmt::Display for BlockTradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoLegs => write!(f, "block trade has no legs"),
+            Self::DuplicateInstrument(name) => write!(f, "duplicate leg for {name}"),
+            Self::UnknownInstrument(name) => write!(f, "unknown instrument {name}"),
+            Self::InactiveInstrument(name) => write!(f, "instrument {name} is not active"),
+            Self::InvalidPrice {
+                instrument_name,
+                price,
+            } => write!(f, "invalid price {price} for {instrument_name}"),
+            Self::InvalidAmount {
+                instrument_name,
+                amount,
+            } => write!(f, "invalid amount {amount:?} for {instrument_name}"),
+            Self::TimestampOutOfWindow { timestamp, now } => write!(
+                f,
+                "timestamp {timestamp} is more than {BLOCK_TRADE_SIGNATURE_VALIDITY_MS} ms from {now}"
+            ),
+            Self::EmptyNonce => write!(f, "nonce must not be empty"),
+            Self::NonceReused(nonce) => write!(f, "nonce {nonce} was already used"),
+        }
+    }
+}
+
+impl std::error::Error for BlockTradeError {}
+
+/// Check if a block trade timestamp is within the signature validity window
+#[must_use]
+pub fn is_block_trade_timestamp_valid(timestamp: i64, now: i64) -> bool {
+    (now - timestamp).abs() <= BLOCK_TRADE_SIGNATURE_VALIDITY_MS
+}
+
+/// Generate a nonce for a block trade
+///
+/// Nonces are unique within the process and URL-safe.
+#[must_use]
+pub fn generate_block_trade_nonce() -> String {
+    format!("bt-{:x}", next_monotonic_id())
+}
+
+/// Block trade agreed between two counterparties
+///
+/// Holds the shared timestamp, nonce and legs (maker perspective) and
+/// produces identical verify requests for both sides.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockTradeProposal {
+    /// Shared timestamp in milliseconds
+    pub timestamp: i64,
+    /// Shared nonce
+    pub nonce: String,
+    /// Legs, directions from the maker's perspective
+    pub trades: Vec<BlockTradeLeg>,
+}
+
+impl BlockTradeProposal {
+    /// Create a proposal with a fresh timestamp and nonce
+    ///
+    /// `trades` are expressed from the perspective of `role`; taker legs are
+    /// mirrored into the maker perspective the API expects.
+    #[must_use]
+    pub fn new(role: BlockTradeRole, trades: Vec<BlockTradeLeg>) -> Self {
+        Self::with_nonce(
+            role,
+            trades,
+            generate_timestamp() as i64,
+            generate_block_trade_nonce(),
+        )
+    }
+
+    /// Create a proposal with an explicit timestamp and nonce
+    #[must_use]
+    pub fn with_nonce(
+        role: BlockTradeRole,
+        trades: Vec<BlockTradeLeg>,
+        timestamp: i64,
+        nonce: String,
+    ) -> Self {
+        let trades = trades
+            .into_iter()
+            .map(|mut leg| {
+                leg.direction = leg.direction_for(role);
+                leg
+            })
+            .collect();
+        Self {
+            timestamp,
+            nonce,
+            trades,
+        }
+    }
+
+    /// Build the verify request for one side
+    #[must_use]
+    pub fn verify_request(&self, role: BlockTradeRole) -> VerifyBlockTradeRequest {
+        VerifyBlockTradeRequest::new(
+            self.timestamp,
+            self.nonce.clone(),
+            role,
+            self.trades.clone(),
+        )
+    }
+
+    /// Build the maker and taker verify requests
+    #[must_use]
+    pub fn verify_requests(&self) -> (VerifyBlockTradeRequest, VerifyBlockTradeRequest) {
+        (
+            self.verify_request(BlockTradeRole::Maker),
+            self.verify_request(BlockTradeRole::Taker),
+        )
+    }
+
+    /// Build the simulate request for one side
+    #[must_use]
+    pub fn simulate_request(&self, role: BlockTradeRole) -> SimulateBlockTradeRequest {
+        SimulateBlockTradeRequest {
+            role: Some(role),
+            trades: self.trades.clone(),
+        }
+    }
+
+    /// Check if the signature window is still open at `now` (milliseconds)
+    #[must_use]
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        is_block_trade_timestamp_valid(self.timestamp, now)
+    }
+
+    /// Validate the shared timestamp, the nonce and every leg
+    ///
+    /// `instruments` must contain the definition of each leg instrument.
+    pub fn validate(
+        &self,
+        instruments: &HashMap<String, Instrument>,
+        now: i64,
+    ) -> Result<(), BlockTradeError> {
+        if self.nonce.is_empty() {
+            return Err(BlockTradeError::EmptyNonce);
+        }
+        if !self.is_valid_at(now) {
+            return Err(BlockTradeError::TimestampOutOfWindow {
+                timestamp: self.timestamp,
+                now,
+            });
+        }
+        validate_block_trade_legs(&self.trades, instruments)
+    }
+}
+
+/// Validate block trade legs against instrument rules
+///
+/// Checks that instruments are known, active and unique, prices are on a
+/// valid tick and amounts are positive multiples of the amount step above
+/// the minimum trade amount.
+pub fn validate_block_trade_legs(
+    trades: &[BlockTradeLeg],
+    instruments: &HashMap<String, Instrument>,
+) -> Result<(), BlockTradeError> {
+    if trades.is_empty() {
+        return Err(BlockTradeError::NoLegs);
+    }
+    let mut seen = HashSet::new();
+    for leg in trades {
+        let name = &leg.instrument_name;
+        if !seen.insert(name.as_str()) {
+            return Err(BlockTradeError::DuplicateInstrument(name.clone()));
+        }
+        let instrument = instruments
+            .get(name)
+            .ok_or_else(|| BlockTradeError::UnknownInstrument(name.clone()))?;
+        if instrument.is_active == Some(false) {
+            return Err(BlockTradeError::InactiveInstrument(name.clone()));
+        }
+        if !leg.price.is_finite() || leg.price <= 0.0 || !instrument.is_price_on_tick(leg.price) {
+            return Err(BlockTradeError::InvalidPrice {
+                instrument_name: name.clone(),
+                price: leg.price,
+            });
+        }
+        let amount_valid = leg.amount.is_some_and(|amount| {
+            amount.is_finite()
+                && amount > 0.0
+                && instrument.min_trade_amount.is_none_or(|min| amount >= min)
+                && (instrument.round_amount(amount) - amount).abs() < 1e-9
+        });
+        if !amount_valid {
+            return Err(BlockTradeError::InvalidAmount {
+                instrument_name: name.clone(),
+                amount: leg.amount,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Tracks nonces used for block trades to prevent reuse
+///
+/// Nonces are forgotten once their timestamp leaves the signature
+/// validity window, as the server would reject them anyway.
+#[derive(Debug, Clone, Default)]
+pub struct BlockTradeNonceTracker {
+    used: HashMap<String, i64>,
+}
+
+impl BlockTradeNonceTracker {
+    /// Create an empty tracker
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a nonce, failing if it is reused or its timestamp is out of window
+    pub fn register(
+        &mut self,
+        nonce: &str,
+        timestamp: i64,
+        now: i64,
+    ) -> Result<(), BlockTradeError> {
+        if nonce.is_empty() {
+            return Err(BlockTradeError::EmptyNonce);
+        }
+        if !is_block_trade_timestamp_valid(timestamp, now) {
+            return Err(BlockTradeError::TimestampOutOfWindow { timestamp, now });
+        }
+        self.prune(now);
+        if self.used.contains_key(nonce) {
+            return Err(BlockTradeError::NonceReused(nonce.to_string()));
+        }
+        self.used.insert(nonce.to_string(), timestamp);
+        Ok(())
+    }
+
+    /// Forget nonces whose timestamp is out of the validity window
+    pub fn prune(&mut self, now: i64) {
+        self.used
+            .retain(|_, timestamp| is_block_trade_timestamp_valid(*timestamp, now));
+    }
+
+    /// Get the number of tracked nonces
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.used.len()
+    }
+
+    /// Check if no nonce is tracked
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.used.is_empty()
+    }
+}
+
+/// Request for `private/simulate_block_trade`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulateBlockTradeRequest {
+    /// Role in the block trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<BlockTradeRole>,
+    /// List of trade legs
+    pub trades: Vec<BlockTradeLeg>,
+}
+
+/// Request for `private/approve_block_trade` and `private/reject_block_trade`
+///
+/// `role` is the opposite of the counterparty's role.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockTradeApprovalRequest {
+    /// Timestamp of the pending block trade
+    pub timestamp: i64,
+    /// Nonce of the pending block trade
+    pub nonce: String,
+    /// Role of the approving user
+    pub role: BlockTradeRole,
+}
+
+impl BlockTradeApprovalRequest {
+    /// Create a new approval request
+    #[must_use]
+    pub fn new(timestamp: i64, nonce: String, role: BlockTradeRole) -> Self {
+        Self {
+            timestamp,
+            nonce,
+            role,
+        }
+    }
+}
+
+/// State of a pending block trade for one party
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockTradeRequestState {
+    /// State value (e.g., `initial`, `approved`, `rejected`)
+    pub value: String,
+    /// State timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl BlockTradeRequestState {
+    /// Check if the trade still waits for approval
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.value == "initial"
+    }
+
+    /// Check if the trade was approved
+    #[must_use]
+    pub fn is_approved(&self) -> bool {
+        self.value == "approved"
+    }
+
+    /// Check if the trade was rejected
+    #[must_use]
+    pub fn is_rejected(&self) -> bool {
+        self.value == "rejected"
+    }
+}
+
+/// Block trade waiting for approval
+///
+/// Returned by `private/get_pending_block_trades` and sent on the
+/// `block_trade_confirmations` channels.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingBlockTrade {
+    /// Nonce identifying the block trade
+    pub nonce: String,
+    /// Timestamp identifying the block trade
+    pub timestamp: i64,
+    /// Role of the user
+    pub role: BlockTradeRole,
+    /// Trade legs
+    pub trades: Vec<BlockTradeLeg>,
+    /// State for the user
+    pub state: BlockTradeRequestState,
+    /// State for the counterparty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty_state: Option<BlockTradeRequestState>,
+    /// User id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u64>,
+    /// Username of the initiator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Application that executed the trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_name: Option<String>,
+    /// Broker code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broker_code: Option<String>,
+    /// Broker name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broker_name: Option<String>,
+    /// Combo instrument identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combo_id: Option<String>,
+}
+
+impl PendingBlockTrade {
+    /// Build the request approving this trade
+    #[must_use]
+    pub fn approve_request(&self) -> BlockTradeApprovalRequest {
+        BlockTradeApprovalRequest::new(self.timestamp, self.nonce.clone(), self.role)
+    }
+
+    /// Build the request rejecting this trade
+    #[must_use]
+    pub fn reject_request(&self) -> BlockTradeApprovalRequest {
+        self.approve_request()
+    }
+}
+
+/// Notification sent on the `block_trade_confirmations` channels
+pub type BlockTradeConfirmation = PendingBlockTrade;
+
+/// Client of a broker on one side of a broker block trade
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokerClient {
+    /// Masked user id of the client
+    pub user_id: String,
+    /// Client id
+    pub client_id: u64,
+    /// Client name
+    pub client_name: String,
+    /// Client link id
+    pub client_link_id: u64,
+    /// Client link name
+    pub client_link_name: String,
+    /// Approval state of the client (pending requests only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+/// Broker block trade waiting for client approval
+///
+/// Returned by `private/get_broker_trade_requests`.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokerTradeRequest {
+    /// Nonce identifying the block trade
+    pub nonce: String,
+    /// Timestamp identifying the block trade
+    pub timestamp: i64,
+    /// State of the request
+    pub state: String,
+    /// Trade legs
+    pub trades: Vec<BlockTradeLeg>,
+    /// Maker client
+    pub maker: BrokerClient,
+    /// Taker client
+    pub taker: BrokerClient,
+}
+
+/// Executed broker block trade
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokerTrade {
+    /// Block trade id
+    pub id: String,
+    /// Execution timestamp in milliseconds
+    pub timestamp: i64,
+    /// Executed trades
+    pub trades: Vec<Trade>,
+    /// Maker client
+    pub maker: BrokerClient,
+    /// Taker client
+    pub taker: BrokerClient,
+}
+
+/// Page of broker block trades returned by `private/get_broker_trades`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokerTradesResponse {
+    /// Broker block trades in this page
+    pub history: Vec<BrokerTrade>,
+    /// Id to pass as `start_id` to fetch the next page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_start_id: Option<u64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,7 +985,7 @@ mod tests {
             order_type: "limit".to_string(),
             liquidity: "M".to_string(),
             index_price: 8900.45,
-            mark_price: 8895.19,
+            mark_price: Some(8895.19),
             block_trade_id: "6165".to_string(),
             timestamp: 1590485535978,
             state: "filled".to_string(),
@@ -460,6 +996,15 @@ mod tests {
             iv: None,
             underlying_price: None,
             label: None,
+            matching_id: None,
+            contracts: None,
+            profit_loss: None,
+            self_trade: None,
+            mmp: None,
+            risk_reducing: None,
+            combo_id: None,
+            block_rfq_id: None,
+            block_trade_leg_count: None,
         }
     }
 
@@ -569,4 +1114,299 @@ mod tests {
         assert_eq!(block_trade.id, deserialized.id);
         assert_eq!(block_trade.app_name, deserialized.app_name);
     }
+
+    fn create_test_instrument() -> Instrument {
+        Instrument {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            price_index: Some("btc_usd".to_string()),
+            kind: None,
+            currency: Some("BTC".to_string()),
+            is_active: Some(true),
+            expiration_timestamp: None,
+            strike: None,
+            option_type: None,
+            tick_size: Some(0.5),
+            tick_size_steps: None,
+            min_trade_amount: Some(10.0),
+            contract_size: Some(10.0),
+            settlement_period: None,
+            instrument_type: None,
+            quote_currency: Some("USD".to_string()),
+            settlement_currency: Some("BTC".to_string()),
+            creation_timestamp: None,
+            max_leverage: None,
+            maker_commission: None,
+            taker_commission: None,
+            instrument_id: None,
+            base_currency: Some("BTC".to_string()),
+            counter_currency: Some("USD".to_string()),
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
+        }
+    }
+
+    fn create_test_instruments() -> HashMap<String, Instrument> {
+        let instrument = create_test_instrument();
+        HashMap::from([(instrument.instrument_name.clone(), instrument)])
+    }
+
+    #[test]
+    fn test_proposal_mirrors_taker_legs() {
+        let taker_view = vec![BlockTradeLeg::new(
+            "BTC-PERPETUAL".to_string(),
+            50000.0,
+            100.0,
+            OrderSide::Buy,
+        )];
+        let proposal = BlockTradeProposal::with_nonce(
+            BlockTradeRole::Taker,
+            taker_view,
+            1_590_485_535_899,
+            "nonce".to_string(),
+        );
+        assert_eq!(proposal.trades[0].direction, OrderSide::Sell);
+        assert_eq!(
+            proposal.trades[0].direction_for(BlockTradeRole::Taker),
+            OrderSide::Buy
+        );
+
+        let (maker, taker) = proposal.verify_requests();
+        assert_eq!(maker.role, BlockTradeRole::Maker);
+        assert_eq!(taker.role, BlockTradeRole::Taker);
+        assert_eq!(maker.trades, taker.trades);
+        assert_eq!(maker.nonce, taker.nonce);
+        assert_eq!(maker.timestamp, taker.timestamp);
+    }
+
+    #[test]
+    fn test_proposal_generates_nonce_and_timestamp() {
+        let legs = vec![BlockTradeLeg::new(
+            "BTC-PERPETUAL".to_string(),
+            50000.0,
+            100.0,
+            OrderSide::Buy,
+        )];
+        let first = BlockTradeProposal::new(BlockTradeRole::Maker, legs.clone());
+        let second = BlockTradeProposal::new(BlockTradeRole::Maker, legs);
+        assert_ne!(first.nonce, second.nonce);
+        assert!(first.is_valid_at(generate_timestamp() as i64));
+    }
+
+    #[test]
+    fn test_timestamp_window() {
+        let ts = 1_000_000_000;
+        assert!(is_block_trade_timestamp_valid(
+            ts,
+            ts + BLOCK_TRADE_SIGNATURE_VALIDITY_MS
+        ));
+        assert!(is_block_trade_timestamp_valid(
+            ts,
+            ts - BLOCK_TRADE_SIGNATURE_VALIDITY_MS
+        ));
+        assert!(!is_block_trade_timestamp_valid(
+            ts,
+            ts + BLOCK_TRADE_SIGNATURE_VALIDITY_MS + 1
+        ));
+    }
+
+    #[test]
+    fn test_validate_legs() {
+        let instruments = create_test_instruments();
+        let leg = |price: f64, amount: f64| {
+            BlockTradeLeg::new("BTC-PERPETUAL".to_string(), price, amount, OrderSide::Buy)
+        };
+
+        assert!(validate_block_trade_legs(&[leg(50000.5, 100.0)], &instruments).is_ok());
+        assert_eq!(
+            validate_block_trade_legs(&[], &instruments),
+            Err(BlockTradeError::NoLegs)
+        );
+        assert!(matches!(
+            validate_block_trade_legs(&[leg(50000.2, 100.0)], &instruments),
+            Err(BlockTradeError::InvalidPrice { .. })
+        ));
+        assert!(matches!(
+            validate_block_trade_legs(&[leg(50000.0, 105.0)], &instruments),
+            Err(BlockTradeError::InvalidAmount { .. })
+        ));
+        assert!(matches!(
+            validate_block_trade_legs(&[leg(50000.0, 0.0)], &instruments),
+            Err(BlockTradeError::InvalidAmount { .. })
+        ));
+        assert_eq!(
+            validate_block_trade_legs(&[leg(50000.0, 100.0), leg(50000.0, 100.0)], &instruments),
+            Err(BlockTradeError::DuplicateInstrument(
+                "BTC-PERPETUAL".to_string()
+            ))
+        );
+        let unknown = BlockTradeLeg::new("ETH-PERPETUAL".to_string(), 3000.0, 1.0, OrderSide::Sell);
+        assert_eq!(
+            validate_block_trade_legs(&[unknown], &instruments),
+            Err(BlockTradeError::UnknownInstrument(
+                "ETH-PERPETUAL".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_proposal_validate() {
+        let instruments = create_test_instruments();
+        let legs = vec![BlockTradeLeg::new(
+            "BTC-PERPETUAL".to_string(),
+            50000.0,
+            100.0,
+            OrderSide::Buy,
+        )];
+        let proposal =
+            BlockTradeProposal::with_nonce(BlockTradeRole::Maker, legs, 1_000, "n".to_string());
+        assert!(proposal.validate(&instruments, 2_000).is_ok());
+        assert!(matches!(
+            proposal.validate(&instruments, 1_000 + BLOCK_TRADE_SIGNATURE_VALIDITY_MS + 1),
+            Err(BlockTradeError::TimestampOutOfWindow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_nonce_tracker() {
+        let mut tracker = BlockTradeNonceTracker::new();
+        assert!(tracker.register("a", 1_000, 1_000).is_ok());
+        assert_eq!(
+            tracker.register("a", 1_000, 2_000),
+            Err(BlockTradeError::NonceReused("a".to_string()))
+        );
+        assert_eq!(
+            tracker.register("", 1_000, 1_000),
+            Err(BlockTradeError::EmptyNonce)
+        );
+
+        let later = 1_000 + BLOCK_TRADE_SIGNATURE_VALIDITY_MS + 1;
+        assert!(tracker.register("a", later, later).is_ok());
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn test_pending_block_trade_deserialization() {
+        let json = r#"{
+            "user_id": 7,
+            "trades": [
+                {"price": 70246.0, "instrument_name": "BTC-PERPETUAL", "direction": "sell", "amount": 10.0}
+            ],
+            "timestamp": 1745936607711,
+            "state": {"value": "initial", "timestamp": 1745936607711},
+            "role": "maker",
+            "nonce": "bt-abc",
+            "counterparty_state": {"value": "rejected", "timestamp": 1745936607711}
+        }"#;
+        let pending: BlockTradeConfirmation = serde_json::from_str(json).unwrap();
+        assert!(pending.state.is_pending());
+        assert!(pending.counterparty_state.as_ref().unwrap().is_rejected());
+        assert_eq!(pending.trades[0].direction, OrderSide::Sell);
+
+        let approve = pending.approve_request();
+        assert_eq!(approve.nonce, "bt-abc");
+        assert_eq!(approve.role, BlockTradeRole::Maker);
+        assert_eq!(approve, pending.reject_request());
+    }
+
+    #[test]
+    fn test_simulate_request_serialization() {
+        let proposal = BlockTradeProposal::with_nonce(
+            BlockTradeRole::Maker,
+            vec![BlockTradeLeg::new(
+                "BTC-PERPETUAL".to_string(),
+                50000.0,
+                100.0,
+                OrderSide::Buy,
+            )],
+            1_000,
+            "n".to_string(),
+        );
+        let value = serde_json::to_value(proposal.simulate_request(BlockTradeRole::Taker)).unwrap();
+        assert_eq!(value["role"], "taker");
+        assert_eq!(value["trades"][0]["direction"], "buy");
+    }
+
+    #[test]
+    fn test_broker_trade_request_deserialization() {
+        let json = r#"[{
+            "timestamp": 1742824052547,
+            "state": "activated",
+            "trades": [
+                {"amount": 100000, "direction": "buy", "price": 87516.83, "instrument_name": "BTC-PERPETUAL"}
+            ],
+            "maker": {
+                "state": "approved",
+                "client_id": 1,
+                "user_id": "***009",
+                "client_name": "Test Client",
+                "client_link_name": "Test Client 2",
+                "client_link_id": 2
+            },
+            "taker": {
+                "state": "initial",
+                "client_id": 1,
+                "user_id": "***008",
+                "client_name": "Test Client",
+                "client_link_name": "Test Client 1",
+                "client_link_id": 1
+            },
+            "nonce": "29rKkuD3NSBPet4njrpNWEuHBm9s"
+        }]"#;
+
+        let requests: Vec<BrokerTradeRequest> = serde_json::from_str(json).unwrap();
+        assert_eq!(requests[0].state, "activated");
+        assert_eq!(requests[0].maker.state.as_deref(), Some("approved"));
+        assert_eq!(requests[0].taker.client_link_id, 1);
+        assert_eq!(requests[0].trades[0].amount, Some(100000.0));
+    }
+
+    #[test]
+    fn test_broker_trades_deserialization() {
+        let json = r#"{
+            "history": [{
+                "id": "BLOCK-7",
+                "timestamp": 1747239767111,
+                "trades": [{
+                    "timestamp": 1747239767111,
+                    "amount": 100000,
+                    "direction": "buy",
+                    "price": 102079.75,
+                    "index_price": 102079.75,
+                    "instrument_name": "BTC-PERPETUAL",
+                    "trade_seq": 7,
+                    "mark_price": 102079.75,
+                    "tick_direction": 1,
+                    "contracts": 10000,
+                    "trade_id": "7",
+                    "block_trade_id": "BLOCK-7",
+                    "block_trade_leg_count": 1
+                }],
+                "maker": {
+                    "user_id": "****009",
+                    "client_id": 2,
+                    "client_name": "Test Client 2",
+                    "client_link_name": "Test Client 2 l",
+                    "client_link_id": 2
+                },
+                "taker": {
+                    "user_id": "****008",
+                    "client_id": 1,
+                    "client_name": "Test Client 1",
+                    "client_link_name": "Test Client 1 l",
+                    "client_link_id": 1
+                }
+            }],
+            "next_start_id": 6
+        }"#;
+
+        let response: BrokerTradesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.next_start_id, Some(6));
+        let trade = &response.history[0];
+        assert_eq!(trade.id, "BLOCK-7");
+        assert_eq!(trade.maker.state, None);
+        assert_eq!(trade.trades[0].block_trade_id.as_deref(), Some("BLOCK-7"));
+    }
 }
@@ -17,7 +17,8 @@ pub struct BookSummary {
     pub quote_currency: String,
     /// 24h trading volume
     pub volume: f64,
-    /// 24h trading volume in USD
+    /// 24h trading volume in USD (futures only)
+    #[serde(default)]
     pub volume_usd: f64,
     /// Open interest
     pub open_interest: f64,
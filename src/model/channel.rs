@@ -0,0 +1,1264 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Typed WebSocket subscription channels
+//!
+//! [`Channel`] covers every channel of the subscription API. `Display`
+//! builds the channel string sent to `public/subscribe` or
+//! `private/subscribe`, and `FromStr` parses the `channel` field of
+//! incoming notifications. Parsing and the checked constructors reject
+//! parameter values the API does not accept for the channel (e.g., a
+//! `raw` interval on a grouped order book). `Display` writes the variant
+//! as is, so use [`Channel::try_to_string`] for channels built directly
+//! from their public fields.
+
+use crate::constants::{
+    CHANNEL_BOOK, CHANNEL_QUOTE, CHANNEL_TICKER, CHANNEL_TRADES, CHANNEL_USER_ORDERS,
+    CHANNEL_USER_PORTFOLIO, CHANNEL_USER_TRADES,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// Price grouping values accepted by grouped book channels
+pub const BOOK_GROUPS: &[u32] = &[1, 2, 5, 10, 25, 100, 250];
+
+/// Depths accepted by grouped book channels
+pub const BOOK_DEPTHS: &[u32] = &[1, 10, 20];
+
+/// Minute resolutions accepted by `chart.trades` channels
+pub const CHART_RESOLUTION_MINUTES: &[u32] = &[1, 3, 5, 10, 15, 30, 60, 120, 180, 360, 720];
+
+/// Error raised when building or parsing a channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelError {
+    /// Channel name does not match any known channel
+    UnknownChannel(String),
+    /// Parameter value is not accepted by the channel
+    InvalidParameter {
+        /// Parameter name
+        name: &'static str,
+        /// Rejected value
+        value: String,
+    },
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownChannel(channel) => write!(f, "unknown channel {channel}"),
+            Self::InvalidParameter { name, value } => {
+                write!(f, "invalid channel parameter {name}: {value}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+/// Notification frequency of a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelInterval {
+    /// Every event, without aggregation (authorized users only)
+    Raw,
+    /// Events aggregated over 100 milliseconds
+    Ms100,
+    /// Events aggregated over a longer, variable period
+    Agg2,
+}
+
+impl ChannelInterval {
+    /// Get the string representation used in channel names
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Ms100 => "100ms",
+            Self::Agg2 => "agg2",
+        }
+    }
+}
+
+impl fmt::Display for ChannelInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ChannelInterval {
+    type Err = ChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(Self::Raw),
+            "100ms" => Ok(Self::Ms100),
+            "agg2" => Ok(Self::Agg2),
+            _ => Err(invalid("interval", s)),
+        }
+    }
+}
+
+/// Instrument kind filter of a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelKind {
+    /// Futures
+    Future,
+    /// Options
+    Option,
+    /// Spot
+    Spot,
+    /// Future combos
+    FutureCombo,
+    /// Option combos
+    OptionCombo,
+    /// Any combo
+    Combo,
+    /// Any kind
+    Any,
+}
+
+impl ChannelKind {
+    /// Get the string representation used in channel names
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Future => "future",
+            Self::Option => "option",
+            Self::Spot => "spot",
+            Self::FutureCombo => "future_combo",
+            Self::OptionCombo => "option_combo",
+            Self::Combo => "combo",
+            Self::Any => "any",
+        }
+    }
+
+    /// Check if the kind is a combo kind
+    #[must_use]
+    pub fn is_combo(&self) -> bool {
+        matches!(self, Self::FutureCombo | Self::OptionCombo | Self::Combo)
+    }
+}
+
+impl fmt::Display for ChannelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ChannelKind {
+    type Err = ChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "future" => Ok(Self::Future),
+            "option" => Ok(Self::Option),
+            "spot" => Ok(Self::Spot),
+            "future_combo" => Ok(Self::FutureCombo),
+            "option_combo" => Ok(Self::OptionCombo),
+            "combo" => Ok(Self::Combo),
+            "any" => Ok(Self::Any),
+            _ => Err(invalid("kind", s)),
+        }
+    }
+}
+
+/// Price grouping of a grouped book channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BookGroup {
+    /// No grouping
+    None,
+    /// Prices rounded to the given group (divided by 100 for ETH)
+    Group(u32),
+}
+
+impl fmt::Display for BookGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Group(group) => write!(f, "{group}"),
+        }
+    }
+}
+
+impl FromStr for BookGroup {
+    type Err = ChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            _ => s.parse().map(Self::Group).map_err(|_| invalid("group", s)),
+        }
+    }
+}
+
+/// Bar resolution of a `chart.trades` channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChartResolution {
+    /// Bars of the given number of minutes
+    Minutes(u32),
+    /// Daily bars
+    Day,
+}
+
+impl fmt::Display for ChartResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Minutes(minutes) => write!(f, "{minutes}"),
+            Self::Day => write!(f, "1D"),
+        }
+    }
+}
+
+impl FromStr for ChartResolution {
+    type Err = ChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1D" => Ok(Self::Day),
+            _ => s
+                .parse()
+                .map(Self::Minutes)
+                .map_err(|_| invalid("resolution", s)),
+        }
+    }
+}
+
+/// WebSocket subscription channel
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// `announcements`
+    Announcements,
+    /// `block_rfq.maker.quotes.{currency}`
+    BlockRfqMakerQuotes {
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `block_rfq.maker.{currency}`
+    BlockRfqMaker {
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `block_rfq.taker.{currency}`
+    BlockRfqTaker {
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `block_rfq.trades.{currency}`
+    BlockRfqTrades {
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `block_trade_confirmations` or `block_trade_confirmations.{currency}`
+    BlockTradeConfirmations {
+        /// Currency symbol or `any`; `None` for every currency
+        currency: Option<String>,
+    },
+    /// `book.{instrument_name}.{interval}`
+    Book {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `book.{instrument_name}.{group}.{depth}.{interval}`
+    GroupedBook {
+        /// Instrument name
+        instrument_name: String,
+        /// Price grouping
+        group: BookGroup,
+        /// Number of price levels
+        depth: u32,
+        /// Notification frequency (`raw` is not accepted)
+        interval: ChannelInterval,
+    },
+    /// `chart.trades.{instrument_name}.{resolution}`
+    ChartTrades {
+        /// Instrument name
+        instrument_name: String,
+        /// Bar resolution
+        resolution: ChartResolution,
+    },
+    /// `deribit_price_index.{index_name}`
+    PriceIndex {
+        /// Index identifier
+        index_name: String,
+    },
+    /// `deribit_price_ranking.{index_name}`
+    PriceRanking {
+        /// Index identifier
+        index_name: String,
+    },
+    /// `deribit_price_statistics.{index_name}`
+    PriceStatistics {
+        /// Index identifier
+        index_name: String,
+    },
+    /// `deribit_volatility_index.{index_name}`
+    VolatilityIndex {
+        /// Index identifier
+        index_name: String,
+    },
+    /// `estimated_expiration_price.{index_name}`
+    EstimatedExpirationPrice {
+        /// Index identifier
+        index_name: String,
+    },
+    /// `incremental_ticker.{instrument_name}`
+    IncrementalTicker {
+        /// Instrument name
+        instrument_name: String,
+    },
+    /// `instrument.state.{kind}.{currency}`
+    InstrumentState {
+        /// Instrument kind (`combo` is not accepted)
+        kind: ChannelKind,
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `markprice.options.{index_name}`
+    MarkPriceOptions {
+        /// Index identifier
+        index_name: String,
+    },
+    /// `perpetual.{instrument_name}.{interval}`
+    Perpetual {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `platform_state`
+    PlatformState,
+    /// `platform_state.public_methods_state`
+    PlatformStatePublicMethods,
+    /// `quote.{instrument_name}`
+    Quote {
+        /// Instrument name
+        instrument_name: String,
+    },
+    /// `rfq.{currency}`
+    Rfq {
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `ticker.{instrument_name}.{interval}`
+    Ticker {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `trades.{instrument_name}.{interval}`
+    Trades {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `trades.{kind}.{currency}.{interval}`
+    TradesByKind {
+        /// Instrument kind (`combo` and `any` are not accepted)
+        kind: ChannelKind,
+        /// Currency symbol or `any`
+        currency: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.access_log`
+    UserAccessLog,
+    /// `user.changes.{instrument_name}.{interval}`
+    UserChanges {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.changes.{kind}.{currency}.{interval}`
+    UserChangesByKind {
+        /// Instrument kind
+        kind: ChannelKind,
+        /// Currency symbol or `any`
+        currency: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.combo_trades.{instrument_name}.{interval}`
+    UserComboTrades {
+        /// Combo instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.combo_trades.{kind}.{currency}.{interval}`
+    UserComboTradesByKind {
+        /// Combo kind (`future_combo`, `option_combo` or `combo`)
+        kind: ChannelKind,
+        /// Currency symbol or `any`
+        currency: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.lock`
+    UserLock,
+    /// `user.mmp_trigger.{index_name}`
+    UserMmpTrigger {
+        /// Index identifier or `all`
+        index_name: String,
+    },
+    /// `user.orders.{instrument_name}.{interval}`
+    UserOrders {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.orders.{kind}.{currency}.{interval}`
+    UserOrdersByKind {
+        /// Instrument kind
+        kind: ChannelKind,
+        /// Currency symbol or `any`
+        currency: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.portfolio.{currency}`
+    UserPortfolio {
+        /// Currency symbol or `any`
+        currency: String,
+    },
+    /// `user.trades.{instrument_name}.{interval}`
+    UserTrades {
+        /// Instrument name
+        instrument_name: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+    /// `user.trades.{kind}.{currency}.{interval}`
+    UserTradesByKind {
+        /// Instrument kind
+        kind: ChannelKind,
+        /// Currency symbol or `any`
+        currency: String,
+        /// Notification frequency
+        interval: ChannelInterval,
+    },
+}
+
+impl Channel {
+    /// Build a `book.{instrument_name}.{interval}` channel
+    #[must_use]
+    pub fn book(instrument_name: impl Into<String>, interval: ChannelInterval) -> Self {
+        Self::Book {
+            instrument_name: instrument_name.into(),
+            interval,
+        }
+    }
+
+    /// Build a validated `book.{instrument_name}.{group}.{depth}.{interval}` channel
+    pub fn grouped_book(
+        instrument_name: impl Into<String>,
+        group: BookGroup,
+        depth: u32,
+        interval: ChannelInterval,
+    ) -> Result<Self, ChannelError> {
+        Self::GroupedBook {
+            instrument_name: instrument_name.into(),
+            group,
+            depth,
+            interval,
+        }
+        .validated()
+    }
+
+    /// Build a `ticker.{instrument_name}.{interval}` channel
+    #[must_use]
+    pub fn ticker(instrument_name: impl Into<String>, interval: ChannelInterval) -> Self {
+        Self::Ticker {
+            instrument_name: instrument_name.into(),
+            interval,
+        }
+    }
+
+    /// Build a `trades.{instrument_name}.{interval}` channel
+    #[must_use]
+    pub fn trades(instrument_name: impl Into<String>, interval: ChannelInterval) -> Self {
+        Self::Trades {
+            instrument_name: instrument_name.into(),
+            interval,
+        }
+    }
+
+    /// Build a validated `trades.{kind}.{currency}.{interval}` channel
+    pub fn trades_by_kind(
+        kind: ChannelKind,
+        currency: impl Into<String>,
+        interval: ChannelInterval,
+    ) -> Result<Self, ChannelError> {
+        Self::TradesByKind {
+            kind,
+            currency: currency.into(),
+            interval,
+        }
+        .validated()
+    }
+
+    /// Build a `deribit_price_index.{index_name}` channel
+    #[must_use]
+    pub fn price_index(index_name: impl Into<String>) -> Self {
+        Self::PriceIndex {
+            index_name: index_name.into(),
+        }
+    }
+
+    /// Build a `user.orders.{instrument_name}.{interval}` channel
+    #[must_use]
+    pub fn user_orders(instrument_name: impl Into<String>, interval: ChannelInterval) -> Self {
+        Self::UserOrders {
+            instrument_name: instrument_name.into(),
+            interval,
+        }
+    }
+
+    /// Build a `user.trades.{instrument_name}.{interval}` channel
+    #[must_use]
+    pub fn user_trades(instrument_name: impl Into<String>, interval: ChannelInterval) -> Self {
+        Self::UserTrades {
+            instrument_name: instrument_name.into(),
+            interval,
+        }
+    }
+
+    /// Build a `user.changes.{kind}.{currency}.{interval}` channel
+    #[must_use]
+    pub fn user_changes_by_kind(
+        kind: ChannelKind,
+        currency: impl Into<String>,
+        interval: ChannelInterval,
+    ) -> Self {
+        Self::UserChangesByKind {
+            kind,
+            currency: currency.into(),
+            interval,
+        }
+    }
+
+    /// Build a `user.portfolio.{currency}` channel
+    #[must_use]
+    pub fn user_portfolio(currency: impl Into<String>) -> Self {
+        Self::UserPortfolio {
+            currency: currency.into(),
+        }
+    }
+
+    /// Check that every parameter is accepted by the channel
+    pub fn validate(&self) -> Result<(), ChannelError> {
+        match self {
+            Self::Announcements
+            | Self::PlatformState
+            | Self::PlatformStatePublicMethods
+            | Self::UserAccessLog
+            | Self::UserLock => Ok(()),
+            Self::BlockRfqMakerQuotes { currency }
+            | Self::BlockRfqMaker { currency }
+            | Self::BlockRfqTaker { currency }
+            | Self::BlockRfqTrades { currency }
+            | Self::Rfq { currency }
+            | Self::UserPortfolio { currency } => check_segment("currency", currency),
+            Self::BlockTradeConfirmations { currency } => match currency {
+                Some(currency) => check_segment("currency", currency),
+                None => Ok(()),
+            },
+            Self::PriceIndex { index_name }
+            | Self::PriceRanking { index_name }
+            | Self::PriceStatistics { index_name }
+            | Self::VolatilityIndex { index_name }
+            | Self::EstimatedExpirationPrice { index_name }
+            | Self::MarkPriceOptions { index_name }
+            | Self::UserMmpTrigger { index_name } => check_segment("index_name", index_name),
+            Self::IncrementalTicker { instrument_name } | Self::Quote { instrument_name } => {
+                check_segment("instrument_name", instrument_name)
+            }
+            Self::Book {
+                instrument_name, ..
+            }
+            | Self::Perpetual {
+                instrument_name, ..
+            }
+            | Self::Ticker {
+                instrument_name, ..
+            }
+            | Self::Trades {
+                instrument_name, ..
+            }
+            | Self::UserChanges {
+                instrument_name, ..
+            }
+            | Self::UserComboTrades {
+                instrument_name, ..
+            }
+            | Self::UserOrders {
+                instrument_name, ..
+            }
+            | Self::UserTrades {
+                instrument_name, ..
+            } => check_segment("instrument_name", instrument_name),
+            Self::GroupedBook {
+                instrument_name,
+                group,
+                depth,
+                interval,
+            } => {
+                check_segment("instrument_name", instrument_name)?;
+                if let BookGroup::Group(value) = group
+                    && !BOOK_GROUPS.contains(value)
+                {
+                    return Err(invalid("group", &value.to_string()));
+                }
+                if !BOOK_DEPTHS.contains(depth) {
+                    return Err(invalid("depth", &depth.to_string()));
+                }
+                if *interval == ChannelInterval::Raw {
+                    return Err(invalid("interval", interval.as_str()));
+                }
+                Ok(())
+            }
+            Self::ChartTrades {
+                instrument_name,
+                resolution,
+            } => {
+                check_segment("instrument_name", instrument_name)?;
+                match resolution {
+                    ChartResolution::Minutes(minutes)
+                        if !CHART_RESOLUTION_MINUTES.contains(minutes) =>
+                    {
+                        Err(invalid("resolution", &minutes.to_string()))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Self::InstrumentState { kind, currency } => {
+                check_kind(*kind, *kind != ChannelKind::Combo)?;
+                check_segment("currency", currency)
+            }
+            Self::TradesByKind { kind, currency, .. } => {
+                check_kind(
+                    *kind,
+                    !matches!(kind, ChannelKind::Combo | ChannelKind::Any),
+                )?;
+                check_segment("currency", currency)
+            }
+            Self::UserComboTradesByKind { kind, currency, .. } => {
+                check_kind(*kind, kind.is_combo())?;
+                check_segment("currency", currency)
+            }
+            Self::UserChangesByKind { currency, .. }
+            | Self::UserOrdersByKind { currency, .. }
+            | Self::UserTradesByKind { currency, .. } => check_segment("currency", currency),
+        }
+    }
+
+    /// Check if subscribing requires `private/subscribe`
+    #[must_use]
+    pub fn is_private(&self) -> bool {
+        matches!(
+            self,
+            Self::BlockRfqMakerQuotes { .. }
+                | Self::BlockRfqMaker { .. }
+                | Self::BlockRfqTaker { .. }
+                | Self::BlockTradeConfirmations { .. }
+                | Self::UserAccessLog
+                | Self::UserChanges { .. }
+                | Self::UserChangesByKind { .. }
+                | Self::UserComboTrades { .. }
+                | Self::UserComboTradesByKind { .. }
+                | Self::UserLock
+                | Self::UserMmpTrigger { .. }
+                | Self::UserOrders { .. }
+                | Self::UserOrdersByKind { .. }
+                | Self::UserPortfolio { .. }
+                | Self::UserTrades { .. }
+                | Self::UserTradesByKind { .. }
+        )
+    }
+
+    /// Check if subscribing requires an authenticated session
+    ///
+    /// Private channels and `raw` intervals are only available to
+    /// authorized users.
+    #[must_use]
+    pub fn requires_authentication(&self) -> bool {
+        self.is_private() || self.interval() == Some(ChannelInterval::Raw)
+    }
+
+    /// Get the notification frequency, if the channel has one
+    #[must_use]
+    pub fn interval(&self) -> Option<ChannelInterval> {
+        match self {
+            Self::Book { interval, .. }
+            | Self::GroupedBook { interval, .. }
+            | Self::Perpetual { interval, .. }
+            | Self::Ticker { interval, .. }
+            | Self::Trades { interval, .. }
+            | Self::TradesByKind { interval, .. }
+            | Self::UserChanges { interval, .. }
+            | Self::UserChangesByKind { interval, .. }
+            | Self::UserComboTrades { interval, .. }
+            | Self::UserComboTradesByKind { interval, .. }
+            | Self::UserOrders { interval, .. }
+            | Self::UserOrdersByKind { interval, .. }
+            | Self::UserTrades { interval, .. }
+            | Self::UserTradesByKind { interval, .. } => Some(*interval),
+            _ => None,
+        }
+    }
+
+    /// Get the instrument name, if the channel addresses one instrument
+    #[must_use]
+    pub fn instrument_name(&self) -> Option<&str> {
+        match self {
+            Self::Book {
+                instrument_name, ..
+            }
+            | Self::GroupedBook {
+                instrument_name, ..
+            }
+            | Self::ChartTrades {
+                instrument_name, ..
+            }
+            | Self::IncrementalTicker { instrument_name }
+            | Self::Perpetual {
+                instrument_name, ..
+            }
+            | Self::Quote { instrument_name }
+            | Self::Ticker {
+                instrument_name, ..
+            }
+            | Self::Trades {
+                instrument_name, ..
+            }
+            | Self::UserChanges {
+                instrument_name, ..
+            }
+            | Self::UserComboTrades {
+                instrument_name, ..
+            }
+            | Self::UserOrders {
+                instrument_name, ..
+            }
+            | Self::UserTrades {
+                instrument_name, ..
+            } => Some(instrument_name),
+            _ => None,
+        }
+    }
+
+    /// Build the channel string after checking its parameters
+    pub fn try_to_string(&self) -> Result<String, ChannelError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+
+    fn validated(self) -> Result<Self, ChannelError> {
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Announcements => write!(f, "announcements"),
+            Self::BlockRfqMakerQuotes { currency } => {
+                write!(f, "block_rfq.maker.quotes.{currency}")
+            }
+            Self::BlockRfqMaker { currency } => write!(f, "block_rfq.maker.{currency}"),
+            Self::BlockRfqTaker { currency } => write!(f, "block_rfq.taker.{currency}"),
+            Self::BlockRfqTrades { currency } => write!(f, "block_rfq.trades.{currency}"),
+            Self::BlockTradeConfirmations { currency: None } => {
+                write!(f, "block_trade_confirmations")
+            }
+            Self::BlockTradeConfirmations {
+                currency: Some(currency),
+            } => write!(f, "block_trade_confirmations.{currency}"),
+            Self::Book {
+                instrument_name,
+                interval,
+            } => write!(f, "{CHANNEL_BOOK}.{instrument_name}.{interval}"),
+            Self::GroupedBook {
+                instrument_name,
+                group,
+                depth,
+                interval,
+            } => write!(
+                f,
+                "{CHANNEL_BOOK}.{instrument_name}.{group}.{depth}.{interval}"
+            ),
+            Self::ChartTrades {
+                instrument_name,
+                resolution,
+            } => write!(f, "chart.trades.{instrument_name}.{resolution}"),
+            Self::PriceIndex { index_name } => write!(f, "deribit_price_index.{index_name}"),
+            Self::PriceRanking { index_name } => write!(f, "deribit_price_ranking.{index_name}"),
+            Self::PriceStatistics { index_name } => {
+                write!(f, "deribit_price_statistics.{index_name}")
+            }
+            Self::VolatilityIndex { index_name } => {
+                write!(f, "deribit_volatility_index.{index_name}")
+            }
+            Self::EstimatedExpirationPrice { index_name } => {
+                write!(f, "estimated_expiration_price.{index_name}")
+            }
+            Self::IncrementalTicker { instrument_name } => {
+                write!(f, "incremental_ticker.{instrument_name}")
+            }
+            Self::InstrumentState { kind, currency } => {
+                write!(f, "instrument.state.{kind}.{currency}")
+            }
+            Self::MarkPriceOptions { index_name } => write!(f, "markprice.options.{index_name}"),
+            Self::Perpetual {
+                instrument_name,
+                interval,
+            } => write!(f, "perpetual.{instrument_name}.{interval}"),
+            Self::PlatformState => write!(f, "platform_state"),
+            Self::PlatformStatePublicMethods => write!(f, "platform_state.public_methods_state"),
+            Self::Quote { instrument_name } => write!(f, "{CHANNEL_QUOTE}.{instrument_name}"),
+            Self::Rfq { currency } => write!(f, "rfq.{currency}"),
+            Self::Ticker {
+                instrument_name,
+                interval,
+            } => write!(f, "{CHANNEL_TICKER}.{instrument_name}.{interval}"),
+            Self::Trades {
+                instrument_name,
+                interval,
+            } => write!(f, "{CHANNEL_TRADES}.{instrument_name}.{interval}"),
+            Self::TradesByKind {
+                kind,
+                currency,
+                interval,
+            } => write!(f, "{CHANNEL_TRADES}.{kind}.{currency}.{interval}"),
+            Self::UserAccessLog => write!(f, "user.access_log"),
+            Self::UserChanges {
+                instrument_name,
+                interval,
+            } => write!(f, "user.changes.{instrument_name}.{interval}"),
+            Self::UserChangesByKind {
+                kind,
+                currency,
+                interval,
+            } => write!(f, "user.changes.{kind}.{currency}.{interval}"),
+            Self::UserComboTrades {
+                instrument_name,
+                interval,
+            } => write!(f, "user.combo_trades.{instrument_name}.{interval}"),
+            Self::UserComboTradesByKind {
+                kind,
+                currency,
+                interval,
+            } => write!(f, "user.combo_trades.{kind}.{currency}.{interval}"),
+            Self::UserLock => write!(f, "user.lock"),
+            Self::UserMmpTrigger { index_name } => write!(f, "user.mmp_trigger.{index_name}"),
+            Self::UserOrders {
+                instrument_name,
+                interval,
+            } => write!(f, "{CHANNEL_USER_ORDERS}.{instrument_name}.{interval}"),
+            Self::UserOrdersByKind {
+                kind,
+                currency,
+                interval,
+            } => write!(f, "{CHANNEL_USER_ORDERS}.{kind}.{currency}.{interval}"),
+            Self::UserPortfolio { currency } => write!(f, "{CHANNEL_USER_PORTFOLIO}.{currency}"),
+            Self::UserTrades {
+                instrument_name,
+                interval,
+            } => write!(f, "{CHANNEL_USER_TRADES}.{instrument_name}.{interval}"),
+            Self::UserTradesByKind {
+                kind,
+                currency,
+                interval,
+            } => write!(f, "{CHANNEL_USER_TRADES}.{kind}.{currency}.{interval}"),
+        }
+    }
+}
+
+impl FromStr for Channel {
+    type Err = ChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('.').collect();
+        let owned = |value: &str| value.to_string();
+        let channel = match parts.as_slice() {
+            ["announcements"] => Self::Announcements,
+            ["block_rfq", "maker", "quotes", currency] => Self::BlockRfqMakerQuotes {
+                currency: owned(currency),
+            },
+            ["block_rfq", "maker", currency] => Self::BlockRfqMaker {
+                currency: owned(currency),
+            },
+            ["block_rfq", "taker", currency] => Self::BlockRfqTaker {
+                currency: owned(currency),
+            },
+            ["block_rfq", "trades", currency] => Self::BlockRfqTrades {
+                currency: owned(currency),
+            },
+            ["block_trade_confirmations"] => Self::BlockTradeConfirmations { currency: None },
+            ["block_trade_confirmations", currency] => Self::BlockTradeConfirmations {
+                currency: Some(owned(currency)),
+            },
+            ["book", instrument_name, interval] => Self::Book {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["book", instrument_name, group, depth, interval] => Self::GroupedBook {
+                instrument_name: owned(instrument_name),
+                group: group.parse()?,
+                depth: depth.parse().map_err(|_| invalid("depth", depth))?,
+                interval: interval.parse()?,
+            },
+            ["chart", "trades", instrument_name, resolution] => Self::ChartTrades {
+                instrument_name: owned(instrument_name),
+                resolution: resolution.parse()?,
+            },
+            ["deribit_price_index", index_name] => Self::PriceIndex {
+                index_name: owned(index_name),
+            },
+            ["deribit_price_ranking", index_name] => Self::PriceRanking {
+                index_name: owned(index_name),
+            },
+            ["deribit_price_statistics", index_name] => Self::PriceStatistics {
+                index_name: owned(index_name),
+            },
+            ["deribit_volatility_index", index_name] => Self::VolatilityIndex {
+                index_name: owned(index_name),
+            },
+            ["estimated_expiration_price", index_name] => Self::EstimatedExpirationPrice {
+                index_name: owned(index_name),
+            },
+            ["incremental_ticker", instrument_name] => Self::IncrementalTicker {
+                instrument_name: owned(instrument_name),
+            },
+            ["instrument", "state", kind, currency] => Self::InstrumentState {
+                kind: kind.parse()?,
+                currency: owned(currency),
+            },
+            ["markprice", "options", index_name] => Self::MarkPriceOptions {
+                index_name: owned(index_name),
+            },
+            ["perpetual", instrument_name, interval] => Self::Perpetual {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["platform_state"] => Self::PlatformState,
+            ["platform_state", "public_methods_state"] => Self::PlatformStatePublicMethods,
+            ["quote", instrument_name] => Self::Quote {
+                instrument_name: owned(instrument_name),
+            },
+            ["rfq", currency] => Self::Rfq {
+                currency: owned(currency),
+            },
+            ["ticker", instrument_name, interval] => Self::Ticker {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["trades", instrument_name, interval] => Self::Trades {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["trades", kind, currency, interval] => Self::TradesByKind {
+                kind: kind.parse()?,
+                currency: owned(currency),
+                interval: interval.parse()?,
+            },
+            ["user", "access_log"] => Self::UserAccessLog,
+            ["user", "changes", instrument_name, interval] => Self::UserChanges {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["user", "changes", kind, currency, interval] => Self::UserChangesByKind {
+                kind: kind.parse()?,
+                currency: owned(currency),
+                interval: interval.parse()?,
+            },
+            ["user", "combo_trades", instrument_name, interval] => Self::UserComboTrades {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["user", "combo_trades", kind, currency, interval] => Self::UserComboTradesByKind {
+                kind: kind.parse()?,
+                currency: owned(currency),
+                interval: interval.parse()?,
+            },
+            ["user", "lock"] => Self::UserLock,
+            ["user", "mmp_trigger", index_name] => Self::UserMmpTrigger {
+                index_name: owned(index_name),
+            },
+            ["user", "orders", instrument_name, interval] => Self::UserOrders {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["user", "orders", kind, currency, interval] => Self::UserOrdersByKind {
+                kind: kind.parse()?,
+                currency: owned(currency),
+                interval: interval.parse()?,
+            },
+            ["user", "portfolio", currency] => Self::UserPortfolio {
+                currency: owned(currency),
+            },
+            ["user", "trades", instrument_name, interval] => Self::UserTrades {
+                instrument_name: owned(instrument_name),
+                interval: interval.parse()?,
+            },
+            ["user", "trades", kind, currency, interval] => Self::UserTradesByKind {
+                kind: kind.parse()?,
+                currency: owned(currency),
+                interval: interval.parse()?,
+            },
+            _ => return Err(ChannelError::UnknownChannel(s.to_string())),
+        };
+        channel.validated()
+    }
+}
+
+impl serde::Serialize for Channel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Channel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Build an invalid parameter error
+fn invalid(name: &'static str, value: &str) -> ChannelError {
+    ChannelError::InvalidParameter {
+        name,
+        value: value.to_string(),
+    }
+}
+
+/// Check that a free-form parameter is a single non-empty channel segment
+fn check_segment(name: &'static str, value: &str) -> Result<(), ChannelError> {
+    if value.is_empty() || value.contains('.') {
+        return Err(invalid(name, value));
+    }
+    Ok(())
+}
+
+/// Reject a kind the channel does not accept
+fn check_kind(kind: ChannelKind, accepted: bool) -> Result<(), ChannelError> {
+    if accepted {
+        Ok(())
+    } else {
+        Err(invalid("kind", kind.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documented_channels() -> Vec<String> {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/doc/DeribitAPI.md");
+        let doc = std::fs::read_to_string(path).unwrap();
+        let start = doc.find("\n## Subscriptions").unwrap();
+        let end = doc.find("\n## RPC Error Codes").unwrap();
+        doc[start..end]
+            .lines()
+            .filter_map(|line| line.strip_prefix("## "))
+            .filter(|name| *name != "Subscriptions")
+            .map(|name| name.trim().replace("\\_", "_"))
+            .collect()
+    }
+
+    /// Fill a documented channel template with sample values
+    fn sample(template: &str) -> String {
+        template
+            .replace("{instrument_name}", "BTC-PERPETUAL")
+            .replace("{index_name}", "btc_usd")
+            .replace("{currency}", "BTC")
+            .replace("{kind}", "future_combo")
+            .replace("{group}", "5")
+            .replace("{depth}", "10")
+            .replace("{resolution}", "60")
+            .replace("{interval}", "100ms")
+    }
+
+    #[test]
+    fn test_every_documented_channel_round_trips() {
+        let channels = documented_channels();
+        assert!(channels.len() >= 40);
+        for template in channels {
+            let name = sample(&template);
+            let channel: Channel = name
+                .parse()
+                .unwrap_or_else(|err| panic!("{template}: {err}"));
+            assert_eq!(channel.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_build_channels() {
+        assert_eq!(
+            Channel::book("BTC-PERPETUAL", ChannelInterval::Raw).to_string(),
+            "book.BTC-PERPETUAL.raw"
+        );
+        assert_eq!(
+            Channel::grouped_book("ETH-PERPETUAL", BookGroup::None, 20, ChannelInterval::Agg2)
+                .unwrap()
+                .to_string(),
+            "book.ETH-PERPETUAL.none.20.agg2"
+        );
+        assert_eq!(
+            Channel::ticker("BTC-PERPETUAL", ChannelInterval::Ms100).to_string(),
+            "ticker.BTC-PERPETUAL.100ms"
+        );
+        assert_eq!(
+            Channel::user_changes_by_kind(ChannelKind::Any, "any", ChannelInterval::Raw)
+                .to_string(),
+            "user.changes.any.any.raw"
+        );
+        assert_eq!(
+            Channel::price_index("btc_usd").to_string(),
+            "deribit_price_index.btc_usd"
+        );
+        assert_eq!(
+            Channel::user_portfolio("ETH").to_string(),
+            "user.portfolio.ETH"
+        );
+    }
+
+    #[test]
+    fn test_invalid_combinations_rejected() {
+        assert!(
+            Channel::grouped_book(
+                "BTC-PERPETUAL",
+                BookGroup::Group(5),
+                10,
+                ChannelInterval::Raw
+            )
+            .is_err()
+        );
+        assert!(
+            Channel::grouped_book(
+                "BTC-PERPETUAL",
+                BookGroup::Group(3),
+                10,
+                ChannelInterval::Agg2
+            )
+            .is_err()
+        );
+        assert!(
+            Channel::grouped_book(
+                "BTC-PERPETUAL",
+                BookGroup::Group(5),
+                5,
+                ChannelInterval::Agg2
+            )
+            .is_err()
+        );
+        assert!(Channel::trades_by_kind(ChannelKind::Any, "BTC", ChannelInterval::Raw).is_err());
+        assert!(
+            "user.combo_trades.future.BTC.raw"
+                .parse::<Channel>()
+                .is_err()
+        );
+        assert!("instrument.state.combo.BTC".parse::<Channel>().is_err());
+        assert!("chart.trades.BTC-PERPETUAL.7".parse::<Channel>().is_err());
+        assert!("ticker.BTC-PERPETUAL.1s".parse::<Channel>().is_err());
+        assert_eq!(
+            "book.BTC-PERPETUAL.5.10.raw".parse::<Channel>(),
+            Err(ChannelError::InvalidParameter {
+                name: "interval",
+                value: "raw".to_string()
+            })
+        );
+        assert_eq!(
+            "unknown.channel".parse::<Channel>(),
+            Err(ChannelError::UnknownChannel("unknown.channel".to_string()))
+        );
+        assert!(Channel::book("", ChannelInterval::Raw).validate().is_err());
+    }
+
+    #[test]
+    fn test_try_to_string_checks_public_variants() {
+        let invalid = Channel::GroupedBook {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            group: BookGroup::Group(5),
+            depth: 10,
+            interval: ChannelInterval::Raw,
+        };
+        assert_eq!(
+            invalid.try_to_string(),
+            Err(ChannelError::InvalidParameter {
+                name: "interval",
+                value: "raw".to_string()
+            })
+        );
+        assert_eq!(
+            Channel::ticker("BTC-PERPETUAL", ChannelInterval::Ms100).try_to_string(),
+            Ok("ticker.BTC-PERPETUAL.100ms".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_disambiguates_by_segment_count() {
+        assert!(matches!(
+            "trades.BTC-PERPETUAL.raw".parse::<Channel>(),
+            Ok(Channel::Trades { .. })
+        ));
+        assert!(matches!(
+            "trades.option.ETH.100ms".parse::<Channel>(),
+            Ok(Channel::TradesByKind {
+                kind: ChannelKind::Option,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "user.orders.BTC-PERPETUAL.raw".parse::<Channel>(),
+            Ok(Channel::UserOrders {
+                interval: ChannelInterval::Raw,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "block_rfq.maker.quotes.any".parse::<Channel>(),
+            Ok(Channel::BlockRfqMakerQuotes { .. })
+        ));
+        assert!(matches!(
+            "chart.trades.BTC-PERPETUAL.1D".parse::<Channel>(),
+            Ok(Channel::ChartTrades {
+                resolution: ChartResolution::Day,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_channel_properties() {
+        let ticker = Channel::ticker("BTC-PERPETUAL", ChannelInterval::Raw);
+        assert!(!ticker.is_private());
+        assert!(ticker.requires_authentication());
+        assert_eq!(ticker.instrument_name(), Some("BTC-PERPETUAL"));
+        assert_eq!(ticker.interval(), Some(ChannelInterval::Raw));
+
+        let portfolio = Channel::user_portfolio("BTC");
+        assert!(portfolio.is_private());
+        assert_eq!(portfolio.interval(), None);
+        assert!(!Channel::price_index("btc_usd").requires_authentication());
+    }
+
+    #[test]
+    fn test_channel_serde() {
+        let channel = Channel::trades("BTC-PERPETUAL", ChannelInterval::Agg2);
+        let json = serde_json::to_string(&channel).unwrap();
+        assert_eq!(json, "\"trades.BTC-PERPETUAL.agg2\"");
+        let back: Channel = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, channel);
+        assert!(serde_json::from_str::<Channel>("\"book.X.5.10.raw\"").is_err());
+    }
+}
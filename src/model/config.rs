@@ -3,6 +3,7 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::risk::RiskConfig;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
@@ -20,9 +21,14 @@ pub struct DeribitConfig {
     /// Maximum number of retries
     pub max_retries: u32,
     /// Rate limit per second
+    ///
+    /// See `RateLimitConfig::from_requests_per_second` for the credit-based equivalent.
     pub rate_limit: Option<u32>,
     /// User agent string
     pub user_agent: Option<String>,
+    /// Pre-trade risk limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskConfig>,
 }
 
 impl DeribitConfig {
@@ -36,6 +42,7 @@ impl DeribitConfig {
             max_retries: 3,
             rate_limit: None,
             user_agent: None,
+            risk: None,
         }
     }
 
@@ -49,6 +56,7 @@ impl DeribitConfig {
             max_retries: 3,
             rate_limit: None,
             user_agent: None,
+            risk: None,
         }
     }
 
@@ -76,6 +84,12 @@ impl DeribitConfig {
         self
     }
 
+    /// Set pre-trade risk limits
+    pub fn with_risk(mut self, risk: RiskConfig) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
     /// Get the base URL for HTTP API
     pub fn base_url(&self) -> &'static str {
         if self.test_net {
@@ -110,6 +124,7 @@ impl Default for DeribitConfig {
             max_retries: 3,
             rate_limit: None,
             user_agent: Some("deribit-rust-client/1.0".to_string()),
+            risk: None,
         }
     }
 }
@@ -260,6 +275,35 @@ mod tests {
         assert_eq!(config.user_agent, None);
     }
 
+    #[test]
+    fn test_deribit_config_with_risk() {
+        let json = r#"{
+            "client_id": "id",
+            "client_secret": "secret",
+            "test_net": true,
+            "timeout_seconds": 30,
+            "max_retries": 3,
+            "rate_limit": null,
+            "user_agent": null,
+            "risk": {"account": {"max_open_orders": 10}}
+        }"#;
+        let config: DeribitConfig = serde_json::from_str(json).unwrap();
+        let risk = config.risk.unwrap();
+        assert_eq!(risk.account.max_open_orders, Some(10));
+
+        let without_risk = DeribitConfig::default();
+        let json = serde_json::to_string(&without_risk).unwrap();
+        assert!(!json.contains("risk"));
+        let restored: DeribitConfig = serde_json::from_str(&json).unwrap();
+        assert!(restored.risk.is_none());
+        assert!(
+            DeribitConfig::default()
+                .with_risk(RiskConfig::new())
+                .risk
+                .is_some()
+        );
+    }
+
     #[test]
     fn test_deribit_config_testnet() {
         let config = DeribitConfig::testnet("client123".to_string(), "secret456".to_string());
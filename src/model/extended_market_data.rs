@@ -152,11 +152,17 @@ impl CurrencyInfo {
 }
 
 /// Index price information
+///
+/// Also the payload of the `deribit_price_index` channel, which sends no
+/// estimated delivery price. The index price is serialized as `price`, as
+/// the channel sends it.
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IndexPrice {
     /// Estimated delivery price
-    pub estimated_delivery_price: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_delivery_price: Option<f64>,
     /// Current index price
+    #[serde(rename = "price", alias = "index_price")]
     pub index_price: f64,
     /// Timestamp (milliseconds since Unix epoch)
     pub timestamp: i64,
@@ -169,7 +175,7 @@ impl IndexPrice {
     /// Create new index price
     pub fn new(estimated_delivery_price: f64, index_price: f64, timestamp: i64) -> Self {
         Self {
-            estimated_delivery_price,
+            estimated_delivery_price: Some(estimated_delivery_price),
             index_price,
             timestamp,
             index_name: None,
@@ -182,17 +188,19 @@ impl IndexPrice {
         self
     }
 
-    /// Get price difference
-    pub fn price_difference(&self) -> f64 {
-        self.estimated_delivery_price - self.index_price
+    /// Get price difference, if the estimated delivery price is known
+    pub fn price_difference(&self) -> Option<f64> {
+        self.estimated_delivery_price
+            .map(|estimated| estimated - self.index_price)
     }
 
-    /// Get price difference percentage
-    pub fn price_difference_percentage(&self) -> f64 {
+    /// Get price difference percentage, if the estimated delivery price is known
+    pub fn price_difference_percentage(&self) -> Option<f64> {
+        let difference = self.price_difference()?;
         if self.index_price != 0.0 {
-            (self.price_difference() / self.index_price) * 100.0
+            Some((difference / self.index_price) * 100.0)
         } else {
-            0.0
+            Some(0.0)
         }
     }
 }
@@ -475,8 +483,8 @@ mod tests {
         let index =
             IndexPrice::new(45000.0, 44950.0, 1640995200000).with_name("BTC-USD".to_string());
 
-        assert_eq!(index.price_difference(), 50.0);
-        assert!((index.price_difference_percentage() - 0.1112).abs() < 0.001);
+        assert_eq!(index.price_difference(), Some(50.0));
+        assert!((index.price_difference_percentage().unwrap() - 0.1112).abs() < 0.001);
     }
 
     #[test]
@@ -3,6 +3,7 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::amount::{AmountError, AmountUnit, OrderSize};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
@@ -44,6 +45,18 @@ pub enum InstrumentType {
     Reversed,
 }
 
+/// Tick size step
+///
+/// Some instruments (mostly options) use a larger tick size once the
+/// price rises above a threshold.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TickSizeStep {
+    /// The price from which the increased tick size applies
+    pub above_price: f64,
+    /// Tick size applicable above `above_price`
+    pub tick_size: f64,
+}
+
 /// Instrument information
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct Instrument {
@@ -70,6 +83,9 @@ pub struct Instrument {
     /// Minimum price movement
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tick_size: Option<f64>,
+    /// Increased tick sizes that apply above given price levels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_size_steps: Option<Vec<TickSizeStep>>,
     /// Minimum trade amount
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_trade_amount: Option<f64>,
@@ -100,6 +116,21 @@ pub struct Instrument {
     /// Counter currency for the instrument
     #[serde(skip_serializing_if = "Option::is_none")]
     pub counter_currency: Option<String>,
+    /// Block trade commission rate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_trade_commission: Option<f64>,
+    /// Minimum amount of a block trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_trade_min_trade_amount: Option<f64>,
+    /// Tick size of a block trade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_trade_tick_size: Option<f64>,
+    /// Maximum liquidation commission rate (futures only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_liquidation_commission: Option<f64>,
+    /// Whether the instrument can be traded through RFQs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rfq: Option<bool>,
 }
 
 impl Instrument {
@@ -132,20 +163,240 @@ impl Instrument {
             .as_ref()
             .is_some_and(|k| matches!(k, InstrumentKind::Spot))
     }
+
+    /// Get the tick size that applies at the given price
+    ///
+    /// Uses the step with the highest `above_price` below `price`,
+    /// falling back to the base `tick_size`.
+    pub fn tick_size_for_price(&self, price: f64) -> Option<f64> {
+        let base = self.tick_size?;
+        let stepped = self
+            .tick_size_steps
+            .iter()
+            .flatten()
+            .filter(|step| price > step.above_price)
+            .max_by(|a, b| a.above_price.total_cmp(&b.above_price))
+            .map(|step| step.tick_size);
+        Some(stepped.unwrap_or(base))
+    }
+
+    /// Round a price down to the nearest valid tick
+    ///
+    /// Returns the price unchanged when the tick size is unknown.
+    pub fn floor_to_tick(&self, price: f64) -> f64 {
+        match self.tick_size_for_price(price) {
+            Some(tick) if tick > 0.0 => snap_to_tick(price, tick, f64::floor),
+            _ => price,
+        }
+    }
+
+    /// Round a price up to the nearest valid tick
+    ///
+    /// Returns the price unchanged when the tick size is unknown.
+    pub fn ceil_to_tick(&self, price: f64) -> f64 {
+        match self.tick_size_for_price(price) {
+            Some(tick) if tick > 0.0 => snap_to_tick(price, tick, f64::ceil),
+            _ => price,
+        }
+    }
+
+    /// Check if a price lies on a valid tick
+    pub fn is_price_on_tick(&self, price: f64) -> bool {
+        (self.floor_to_tick(price) - price).abs() < TICK_EPSILON
+    }
+
+    /// Check if the instrument is an inverse (USD-margined amount) future or perpetual
+    ///
+    /// Falls back to the quote currency when `instrument_type` is missing.
+    pub fn is_inverse(&self) -> bool {
+        if !self.is_future() {
+            return false;
+        }
+        match &self.instrument_type {
+            Some(InstrumentType::Reversed) => true,
+            Some(InstrumentType::Linear) => false,
+            None => self.quote_currency.as_deref() == Some("USD"),
+        }
+    }
+
+    /// Get the unit of the `amount` field for this instrument
+    pub fn amount_unit(&self) -> AmountUnit {
+        if self.is_inverse() {
+            AmountUnit::Usd
+        } else {
+            AmountUnit::BaseCurrency
+        }
+    }
+
+    /// Convert a number of contracts into the native amount
+    pub fn contracts_to_amount(&self, contracts: f64) -> Result<f64, AmountError> {
+        Ok(contracts * self.require_contract_size()?)
+    }
+
+    /// Convert a native amount into a number of contracts
+    pub fn amount_to_contracts(&self, amount: f64) -> Result<f64, AmountError> {
+        Ok(amount / self.require_contract_size()?)
+    }
+
+    /// Convert a native amount into base currency
+    ///
+    /// `price` is the USD price of one unit of base currency: the contract
+    /// price for futures, the underlying or index price for options.
+    pub fn amount_to_coin(&self, amount: f64, price: f64) -> Result<f64, AmountError> {
+        match self.amount_unit() {
+            AmountUnit::Usd => Ok(amount / validate_price(price)?),
+            AmountUnit::BaseCurrency => Ok(amount),
+        }
+    }
+
+    /// Convert a native amount into a USD notional
+    ///
+    /// See [`amount_to_coin`](Self::amount_to_coin) for the meaning of `price`.
+    pub fn amount_to_usd(&self, amount: f64, price: f64) -> Result<f64, AmountError> {
+        match self.amount_unit() {
+            AmountUnit::Usd => Ok(amount),
+            AmountUnit::BaseCurrency => Ok(amount * validate_price(price)?),
+        }
+    }
+
+    /// Convert a base currency quantity into the native amount (unrounded)
+    pub fn coin_to_amount(&self, coin: f64, price: f64) -> Result<f64, AmountError> {
+        match self.amount_unit() {
+            AmountUnit::Usd => Ok(coin * validate_price(price)?),
+            AmountUnit::BaseCurrency => Ok(coin),
+        }
+    }
+
+    /// Convert a USD notional into the native amount (unrounded)
+    pub fn usd_to_amount(&self, usd: f64, price: f64) -> Result<f64, AmountError> {
+        match self.amount_unit() {
+            AmountUnit::Usd => Ok(usd),
+            AmountUnit::BaseCurrency => Ok(usd / validate_price(price)?),
+        }
+    }
+
+    /// Get the increment valid amounts must be a multiple of
+    pub fn amount_step(&self) -> Option<f64> {
+        self.min_trade_amount
+            .or(self.contract_size)
+            .filter(|step| *step > 0.0)
+    }
+
+    /// Round a native amount down to a valid amount step
+    ///
+    /// Returns the amount unchanged when the step is unknown.
+    pub fn round_amount(&self, amount: f64) -> f64 {
+        match self.amount_step() {
+            Some(step) => snap_to_tick(amount, step, f64::floor),
+            None => amount,
+        }
+    }
+
+    /// Convert an order size in any unit into a valid native order amount
+    ///
+    /// The result is rounded down to the amount step and checked against
+    /// the minimum trade amount. See [`amount_to_coin`](Self::amount_to_coin)
+    /// for the meaning of `price`; it is ignored for contract and native sizes.
+    pub fn order_amount(&self, size: OrderSize, price: f64) -> Result<f64, AmountError> {
+        let raw = match size {
+            OrderSize::Contracts(contracts) => {
+                self.contracts_to_amount(validate_size(contracts)?)?
+            }
+            OrderSize::Coin(coin) => self.coin_to_amount(validate_size(coin)?, price)?,
+            OrderSize::Usd(usd) => self.usd_to_amount(validate_size(usd)?, price)?,
+            OrderSize::Amount(amount) => validate_size(amount)?,
+        };
+        let amount = self.round_amount(raw);
+        if let Some(min_trade_amount) = self.min_trade_amount
+            && amount + TICK_EPSILON < min_trade_amount
+        {
+            return Err(AmountError::BelowMinimum {
+                amount,
+                min_trade_amount,
+            });
+        }
+        if amount <= 0.0 {
+            return Err(AmountError::InvalidSize(amount));
+        }
+        Ok(amount)
+    }
+
+    /// Get the contract size or fail with a descriptive error
+    fn require_contract_size(&self) -> Result<f64, AmountError> {
+        self.contract_size
+            .filter(|size| *size > 0.0)
+            .ok_or_else(|| AmountError::MissingContractSize(self.instrument_name.clone()))
+    }
+}
+
+/// Check that a reference price can be used for conversions
+fn validate_price(price: f64) -> Result<f64, AmountError> {
+    if price.is_finite() && price > 0.0 {
+        Ok(price)
+    } else {
+        Err(AmountError::InvalidPrice(price))
+    }
+}
+
+/// Check that an order size is a positive finite number
+fn validate_size(size: f64) -> Result<f64, AmountError> {
+    if size.is_finite() && size > 0.0 {
+        Ok(size)
+    } else {
+        Err(AmountError::InvalidSize(size))
+    }
+}
+
+/// Tolerance used when comparing prices against tick multiples
+const TICK_EPSILON: f64 = 1e-9;
+
+/// Snap `value` to a multiple of `tick` using the given rounding function,
+/// removing floating point noise from the result.
+fn snap_to_tick(value: f64, tick: f64, round: fn(f64) -> f64) -> f64 {
+    let ratio = value / tick;
+    let nearest = ratio.round();
+    let steps = if (ratio - nearest).abs() < TICK_EPSILON {
+        nearest
+    } else {
+        round(ratio)
+    };
+    let mut decimals = 0;
+    while decimals < 12 && ((tick * 10f64.powi(decimals)).fract()).abs() > TICK_EPSILON {
+        decimals += 1;
+    }
+    let scale = 10f64.powi(decimals);
+    (steps * tick * scale).round() / scale
 }
 
 /// Index data
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct IndexData {
     /// BTC component (optional)
+    #[serde(rename = "BTC", alias = "btc", skip_serializing_if = "Option::is_none")]
     pub btc: Option<f64>,
     /// ETH component (optional)
+    #[serde(rename = "ETH", alias = "eth", skip_serializing_if = "Option::is_none")]
     pub eth: Option<f64>,
     /// USDC component (optional)
+    #[serde(
+        rename = "USDC",
+        alias = "usdc",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub usdc: Option<f64>,
     /// USDT component (optional)
+    #[serde(
+        rename = "USDT",
+        alias = "usdt",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub usdt: Option<f64>,
     /// EURR component (optional)
+    #[serde(
+        rename = "EURR",
+        alias = "eurr",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub eurr: Option<f64>,
     /// EDP (Estimated Delivery Price)
     pub edp: f64,
@@ -175,6 +426,7 @@ mod tests {
             strike: None,
             option_type: None,
             tick_size: Some(0.5),
+            tick_size_steps: None,
             min_trade_amount: Some(10.0),
             contract_size: Some(1.0),
             settlement_period: Some("perpetual".to_string()),
@@ -188,6 +440,11 @@ mod tests {
             instrument_id: Some(12345),
             base_currency: Some("BTC".to_string()),
             counter_currency: Some("USD".to_string()),
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
         }
     }
 
@@ -202,6 +459,16 @@ mod tests {
             strike: Some(50000.0),
             option_type: Some(OptionType::Call),
             tick_size: Some(0.0005),
+            tick_size_steps: Some(vec![
+                TickSizeStep {
+                    above_price: 0.005,
+                    tick_size: 0.001,
+                },
+                TickSizeStep {
+                    above_price: 0.2,
+                    tick_size: 0.005,
+                },
+            ]),
             min_trade_amount: Some(0.1),
             contract_size: Some(1.0),
             settlement_period: Some("week".to_string()),
@@ -215,6 +482,11 @@ mod tests {
             instrument_id: Some(67890),
             base_currency: Some("BTC".to_string()),
             counter_currency: Some("USD".to_string()),
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
         }
     }
 
@@ -270,6 +542,123 @@ mod tests {
         assert!(!option.is_spot());
     }
 
+    #[test]
+    fn test_tick_size_for_price() {
+        let option = create_test_option();
+        assert_eq!(option.tick_size_for_price(0.004), Some(0.0005));
+        assert_eq!(option.tick_size_for_price(0.05), Some(0.001));
+        assert_eq!(option.tick_size_for_price(0.3), Some(0.005));
+
+        let mut no_tick = create_test_instrument();
+        no_tick.tick_size = None;
+        assert_eq!(no_tick.tick_size_for_price(100.0), None);
+        assert_eq!(no_tick.floor_to_tick(100.3), 100.3);
+    }
+
+    #[test]
+    fn test_round_to_tick() {
+        let perpetual = create_test_instrument();
+        assert_eq!(perpetual.floor_to_tick(50000.7), 50000.5);
+        assert_eq!(perpetual.ceil_to_tick(50000.2), 50000.5);
+        assert_eq!(perpetual.ceil_to_tick(50000.5), 50000.5);
+        assert!(perpetual.is_price_on_tick(50000.5));
+        assert!(!perpetual.is_price_on_tick(50000.3));
+
+        let option = create_test_option();
+        assert_eq!(option.floor_to_tick(0.0037), 0.0035);
+        assert_eq!(option.ceil_to_tick(0.0531), 0.054);
+        assert_eq!(option.floor_to_tick(0.2533), 0.25);
+        assert!(option.is_price_on_tick(0.0035));
+    }
+
+    #[test]
+    fn test_amount_unit() {
+        let mut inverse = create_test_instrument();
+        inverse.instrument_type = Some(InstrumentType::Reversed);
+        assert!(inverse.is_inverse());
+        assert_eq!(inverse.amount_unit(), AmountUnit::Usd);
+
+        let linear = create_test_instrument();
+        assert_eq!(linear.amount_unit(), AmountUnit::BaseCurrency);
+
+        let mut unknown = create_test_instrument();
+        unknown.instrument_type = None;
+        assert!(unknown.is_inverse());
+
+        let mut option = create_test_option();
+        option.instrument_type = Some(InstrumentType::Reversed);
+        assert!(!option.is_inverse());
+        assert_eq!(option.amount_unit(), AmountUnit::BaseCurrency);
+    }
+
+    #[test]
+    fn test_inverse_amount_conversions() {
+        let mut perpetual = create_test_instrument();
+        perpetual.instrument_type = Some(InstrumentType::Reversed);
+        perpetual.contract_size = Some(10.0);
+
+        assert_eq!(perpetual.contracts_to_amount(3.0).unwrap(), 30.0);
+        assert_eq!(perpetual.amount_to_contracts(30.0).unwrap(), 3.0);
+        assert_eq!(perpetual.amount_to_coin(25000.0, 50000.0).unwrap(), 0.5);
+        assert_eq!(perpetual.amount_to_usd(25000.0, 50000.0).unwrap(), 25000.0);
+        assert_eq!(perpetual.coin_to_amount(0.5, 50000.0).unwrap(), 25000.0);
+        assert_eq!(
+            perpetual.amount_to_coin(10.0, 0.0),
+            Err(AmountError::InvalidPrice(0.0))
+        );
+
+        assert_eq!(
+            perpetual
+                .order_amount(OrderSize::Coin(0.5), 50000.0)
+                .unwrap(),
+            25000.0
+        );
+        assert_eq!(
+            perpetual
+                .order_amount(OrderSize::Usd(1234.0), 50000.0)
+                .unwrap(),
+            1230.0
+        );
+        assert_eq!(
+            perpetual
+                .order_amount(OrderSize::Contracts(2.0), 0.0)
+                .unwrap(),
+            20.0
+        );
+        assert!(matches!(
+            perpetual.order_amount(OrderSize::Usd(5.0), 50000.0),
+            Err(AmountError::BelowMinimum { .. })
+        ));
+    }
+
+    #[test]
+    fn test_option_amount_conversions() {
+        let option = create_test_option();
+        assert_eq!(option.amount_to_coin(1.5, 60000.0).unwrap(), 1.5);
+        assert_eq!(option.amount_to_usd(1.5, 60000.0).unwrap(), 90000.0);
+        assert_eq!(
+            option.order_amount(OrderSize::Coin(0.57), 60000.0).unwrap(),
+            0.5
+        );
+        assert_eq!(
+            option
+                .order_amount(OrderSize::Usd(30000.0), 60000.0)
+                .unwrap(),
+            0.5
+        );
+        assert_eq!(
+            option.order_amount(OrderSize::Coin(-1.0), 60000.0),
+            Err(AmountError::InvalidSize(-1.0))
+        );
+
+        let mut no_contract_size = create_test_option();
+        no_contract_size.contract_size = None;
+        assert!(matches!(
+            no_contract_size.amount_to_contracts(1.0),
+            Err(AmountError::MissingContractSize(_))
+        ));
+    }
+
     #[test]
     fn test_instrument_kind_serialization() {
         assert_eq!(
@@ -0,0 +1,281 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Structured order labels
+//!
+//! Deribit echoes the order `label` on every order update and trade, which
+//! makes it the natural place to carry routing metadata. [`OrderLabel`]
+//! packs a strategy id, a leg index and a sequence number into a label of
+//! at most [`MAX_LABEL_LENGTH`] characters and parses it back, so fills can
+//! be routed to their strategy without an external lookup table.
+//!
+//! The encoded form is `<strategy_id>:<leg>:<sequence>`, with leg and
+//! sequence written in lowercase base 36, e.g. `mm-btc:1:lx3k9q0a`.
+
+use crate::utils::ids::next_monotonic_id;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Maximum label length accepted by Deribit
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+/// Separator between label components
+pub const LABEL_SEPARATOR: char = ':';
+
+/// Maximum strategy id length, chosen so that any leg and sequence fit
+///
+/// A `u16` leg needs up to 4 base 36 digits and a `u64` sequence up to 13,
+/// plus two separators.
+pub const MAX_STRATEGY_ID_LENGTH: usize = MAX_LABEL_LENGTH - 4 - 13 - 2;
+
+/// Error raised while encoding or decoding an [`OrderLabel`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelError {
+    /// Strategy id is empty
+    EmptyStrategyId,
+    /// Strategy id exceeds [`MAX_STRATEGY_ID_LENGTH`]
+    StrategyIdTooLong(usize),
+    /// Strategy id contains a character other than ASCII alphanumerics, `-` or `_`
+    InvalidStrategyId(String),
+    /// Label does not have the `<strategy>:<leg>:<sequence>` shape
+    Malformed(String),
+    /// Leg index is not valid base 36 or does not fit in a `u16`
+    InvalidLeg(String),
+    /// Sequence is not valid base 36 or does not fit in a `u64`
+    InvalidSequence(String),
+}
+
+impl std::fmt::Display for LabelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyStrategyId => write!(f, "strategy id must not be empty"),
+            Self::StrategyIdTooLong(len) => write!(
+                f,
+                "strategy id has {len} characters, maximum is {MAX_STRATEGY_ID_LENGTH}"
+            ),
+            Self::InvalidStrategyId(id) => write!(f, "invalid strategy id: '{id}'"),
+            Self::Malformed(label) => write!(f, "malformed order label: '{label}'"),
+            Self::InvalidLeg(leg) => write!(f, "invalid leg index: '{leg}'"),
+            Self::InvalidSequence(seq) => write!(f, "invalid sequence: '{seq}'"),
+        }
+    }
+}
+
+impl std::error::Error for LabelError {}
+
+/// Order label carrying strategy routing metadata
+#[derive(
+    DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct OrderLabel {
+    /// Identifier of the strategy owning the order
+    pub strategy_id: String,
+    /// Index of the leg within the strategy
+    pub leg: u16,
+    /// Sequence number, unique per strategy
+    pub sequence: u64,
+}
+
+impl OrderLabel {
+    /// Create a new label, validating the strategy id
+    pub fn new(strategy_id: String, leg: u16, sequence: u64) -> Result<Self, LabelError> {
+        validate_strategy_id(&strategy_id)?;
+        Ok(Self {
+            strategy_id,
+            leg,
+            sequence,
+        })
+    }
+
+    /// Create a label with the next process-wide monotonic sequence number
+    pub fn generate(strategy_id: String, leg: u16) -> Result<Self, LabelError> {
+        Self::new(strategy_id, leg, next_monotonic_id())
+    }
+
+    /// Create a label for another leg with the same strategy and sequence
+    #[must_use]
+    pub fn for_leg(&self, leg: u16) -> Self {
+        Self {
+            strategy_id: self.strategy_id.clone(),
+            leg,
+            sequence: self.sequence,
+        }
+    }
+
+    /// Encode the label into the string sent to Deribit
+    #[must_use]
+    pub fn encode(&self) -> String {
+        format!(
+            "{}{LABEL_SEPARATOR}{}{LABEL_SEPARATOR}{}",
+            self.strategy_id,
+            to_base36(u64::from(self.leg)),
+            to_base36(self.sequence)
+        )
+    }
+
+    /// Decode a label string
+    pub fn decode(label: &str) -> Result<Self, LabelError> {
+        let mut parts = label.split(LABEL_SEPARATOR);
+        let (Some(strategy_id), Some(leg), Some(sequence), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(LabelError::Malformed(label.to_string()));
+        };
+        validate_strategy_id(strategy_id)?;
+        let leg = from_base36(leg)
+            .and_then(|v| u16::try_from(v).ok())
+            .ok_or_else(|| LabelError::InvalidLeg(leg.to_string()))?;
+        let sequence = from_base36(sequence)
+            .ok_or_else(|| LabelError::InvalidSequence(sequence.to_string()))?;
+        Ok(Self {
+            strategy_id: strategy_id.to_string(),
+            leg,
+            sequence,
+        })
+    }
+
+    /// Decode a label if it was produced by this codec
+    ///
+    /// Returns `None` for empty labels and labels set by other tools,
+    /// which is what a fill router usually wants.
+    #[must_use]
+    pub fn try_decode(label: &str) -> Option<Self> {
+        Self::decode(label).ok()
+    }
+}
+
+impl FromStr for OrderLabel {
+    type Err = LabelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::decode(s)
+    }
+}
+
+impl From<OrderLabel> for String {
+    fn from(label: OrderLabel) -> Self {
+        label.encode()
+    }
+}
+
+/// Validate a strategy id
+fn validate_strategy_id(strategy_id: &str) -> Result<(), LabelError> {
+    if strategy_id.is_empty() {
+        return Err(LabelError::EmptyStrategyId);
+    }
+    if strategy_id.len() > MAX_STRATEGY_ID_LENGTH {
+        return Err(LabelError::StrategyIdTooLong(strategy_id.len()));
+    }
+    if !strategy_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(LabelError::InvalidStrategyId(strategy_id.to_string()));
+    }
+    Ok(())
+}
+
+/// Encode a number in lowercase base 36
+fn to_base36(mut value: u64) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    if value == 0 {
+        return "0".to_string();
+    }
+    let mut buf = Vec::with_capacity(13);
+    while value > 0 {
+        buf.push(DIGITS[(value % 36) as usize]);
+        value /= 36;
+    }
+    buf.reverse();
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+/// Decode a lowercase base 36 number
+fn from_base36(value: &str) -> Option<u64> {
+    if value.is_empty() || value.chars().any(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    u64::from_str_radix(value, 36).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_round_trip() {
+        let label = OrderLabel::new("mm-btc".to_string(), 3, 1_700_000_000_123_456).unwrap();
+        let encoded = label.encode();
+        assert_eq!(encoded, "mm-btc:3:gqll7vdgzk");
+        assert_eq!(OrderLabel::decode(&encoded).unwrap(), label);
+        assert_eq!(encoded.parse::<OrderLabel>().unwrap(), label);
+    }
+
+    #[test]
+    fn test_label_fits_deribit_limit() {
+        let strategy = "s".repeat(MAX_STRATEGY_ID_LENGTH);
+        let label = OrderLabel::new(strategy, u16::MAX, u64::MAX).unwrap();
+        let encoded = label.encode();
+        assert_eq!(encoded.len(), MAX_LABEL_LENGTH);
+        assert_eq!(OrderLabel::decode(&encoded).unwrap(), label);
+    }
+
+    #[test]
+    fn test_invalid_strategy_ids() {
+        assert_eq!(
+            OrderLabel::new(String::new(), 0, 0).unwrap_err(),
+            LabelError::EmptyStrategyId
+        );
+        assert!(matches!(
+            OrderLabel::new("s".repeat(MAX_STRATEGY_ID_LENGTH + 1), 0, 0),
+            Err(LabelError::StrategyIdTooLong(_))
+        ));
+        assert!(matches!(
+            OrderLabel::new("a:b".to_string(), 0, 0),
+            Err(LabelError::InvalidStrategyId(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            OrderLabel::decode("manual order"),
+            Err(LabelError::Malformed(_))
+        ));
+        assert!(matches!(
+            OrderLabel::decode("a:1:2:3"),
+            Err(LabelError::Malformed(_))
+        ));
+        assert!(matches!(
+            OrderLabel::decode("strat:zzzz:1"),
+            Err(LabelError::InvalidLeg(_))
+        ));
+        assert!(matches!(
+            OrderLabel::decode("strat:1:"),
+            Err(LabelError::InvalidSequence(_))
+        ));
+        assert!(matches!(
+            OrderLabel::decode("strat:1:ABC"),
+            Err(LabelError::InvalidSequence(_))
+        ));
+        assert_eq!(OrderLabel::try_decode(""), None);
+    }
+
+    #[test]
+    fn test_generate_and_for_leg() {
+        let first = OrderLabel::generate("spread".to_string(), 0).unwrap();
+        let second = OrderLabel::generate("spread".to_string(), 0).unwrap();
+        assert!(second.sequence > first.sequence);
+        assert_ne!(first.encode(), second.encode());
+
+        let other_leg = first.for_leg(1);
+        assert_eq!(other_leg.sequence, first.sequence);
+        assert_eq!(other_leg.leg, 1);
+        let label: String = other_leg.into();
+        assert!(label.starts_with("spread:1:"));
+    }
+}
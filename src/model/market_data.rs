@@ -4,6 +4,8 @@
    Date: 21/7/25
 ******************************************************************************/
 use crate::model::instrument::InstrumentKind;
+use crate::model::order::OrderSide;
+use crate::model::ticker::{Greeks as TickerGreeks, TickerStats};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
@@ -90,7 +92,10 @@ impl Ticker {
 }
 
 /// Order book entry
+///
+/// Sent as a `[price, amount]` pair.
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[serde(from = "(f64, f64)", into = "(f64, f64)")]
 pub struct OrderBookEntry {
     /// Price level
     pub price: f64,
@@ -98,6 +103,18 @@ pub struct OrderBookEntry {
     pub amount: f64,
 }
 
+impl From<(f64, f64)> for OrderBookEntry {
+    fn from((price, amount): (f64, f64)) -> Self {
+        Self { price, amount }
+    }
+}
+
+impl From<OrderBookEntry> for (f64, f64) {
+    fn from(entry: OrderBookEntry) -> Self {
+        (entry.price, entry.amount)
+    }
+}
+
 impl OrderBookEntry {
     /// Create a new order book entry
     pub fn new(price: f64, amount: f64) -> Self {
@@ -125,6 +142,75 @@ pub struct OrderBook {
     pub change_id: u64,
     /// Previous change ID
     pub prev_change_id: Option<u64>,
+    /// Instrument state (e.g., "open", "closed")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Best bid price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_bid_price: Option<f64>,
+    /// Amount at the best bid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_bid_amount: Option<f64>,
+    /// Best ask price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_ask_price: Option<f64>,
+    /// Amount at the best ask
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_ask_amount: Option<f64>,
+    /// Last trade price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_price: Option<f64>,
+    /// Mark price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
+    /// Index price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_price: Option<f64>,
+    /// Settlement price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlement_price: Option<f64>,
+    /// Delivery price (closed instruments only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_price: Option<f64>,
+    /// Minimum price allowed for sell orders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_price: Option<f64>,
+    /// Maximum price allowed for buy orders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<f64>,
+    /// Open interest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_interest: Option<f64>,
+    /// Current funding (perpetuals only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_funding: Option<f64>,
+    /// Funding over the last 8 hours (perpetuals only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funding_8h: Option<f64>,
+    /// Implied volatility of the best bid (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bid_iv: Option<f64>,
+    /// Implied volatility of the best ask (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ask_iv: Option<f64>,
+    /// Implied volatility of the mark price (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mark_iv: Option<f64>,
+    /// Interest rate used for the implied volatility (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interest_rate: Option<f64>,
+    /// Underlying price used for the implied volatility (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underlying_price: Option<f64>,
+    /// Underlying index used for the implied volatility (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underlying_index: Option<String>,
+    /// Option greeks (options only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub greeks: Option<TickerGreeks>,
+    /// 24h statistics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<TickerStats>,
 }
 
 impl OrderBook {
@@ -137,6 +223,29 @@ impl OrderBook {
             asks: Vec::new(),
             change_id,
             prev_change_id: None,
+            state: None,
+            best_bid_price: None,
+            best_bid_amount: None,
+            best_ask_price: None,
+            best_ask_amount: None,
+            last_price: None,
+            mark_price: None,
+            index_price: None,
+            settlement_price: None,
+            delivery_price: None,
+            min_price: None,
+            max_price: None,
+            open_interest: None,
+            current_funding: None,
+            funding_8h: None,
+            bid_iv: None,
+            ask_iv: None,
+            mark_iv: None,
+            interest_rate: None,
+            underlying_price: None,
+            underlying_index: None,
+            greeks: None,
+            stats: None,
         }
     }
 
@@ -460,11 +569,34 @@ impl TradeVolume {
     }
 }
 
+/// Active request for quote on an instrument
+///
+/// Returned by `/public/get_rfqs`.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveRfq {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Requested side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<OrderSide>,
+    /// Requested amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// Volume traded since the last RFQ
+    #[serde(default)]
+    pub traded_volume: f64,
+    /// Timestamp of the last RFQ in milliseconds
+    #[serde(rename = "last_rfq_tstamp", alias = "last_rfq_timestamp")]
+    pub last_rfq_timestamp: u64,
+}
+
 /// Volatility index OHLC candle
 ///
 /// Represents a single volatility index candle with OHLC data,
-/// returned by `/public/get_volatility_index_data`.
+/// returned by `/public/get_volatility_index_data` as a
+/// `[timestamp, open, high, low, close]` array.
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "(i64, f64, f64, f64, f64)", into = "(i64, f64, f64, f64, f64)")]
 pub struct VolatilityIndexCandle {
     /// Timestamp in milliseconds since Unix epoch
     pub timestamp: i64,
@@ -522,19 +654,38 @@ impl VolatilityIndexCandle {
     }
 }
 
+impl From<(i64, f64, f64, f64, f64)> for VolatilityIndexCandle {
+    fn from(data: (i64, f64, f64, f64, f64)) -> Self {
+        Self::from_tuple(data)
+    }
+}
+
+impl From<VolatilityIndexCandle> for (i64, f64, f64, f64, f64) {
+    fn from(candle: VolatilityIndexCandle) -> Self {
+        (
+            candle.timestamp,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+        )
+    }
+}
+
 /// Volatility index data response
 ///
 /// Collection of volatility index candles with optional continuation token,
 /// returned by `/public/get_volatility_index_data`.
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VolatilityIndexData {
-    /// Currency for this volatility index
+    /// Currency for this volatility index (not part of the API response)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub currency: String,
     /// Collection of volatility candles
     pub data: Vec<VolatilityIndexCandle>,
     /// Continuation token for pagination
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub continuation: Option<String>,
+    pub continuation: Option<u64>,
 }
 
 impl VolatilityIndexData {
@@ -553,7 +704,7 @@ impl VolatilityIndexData {
     pub fn from_raw(
         currency: String,
         data: Vec<(i64, f64, f64, f64, f64)>,
-        continuation: Option<String>,
+        continuation: Option<u64>,
     ) -> Self {
         Self {
             currency,
@@ -1070,13 +1221,9 @@ mod tests {
     #[test]
     fn test_volatility_index_data_with_continuation() {
         let raw_data = vec![(1640995200000, 0.21, 0.22, 0.20, 0.215)];
-        let data = VolatilityIndexData::from_raw(
-            "BTC".to_string(),
-            raw_data,
-            Some("next_page_token".to_string()),
-        );
+        let data = VolatilityIndexData::from_raw("BTC".to_string(), raw_data, Some(1598019300000));
         assert!(data.has_more());
-        assert_eq!(data.continuation, Some("next_page_token".to_string()));
+        assert_eq!(data.continuation, Some(1598019300000));
     }
 
     #[test]
@@ -1138,4 +1285,23 @@ mod tests {
         let deserialized: IndexType = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, IndexType::Spot);
     }
+
+    #[test]
+    fn test_active_rfq_deserialization() {
+        let json = r#"{
+            "traded_volume": 0,
+            "amount": 10,
+            "side": "buy",
+            "last_rfq_tstamp": 1634816611595,
+            "instrument_name": "BTC-PERPETUAL"
+        }"#;
+
+        let rfq: ActiveRfq = serde_json::from_str(json).unwrap();
+        assert_eq!(rfq.instrument_name, "BTC-PERPETUAL");
+        assert_eq!(rfq.side, Some(OrderSide::Buy));
+        assert_eq!(rfq.last_rfq_timestamp, 1634816611595);
+
+        let value = serde_json::to_value(&rfq).unwrap();
+        assert_eq!(value["last_rfq_tstamp"], 1634816611595_u64);
+    }
 }
@@ -0,0 +1,497 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Market maker protection (MMP) data structures and types
+//!
+//! This module contains types for configuring and monitoring Deribit's
+//! market maker protection via `/private/get_mmp_config`,
+//! `/private/set_mmp_config`, `/private/get_mmp_status` and
+//! `/private/reset_mmp`, as well as the `user.mmp_trigger.{index_name}`
+//! notification payload.
+//!
+//! MMP limits are set per index and optionally per MMP group. Groups are
+//! used by mass quotes, while the group-less configuration applies to
+//! regular orders placed with the `mmp` flag.
+
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+
+/// Index name that addresses every index at once
+pub const MMP_INDEX_ALL: &str = "all";
+
+/// MMP configuration
+///
+/// Returned by `/private/get_mmp_config` and `/private/set_mmp_config`.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MmpConfig {
+    /// Index identifier (e.g., "btc_usd")
+    pub index_name: String,
+    /// MMP group the configuration belongs to (absent for regular orders)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmp_group: Option<String>,
+    /// MMP interval in seconds, 0 means MMP is disabled
+    pub interval: u32,
+    /// Frozen time in seconds, 0 means a manual reset is required
+    pub frozen_time: u32,
+    /// Quantity limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_limit: Option<f64>,
+    /// Delta limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_limit: Option<f64>,
+    /// Vega limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vega_limit: Option<f64>,
+    /// Maximum number of Block RFQ trades in the interval (Block RFQ only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trade_count_limit: Option<u32>,
+    /// Whether this is a Block RFQ MMP configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_rfq: Option<bool>,
+}
+
+/// MMP configurations returned by `/private/get_mmp_config` and `/private/set_mmp_config`
+///
+/// `get_mmp_config` returns a list, while the documented `set_mmp_config`
+/// example returns a single configuration; both shapes are kept as received.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MmpConfigs {
+    /// List of configurations, empty when none is set
+    List(Vec<MmpConfig>),
+    /// Single configuration
+    Single(Box<MmpConfig>),
+}
+
+impl MmpConfigs {
+    /// Get the configurations
+    #[must_use]
+    pub fn configs(&self) -> &[MmpConfig] {
+        match self {
+            Self::List(configs) => configs,
+            Self::Single(config) => std::slice::from_ref(config.as_ref()),
+        }
+    }
+}
+
+impl From<MmpConfigs> for Vec<MmpConfig> {
+    fn from(configs: MmpConfigs) -> Self {
+        match configs {
+            MmpConfigs::List(configs) => configs,
+            MmpConfigs::Single(config) => vec![*config],
+        }
+    }
+}
+
+impl MmpConfig {
+    /// Check if MMP is enabled (non-zero interval)
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.interval > 0
+    }
+
+    /// Check if a triggered MMP requires a manual reset
+    #[must_use]
+    pub fn requires_manual_reset(&self) -> bool {
+        self.frozen_time == 0
+    }
+
+    /// Check if this configuration applies to Block RFQ
+    #[must_use]
+    pub fn is_block_rfq(&self) -> bool {
+        self.block_rfq.unwrap_or(false)
+    }
+}
+
+/// Request for `/private/set_mmp_config`
+///
+/// Setting a configuration also resets a triggered MMP.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetMmpConfigRequest {
+    /// Index identifier (e.g., "btc_usd", or "all")
+    pub index_name: String,
+    /// MMP interval in seconds, 0 removes MMP
+    pub interval: u32,
+    /// Frozen time in seconds, 0 means a manual reset is required
+    pub frozen_time: u32,
+    /// MMP group to configure (omit for regular orders)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mmp_group: Option<String>,
+    /// Quantity limit, positive value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity_limit: Option<f64>,
+    /// Delta limit, positive value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_limit: Option<f64>,
+    /// Vega limit, positive value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vega_limit: Option<f64>,
+    /// Configure Block RFQ MMP instead of order/quote MMP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq: Option<bool>,
+    /// Maximum number of Block RFQ trades in the interval (Block RFQ only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_count_limit: Option<u32>,
+}
+
+impl SetMmpConfigRequest {
+    /// Create a new configuration request
+    #[must_use]
+    pub fn new(index_name: String, interval: u32, frozen_time: u32) -> Self {
+        Self {
+            index_name,
+            interval,
+            frozen_time,
+            mmp_group: None,
+            quantity_limit: None,
+            delta_limit: None,
+            vega_limit: None,
+            block_rfq: None,
+            trade_count_limit: None,
+        }
+    }
+
+    /// Set the MMP group
+    #[must_use]
+    pub fn with_mmp_group(mut self, mmp_group: String) -> Self {
+        self.mmp_group = Some(mmp_group);
+        self
+    }
+
+    /// Set the quantity limit
+    #[must_use]
+    pub fn with_quantity_limit(mut self, quantity_limit: f64) -> Self {
+        self.quantity_limit = Some(quantity_limit);
+        self
+    }
+
+    /// Set the delta limit
+    #[must_use]
+    pub fn with_delta_limit(mut self, delta_limit: f64) -> Self {
+        self.delta_limit = Some(delta_limit);
+        self
+    }
+
+    /// Set the vega limit
+    #[must_use]
+    pub fn with_vega_limit(mut self, vega_limit: f64) -> Self {
+        self.vega_limit = Some(vega_limit);
+        self
+    }
+
+    /// Configure Block RFQ MMP
+    #[must_use]
+    pub fn with_block_rfq(mut self, block_rfq: bool) -> Self {
+        self.block_rfq = Some(block_rfq);
+        self
+    }
+
+    /// Set the Block RFQ trade count limit
+    #[must_use]
+    pub fn with_trade_count_limit(mut self, trade_count_limit: u32) -> Self {
+        self.trade_count_limit = Some(trade_count_limit);
+        self
+    }
+
+    /// Check if the request satisfies the documented constraints
+    ///
+    /// Limits must be positive, and `trade_count_limit` is only allowed
+    /// for Block RFQ with `index_name` set to `"all"`.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        if self.index_name.is_empty() {
+            return false;
+        }
+        let limits_positive = [self.quantity_limit, self.delta_limit, self.vega_limit]
+            .iter()
+            .flatten()
+            .all(|limit| limit.is_finite() && *limit > 0.0);
+        if !limits_positive {
+            return false;
+        }
+        if self.trade_count_limit.is_some() {
+            return self.block_rfq == Some(true) && self.index_name == MMP_INDEX_ALL;
+        }
+        true
+    }
+}
+
+/// Request for `/private/get_mmp_config`, `/private/get_mmp_status`
+/// and `/private/reset_mmp`
+///
+/// All three methods address MMP settings by index, group and Block RFQ flag.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct MmpTarget {
+    /// Index identifier; omitted to address all indices (not allowed for reset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_name: Option<String>,
+    /// MMP group; requires `index_name`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mmp_group: Option<String>,
+    /// Address Block RFQ MMP instead of order/quote MMP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq: Option<bool>,
+}
+
+impl MmpTarget {
+    /// Address every MMP configuration
+    #[must_use]
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Address a single index
+    #[must_use]
+    pub fn index(index_name: String) -> Self {
+        Self {
+            index_name: Some(index_name),
+            mmp_group: None,
+            block_rfq: None,
+        }
+    }
+
+    /// Address an MMP group on an index
+    #[must_use]
+    pub fn group(index_name: String, mmp_group: String) -> Self {
+        Self {
+            index_name: Some(index_name),
+            mmp_group: Some(mmp_group),
+            block_rfq: None,
+        }
+    }
+
+    /// Address Block RFQ MMP
+    #[must_use]
+    pub fn with_block_rfq(mut self, block_rfq: bool) -> Self {
+        self.block_rfq = Some(block_rfq);
+        self
+    }
+
+    /// Check if the target is valid for queries
+    ///
+    /// An MMP group can only be given together with an index.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.mmp_group.is_none() || self.index_name.is_some()
+    }
+
+    /// Check if the target is valid for `/private/reset_mmp`,
+    /// which requires an index
+    #[must_use]
+    pub fn is_valid_for_reset(&self) -> bool {
+        self.index_name.is_some()
+    }
+}
+
+/// MMP status of a triggered index or group
+///
+/// Returned by `/private/get_mmp_status`.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MmpStatus {
+    /// Index identifier
+    pub index_name: String,
+    /// Timestamp in milliseconds until which the user is frozen,
+    /// 0 means frozen until manual reset
+    pub frozen_until: i64,
+    /// Triggered MMP group (only for mass quote triggers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmp_group: Option<String>,
+    /// Whether this status is for Block RFQ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_rfq: Option<bool>,
+}
+
+impl MmpStatus {
+    /// Check if the freeze lasts until a manual reset
+    #[must_use]
+    pub fn requires_manual_reset(&self) -> bool {
+        self.frozen_until == 0
+    }
+
+    /// Check if quoting is still frozen at the given time in milliseconds
+    #[must_use]
+    pub fn is_frozen_at(&self, now_ms: i64) -> bool {
+        self.requires_manual_reset() || now_ms < self.frozen_until
+    }
+}
+
+/// Payload of the `user.mmp_trigger.{index_name}` notification
+///
+/// Sent when one of the MMP limits is crossed.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MmpTrigger {
+    /// Index identifier ("all" for Block RFQ trade count triggers)
+    pub index_name: String,
+    /// Timestamp in milliseconds until which the user is frozen,
+    /// 0 means frozen until manual reset
+    pub frozen_until: i64,
+    /// Triggered MMP group (only for mass quote triggers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmp_group: Option<String>,
+    /// Whether this trigger is for Block RFQ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_rfq: Option<bool>,
+}
+
+impl MmpTrigger {
+    /// Build the target needed to reset this trigger
+    #[must_use]
+    pub fn reset_target(&self) -> MmpTarget {
+        MmpTarget {
+            index_name: Some(self.index_name.clone()),
+            mmp_group: self.mmp_group.clone(),
+            block_rfq: self.block_rfq,
+        }
+    }
+}
+
+impl From<MmpTrigger> for MmpStatus {
+    fn from(trigger: MmpTrigger) -> Self {
+        Self {
+            index_name: trigger.index_name,
+            frozen_until: trigger.frozen_until,
+            mmp_group: trigger.mmp_group,
+            block_rfq: trigger.block_rfq,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmp_config_deserialization() {
+        let json = r#"{
+            "index_name": "btc_usd",
+            "mmp_group": "MassQuoteBot7",
+            "interval": 60,
+            "frozen_time": 0,
+            "quantity_limit": 0.5,
+            "delta_limit": 0.3,
+            "vega_limit": 0.1
+        }"#;
+        let config: MmpConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.mmp_group.as_deref(), Some("MassQuoteBot7"));
+        assert!(config.is_enabled());
+        assert!(config.requires_manual_reset());
+        assert!(!config.is_block_rfq());
+        assert_eq!(config.quantity_limit, Some(0.5));
+    }
+
+    #[test]
+    fn test_mmp_configs_accept_documented_results() {
+        // Result of the documented `/private/get_mmp_config` example
+        let get = r#"[
+            {
+                "index_name": "btc_usd",
+                "mmp_group": "MassQuoteBot7",
+                "interval": 60,
+                "frozen_time": 0,
+                "quantity_limit": 0.5,
+                "delta_limit": 0.3,
+                "vega_limit": 0.1
+            }
+        ]"#;
+        let configs: MmpConfigs = serde_json::from_str(get).unwrap();
+        assert_eq!(configs.configs().len(), 1);
+        assert_eq!(configs.configs()[0].delta_limit, Some(0.3));
+
+        // Result of the documented `/private/set_mmp_config` example
+        let set = r#"{
+            "index_name": "btc_usd",
+            "mmp_group": "MassQuoteBot7",
+            "interval" : 60,
+            "frozen_time": 0,
+            "quantity_limit": 3.0
+        }"#;
+        let configs: MmpConfigs = serde_json::from_str(set).unwrap();
+        assert!(matches!(configs, MmpConfigs::Single(_)));
+        assert_eq!(configs.configs().len(), 1);
+        assert_eq!(configs.configs()[0].quantity_limit, Some(3.0));
+
+        let value = serde_json::to_value(&configs).unwrap();
+        assert_eq!(value["index_name"], "btc_usd");
+        assert_eq!(
+            serde_json::from_value::<MmpConfigs>(value).unwrap(),
+            configs
+        );
+        assert!(
+            serde_json::from_str::<MmpConfigs>("[]")
+                .unwrap()
+                .configs()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_set_mmp_config_request_serialization() {
+        let request = SetMmpConfigRequest::new("btc_usd".to_string(), 60, 0)
+            .with_mmp_group("MassQuoteBot7".to_string())
+            .with_quantity_limit(3.0);
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["index_name"], "btc_usd");
+        assert_eq!(value["quantity_limit"], 3.0);
+        assert!(value.get("delta_limit").is_none());
+        assert!(request.is_valid());
+    }
+
+    #[test]
+    fn test_set_mmp_config_request_validation() {
+        let negative =
+            SetMmpConfigRequest::new("btc_usd".to_string(), 60, 10).with_delta_limit(-1.0);
+        assert!(!negative.is_valid());
+
+        let trade_count =
+            SetMmpConfigRequest::new("btc_usd".to_string(), 60, 10).with_trade_count_limit(5);
+        assert!(!trade_count.is_valid());
+
+        let block_rfq = SetMmpConfigRequest::new(MMP_INDEX_ALL.to_string(), 60, 10)
+            .with_block_rfq(true)
+            .with_trade_count_limit(5);
+        assert!(block_rfq.is_valid());
+    }
+
+    #[test]
+    fn test_mmp_target() {
+        assert!(MmpTarget::all().is_valid());
+        assert!(!MmpTarget::all().is_valid_for_reset());
+        assert!(MmpTarget::index("btc_usd".to_string()).is_valid_for_reset());
+
+        let orphan_group = MmpTarget {
+            index_name: None,
+            mmp_group: Some("bot".to_string()),
+            block_rfq: None,
+        };
+        assert!(!orphan_group.is_valid());
+
+        let json = serde_json::to_string(&MmpTarget::all()).unwrap();
+        assert_eq!(json, "{}");
+    }
+
+    #[test]
+    fn test_mmp_status() {
+        let json = r#"{"index_name": "btc_usd", "frozen_until": 1744275841861, "mmp_group": "MassQuoteBot7"}"#;
+        let status: MmpStatus = serde_json::from_str(json).unwrap();
+        assert!(!status.requires_manual_reset());
+        assert!(status.is_frozen_at(1744275841000));
+        assert!(!status.is_frozen_at(1744275842000));
+    }
+
+    #[test]
+    fn test_mmp_trigger_notification() {
+        let json = r#"{"frozen_until":0,"index_name":"btc_usdc","mmp_group":"MassQuoteBot7"}"#;
+        let trigger: MmpTrigger = serde_json::from_str(json).unwrap();
+        let target = trigger.reset_target();
+        assert_eq!(target.index_name.as_deref(), Some("btc_usdc"));
+        assert_eq!(target.mmp_group.as_deref(), Some("MassQuoteBot7"));
+        assert!(target.is_valid_for_reset());
+
+        let status = MmpStatus::from(trigger);
+        assert!(status.requires_manual_reset());
+        assert!(status.is_frozen_at(i64::MAX));
+    }
+}
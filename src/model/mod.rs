@@ -19,10 +19,16 @@
 
 /// Account-related data structures and types
 pub mod account;
+/// Order amount units and conversions
+pub mod amount;
+pub mod api_key;
+/// Block RFQ data structures and workflow
+pub mod block_rfq;
 /// Block trade data structures and types
 pub mod block_trade;
 /// Book summary and market overview structures
 pub mod book_summary;
+pub mod channel;
 /// Combo Books data structures and types
 pub mod combo;
 /// Configuration structures
@@ -35,22 +41,33 @@ pub mod extended_market_data;
 pub mod funding;
 /// Instrument-related data structures and types
 pub mod instrument;
+pub mod label;
 /// Market data structures
 pub mod market_data;
+/// Market maker protection data structures and types
+pub mod mmp;
+pub mod move_positions;
 /// Options-related data structures and types
 pub mod options;
 /// Order-related data structures and types
 pub mod order;
 /// Order management operations and requests
 pub mod order_management;
+/// Portfolio margin engine simulation results
+pub mod pme;
 /// Position-related data structures and types
 pub mod position;
 /// Request structures for API calls
 pub mod request;
 /// Response structures and wrappers
 pub mod response;
+/// Pre-trade risk limits
+pub mod risk;
+pub mod rpc;
+pub mod scope;
 /// Settlement and delivery data structures
 pub mod settlement;
+pub mod subscription;
 /// Ticker data structures
 pub mod ticker;
 /// Trade-related data structures and types
@@ -62,21 +79,50 @@ pub mod transfer;
 /// Wallet-related data structures and types
 pub mod wallet;
 
+pub use amount::{AmountError, AmountUnit, OrderSize};
+pub use block_rfq::{
+    AcceptBlockRfqRequest, AddBlockRfqQuoteRequest, BlockRfq, BlockRfqError, BlockRfqHedge,
+    BlockRfqIdentity, BlockRfqLeg, BlockRfqLegPrices, BlockRfqMaker, BlockRfqQuote,
+    BlockRfqQuoteLeg, BlockRfqQuoteLevel, BlockRfqQuoteState, BlockRfqRoleFilter, BlockRfqState,
+    BlockRfqTaker, BlockRfqTimeInForce, BlockRfqTrade, BlockRfqTradeTrigger, BlockRfqUser,
+    BlockRfqUserInfo, CreateBlockRfqLeg, CreateBlockRfqRequest, EditBlockRfqQuoteRequest,
+    ExecutionInstruction, GetBlockRfqQuotesResponse, GetBlockRfqTradesResponse,
+    GetBlockRfqsRequest, GetBlockRfqsResponse,
+};
 pub use block_trade::{
-    BlockTrade, BlockTradeExecution, BlockTradeLeg, BlockTradeRole, BlockTradeSignature,
-    ExecuteBlockTradeRequest, VerifyBlockTradeRequest,
+    BLOCK_TRADE_SIGNATURE_VALIDITY_MS, BlockTrade, BlockTradeApprovalRequest,
+    BlockTradeConfirmation, BlockTradeError, BlockTradeExecution, BlockTradeLeg,
+    BlockTradeNonceTracker, BlockTradeProposal, BlockTradeRequestState, BlockTradeRole,
+    BlockTradeSignature, BrokerClient, BrokerTrade, BrokerTradeRequest, BrokerTradesResponse,
+    ExecuteBlockTradeRequest, PendingBlockTrade, SimulateBlockTradeRequest,
+    VerifyBlockTradeRequest, generate_block_trade_nonce, is_block_trade_timestamp_valid,
+    validate_block_trade_legs,
 };
 pub use combo::{ComboDetails, ComboIds, ComboLeg, ComboState, ComboTradeLeg, CreateComboRequest};
 pub use funding::{FundingChartData, FundingDataPoint, FundingRateData, TradingViewChartData};
+pub use label::{LabelError, OrderLabel};
+pub use mmp::{MmpConfig, MmpConfigs, MmpStatus, MmpTarget, MmpTrigger, SetMmpConfigRequest};
+pub use move_positions::{
+    MovePositionTrade, MovePositionTradeResult, MovePositionsError, MovePositionsPreview,
+    MovePositionsRequest, MovePositionsResponse, ProjectedPosition,
+};
 pub use order_management::{
-    BuyOrderRequest, EditOrderRequest, MassQuoteItem, MassQuoteRequest, SellOrderRequest,
-    TransferResult,
+    BuyOrderRequest, EditOrderRequest, MassQuoteBuilder, MassQuoteError, MassQuoteItem,
+    MassQuoteRequest, QuoteError, QuoteLevel, QuoteSide, SellOrderRequest, TransferResult,
+};
+pub use pme::{
+    PmeCurrencyPairParams, PmeCurrencyParams, PmeGeneralParams, PmeInitialMarginDetails, PmeMargin,
+    PmeModelParams, PmePortfolio, PmeRiskMatrixMarginDetails, PmeRiskVector, PmeSimulation,
+    PmeTicker, PmeWorstCaseBucket,
+};
+pub use risk::{
+    PriceReference, RiskConfig, RiskLimits, RiskOrder, RiskScope, RiskState, RiskViolation,
 };
 pub use transaction::{
     Deposit, DepositsResponse, TransactionLog, TransactionLogEntry, TransactionType, Withdrawal,
     WithdrawalsResponse,
 };
 pub use wallet::{
-    ClearanceOriginator, ClearanceState, DepositAddress, DepositId, WithdrawalPriority,
-    WithdrawalRequest, WithdrawalState,
+    ClearanceOriginator, ClearanceState, CustodyAccount, DepositAddress, DepositId,
+    WithdrawalPriority, WithdrawalRequest, WithdrawalState,
 };
@@ -0,0 +1,583 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Position moves between subaccounts
+//!
+//! Models for `private/move_positions`, plus a local pre-check: a
+//! [`MovePositionsRequest`] can be validated against the source
+//! subaccount's [`Position`]s and projected onto both subaccounts before
+//! it is sent.
+//!
+//! Amounts follow the instrument's native unit: USD for perpetuals and
+//! inverse futures, base currency for options and linear futures.
+
+use crate::model::account::Subaccount;
+use crate::model::order::OrderSide;
+use crate::model::position::{Direction, Position};
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tolerance when comparing amounts
+const AMOUNT_EPSILON: f64 = 1e-9;
+
+/// Error raised while validating a position move
+#[derive(Debug, Clone, PartialEq)]
+pub enum MovePositionsError {
+    /// Source and target subaccounts are the same
+    SameSubaccount(u64),
+    /// No trades in the request
+    NoTrades,
+    /// Trade amount is not a positive finite number
+    InvalidAmount {
+        /// Instrument of the trade
+        instrument_name: String,
+        /// Requested amount
+        amount: f64,
+    },
+    /// Trade price is not a positive finite number
+    InvalidPrice {
+        /// Instrument of the trade
+        instrument_name: String,
+        /// Requested price
+        price: f64,
+    },
+    /// Source subaccount has no position in the instrument
+    NoPosition(String),
+    /// Total amount moved exceeds the source position size
+    AmountExceedsPosition {
+        /// Instrument of the trade
+        instrument_name: String,
+        /// Total amount requested for the instrument
+        amount: f64,
+        /// Absolute size of the source position
+        size: f64,
+    },
+}
+
+impl std::fmt::Display for MovePositionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SameSubaccount(uid) => {
+                write!(f, "source and target subaccount are both {uid}")
+            }
+            Self::NoTrades => write!(f, "no trades to move"),
+            Self::InvalidAmount {
+                instrument_name,
+                amount,
+            } => write!(f, "invalid amount {amount} for {instrument_name}"),
+            Self::InvalidPrice {
+                instrument_name,
+                price,
+            } => write!(f, "invalid price {price} for {instrument_name}"),
+            Self::NoPosition(instrument_name) => {
+                write!(f, "source has no position in {instrument_name}")
+            }
+            Self::AmountExceedsPosition {
+                instrument_name,
+                amount,
+                size,
+            } => write!(
+                f,
+                "amount {amount} exceeds position size {size} in {instrument_name}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MovePositionsError {}
+
+/// Single trade of a position move
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovePositionTrade {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Amount to move, never above the position size
+    pub amount: f64,
+    /// Trade price, the position average price when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+}
+
+impl MovePositionTrade {
+    /// Create a trade at the position average price
+    #[must_use]
+    pub fn new(instrument_name: String, amount: f64) -> Self {
+        Self {
+            instrument_name,
+            amount,
+            price: None,
+        }
+    }
+
+    /// Set the trade price
+    #[must_use]
+    pub fn with_price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+}
+
+/// Request for `private/move_positions`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovePositionsRequest {
+    /// Currency symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Id of the source subaccount
+    pub source_uid: u64,
+    /// Id of the target subaccount
+    pub target_uid: u64,
+    /// Trades to execute
+    pub trades: Vec<MovePositionTrade>,
+}
+
+impl MovePositionsRequest {
+    /// Create a new request
+    #[must_use]
+    pub fn new(source_uid: u64, target_uid: u64, trades: Vec<MovePositionTrade>) -> Self {
+        Self {
+            currency: None,
+            source_uid,
+            target_uid,
+            trades,
+        }
+    }
+
+    /// Create a request moving positions between two subaccounts
+    #[must_use]
+    pub fn between(
+        source: &Subaccount,
+        target: &Subaccount,
+        trades: Vec<MovePositionTrade>,
+    ) -> Self {
+        Self::new(source.id, target.id, trades)
+    }
+
+    /// Set the currency
+    #[must_use]
+    pub fn with_currency(mut self, currency: String) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    /// Add a trade
+    #[must_use]
+    pub fn with_trade(mut self, trade: MovePositionTrade) -> Self {
+        self.trades.push(trade);
+        self
+    }
+
+    /// Validate the trades against the source subaccount's positions
+    ///
+    /// Amounts are summed per instrument before comparing with the position size.
+    pub fn validate(&self, source_positions: &[Position]) -> Result<(), MovePositionsError> {
+        if self.source_uid == self.target_uid {
+            return Err(MovePositionsError::SameSubaccount(self.source_uid));
+        }
+        if self.trades.is_empty() {
+            return Err(MovePositionsError::NoTrades);
+        }
+        let mut totals: HashMap<&str, f64> = HashMap::new();
+        for trade in &self.trades {
+            if !trade.amount.is_finite() || trade.amount <= 0.0 {
+                return Err(MovePositionsError::InvalidAmount {
+                    instrument_name: trade.instrument_name.clone(),
+                    amount: trade.amount,
+                });
+            }
+            if let Some(price) = trade.price
+                && (!price.is_finite() || price <= 0.0)
+            {
+                return Err(MovePositionsError::InvalidPrice {
+                    instrument_name: trade.instrument_name.clone(),
+                    price,
+                });
+            }
+            *totals.entry(trade.instrument_name.as_str()).or_default() += trade.amount;
+        }
+        for trade in &self.trades {
+            let size = find_position(source_positions, &trade.instrument_name)
+                .map(|p| signed_size(p).abs())
+                .filter(|size| *size > AMOUNT_EPSILON)
+                .ok_or_else(|| MovePositionsError::NoPosition(trade.instrument_name.clone()))?;
+            let amount = totals[trade.instrument_name.as_str()];
+            if amount > size + AMOUNT_EPSILON {
+                return Err(MovePositionsError::AmountExceedsPosition {
+                    instrument_name: trade.instrument_name.clone(),
+                    amount,
+                    size,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the move and compute the resulting positions of both subaccounts
+    ///
+    /// Only instruments touched by the move are returned. Positions that are
+    /// fully moved out are returned with a size of zero.
+    pub fn preview(
+        &self,
+        source_positions: &[Position],
+        target_positions: &[Position],
+    ) -> Result<MovePositionsPreview, MovePositionsError> {
+        self.validate(source_positions)?;
+        let mut source: Vec<ProjectedPosition> = Vec::new();
+        let mut target: Vec<ProjectedPosition> = Vec::new();
+        for trade in &self.trades {
+            let position = find_position(source_positions, &trade.instrument_name)
+                .ok_or_else(|| MovePositionsError::NoPosition(trade.instrument_name.clone()))?;
+            let moved = trade.amount.copysign(signed_size(position));
+            let price = trade.price.unwrap_or(position.average_price);
+
+            let source_entry =
+                projected_entry(&mut source, source_positions, &trade.instrument_name);
+            source_entry.apply(-moved, price);
+            let target_entry =
+                projected_entry(&mut target, target_positions, &trade.instrument_name);
+            target_entry.apply(moved, price);
+        }
+        Ok(MovePositionsPreview { source, target })
+    }
+}
+
+/// Expected position of a subaccount after a move
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectedPosition {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Signed size, negative for short positions
+    pub size: f64,
+    /// Average entry price, zero for a flat position
+    pub average_price: f64,
+}
+
+impl ProjectedPosition {
+    /// Get the position direction, `None` when flat
+    #[must_use]
+    pub fn direction(&self) -> Option<OrderSide> {
+        if self.size > AMOUNT_EPSILON {
+            Some(OrderSide::Buy)
+        } else if self.size < -AMOUNT_EPSILON {
+            Some(OrderSide::Sell)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the position is flat
+    #[must_use]
+    pub fn is_flat(&self) -> bool {
+        self.direction().is_none()
+    }
+
+    /// Apply a signed fill at a price
+    fn apply(&mut self, delta: f64, price: f64) {
+        let size = self.size + delta;
+        if size.abs() <= AMOUNT_EPSILON {
+            self.size = 0.0;
+            self.average_price = 0.0;
+            return;
+        }
+        if self.size * delta >= 0.0 {
+            // Opening or increasing
+            self.average_price =
+                (self.average_price * self.size.abs() + price * delta.abs()) / size.abs();
+        } else if self.size * size < 0.0 {
+            // Flipped through zero
+            self.average_price = price;
+        }
+        self.size = size;
+    }
+}
+
+/// Resulting positions of both subaccounts after a move
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovePositionsPreview {
+    /// Positions of the source subaccount
+    pub source: Vec<ProjectedPosition>,
+    /// Positions of the target subaccount
+    pub target: Vec<ProjectedPosition>,
+}
+
+impl MovePositionsPreview {
+    /// Get the projected source position for an instrument
+    #[must_use]
+    pub fn source_position(&self, instrument_name: &str) -> Option<&ProjectedPosition> {
+        self.source
+            .iter()
+            .find(|p| p.instrument_name == instrument_name)
+    }
+
+    /// Get the projected target position for an instrument
+    #[must_use]
+    pub fn target_position(&self, instrument_name: &str) -> Option<&ProjectedPosition> {
+        self.target
+            .iter()
+            .find(|p| p.instrument_name == instrument_name)
+    }
+}
+
+/// Trade executed by `private/move_positions`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovePositionTradeResult {
+    /// Id of the source subaccount
+    pub source_uid: u64,
+    /// Id of the target subaccount
+    pub target_uid: u64,
+    /// Instrument name
+    pub instrument_name: String,
+    /// Trade direction
+    pub direction: OrderSide,
+    /// Trade amount
+    pub amount: f64,
+    /// Trade price
+    pub price: f64,
+}
+
+/// Response of `private/move_positions`
+///
+/// The method is documented as returning a `{"trades": [...]}` object, while
+/// its example returns the bare array; both shapes are kept as received.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MovePositionsResponse {
+    /// Trades wrapped in an object
+    Trades {
+        /// Executed trades
+        trades: Vec<MovePositionTradeResult>,
+    },
+    /// Bare list of trades
+    List(Vec<MovePositionTradeResult>),
+}
+
+impl MovePositionsResponse {
+    /// Get the executed trades
+    #[must_use]
+    pub fn trades(&self) -> &[MovePositionTradeResult] {
+        match self {
+            Self::Trades { trades } | Self::List(trades) => trades,
+        }
+    }
+}
+
+/// Find the position in an instrument
+fn find_position<'a>(positions: &'a [Position], instrument_name: &str) -> Option<&'a Position> {
+    positions
+        .iter()
+        .find(|p| p.instrument_name == instrument_name)
+}
+
+/// Get a position size signed by its direction
+fn signed_size(position: &Position) -> f64 {
+    match position.direction {
+        Direction::Sell => -position.size.abs(),
+        Direction::Buy => position.size.abs(),
+        Direction::Zero => 0.0,
+    }
+}
+
+/// Get or create the projected position for an instrument
+fn projected_entry<'a>(
+    projected: &'a mut Vec<ProjectedPosition>,
+    positions: &[Position],
+    instrument_name: &str,
+) -> &'a mut ProjectedPosition {
+    let index = match projected
+        .iter()
+        .position(|p| p.instrument_name == instrument_name)
+    {
+        Some(index) => index,
+        None => {
+            let (size, average_price) = find_position(positions, instrument_name)
+                .map_or((0.0, 0.0), |p| (signed_size(p), p.average_price));
+            projected.push(ProjectedPosition {
+                instrument_name: instrument_name.to_string(),
+                size,
+                average_price,
+            });
+            projected.len() - 1
+        }
+    };
+    &mut projected[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_position(instrument_name: &str, direction: Direction, size: f64) -> Position {
+        Position {
+            average_price: 40000.0,
+            average_price_usd: None,
+            delta: None,
+            direction,
+            estimated_liquidation_price: None,
+            floating_profit_loss: None,
+            floating_profit_loss_usd: None,
+            gamma: None,
+            index_price: None,
+            initial_margin: None,
+            instrument_name: instrument_name.to_string(),
+            interest_value: None,
+            kind: Some("future".to_string()),
+            leverage: None,
+            maintenance_margin: None,
+            mark_price: None,
+            open_orders_margin: None,
+            realized_funding: None,
+            realized_profit_loss: None,
+            settlement_price: None,
+            size,
+            size_currency: None,
+            theta: None,
+            total_profit_loss: None,
+            vega: None,
+            unrealized_profit_loss: None,
+        }
+    }
+
+    #[test]
+    fn test_request_serialization() {
+        let request = MovePositionsRequest::new(
+            3,
+            23,
+            vec![MovePositionTrade::new("BTC-PERPETUAL".to_string(), 110.0).with_price(35800.0)],
+        )
+        .with_currency("BTC".to_string());
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["source_uid"], 3);
+        assert_eq!(json["trades"][0]["price"], 35800.0);
+        assert!(
+            serde_json::to_value(MovePositionTrade::new("X".to_string(), 1.0))
+                .unwrap()
+                .get("price")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let positions = vec![
+            create_test_position("BTC-PERPETUAL", Direction::Sell, -200.0),
+            create_test_position("BTC-28JAN22-32500-C", Direction::Buy, 0.0),
+        ];
+        let trade = |amount| MovePositionTrade::new("BTC-PERPETUAL".to_string(), amount);
+
+        assert!(
+            MovePositionsRequest::new(3, 23, vec![trade(150.0)])
+                .validate(&positions)
+                .is_ok()
+        );
+        assert_eq!(
+            MovePositionsRequest::new(3, 3, vec![trade(1.0)]).validate(&positions),
+            Err(MovePositionsError::SameSubaccount(3))
+        );
+        assert_eq!(
+            MovePositionsRequest::new(3, 23, vec![]).validate(&positions),
+            Err(MovePositionsError::NoTrades)
+        );
+        assert!(matches!(
+            MovePositionsRequest::new(3, 23, vec![trade(150.0), trade(100.0)])
+                .validate(&positions),
+            Err(MovePositionsError::AmountExceedsPosition { amount, .. }) if amount == 250.0
+        ));
+        assert!(matches!(
+            MovePositionsRequest::new(3, 23, vec![trade(-1.0)]).validate(&positions),
+            Err(MovePositionsError::InvalidAmount { .. })
+        ));
+        assert_eq!(
+            MovePositionsRequest::new(
+                3,
+                23,
+                vec![MovePositionTrade::new(
+                    "BTC-28JAN22-32500-C".to_string(),
+                    0.1
+                )]
+            )
+            .validate(&positions),
+            Err(MovePositionsError::NoPosition(
+                "BTC-28JAN22-32500-C".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_preview() {
+        let source = vec![create_test_position(
+            "BTC-PERPETUAL",
+            Direction::Sell,
+            -200.0,
+        )];
+        let mut target_short = create_test_position("BTC-PERPETUAL", Direction::Sell, -100.0);
+        target_short.average_price = 30000.0;
+        let request = MovePositionsRequest::new(
+            3,
+            23,
+            vec![MovePositionTrade::new("BTC-PERPETUAL".to_string(), 100.0).with_price(36000.0)],
+        );
+
+        let preview = request.preview(&source, &[target_short]).unwrap();
+        let source_after = preview.source_position("BTC-PERPETUAL").unwrap();
+        assert_eq!(source_after.size, -100.0);
+        assert_eq!(source_after.average_price, 40000.0);
+        let target_after = preview.target_position("BTC-PERPETUAL").unwrap();
+        assert_eq!(target_after.size, -200.0);
+        assert_eq!(target_after.average_price, 33000.0);
+        assert_eq!(target_after.direction(), Some(OrderSide::Sell));
+
+        let full = MovePositionsRequest::new(
+            3,
+            23,
+            vec![MovePositionTrade::new("BTC-PERPETUAL".to_string(), 200.0)],
+        );
+        let preview = full.preview(&source, &[]).unwrap();
+        assert!(preview.source_position("BTC-PERPETUAL").unwrap().is_flat());
+        let target_after = preview.target_position("BTC-PERPETUAL").unwrap();
+        assert_eq!(target_after.size, -200.0);
+        assert_eq!(target_after.average_price, 40000.0);
+    }
+
+    #[test]
+    fn test_preview_flips_target() {
+        let source = vec![create_test_position("BTC-PERPETUAL", Direction::Buy, 300.0)];
+        let target = vec![create_test_position(
+            "BTC-PERPETUAL",
+            Direction::Sell,
+            -100.0,
+        )];
+        let request = MovePositionsRequest::new(
+            3,
+            23,
+            vec![MovePositionTrade::new("BTC-PERPETUAL".to_string(), 300.0).with_price(50000.0)],
+        );
+        let preview = request.preview(&source, &target).unwrap();
+        let target_after = preview.target_position("BTC-PERPETUAL").unwrap();
+        assert_eq!(target_after.size, 200.0);
+        assert_eq!(target_after.average_price, 50000.0);
+    }
+
+    #[test]
+    fn test_response_deserialization() {
+        let list = r#"[
+            {"target_uid": 23, "source_uid": 3, "price": 0.1223,
+             "instrument_name": "BTC-28JAN22-32500-C", "direction": "sell", "amount": 0.1},
+            {"target_uid": 23, "source_uid": 3, "price": 35800,
+             "instrument_name": "BTC-PERPETUAL", "direction": "buy", "amount": 110}
+        ]"#;
+        let response: MovePositionsResponse = serde_json::from_str(list).unwrap();
+        assert_eq!(response.trades().len(), 2);
+        assert_eq!(response.trades()[0].direction, OrderSide::Sell);
+        assert_eq!(response.trades()[1].price, 35800.0);
+        assert!(serde_json::to_value(&response).unwrap().is_array());
+
+        let object = format!(r#"{{"trades": {list}}}"#);
+        let response: MovePositionsResponse = serde_json::from_str(&object).unwrap();
+        assert_eq!(response.trades().len(), 2);
+        assert!(serde_json::to_value(&response).unwrap()["trades"].is_array());
+    }
+}
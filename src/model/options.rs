@@ -320,10 +320,16 @@ mod tests {
             is_active: Some(true),
             contract_size: Some(1.0),
             tick_size: Some(0.0001),
+            tick_size_steps: None,
             min_trade_amount: Some(0.1),
             settlement_currency: Some("BTC".to_string()),
             base_currency: Some("BTC".to_string()),
             counter_currency: Some("USD".to_string()),
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
             quote_currency: Some("BTC".to_string()),
             price_index: None,
             maker_commission: None,
@@ -392,6 +398,9 @@ mod tests {
             max_price: None,
             underlying_index: None,
             estimated_delivery_price: None,
+            current_funding: None,
+            funding_8h: None,
+            interest_value: None,
         }
     }
 
@@ -3,8 +3,10 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::label::OrderLabel;
+use crate::model::request::{AdvancedOrderType, TriggerType};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 /// Time in force enumeration
 #[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -121,9 +123,16 @@ pub struct NewOrderRequest {
 }
 
 /// Order status enumeration
-#[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Serialized as the JSON-RPC `order_state`: `open`, `filled`, `rejected`,
+/// `cancelled`, `untriggered` and `triggered`. Statuses only reported over
+/// FIX serialize as the state Deribit reports for them and deserialize from
+/// their snake case names.
+#[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OrderStatus {
     /// Order has been accepted by the system
+    #[serde(rename = "open", alias = "new")]
     New,
     /// Order has been partially filled
     PartiallyFilled,
@@ -132,16 +141,19 @@ pub enum OrderStatus {
     /// Order is done for the day
     DoneForDay,
     /// Order has been cancelled
+    #[serde(rename = "cancelled")]
     Canceled,
     /// Order has been replaced
     Replaced,
     /// Order cancellation is pending
     PendingCancel,
-    /// Order has been stopped
+    /// Order has been stopped; a trigger order that fired
+    #[serde(rename = "triggered")]
     Stopped,
     /// Order has been rejected
     Rejected,
-    /// Order has been suspended
+    /// Order has been suspended; a trigger order waiting for its trigger
+    #[serde(rename = "untriggered")]
     Suspended,
     /// Order is pending acceptance
     PendingNew,
@@ -155,26 +167,134 @@ pub enum OrderStatus {
     PendingReplace,
 }
 
+impl OrderStatus {
+    /// Returns the JSON-RPC `order_state` of the status
+    ///
+    /// Statuses without a JSON-RPC counterpart map to the state Deribit
+    /// reports for them, e.g. `open` for a partially filled order.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Filled => "filled",
+            OrderStatus::Rejected => "rejected",
+            OrderStatus::Canceled | OrderStatus::DoneForDay | OrderStatus::Expired => "cancelled",
+            OrderStatus::Stopped => "triggered",
+            OrderStatus::Suspended => "untriggered",
+            OrderStatus::New
+            | OrderStatus::PartiallyFilled
+            | OrderStatus::Replaced
+            | OrderStatus::PendingCancel
+            | OrderStatus::PendingNew
+            | OrderStatus::Calculated
+            | OrderStatus::AcceptedForBidding
+            | OrderStatus::PendingReplace => "open",
+        }
+    }
+
+    /// Whether the order can still trade: open or waiting for its trigger
+    pub fn is_open(&self) -> bool {
+        matches!(self.as_str(), "open" | "untriggered")
+    }
+
+    /// Returns the FIX `OrdStatus(39)` value
+    pub fn as_fix(&self) -> &'static str {
+        match self {
+            OrderStatus::New => "0",
+            OrderStatus::PartiallyFilled => "1",
+            OrderStatus::Filled => "2",
+            OrderStatus::DoneForDay => "3",
+            OrderStatus::Canceled => "4",
+            OrderStatus::Replaced => "5",
+            OrderStatus::PendingCancel => "6",
+            OrderStatus::Stopped => "7",
+            OrderStatus::Rejected => "8",
+            OrderStatus::Suspended => "9",
+            OrderStatus::PendingNew => "A",
+            OrderStatus::Calculated => "B",
+            OrderStatus::Expired => "C",
+            OrderStatus::AcceptedForBidding => "D",
+            OrderStatus::PendingReplace => "E",
+        }
+    }
+
+    /// Parses a FIX `OrdStatus(39)` value
+    pub fn from_fix(value: &str) -> Option<Self> {
+        let status = match value {
+            "0" => OrderStatus::New,
+            "1" => OrderStatus::PartiallyFilled,
+            "2" => OrderStatus::Filled,
+            "3" => OrderStatus::DoneForDay,
+            "4" => OrderStatus::Canceled,
+            "5" => OrderStatus::Replaced,
+            "6" => OrderStatus::PendingCancel,
+            "7" => OrderStatus::Stopped,
+            "8" => OrderStatus::Rejected,
+            "9" => OrderStatus::Suspended,
+            "A" => OrderStatus::PendingNew,
+            "B" => OrderStatus::Calculated,
+            "C" => OrderStatus::Expired,
+            "D" => OrderStatus::AcceptedForBidding,
+            "E" => OrderStatus::PendingReplace,
+            _ => return None,
+        };
+        Some(status)
+    }
+}
+
+impl Serialize for OrderStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Order price, a number or `market_price` for market orders
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OrderPrice {
+    /// Limit price
+    Value(f64),
+    /// Symbolic price (e.g., `market_price`)
+    Text(String),
+}
+
+impl OrderPrice {
+    /// Get the numeric price, if any
+    #[must_use]
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Self::Value(price) => Some(*price),
+            Self::Text(_) => None,
+        }
+    }
+}
+
 /// Order information
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+///
+/// Returned by the order methods and sent by the `user.orders` and
+/// `user.changes` channels.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderInfo {
     /// Order amount
     pub amount: f64,
     /// Whether order was placed via API
+    #[serde(default)]
     pub api: bool,
     /// Average execution price
+    #[serde(default)]
     pub average_price: f64,
     /// Order creation timestamp
     pub creation_timestamp: u64,
     /// Order direction (buy/sell)
-    pub direction: String,
+    pub direction: OrderSide,
     /// Amount that has been filled
+    #[serde(default)]
     pub filled_amount: f64,
     /// Instrument name
     pub instrument_name: String,
     /// Whether this is a liquidation order
+    #[serde(default)]
     pub is_liquidation: bool,
     /// Order label
+    #[serde(default)]
     pub label: String,
     /// Last update timestamp
     pub last_update_timestamp: u64,
@@ -183,33 +303,156 @@ pub struct OrderInfo {
     /// Unique order identifier
     pub order_id: String,
     /// Current order state
-    pub order_state: String,
+    pub order_state: OrderStatus,
     /// Type of order
-    pub order_type: String,
+    pub order_type: OrderType,
     /// Original order type before any modifications
-    pub original_order_type: Option<String>,
+    pub original_order_type: Option<OrderType>,
     /// Whether this is a post-only order
+    #[serde(default)]
     pub post_only: bool,
-    /// Order price
-    pub price: f64,
+    /// Order price, absent for some trigger orders
+    pub price: Option<OrderPrice>,
     /// Current profit/loss on the order
     pub profit_loss: Option<f64>,
     /// Whether this order only reduces position
+    #[serde(default)]
     pub reduce_only: bool,
     /// Whether this order has been replaced
+    #[serde(default)]
     pub replaced: bool,
     /// Whether this order reduces risk
+    #[serde(default)]
     pub risk_reducing: bool,
-    /// Time in force specification
-    pub time_in_force: String,
+    /// Time in force specification, `good_til_cancelled` when not sent
+    #[serde(default = "default_time_in_force")]
+    pub time_in_force: TimeInForce,
     /// Whether the order has been triggered
     pub triggered: Option<bool>,
     /// Trigger condition for the order
-    pub trigger: Option<String>,
+    pub trigger: Option<TriggerType>,
+    /// Trigger price
+    pub trigger_price: Option<f64>,
     /// USD value of the order
     pub usd: Option<f64>,
+    /// Implied volatility price (options only)
+    pub implv: Option<f64>,
+    /// Advanced option order type
+    pub advanced: Option<AdvancedOrderType>,
     /// Whether order was placed via web interface
+    #[serde(default)]
     pub web: bool,
+    /// Order size in contracts
+    pub contracts: Option<f64>,
+    /// Whether the order was automatically created to restore the balance
+    pub is_rebalance: Option<bool>,
+    /// Cancellation reason
+    pub cancel_reason: Option<String>,
+    /// Whether the order is part of a mass quote
+    pub quote: Option<bool>,
+    /// Mass quote identifier
+    pub quote_id: Option<String>,
+    /// Mass quote set identifier
+    pub quote_set_id: Option<String>,
+    /// Whether the order is protected by MMP
+    pub mmp: Option<bool>,
+    /// MMP group
+    pub mmp_group: Option<String>,
+    /// Whether the order was cancelled by MMP
+    pub mmp_cancelled: Option<bool>,
+}
+
+fn default_time_in_force() -> TimeInForce {
+    TimeInForce::GoodTilCancelled
+}
+
+impl OrderInfo {
+    /// Decode the structured order label, if any
+    pub fn order_label(&self) -> Option<OrderLabel> {
+        OrderLabel::try_decode(&self.label)
+    }
+
+    /// Get the limit price, if the order has one
+    pub fn limit_price(&self) -> Option<f64> {
+        self.price.as_ref().and_then(OrderPrice::value)
+    }
+
+    /// Get the amount still open
+    #[must_use]
+    pub fn remaining_amount(&self) -> f64 {
+        (self.amount - self.filled_amount).max(0.0)
+    }
+}
+
+/// Entry of the trigger order log returned by `private/get_trigger_order_history`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerOrderHistoryEntry {
+    /// Requested order size
+    pub amount: f64,
+    /// Order direction (buy/sell)
+    pub direction: String,
+    /// Instrument name
+    pub instrument_name: String,
+    /// Id of the order spawned by the trigger, or of the trigger order itself
+    pub order_id: String,
+    /// Order state: `triggered`, `cancelled` or `rejected` with a reason
+    pub order_state: String,
+    /// Last request performed on the trigger order, e.g. `trigger:order`
+    pub request: String,
+    /// Event timestamp (milliseconds since Unix epoch)
+    pub timestamp: u64,
+    /// Id of the trigger order before triggering
+    pub trigger_order_id: String,
+    /// Order price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Trigger type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<String>,
+    /// Trigger price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<f64>,
+    /// Maximum deviation from the price peak for trailing trigger orders
+    #[serde(
+        rename = "offset",
+        alias = "trigger_offset",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub trigger_offset: Option<f64>,
+    /// Requested order type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+    /// Order label
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Whether this is a post-only order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_only: Option<bool>,
+    /// Whether this order only reduces position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    /// Whether the order can be triggered by another order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_secondary_oto: Option<bool>,
+    /// Reference of the one-cancels-others pair
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oco_ref: Option<String>,
+    /// Source of the order linked to the trigger order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Last update timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update_timestamp: Option<u64>,
+}
+
+/// Page of the trigger order log
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerOrderHistory {
+    /// Log entries in this page
+    pub entries: Vec<TriggerOrderHistoryEntry>,
+    /// Continuation token for the next page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
 }
 
 #[cfg(test)]
@@ -342,6 +585,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_order_status_fix_round_trip() {
+        let statuses = [
+            OrderStatus::New,
+            OrderStatus::PartiallyFilled,
+            OrderStatus::Filled,
+            OrderStatus::DoneForDay,
+            OrderStatus::Canceled,
+            OrderStatus::Replaced,
+            OrderStatus::PendingCancel,
+            OrderStatus::Stopped,
+            OrderStatus::Rejected,
+            OrderStatus::Suspended,
+            OrderStatus::PendingNew,
+            OrderStatus::Calculated,
+            OrderStatus::Expired,
+            OrderStatus::AcceptedForBidding,
+            OrderStatus::PendingReplace,
+        ];
+
+        for status in statuses {
+            assert_eq!(OrderStatus::from_fix(status.as_fix()), Some(status));
+        }
+        assert_eq!(OrderStatus::from_fix("4"), Some(OrderStatus::Canceled));
+        assert_eq!(OrderStatus::from_fix("Z"), None);
+    }
+
     #[test]
     fn test_order_info_creation() {
         let order_info = OrderInfo {
@@ -349,7 +619,7 @@ mod tests {
             api: true,
             average_price: 50000.0,
             creation_timestamp: 1640995200000,
-            direction: "buy".to_string(),
+            direction: OrderSide::Buy,
             filled_amount: 0.5,
             instrument_name: "BTC-PERPETUAL".to_string(),
             is_liquidation: false,
@@ -357,20 +627,32 @@ mod tests {
             last_update_timestamp: 1640995300000,
             max_show: Some(0.8),
             order_id: "ORDER_123".to_string(),
-            order_state: "open".to_string(),
-            order_type: "limit".to_string(),
+            order_state: OrderStatus::New,
+            order_type: OrderType::Limit,
             original_order_type: None,
             post_only: false,
-            price: 50000.0,
+            price: Some(OrderPrice::Value(50000.0)),
             profit_loss: Some(100.0),
             reduce_only: false,
             replaced: false,
             risk_reducing: false,
-            time_in_force: "good_til_cancelled".to_string(),
+            time_in_force: TimeInForce::GoodTilCancelled,
             triggered: Some(false),
             trigger: None,
             usd: Some(50000.0),
             web: false,
+            trigger_price: None,
+            implv: None,
+            advanced: None,
+            contracts: None,
+            is_rebalance: None,
+            cancel_reason: None,
+            quote: None,
+            quote_id: None,
+            quote_set_id: None,
+            mmp: None,
+            mmp_group: None,
+            mmp_cancelled: None,
         };
 
         assert_eq!(order_info.amount, 1.0);
@@ -389,7 +671,7 @@ mod tests {
             api: true,
             average_price: 0.0,
             creation_timestamp: 1640995200000,
-            direction: "buy".to_string(),
+            direction: OrderSide::Buy,
             filled_amount: 0.0,
             instrument_name: "BTC-PERPETUAL".to_string(),
             is_liquidation: false,
@@ -397,20 +679,32 @@ mod tests {
             last_update_timestamp: 1640995200000,
             max_show: None,
             order_id: "ORDER_123".to_string(),
-            order_state: "new".to_string(),
-            order_type: "limit".to_string(),
+            order_state: OrderStatus::New,
+            order_type: OrderType::Limit,
             original_order_type: None,
             post_only: false,
-            price: 50000.0,
+            price: Some(OrderPrice::Value(50000.0)),
             profit_loss: None,
             reduce_only: false,
             replaced: false,
             risk_reducing: false,
-            time_in_force: "good_til_cancelled".to_string(),
+            time_in_force: TimeInForce::GoodTilCancelled,
             triggered: None,
             trigger: None,
             usd: None,
             web: false,
+            trigger_price: None,
+            implv: None,
+            advanced: None,
+            contracts: None,
+            is_rebalance: None,
+            cancel_reason: None,
+            quote: None,
+            quote_id: None,
+            quote_set_id: None,
+            mmp: None,
+            mmp_group: None,
+            mmp_cancelled: None,
         };
 
         assert_eq!(minimal_order_info.max_show, None);
@@ -528,4 +822,86 @@ mod tests {
             assert_eq!(tif, deserialized);
         }
     }
+
+    #[test]
+    fn test_order_status_json_round_trip() {
+        for (state, status) in [
+            ("open", OrderStatus::New),
+            ("filled", OrderStatus::Filled),
+            ("rejected", OrderStatus::Rejected),
+            ("cancelled", OrderStatus::Canceled),
+            ("untriggered", OrderStatus::Suspended),
+            ("triggered", OrderStatus::Stopped),
+        ] {
+            let json = format!("\"{state}\"");
+            assert_eq!(serde_json::from_str::<OrderStatus>(&json).unwrap(), status);
+            assert_eq!(serde_json::to_string(&status).unwrap(), json);
+        }
+        assert_eq!(
+            serde_json::to_string(&OrderStatus::PartiallyFilled).unwrap(),
+            "\"open\""
+        );
+        assert!(OrderStatus::Suspended.is_open());
+        assert!(!OrderStatus::Expired.is_open());
+    }
+
+    #[test]
+    fn test_order_info_deserialization() {
+        let json = r#"{
+            "web": false,
+            "time_in_force": "good_til_cancelled",
+            "price": "market_price",
+            "post_only": false,
+            "order_type": "stop_market",
+            "order_state": "untriggered",
+            "order_id": "ETH-SLTS-28",
+            "max_show": 123,
+            "last_update_timestamp": 1550659803407,
+            "label": "",
+            "is_liquidation": false,
+            "instrument_name": "ETH-PERPETUAL",
+            "direction": "sell",
+            "creation_timestamp": 1550659803407,
+            "api": true,
+            "amount": 123,
+            "trigger": "last_price",
+            "trigger_price": 145.61
+        }"#;
+        let order: OrderInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(order.direction, OrderSide::Sell);
+        assert_eq!(order.order_state, OrderStatus::Suspended);
+        assert_eq!(order.order_type, OrderType::StopMarket);
+        assert_eq!(order.trigger, Some(TriggerType::LastPrice));
+        assert_eq!(order.limit_price(), None);
+        assert_eq!(order.filled_amount, 0.0);
+        assert_eq!(order.remaining_amount(), 123.0);
+        assert!(!order.replaced);
+    }
+
+    #[test]
+    fn test_trigger_order_history_deserialization() {
+        let json = r#"{
+            "entries": [{
+                "trigger": "index",
+                "timestamp": 1555918941451,
+                "trigger_price": 5285,
+                "trigger_order_id": "SLIS-103",
+                "order_state": "new",
+                "request": "trigger:order",
+                "price": 5179.28,
+                "order_id": "671473",
+                "offset": 277,
+                "instrument_name": "BTC-PERPETUAL",
+                "amount": 10,
+                "direction": "buy"
+            }],
+            "continuation": "1555918941451.SLIS-103"
+        }"#;
+        let history: TriggerOrderHistory = serde_json::from_str(json).unwrap();
+        assert_eq!(history.entries[0].trigger_offset, Some(277.0));
+        assert_eq!(
+            history.continuation.as_deref(),
+            Some("1555918941451.SLIS-103")
+        );
+    }
 }
@@ -3,8 +3,11 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::amount::{AmountError, OrderSize};
+use crate::model::instrument::Instrument;
 use crate::model::order::{OrderSide, OrderType, TimeInForce};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
+use std::collections::{HashMap, HashSet};
 
 use crate::model::request::{LinkedOrderType, TriggerFillCondition};
 use crate::prelude::{AdvancedOrderType, TriggerType};
@@ -16,45 +19,80 @@ pub struct BuyOrderRequest {
     /// Name of the instrument to trade
     pub instrument_name: String,
     /// Amount/quantity to buy
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub amount: Option<f64>,
     /// Number of contracts to buy
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contracts: Option<f64>,
     /// Type of order to place
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub type_: Option<OrderType>,
     /// User-defined label for the order
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
     /// Order price (required for limit orders)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub price: Option<f64>,
     /// Time in force specification
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub time_in_force: Option<TimeInForce>,
     /// Amount to display in the order book (iceberg orders)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub display_amount: Option<f64>,
     /// Whether this is a post-only order
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub post_only: Option<bool>,
     /// If true, reject order if it would be a taker
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reject_post_only: Option<bool>,
     /// Whether this order only reduces position
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reduce_only: Option<bool>,
     /// Trigger price for stop/take orders
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trigger_price: Option<f64>,
     /// Trigger offset from mark price
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trigger_offset: Option<f64>,
     /// Trigger type (index_price, mark_price, last_price)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trigger: Option<TriggerType>,
     /// Advanced order type (usd, implv)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub advanced: Option<AdvancedOrderType>,
     /// Market maker protection flag
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mmp: Option<bool>,
     /// Order validity timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub valid_until: Option<i64>,
     /// Type of linked order (OTO, OCO, OTOCO)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linked_order_type: Option<LinkedOrderType>,
     /// Trigger fill condition for linked orders
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trigger_fill_condition: Option<TriggerFillCondition>,
     /// OTOCO configuration for linked orders
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub otoco_config: Option<Vec<String>>,
 }
 
+impl BuyOrderRequest {
+    /// Set the amount from a size in any unit, clearing `contracts`
+    ///
+    /// See [`Instrument::order_amount`] for the conversion and rounding rules.
+    pub fn with_size(
+        mut self,
+        instrument: &Instrument,
+        size: OrderSize,
+        reference_price: f64,
+    ) -> Result<Self, AmountError> {
+        self.amount = Some(instrument.order_amount(size, reference_price)?);
+        self.contracts = None;
+        Ok(self)
+    }
+}
+
 /// Sell order request
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct SellOrderRequest {
@@ -63,19 +101,40 @@ pub struct SellOrderRequest {
     /// Amount/quantity to sell
     pub amount: f64,
     /// Order price (required for limit orders)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub price: Option<f64>,
     /// User-defined label for the order
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
     /// Time in force specification
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub time_in_force: Option<TimeInForce>,
     /// Whether this order only reduces position
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reduce_only: Option<bool>,
     /// Whether this is a post-only order
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub post_only: Option<bool>,
     /// Type of order to place
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub type_: Option<OrderType>,
 }
 
+impl SellOrderRequest {
+    /// Set the amount from a size in any unit
+    ///
+    /// See [`Instrument::order_amount`] for the conversion and rounding rules.
+    pub fn with_size(
+        mut self,
+        instrument: &Instrument,
+        size: OrderSize,
+        reference_price: f64,
+    ) -> Result<Self, AmountError> {
+        self.amount = instrument.order_amount(size, reference_price)?;
+        Ok(self)
+    }
+}
+
 /// Edit order request
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct EditOrderRequest {
@@ -93,26 +152,577 @@ pub struct EditOrderRequest {
     pub time_in_force: Option<TimeInForce>,
 }
 
+/// Side of a two-sided quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteSide {
+    /// Bid (buy) side
+    Bid,
+    /// Ask (sell) side
+    Ask,
+}
+
+impl QuoteSide {
+    /// Get the string representation for API requests
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bid => "bid",
+            Self::Ask => "ask",
+        }
+    }
+
+    /// Get the order side this quote side results in
+    #[must_use]
+    pub fn order_side(&self) -> OrderSide {
+        match self {
+            Self::Bid => OrderSide::Buy,
+            Self::Ask => OrderSide::Sell,
+        }
+    }
+}
+
+impl std::fmt::Display for QuoteSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Order details for one side of a mass quote
+///
+/// If no price is supplied only the amount is amended, and vice versa.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct QuoteLevel {
+    /// Price of this side of the quote
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Amount of this side of the quote
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// Whether this side is post-only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_only: Option<bool>,
+    /// If true, reject instead of repricing a post-only side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject_post_only: Option<bool>,
+}
+
+impl QuoteLevel {
+    /// Create a new quote level with price and amount
+    #[must_use]
+    pub fn new(price: f64, amount: f64) -> Self {
+        Self {
+            price: Some(price),
+            amount: Some(amount),
+            post_only: None,
+            reject_post_only: None,
+        }
+    }
+
+    /// Set the post-only flag
+    #[must_use]
+    pub fn with_post_only(mut self, post_only: bool) -> Self {
+        self.post_only = Some(post_only);
+        self
+    }
+
+    /// Set the reject post-only flag
+    #[must_use]
+    pub fn with_reject_post_only(mut self, reject_post_only: bool) -> Self {
+        self.reject_post_only = Some(reject_post_only);
+        self
+    }
+}
+
 /// Mass quote request item
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+///
+/// A quote on a single instrument. At least one of `bid` or `ask`
+/// must be present.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MassQuoteItem {
     /// Name of the instrument to quote
     pub instrument_name: String,
-    /// Order side (buy or sell)
-    pub side: OrderSide,
-    /// Quote amount/quantity
-    pub amount: f64,
-    /// Quote price
-    pub price: f64,
+    /// User-defined quote set label, usable for targeted cancels
+    pub quote_set_id: String,
+    /// Bid side of the quote
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bid: Option<QuoteLevel>,
+    /// Ask side of the quote
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ask: Option<QuoteLevel>,
+}
+
+impl MassQuoteItem {
+    /// Create a two-sided quote item
+    #[must_use]
+    pub fn two_sided(
+        instrument_name: String,
+        quote_set_id: String,
+        bid: QuoteLevel,
+        ask: QuoteLevel,
+    ) -> Self {
+        Self {
+            instrument_name,
+            quote_set_id,
+            bid: Some(bid),
+            ask: Some(ask),
+        }
+    }
+
+    /// Create a quote item with a single side
+    #[must_use]
+    pub fn one_sided(
+        instrument_name: String,
+        quote_set_id: String,
+        side: QuoteSide,
+        level: QuoteLevel,
+    ) -> Self {
+        let (bid, ask) = match side {
+            QuoteSide::Bid => (Some(level), None),
+            QuoteSide::Ask => (None, Some(level)),
+        };
+        Self {
+            instrument_name,
+            quote_set_id,
+            bid,
+            ask,
+        }
+    }
+
+    /// Get the quote level for the given side
+    #[must_use]
+    pub fn level(&self, side: QuoteSide) -> Option<&QuoteLevel> {
+        match side {
+            QuoteSide::Bid => self.bid.as_ref(),
+            QuoteSide::Ask => self.ask.as_ref(),
+        }
+    }
+
+    /// Check if both sides are quoted
+    #[must_use]
+    pub fn is_two_sided(&self) -> bool {
+        self.bid.is_some() && self.ask.is_some()
+    }
 }
 
 /// Mass quote request
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+///
+/// Used to place quotes on many instruments at once via `/private/mass_quote`.
+/// Build it with [`MassQuoteBuilder`] to get validation and tick rounding.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MassQuoteRequest {
-    /// List of quote items
-    pub items: Vec<MassQuoteItem>,
-    /// User-defined label for the mass quote
-    pub label: Option<String>,
+    /// Identifier of this mass quote message, used to match trades to requests
+    pub quote_id: String,
+    /// Name of the MMP group the quotes belong to
+    pub mmp_group: String,
+    /// List of quotes
+    pub quotes: Vec<MassQuoteItem>,
+    /// Whether to receive all order changes and errors in the response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+    /// Whether to respond only after the matching engine processed the quotes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for_response: Option<bool>,
+    /// Timestamp in milliseconds after which the request is rejected as timed out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<i64>,
+}
+
+impl MassQuoteRequest {
+    /// Create a new mass quote request
+    #[must_use]
+    pub fn new(quote_id: String, mmp_group: String, quotes: Vec<MassQuoteItem>) -> Self {
+        Self {
+            quote_id,
+            mmp_group,
+            quotes,
+            detailed: None,
+            wait_for_response: None,
+            valid_until: None,
+        }
+    }
+
+    /// Get the number of quoted instruments
+    #[must_use]
+    pub fn quote_count(&self) -> usize {
+        self.quotes.len()
+    }
+
+    /// Get all quoted instrument names
+    #[must_use]
+    pub fn instruments(&self) -> Vec<&str> {
+        self.quotes
+            .iter()
+            .map(|q| q.instrument_name.as_str())
+            .collect()
+    }
+}
+
+/// Maximum length accepted for quote and quote set identifiers
+pub const MAX_QUOTE_ID_LENGTH: usize = 64;
+
+/// Validation error raised while building a mass quote
+#[derive(Debug, Clone, PartialEq)]
+pub enum MassQuoteError {
+    /// Quote id is empty, too long or contains invalid characters
+    InvalidQuoteId(String),
+    /// MMP group name is empty
+    EmptyMmpGroup,
+    /// No quotes were added
+    NoQuotes,
+    /// Quote set id is empty, too long or contains invalid characters
+    InvalidQuoteSetId {
+        /// Instrument the quote belongs to
+        instrument_name: String,
+        /// Offending quote set id
+        quote_set_id: String,
+    },
+    /// Quote has neither a bid nor an ask
+    MissingSides(String),
+    /// Instrument is quoted more than once in the same MMP group
+    DuplicateInstrument(String),
+    /// Price is not a positive finite number
+    InvalidPrice {
+        /// Instrument the quote belongs to
+        instrument_name: String,
+        /// Side of the quote
+        side: QuoteSide,
+        /// Offending price
+        price: f64,
+    },
+    /// Amount is not a positive finite number
+    InvalidAmount {
+        /// Instrument the quote belongs to
+        instrument_name: String,
+        /// Side of the quote
+        side: QuoteSide,
+        /// Offending amount
+        amount: f64,
+    },
+    /// Amount is below the instrument minimum trade amount
+    AmountBelowMinimum {
+        /// Instrument the quote belongs to
+        instrument_name: String,
+        /// Side of the quote
+        side: QuoteSide,
+        /// Offending amount
+        amount: f64,
+        /// Minimum trade amount of the instrument
+        min_trade_amount: f64,
+    },
+    /// Bid price is at or above the ask price
+    CrossedQuote {
+        /// Instrument the quote belongs to
+        instrument_name: String,
+        /// Bid price
+        bid: f64,
+        /// Ask price
+        ask: f64,
+    },
+}
+
+impl std::fmt::Display for MassQuoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidQuoteId(id) => write!(f, "invalid quote id: '{id}'"),
+            Self::EmptyMmpGroup => write!(f, "MMP group must not be empty"),
+            Self::NoQuotes => write!(f, "mass quote contains no quotes"),
+            Self::InvalidQuoteSetId {
+                instrument_name,
+                quote_set_id,
+            } => write!(
+                f,
+                "invalid quote set id '{quote_set_id}' for {instrument_name}"
+            ),
+            Self::MissingSides(instrument) => {
+                write!(f, "quote for {instrument} has neither bid nor ask")
+            }
+            Self::DuplicateInstrument(instrument) => {
+                write!(f, "instrument {instrument} is quoted more than once")
+            }
+            Self::InvalidPrice {
+                instrument_name,
+                side,
+                price,
+            } => write!(f, "invalid {side} price {price} for {instrument_name}"),
+            Self::InvalidAmount {
+                instrument_name,
+                side,
+                amount,
+            } => write!(f, "invalid {side} amount {amount} for {instrument_name}"),
+            Self::AmountBelowMinimum {
+                instrument_name,
+                side,
+                amount,
+                min_trade_amount,
+            } => write!(
+                f,
+                "{side} amount {amount} for {instrument_name} is below minimum {min_trade_amount}"
+            ),
+            Self::CrossedQuote {
+                instrument_name,
+                bid,
+                ask,
+            } => write!(
+                f,
+                "crossed quote for {instrument_name}: bid {bid} >= ask {ask}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MassQuoteError {}
+
+/// Check whether a quote or quote set identifier is acceptable
+fn is_valid_quote_identifier(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_QUOTE_ID_LENGTH && id.chars().all(|c| c.is_ascii_graphic())
+}
+
+/// Builder for [`MassQuoteRequest`]
+///
+/// Collects quotes for many instruments under one MMP group, rounds prices
+/// to each registered instrument's tick (bids down, asks up, so quotes are
+/// never tightened) and validates the result on [`build`](Self::build).
+#[derive(Debug, Clone)]
+pub struct MassQuoteBuilder {
+    quote_id: String,
+    mmp_group: String,
+    quote_set_id: String,
+    detailed: Option<bool>,
+    wait_for_response: Option<bool>,
+    valid_until: Option<i64>,
+    instruments: HashMap<String, Instrument>,
+    quotes: Vec<MassQuoteItem>,
+}
+
+impl MassQuoteBuilder {
+    /// Default quote set id used when none is configured
+    pub const DEFAULT_QUOTE_SET_ID: &'static str = "default";
+
+    /// Create a new builder for the given MMP group and quote id
+    #[must_use]
+    pub fn new(mmp_group: String, quote_id: String) -> Self {
+        Self {
+            quote_id,
+            mmp_group,
+            quote_set_id: Self::DEFAULT_QUOTE_SET_ID.to_string(),
+            detailed: None,
+            wait_for_response: None,
+            valid_until: None,
+            instruments: HashMap::new(),
+            quotes: Vec::new(),
+        }
+    }
+
+    /// Set the quote set id applied to subsequently added quotes
+    #[must_use]
+    pub fn with_quote_set_id(mut self, quote_set_id: String) -> Self {
+        self.quote_set_id = quote_set_id;
+        self
+    }
+
+    /// Request a detailed response
+    #[must_use]
+    pub fn with_detailed(mut self, detailed: bool) -> Self {
+        self.detailed = Some(detailed);
+        self
+    }
+
+    /// Set whether to wait for the matching engine before responding
+    #[must_use]
+    pub fn with_wait_for_response(mut self, wait_for_response: bool) -> Self {
+        self.wait_for_response = Some(wait_for_response);
+        self
+    }
+
+    /// Set the validity deadline in milliseconds
+    #[must_use]
+    pub fn with_valid_until(mut self, valid_until: i64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    /// Register instrument rules (tick size and minimum amount)
+    #[must_use]
+    pub fn with_instrument(mut self, instrument: Instrument) -> Self {
+        self.instruments
+            .insert(instrument.instrument_name.clone(), instrument);
+        self
+    }
+
+    /// Register rules for several instruments
+    #[must_use]
+    pub fn with_instruments<I>(mut self, instruments: I) -> Self
+    where
+        I: IntoIterator<Item = Instrument>,
+    {
+        for instrument in instruments {
+            self.instruments
+                .insert(instrument.instrument_name.clone(), instrument);
+        }
+        self
+    }
+
+    /// Add a two-sided quote
+    #[must_use]
+    pub fn two_sided(mut self, instrument_name: String, bid: QuoteLevel, ask: QuoteLevel) -> Self {
+        let item = MassQuoteItem::two_sided(instrument_name, self.quote_set_id.clone(), bid, ask);
+        self.quotes.push(item);
+        self
+    }
+
+    /// Add a two-sided quote around a mid price
+    ///
+    /// Bid and ask are placed `half_spread` away from `mid`, both with `amount`.
+    #[must_use]
+    pub fn around_mid(
+        self,
+        instrument_name: String,
+        mid: f64,
+        half_spread: f64,
+        amount: f64,
+    ) -> Self {
+        self.two_sided(
+            instrument_name,
+            QuoteLevel::new(mid - half_spread, amount),
+            QuoteLevel::new(mid + half_spread, amount),
+        )
+    }
+
+    /// Add a single-sided quote
+    #[must_use]
+    pub fn one_sided(
+        mut self,
+        instrument_name: String,
+        side: QuoteSide,
+        level: QuoteLevel,
+    ) -> Self {
+        let item =
+            MassQuoteItem::one_sided(instrument_name, self.quote_set_id.clone(), side, level);
+        self.quotes.push(item);
+        self
+    }
+
+    /// Add a pre-built quote item as is
+    #[must_use]
+    pub fn quote(mut self, item: MassQuoteItem) -> Self {
+        self.quotes.push(item);
+        self
+    }
+
+    /// Validate the quotes and build the request
+    pub fn build(self) -> Result<MassQuoteRequest, MassQuoteError> {
+        if !is_valid_quote_identifier(&self.quote_id) {
+            return Err(MassQuoteError::InvalidQuoteId(self.quote_id));
+        }
+        if self.mmp_group.trim().is_empty() {
+            return Err(MassQuoteError::EmptyMmpGroup);
+        }
+        if self.quotes.is_empty() {
+            return Err(MassQuoteError::NoQuotes);
+        }
+
+        let mut seen = HashSet::new();
+        let mut quotes = Vec::with_capacity(self.quotes.len());
+        for mut item in self.quotes {
+            if !is_valid_quote_identifier(&item.quote_set_id) {
+                return Err(MassQuoteError::InvalidQuoteSetId {
+                    instrument_name: item.instrument_name,
+                    quote_set_id: item.quote_set_id,
+                });
+            }
+            if item.bid.is_none() && item.ask.is_none() {
+                return Err(MassQuoteError::MissingSides(item.instrument_name));
+            }
+            if !seen.insert(item.instrument_name.clone()) {
+                return Err(MassQuoteError::DuplicateInstrument(item.instrument_name));
+            }
+
+            let instrument = self.instruments.get(&item.instrument_name);
+            let name = item.instrument_name.clone();
+            if let Some(bid) = item.bid.as_mut() {
+                normalize_level(&name, QuoteSide::Bid, bid, instrument)?;
+            }
+            if let Some(ask) = item.ask.as_mut() {
+                normalize_level(&name, QuoteSide::Ask, ask, instrument)?;
+            }
+
+            if let (Some(bid), Some(ask)) = (
+                item.bid.as_ref().and_then(|l| l.price),
+                item.ask.as_ref().and_then(|l| l.price),
+            ) && bid >= ask
+            {
+                return Err(MassQuoteError::CrossedQuote {
+                    instrument_name: name,
+                    bid,
+                    ask,
+                });
+            }
+            quotes.push(item);
+        }
+
+        Ok(MassQuoteRequest {
+            quote_id: self.quote_id,
+            mmp_group: self.mmp_group,
+            quotes,
+            detailed: self.detailed,
+            wait_for_response: self.wait_for_response,
+            valid_until: self.valid_until,
+        })
+    }
+}
+
+/// Validate one side of a quote and round its price to the instrument tick
+fn normalize_level(
+    instrument_name: &str,
+    side: QuoteSide,
+    level: &mut QuoteLevel,
+    instrument: Option<&Instrument>,
+) -> Result<(), MassQuoteError> {
+    if let Some(price) = level.price {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(MassQuoteError::InvalidPrice {
+                instrument_name: instrument_name.to_string(),
+                side,
+                price,
+            });
+        }
+        if let Some(instrument) = instrument {
+            let rounded = match side {
+                QuoteSide::Bid => instrument.floor_to_tick(price),
+                QuoteSide::Ask => instrument.ceil_to_tick(price),
+            };
+            if rounded <= 0.0 {
+                return Err(MassQuoteError::InvalidPrice {
+                    instrument_name: instrument_name.to_string(),
+                    side,
+                    price,
+                });
+            }
+            level.price = Some(rounded);
+        }
+    }
+
+    if let Some(amount) = level.amount {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(MassQuoteError::InvalidAmount {
+                instrument_name: instrument_name.to_string(),
+                side,
+                amount,
+            });
+        }
+        if let Some(min_trade_amount) = instrument.and_then(|i| i.min_trade_amount)
+            && amount < min_trade_amount
+        {
+            return Err(MassQuoteError::AmountBelowMinimum {
+                instrument_name: instrument_name.to_string(),
+                side,
+                amount,
+                min_trade_amount,
+            });
+        }
+    }
+    Ok(())
 }
 
 /// Transfer result for order-related transfers (e.g., fee rebates)
@@ -124,35 +734,246 @@ pub struct TransferResult {
     pub status: String,
 }
 
-/// Quote result
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
-pub struct QuoteResult {
-    /// Name of the instrument that was quoted
+/// Error reported for one side of a mass quote
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteError {
+    /// Name of the instrument the error refers to
     pub instrument_name: String,
-    /// Whether the quote was successful
-    pub success: bool,
-    /// Error message if quote failed
-    pub error: Option<String>,
+    /// Side of the quote that failed
+    pub side: QuoteSide,
+    /// Error code and message
+    pub error: QuoteErrorDetail,
+}
+
+/// Error code and message of a rejected quote side
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteErrorDetail {
+    /// Deribit error code
+    pub code: i32,
+    /// Error message
+    pub message: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::instrument::{InstrumentKind, TickSizeStep};
+
+    fn instrument(name: &str, tick_size: f64, min_trade_amount: f64) -> Instrument {
+        serde_json::from_value(serde_json::json!({
+            "instrument_name": name,
+            "kind": "future",
+            "tick_size": tick_size,
+            "min_trade_amount": min_trade_amount,
+        }))
+        .unwrap()
+    }
 
     #[test]
     fn test_mass_quote_request() {
-        let item = MassQuoteItem {
+        let item = MassQuoteItem::two_sided(
+            "BTC-PERPETUAL".to_string(),
+            "futures".to_string(),
+            QuoteLevel::new(43700.0, 10.0),
+            QuoteLevel::new(43800.0, 10.0),
+        );
+        let req = MassQuoteRequest::new("1".to_string(), "default".to_string(), vec![item]);
+        let json = serde_json::to_string(&req).unwrap();
+        let de: MassQuoteRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(de.quotes.len(), 1);
+        assert_eq!(de, req);
+    }
+
+    #[test]
+    fn test_mass_quote_request_serialization_matches_api() {
+        let req = MassQuoteBuilder::new("default".to_string(), "1".to_string())
+            .with_quote_set_id("options".to_string())
+            .one_sided(
+                "BTC-22DEC23-41600-C".to_string(),
+                QuoteSide::Ask,
+                QuoteLevel::new(0.05, 1.0).with_post_only(true),
+            )
+            .with_detailed(true)
+            .build()
+            .unwrap();
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["mmp_group"], "default");
+        assert_eq!(value["detailed"], true);
+        assert_eq!(value["quotes"][0]["quote_set_id"], "options");
+        assert_eq!(value["quotes"][0]["ask"]["post_only"], true);
+        assert!(value["quotes"][0].get("bid").is_none());
+        assert!(value.get("valid_until").is_none());
+    }
+
+    #[test]
+    fn test_quote_side() {
+        assert_eq!(QuoteSide::Bid.as_str(), "bid");
+        assert_eq!(QuoteSide::Ask.to_string(), "ask");
+        assert_eq!(QuoteSide::Bid.order_side(), OrderSide::Buy);
+        assert_eq!(QuoteSide::Ask.order_side(), OrderSide::Sell);
+    }
+
+    #[test]
+    fn test_builder_rounds_to_tick() {
+        let req = MassQuoteBuilder::new("mm".to_string(), "7".to_string())
+            .with_instrument(instrument("BTC-PERPETUAL", 0.5, 10.0))
+            .around_mid("BTC-PERPETUAL".to_string(), 43750.2, 40.0, 10.0)
+            .build()
+            .unwrap();
+        let quote = &req.quotes[0];
+        assert_eq!(quote.bid.as_ref().unwrap().price, Some(43710.0));
+        assert_eq!(quote.ask.as_ref().unwrap().price, Some(43790.5));
+        assert_eq!(quote.quote_set_id, MassQuoteBuilder::DEFAULT_QUOTE_SET_ID);
+    }
+
+    #[test]
+    fn test_builder_uses_tick_size_steps() {
+        let mut option = instrument("BTC-22DEC23-41600-C", 0.0005, 0.1);
+        option.kind = Some(InstrumentKind::Option);
+        option.tick_size_steps = Some(vec![TickSizeStep {
+            above_price: 0.005,
+            tick_size: 0.001,
+        }]);
+        let req = MassQuoteBuilder::new("mm".to_string(), "1".to_string())
+            .with_instruments(vec![option])
+            .two_sided(
+                "BTC-22DEC23-41600-C".to_string(),
+                QuoteLevel::new(0.0412, 1.0),
+                QuoteLevel::new(0.0501, 1.0),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(req.quotes[0].bid.as_ref().unwrap().price, Some(0.041));
+        assert_eq!(req.quotes[0].ask.as_ref().unwrap().price, Some(0.051));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_ids() {
+        let err = MassQuoteBuilder::new("mm".to_string(), String::new())
+            .around_mid("BTC-PERPETUAL".to_string(), 100.0, 1.0, 10.0)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, MassQuoteError::InvalidQuoteId(String::new()));
+
+        let err = MassQuoteBuilder::new("mm".to_string(), "has space".to_string())
+            .around_mid("BTC-PERPETUAL".to_string(), 100.0, 1.0, 10.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, MassQuoteError::InvalidQuoteId(_)));
+
+        let err = MassQuoteBuilder::new(" ".to_string(), "1".to_string())
+            .around_mid("BTC-PERPETUAL".to_string(), 100.0, 1.0, 10.0)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, MassQuoteError::EmptyMmpGroup);
+
+        let err = MassQuoteBuilder::new("mm".to_string(), "1".to_string())
+            .with_quote_set_id(String::new())
+            .around_mid("BTC-PERPETUAL".to_string(), 100.0, 1.0, 10.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, MassQuoteError::InvalidQuoteSetId { .. }));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_quotes() {
+        let builder = MassQuoteBuilder::new("mm".to_string(), "1".to_string());
+        assert_eq!(
+            builder.clone().build().unwrap_err(),
+            MassQuoteError::NoQuotes
+        );
+
+        let err = builder
+            .clone()
+            .around_mid("BTC-PERPETUAL".to_string(), 100.0, 1.0, 10.0)
+            .around_mid("BTC-PERPETUAL".to_string(), 101.0, 1.0, 10.0)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MassQuoteError::DuplicateInstrument("BTC-PERPETUAL".to_string())
+        );
+
+        let err = builder
+            .clone()
+            .two_sided(
+                "BTC-PERPETUAL".to_string(),
+                QuoteLevel::new(101.0, 10.0),
+                QuoteLevel::new(100.0, 10.0),
+            )
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, MassQuoteError::CrossedQuote { .. }));
+
+        let err = builder
+            .clone()
+            .one_sided(
+                "BTC-PERPETUAL".to_string(),
+                QuoteSide::Bid,
+                QuoteLevel::new(-1.0, 10.0),
+            )
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MassQuoteError::InvalidPrice {
+                side: QuoteSide::Bid,
+                ..
+            }
+        ));
+
+        let err = builder
+            .clone()
+            .quote(MassQuoteItem {
+                instrument_name: "BTC-PERPETUAL".to_string(),
+                quote_set_id: "set".to_string(),
+                bid: None,
+                ask: None,
+            })
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MassQuoteError::MissingSides("BTC-PERPETUAL".to_string())
+        );
+
+        let err = builder
+            .with_instrument(instrument("BTC-PERPETUAL", 0.5, 10.0))
+            .around_mid("BTC-PERPETUAL".to_string(), 100.0, 1.0, 5.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, MassQuoteError::AmountBelowMinimum { .. }));
+    }
+
+    #[test]
+    fn test_quote_error_deserialization() {
+        let json = r#"{
+            "instrument_name": "BTC-PERPETUAL",
+            "side": "bid",
+            "error": {"message": "price_too_high 43666.4288", "code": 10007}
+        }"#;
+        let error: QuoteError = serde_json::from_str(json).unwrap();
+        assert_eq!(error.side, QuoteSide::Bid);
+        assert_eq!(error.error.code, 10007);
+    }
+
+    #[test]
+    fn test_sell_order_request_serialization_matches_api() {
+        let request = SellOrderRequest {
             instrument_name: "BTC-PERPETUAL".to_string(),
-            side: OrderSide::Buy,
             amount: 10.0,
-            price: 50000.0,
-        };
-        let req = MassQuoteRequest {
-            items: vec![item],
-            label: Some("test".to_string()),
+            price: Some(50000.0),
+            label: None,
+            time_in_force: None,
+            reduce_only: None,
+            post_only: Some(true),
+            type_: Some(OrderType::Limit),
         };
-        let json = serde_json::to_string(&req).unwrap();
-        let de: MassQuoteRequest = serde_json::from_str(&json).unwrap();
-        assert_eq!(de.items.len(), 1);
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["type"], "limit");
+        assert!(value.get("type_").is_none());
+        assert!(value.get("label").is_none());
+        let back: SellOrderRequest = serde_json::from_value(value).unwrap();
+        assert_eq!(back.type_, Some(OrderType::Limit));
     }
 }
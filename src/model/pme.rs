@@ -0,0 +1,313 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Portfolio margin engine (PME) simulation results
+//!
+//! `private/pme/simulate` runs the portfolio margin model on the current or
+//! a hypothetical portfolio and returns the model parameters, the risk
+//! vectors per instrument and the resulting margins per currency.
+
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Model parameters of one currency pair (e.g., `btc_usd`)
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeCurrencyPairParams {
+    /// Extended table factor
+    pub extended_table_factor: f64,
+    /// Margin increment
+    pub m_inc: f64,
+    /// Minimum volatility for an upward volatility shock
+    pub min_volatility_for_shock_up: f64,
+    /// Maximum delta shock
+    pub max_delta_shock: f64,
+    /// Total delta threshold for the liquidity shock
+    pub delta_total_liq_shock_threshold: f64,
+    /// Downward volatility range
+    pub volatility_range_down: f64,
+    /// Upward volatility range
+    pub volatility_range_up: f64,
+    /// Long term vega power
+    pub long_term_vega_power: f64,
+    /// Short term vega power
+    pub short_term_vega_power: f64,
+    /// Price range
+    pub price_range: f64,
+}
+
+/// Model parameters of one currency (e.g., `btc`)
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeCurrencyParams {
+    /// Maximum offsetable profit and loss
+    pub max_offsetable_pnl: f64,
+    /// Annualised move risk
+    pub annualised_move_risk: f64,
+    /// Extended dampener
+    pub extended_dampener: f64,
+    /// Minimum annualised move
+    pub min_annualised_move: f64,
+    /// Haircut applied to the currency
+    pub haircut: f64,
+    /// Side of the equity impact (`none`, `left`, `right` or `both`)
+    pub equity_side_impact: String,
+    /// Profit and loss offset
+    pub pnl_offset: f64,
+    /// Whether the currency belongs to a correlation set
+    pub correlation_set: bool,
+}
+
+/// Model parameters shared by all currencies
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeGeneralParams {
+    /// Maintenance margin factor
+    pub mm_factor: f64,
+    /// Number of price buckets
+    pub buckets_count: u32,
+    /// Number of volatility scenarios
+    pub vol_scenarios_count: u32,
+    /// Timestamp of the parameters in milliseconds
+    pub timestamp: u64,
+}
+
+/// Parameters of the margin model
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeModelParams {
+    /// Parameters per currency pair
+    #[serde(default)]
+    pub currency_pair: HashMap<String, PmeCurrencyPairParams>,
+    /// Parameters per currency
+    #[serde(default)]
+    pub currency: HashMap<String, PmeCurrencyParams>,
+    /// General parameters
+    pub general: PmeGeneralParams,
+}
+
+/// Profit and loss per scenario
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeRiskVector {
+    /// Standard scenarios
+    pub standard: Vec<f64>,
+    /// Extended scenarios
+    pub extended: Vec<f64>,
+}
+
+/// Scenario bucket with the worst outcome
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeWorstCaseBucket {
+    /// Bucket number
+    pub bucket: u32,
+    /// Side of the bucket (`left` or `right`)
+    pub side: String,
+    /// Scenario set (`standard` or `extended`)
+    pub source: String,
+    /// Index of the scenario in the risk vector
+    pub index: u32,
+}
+
+/// Components of the risk matrix margin
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeRiskMatrixMarginDetails {
+    /// Delta shock
+    pub delta_shock: f64,
+    /// Roll shock
+    pub roll_shock: f64,
+    /// Scenario bucket with the worst outcome
+    pub worst_case_bucket: PmeWorstCaseBucket,
+    /// Worst case loss
+    pub worst_case: f64,
+    /// Correlation contingency
+    pub correlation_contingency: f64,
+}
+
+/// Components of the initial margin
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeInitialMarginDetails {
+    /// Components of the risk matrix margin
+    pub risk_matrix_margin_details: PmeRiskMatrixMarginDetails,
+    /// Risk matrix margin
+    pub risk_matrix_margin: f64,
+    /// Spot margin
+    pub spot_margin: f64,
+    /// Margin reserved by market maker protection
+    pub mmp_margin: f64,
+    /// Margin of open orders
+    pub open_orders_margin: f64,
+}
+
+/// Margins of one currency
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeMargin {
+    /// Components of the initial margin
+    pub initial_margin_details: PmeInitialMarginDetails,
+    /// Initial margin
+    pub initial_margin: f64,
+    /// Maintenance margin
+    pub maintenance_margin: f64,
+}
+
+/// Simulated portfolio
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmePortfolio {
+    /// Balance per currency
+    #[serde(default)]
+    pub currency: HashMap<String, f64>,
+    /// Position size per instrument
+    #[serde(default)]
+    pub position: HashMap<String, f64>,
+}
+
+/// Prices of one instrument used by the simulation
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeTicker {
+    /// Mark price
+    pub mark_price: f64,
+    /// Index price
+    pub index_price: f64,
+}
+
+/// Result of `private/pme/simulate`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmeSimulation {
+    /// Parameters of the margin model
+    pub model_params: PmeModelParams,
+    /// Risk vectors aggregated per currency pair
+    #[serde(default)]
+    pub aggregated_risk_vectors: HashMap<String, PmeRiskVector>,
+    /// Risk vectors per instrument
+    #[serde(default)]
+    pub initial_risk_vectors: HashMap<String, PmeRiskVector>,
+    /// Margins per currency
+    #[serde(default)]
+    pub margins: HashMap<String, PmeMargin>,
+    /// Simulated portfolio
+    pub portfolio: PmePortfolio,
+    /// Index price per currency pair
+    #[serde(default)]
+    pub index_price: HashMap<String, f64>,
+    /// Prices per instrument
+    #[serde(default)]
+    pub ticker: HashMap<String, PmeTicker>,
+}
+
+impl PmeSimulation {
+    /// Get the initial margin of a currency (e.g., `btc`)
+    #[must_use]
+    pub fn initial_margin(&self, currency: &str) -> Option<f64> {
+        self.margins
+            .get(currency)
+            .map(|margin| margin.initial_margin)
+    }
+
+    /// Get the maintenance margin of a currency (e.g., `btc`)
+    #[must_use]
+    pub fn maintenance_margin(&self, currency: &str) -> Option<f64> {
+        self.margins
+            .get(currency)
+            .map(|margin| margin.maintenance_margin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pme_simulation_deserialization() {
+        let json = r#"{
+            "model_params": {
+                "currency_pair": {
+                    "btc_usd": {
+                        "extended_table_factor": 1,
+                        "m_inc": 0.00005,
+                        "min_volatility_for_shock_up": 0.5,
+                        "max_delta_shock": 0.1,
+                        "delta_total_liq_shock_threshold": 20000000,
+                        "volatility_range_down": 0.25,
+                        "volatility_range_up": 0.5,
+                        "long_term_vega_power": 0.13,
+                        "short_term_vega_power": 0.3,
+                        "price_range": 0.16
+                    }
+                },
+                "currency": {
+                    "btc": {
+                        "max_offsetable_pnl": 0,
+                        "annualised_move_risk": 0.075,
+                        "extended_dampener": 100000,
+                        "min_annualised_move": 0.01,
+                        "haircut": 0,
+                        "equity_side_impact": "both",
+                        "pnl_offset": 0,
+                        "correlation_set": false
+                    }
+                },
+                "general": {
+                    "mm_factor": 0.8,
+                    "buckets_count": 4,
+                    "vol_scenarios_count": 3,
+                    "timestamp": 1718619740501
+                }
+            },
+            "aggregated_risk_vectors": {
+                "btc_btc": {"standard": [-0.01, 0.0, 0.01], "extended": [0, 0]}
+            },
+            "initial_risk_vectors": {
+                "BTC-PERPETUAL": {"standard": [-0.01, 0.0, 0.01], "extended": [0.0, 0.0]}
+            },
+            "margins": {
+                "btc": {
+                    "initial_margin_details": {
+                        "risk_matrix_margin_details": {
+                            "delta_shock": 0,
+                            "roll_shock": 0.00315725898,
+                            "worst_case_bucket": {
+                                "bucket": 1,
+                                "side": "left",
+                                "source": "standard",
+                                "index": 1
+                            },
+                            "worst_case": 0.05968587238095239,
+                            "correlation_contingency": 0
+                        },
+                        "risk_matrix_margin": 0.06284313098,
+                        "spot_margin": 0,
+                        "mmp_margin": 0.06,
+                        "open_orders_margin": 0.000018212
+                    },
+                    "initial_margin": 0.122861343,
+                    "maintenance_margin": 0.050274504784
+                }
+            },
+            "portfolio": {"position": {"BTC-PERPETUAL": 0.314538364}},
+            "index_price": {"btc_usd": 65666.19},
+            "ticker": {"BTC-PERPETUAL": {"mark_price": 65910.57, "index_price": 65666.19}}
+        }"#;
+
+        let simulation: PmeSimulation = serde_json::from_str(json).unwrap();
+        assert_eq!(simulation.model_params.general.buckets_count, 4);
+        assert_eq!(
+            simulation.model_params.currency["btc"].equity_side_impact,
+            "both"
+        );
+        assert_eq!(simulation.initial_margin("btc"), Some(0.122861343));
+        assert_eq!(simulation.maintenance_margin("btc"), Some(0.050274504784));
+        assert_eq!(simulation.initial_margin("eth"), None);
+        assert_eq!(
+            simulation.margins["btc"]
+                .initial_margin_details
+                .risk_matrix_margin_details
+                .worst_case_bucket
+                .side,
+            "left"
+        );
+        assert_eq!(simulation.ticker["BTC-PERPETUAL"].mark_price, 65910.57);
+
+        let value = serde_json::to_value(&simulation).unwrap();
+        let again: PmeSimulation = serde_json::from_value(value).unwrap();
+        assert_eq!(again, simulation);
+    }
+}
@@ -3,6 +3,8 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::amount::AmountError;
+use crate::model::instrument::Instrument;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +16,8 @@ pub enum Direction {
     Buy,
     /// Sell direction
     Sell,
+    /// No open position
+    Zero,
 }
 
 /// Position structure
@@ -72,3 +76,139 @@ pub struct Position {
     /// Unrealized profit/loss
     pub unrealized_profit_loss: Option<f64>,
 }
+
+impl Position {
+    /// Get the USD price of one unit of base currency used for conversions
+    ///
+    /// Options are priced in coin, so the index price is used for them;
+    /// other instruments use the mark price, falling back to the index price.
+    pub fn reference_price(&self, instrument: &Instrument) -> Option<f64> {
+        if instrument.is_option() {
+            self.index_price
+        } else {
+            self.mark_price.or(self.index_price)
+        }
+    }
+
+    /// Get the position size in base currency
+    ///
+    /// Uses `size_currency` when reported, otherwise converts `size`.
+    pub fn size_in_coin(&self, instrument: &Instrument) -> Result<f64, AmountError> {
+        if let Some(size_currency) = self.size_currency {
+            return Ok(size_currency);
+        }
+        instrument.amount_to_coin(self.size, self.reference_price_or_nan(instrument))
+    }
+
+    /// Get the position size as a USD notional
+    pub fn size_in_usd(&self, instrument: &Instrument) -> Result<f64, AmountError> {
+        instrument.amount_to_usd(self.size, self.reference_price_or_nan(instrument))
+    }
+
+    /// Get the position size in contracts
+    pub fn size_in_contracts(&self, instrument: &Instrument) -> Result<f64, AmountError> {
+        instrument.amount_to_contracts(self.size)
+    }
+
+    /// Reference price, or NaN so conversions that need it report `InvalidPrice`
+    fn reference_price_or_nan(&self, instrument: &Instrument) -> f64 {
+        self.reference_price(instrument).unwrap_or(f64::NAN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::instrument::{InstrumentKind, InstrumentType};
+
+    fn create_test_position(size: f64) -> Position {
+        Position {
+            average_price: 50000.0,
+            average_price_usd: None,
+            delta: None,
+            direction: Direction::Buy,
+            estimated_liquidation_price: None,
+            floating_profit_loss: None,
+            floating_profit_loss_usd: None,
+            gamma: None,
+            index_price: Some(49900.0),
+            initial_margin: None,
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            interest_value: None,
+            kind: Some("future".to_string()),
+            leverage: None,
+            maintenance_margin: None,
+            mark_price: Some(50000.0),
+            open_orders_margin: None,
+            realized_funding: None,
+            realized_profit_loss: None,
+            settlement_price: None,
+            size,
+            size_currency: None,
+            theta: None,
+            total_profit_loss: None,
+            vega: None,
+            unrealized_profit_loss: None,
+        }
+    }
+
+    fn create_inverse_perpetual() -> Instrument {
+        Instrument {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            price_index: Some("btc_usd".to_string()),
+            kind: Some(InstrumentKind::Future),
+            currency: Some("BTC".to_string()),
+            is_active: Some(true),
+            expiration_timestamp: None,
+            strike: None,
+            option_type: None,
+            tick_size: Some(0.5),
+            tick_size_steps: None,
+            min_trade_amount: Some(10.0),
+            contract_size: Some(10.0),
+            settlement_period: Some("perpetual".to_string()),
+            instrument_type: Some(InstrumentType::Reversed),
+            quote_currency: Some("USD".to_string()),
+            settlement_currency: Some("BTC".to_string()),
+            creation_timestamp: None,
+            max_leverage: None,
+            maker_commission: None,
+            taker_commission: None,
+            instrument_id: None,
+            base_currency: Some("BTC".to_string()),
+            counter_currency: Some("USD".to_string()),
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
+        }
+    }
+
+    #[test]
+    fn test_inverse_position_sizes() {
+        let instrument = create_inverse_perpetual();
+        let position = create_test_position(25000.0);
+        assert_eq!(position.reference_price(&instrument), Some(50000.0));
+        assert_eq!(position.size_in_coin(&instrument).unwrap(), 0.5);
+        assert_eq!(position.size_in_usd(&instrument).unwrap(), 25000.0);
+        assert_eq!(position.size_in_contracts(&instrument).unwrap(), 2500.0);
+
+        let mut reported = create_test_position(25000.0);
+        reported.size_currency = Some(0.499);
+        assert_eq!(reported.size_in_coin(&instrument).unwrap(), 0.499);
+    }
+
+    #[test]
+    fn test_position_sizes_without_price() {
+        let instrument = create_inverse_perpetual();
+        let mut position = create_test_position(25000.0);
+        position.mark_price = None;
+        position.index_price = None;
+        assert!(matches!(
+            position.size_in_coin(&instrument),
+            Err(AmountError::InvalidPrice(_))
+        ));
+        assert_eq!(position.size_in_usd(&instrument).unwrap(), 25000.0);
+    }
+}
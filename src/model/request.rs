@@ -3,7 +3,11 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::amount::{AmountError, OrderSize};
+use crate::model::instrument::Instrument;
 use crate::model::order::{OrderSide, OrderType, TimeInForce};
+use crate::utils::signature::{client_signature, generate_signature_nonce};
+use crate::utils::tools::generate_timestamp;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 
 use serde::{Deserialize, Serialize};
@@ -29,6 +33,9 @@ pub mod fix {
         pub time_in_force: TimeInForce,
         /// Client order ID
         pub client_order_id: Option<String>,
+        /// Reduce only flag
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub reduce_only: Option<bool>,
     }
 
     impl NewOrderRequest {
@@ -42,6 +49,7 @@ pub mod fix {
                 price: None,
                 time_in_force: TimeInForce::ImmediateOrCancel,
                 client_order_id: None,
+                reduce_only: None,
             }
         }
 
@@ -55,6 +63,7 @@ pub mod fix {
                 price: None,
                 time_in_force: TimeInForce::ImmediateOrCancel,
                 client_order_id: None,
+                reduce_only: None,
             }
         }
 
@@ -68,6 +77,7 @@ pub mod fix {
                 price: Some(price),
                 time_in_force: TimeInForce::GoodTilCancelled,
                 client_order_id: None,
+                reduce_only: None,
             }
         }
 
@@ -81,6 +91,7 @@ pub mod fix {
                 price: Some(price),
                 time_in_force: TimeInForce::GoodTilCancelled,
                 client_order_id: None,
+                reduce_only: None,
             }
         }
 
@@ -95,6 +106,12 @@ pub mod fix {
             self.time_in_force = tif;
             self
         }
+
+        /// Set reduce only flag
+        pub fn with_reduce_only(mut self, reduce_only: bool) -> Self {
+            self.reduce_only = Some(reduce_only);
+            self
+        }
     }
 
     /// Convert from REST/WebSocket NewOrderRequest to FIX NewOrderRequest
@@ -108,6 +125,7 @@ pub mod fix {
                 price: rest_order.price,
                 time_in_force: rest_order.time_in_force,
                 client_order_id: rest_order.client_order_id,
+                reduce_only: rest_order.reduce_only,
             }
         }
     }
@@ -123,7 +141,7 @@ pub mod fix {
                 price: fix_order.price,
                 time_in_force: fix_order.time_in_force,
                 post_only: None,
-                reduce_only: None,
+                reduce_only: fix_order.reduce_only,
                 label: None,
                 stop_price: None,
                 trigger: None,
@@ -287,6 +305,19 @@ impl NewOrderRequest {
         self.time_in_force = tif;
         self
     }
+
+    /// Set the amount from a size in any unit
+    ///
+    /// See [`Instrument::order_amount`] for the conversion and rounding rules.
+    pub fn with_size(
+        mut self,
+        instrument: &Instrument,
+        size: OrderSize,
+        reference_price: f64,
+    ) -> Result<Self, AmountError> {
+        self.amount = instrument.order_amount(size, reference_price)?;
+        Ok(self)
+    }
 }
 
 /// Trigger type for stop orders
@@ -394,12 +425,25 @@ pub struct AuthRequest {
     pub grant_type: String,
     /// Client ID
     pub client_id: String,
-    /// Client secret
+    /// Client secret, empty and not sent for the signature grant
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub client_secret: String,
     /// Refresh token (for refresh grant)
     pub refresh_token: Option<String>,
     /// Scope
     pub scope: Option<String>,
+    /// Signing time in milliseconds (for signature grant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// HMAC-SHA256 signature (for signature grant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Single use nonce (for signature grant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// User data covered by the signature (for signature grant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
 }
 
 impl AuthRequest {
@@ -411,6 +455,10 @@ impl AuthRequest {
             client_secret,
             refresh_token: None,
             scope: None,
+            timestamp: None,
+            signature: None,
+            nonce: None,
+            data: None,
         }
     }
 
@@ -422,13 +470,110 @@ impl AuthRequest {
             client_secret,
             refresh_token: Some(refresh_token),
             scope: None,
+            timestamp: None,
+            signature: None,
+            nonce: None,
+            data: None,
+        }
+    }
+
+    /// Create a client signature authentication request
+    ///
+    /// The secret only signs `timestamp`, `nonce` and `data` and is not sent.
+    pub fn client_signature(
+        client_id: String,
+        client_secret: &str,
+        timestamp: u64,
+        nonce: String,
+        data: Option<String>,
+    ) -> Self {
+        let signature = client_signature(
+            client_secret,
+            timestamp,
+            &nonce,
+            data.as_deref().unwrap_or_default(),
+        );
+        Self {
+            grant_type: "client_signature".to_string(),
+            client_id,
+            client_secret: String::new(),
+            refresh_token: None,
+            scope: None,
+            timestamp: Some(timestamp),
+            signature: Some(signature),
+            nonce: Some(nonce),
+            data,
         }
     }
+
+    /// Create a client signature authentication request signed now with a fresh nonce
+    pub fn client_signature_now(client_id: String, client_secret: &str) -> Self {
+        Self::client_signature(
+            client_id,
+            client_secret,
+            generate_timestamp(),
+            generate_signature_nonce(),
+            None,
+        )
+    }
+
+    /// Set the requested scope
+    #[must_use]
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::instrument::{InstrumentKind, InstrumentType};
+
+    #[test]
+    fn test_new_order_request_with_size() {
+        let instrument = Instrument {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            price_index: None,
+            kind: Some(InstrumentKind::Future),
+            currency: Some("BTC".to_string()),
+            is_active: Some(true),
+            expiration_timestamp: None,
+            strike: None,
+            option_type: None,
+            tick_size: Some(0.5),
+            tick_size_steps: None,
+            min_trade_amount: Some(10.0),
+            contract_size: Some(10.0),
+            settlement_period: None,
+            instrument_type: Some(InstrumentType::Reversed),
+            quote_currency: Some("USD".to_string()),
+            settlement_currency: None,
+            creation_timestamp: None,
+            max_leverage: None,
+            maker_commission: None,
+            taker_commission: None,
+            instrument_id: None,
+            base_currency: None,
+            counter_currency: None,
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
+        };
+        let order = NewOrderRequest::limit_buy("BTC-PERPETUAL".to_string(), 0.0, 50000.0)
+            .with_size(&instrument, OrderSize::Coin(0.5), 50000.0)
+            .unwrap();
+        assert_eq!(order.amount, 25000.0);
+
+        let result = NewOrderRequest::market_sell("BTC-PERPETUAL".to_string(), 0.0).with_size(
+            &instrument,
+            OrderSize::Usd(1.0),
+            50000.0,
+        );
+        assert!(matches!(result, Err(AmountError::BelowMinimum { .. })));
+    }
 
     #[test]
     fn test_fix_new_order_request_market_buy() {
@@ -699,6 +844,44 @@ mod tests {
         assert_eq!(auth_request.scope, None);
     }
 
+    #[test]
+    fn test_auth_request_client_signature() {
+        let auth_request = AuthRequest::client_signature(
+            "AMANDA".to_string(),
+            "AMANDASECRECT",
+            1_576_074_319_000,
+            "1iqt2wls".to_string(),
+            Some(String::new()),
+        );
+
+        assert_eq!(auth_request.grant_type, "client_signature");
+        assert_eq!(
+            auth_request.signature.as_deref(),
+            Some("56590594f97921b09b18f166befe0d1319b198bbcdad7ca73382de2f88fe9aa1")
+        );
+        let json = serde_json::to_value(&auth_request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "grant_type": "client_signature",
+                "client_id": "AMANDA",
+                "refresh_token": null,
+                "scope": null,
+                "timestamp": 1_576_074_319_000u64,
+                "signature": "56590594f97921b09b18f166befe0d1319b198bbcdad7ca73382de2f88fe9aa1",
+                "nonce": "1iqt2wls",
+                "data": ""
+            })
+        );
+        assert!(!json.to_string().contains("AMANDASECRECT"));
+
+        let fresh = AuthRequest::client_signature_now("AMANDA".to_string(), "AMANDASECRECT")
+            .with_scope("session:name");
+        assert!(fresh.client_secret.is_empty());
+        assert_eq!(fresh.scope.as_deref(), Some("session:name"));
+        assert!(fresh.data.is_none());
+    }
+
     #[test]
     fn test_fix_to_rest_conversion() {
         let fix_order = fix::NewOrderRequest::limit_buy("BTC-PERPETUAL".to_string(), 1.0, 50000.0)
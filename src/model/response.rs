@@ -4,8 +4,8 @@
    Date: 21/7/25
 ******************************************************************************/
 use crate::model::order::OrderInfo;
-use crate::model::order_management::QuoteResult;
-use crate::model::trade::{LastTrade, TradeExecution};
+use crate::model::order_management::QuoteError;
+use crate::model::trade::{LastTrade, TradeExecution, UserTrade};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 
 use serde::{Deserialize, Serialize};
@@ -167,6 +167,9 @@ pub struct AuthResponse {
     pub refresh_token: String,
     /// Scope
     pub scope: String,
+    /// Features enabled for the account
+    #[serde(default)]
+    pub enabled_features: Vec<String>,
 }
 
 /// Pagination information
@@ -343,8 +346,8 @@ pub struct StatusResponse {
 pub struct AprHistoryResponse {
     /// List of APR data points
     pub data: Vec<AprDataPoint>,
-    /// Continuation token for pagination
-    pub continuation: Option<String>,
+    /// Day to pass as `before` to fetch the next page
+    pub continuation: Option<u64>,
 }
 /// APR data point
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
@@ -421,11 +424,80 @@ pub struct OrderResponse {
     pub trades: Vec<TradeExecution>,
 }
 
+/// User trades response
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct UserTradesResponse {
+    /// Whether there are more trades available
+    pub has_more: bool,
+    /// List of user trades
+    pub trades: Vec<UserTrade>,
+}
+
+/// Cancel-on-disconnect configuration response
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CancelOnDisconnectResponse {
+    /// Whether cancel-on-disconnect is enabled
+    pub enabled: bool,
+    /// Scope of the configuration (`connection` or `account`)
+    pub scope: String,
+}
+
+/// Margins required for a hypothetical order
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginsResponse {
+    /// Margin required to buy
+    pub buy: f64,
+    /// Margin required to sell
+    pub sell: f64,
+    /// Maximum price allowed for a buy order
+    pub max_price: f64,
+    /// Minimum price allowed for a sell order
+    pub min_price: f64,
+}
+
+/// Initial margin of an open order
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderMarginResponse {
+    /// Order identifier
+    pub order_id: String,
+    /// Initial margin of the order
+    pub initial_margin: f64,
+    /// Currency of the initial margin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_margin_currency: Option<String>,
+}
+
 /// Mass quote response
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
 pub struct MassQuoteResponse {
-    /// List of quote results
-    pub quotes: Vec<QuoteResult>,
+    /// List of errors (present when `detailed` is true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<QuoteError>>,
+    /// Number of errors (present when `detailed` is false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors_count: Option<u32>,
+    /// Orders created or changed by the quotes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orders: Option<Vec<OrderInfo>>,
+    /// Trades resulting from the quotes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trades: Option<Vec<TradeExecution>>,
+}
+
+impl MassQuoteResponse {
+    /// Get the number of rejected quote sides
+    pub fn error_count(&self) -> usize {
+        match (&self.errors, self.errors_count) {
+            (Some(errors), _) => errors.len(),
+            (None, Some(count)) => count as usize,
+            (None, None) => 0,
+        }
+    }
+
+    /// Check if every quote side was accepted
+    pub fn is_fully_accepted(&self) -> bool {
+        self.error_count() == 0
+    }
 }
 
 impl std::error::Error for JsonRpcError {}
@@ -566,6 +638,7 @@ mod tests {
             expires_in: 3600,
             refresh_token: "refresh_token_456".to_string(),
             scope: "read write".to_string(),
+            enabled_features: Vec::new(),
         };
 
         assert_eq!(auth_response.access_token, "access_token_123");
@@ -741,10 +814,10 @@ mod tests {
         }];
         let apr_history = AprHistoryResponse {
             data: data_points,
-            continuation: Some("token_456".to_string()),
+            continuation: Some(20196),
         };
         assert_eq!(apr_history.data.len(), 1);
-        assert_eq!(apr_history.continuation, Some("token_456".to_string()));
+        assert_eq!(apr_history.continuation, Some(20196));
     }
 
     #[test]
@@ -836,6 +909,40 @@ mod tests {
         assert_eq!(response.error.is_none(), deserialized.error.is_none());
     }
 
+    #[test]
+    fn test_mass_quote_response_deserialization() {
+        let json = r#"{
+            "errors": [
+                {
+                    "instrument_name": "BTC-PERPETUAL",
+                    "side": "bid",
+                    "error": {"message": "price_too_high 43666.4288", "code": 10007}
+                }
+            ],
+            "orders": [
+                {
+                    "is_liquidation": false, "reduce_only": false, "risk_reducing": false,
+                    "last_update_timestamp": 1703162550180, "creation_timestamp": 1703162478689,
+                    "filled_amount": 0, "average_price": 0, "order_type": "limit",
+                    "order_state": "open", "quote": true, "quote_set_id": "options",
+                    "quote_id": "1", "post_only": false, "replaced": false,
+                    "mmp_group": "default", "web": false, "mmp": true, "api": false,
+                    "instrument_name": "BTC-22DEC23-41600-C", "order_id": "6653852",
+                    "max_show": 1, "time_in_force": "good_til_cancelled", "price": 0.04,
+                    "direction": "buy", "amount": 1, "label": ""
+                }
+            ],
+            "trades": []
+        }"#;
+        let response: MassQuoteResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.error_count(), 1);
+        assert!(!response.is_fully_accepted());
+        assert_eq!(response.orders.as_ref().unwrap()[0].order_id, "6653852");
+
+        let summary: MassQuoteResponse = serde_json::from_str(r#"{"errors_count": 0}"#).unwrap();
+        assert!(summary.is_fully_accepted());
+    }
+
     #[test]
     fn test_debug_and_display_implementations() {
         let auth_response = AuthResponse {
@@ -844,6 +951,7 @@ mod tests {
             expires_in: 3600,
             refresh_token: "refresh".to_string(),
             scope: "read".to_string(),
+            enabled_features: Vec::new(),
         };
 
         let debug_str = format!("{:?}", auth_response);
@@ -0,0 +1,1180 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Pre-trade risk limits
+//!
+//! [`RiskConfig`] holds [`RiskLimits`] at account, currency and instrument
+//! scope and evaluates a [`RiskOrder`] against the current [`RiskState`]
+//! (positions, account summaries and open orders). Every breached limit is
+//! reported as a [`RiskViolation`], so callers can log or reject with full
+//! context.
+//!
+//! Orders from the JSON-RPC and FIX paths are normalized into a [`RiskOrder`]
+//! first, so both go through exactly the same checks. FIX quantities are in
+//! contracts and are converted with the instrument's contract size.
+//!
+//! Units:
+//! - Order notional and position limits are USD notionals
+//! - Daily loss limits are in the settlement currency of instrument and
+//!   currency scopes, and in USD for the account scope, which adds up the
+//!   losses of every currency at its index price
+//! - Price collars are a fraction of the reference price (0.05 = 5%)
+//!
+//! Currency scopes are keyed by settlement currency (e.g. `BTC`, `USDC`).
+
+use crate::model::account::AccountSummary;
+use crate::model::amount::AmountError;
+use crate::model::instrument::Instrument;
+use crate::model::order::{OrderInfo, OrderSide};
+use crate::model::position::Position;
+use crate::model::request::{NewOrderRequest, fix};
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Limits applied at a single scope
+///
+/// Unset limits are not checked.
+#[derive(DebugPretty, DisplaySimple, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskLimits {
+    /// Maximum USD notional of a single order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_order_notional: Option<f64>,
+    /// Maximum gross USD notional of positions after the order fills
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_position: Option<f64>,
+    /// Maximum number of open orders, including the new one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_open_orders: Option<u32>,
+    /// Maximum session loss, in settlement currency (USD at account scope)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_daily_loss: Option<f64>,
+    /// Maximum relative distance between the order price and the reference price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_price_deviation: Option<f64>,
+}
+
+impl RiskLimits {
+    /// Create limits with nothing set
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum USD notional of a single order
+    #[must_use]
+    pub fn with_max_order_notional(mut self, max_order_notional: f64) -> Self {
+        self.max_order_notional = Some(max_order_notional);
+        self
+    }
+
+    /// Set the maximum gross USD position notional
+    #[must_use]
+    pub fn with_max_position(mut self, max_position: f64) -> Self {
+        self.max_position = Some(max_position);
+        self
+    }
+
+    /// Set the maximum number of open orders
+    #[must_use]
+    pub fn with_max_open_orders(mut self, max_open_orders: u32) -> Self {
+        self.max_open_orders = Some(max_open_orders);
+        self
+    }
+
+    /// Set the maximum session loss
+    #[must_use]
+    pub fn with_max_daily_loss(mut self, max_daily_loss: f64) -> Self {
+        self.max_daily_loss = Some(max_daily_loss);
+        self
+    }
+
+    /// Set the price collar as a fraction of the reference price
+    #[must_use]
+    pub fn with_max_price_deviation(mut self, max_price_deviation: f64) -> Self {
+        self.max_price_deviation = Some(max_price_deviation);
+        self
+    }
+
+    /// Check if no limit is set
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Scope a limit applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "scope", content = "name", rename_all = "snake_case")]
+pub enum RiskScope {
+    /// Whole account
+    Account,
+    /// All instruments settled in a currency
+    Currency(String),
+    /// A single instrument
+    Instrument(String),
+}
+
+impl std::fmt::Display for RiskScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Account => write!(f, "account"),
+            Self::Currency(currency) => write!(f, "currency {currency}"),
+            Self::Instrument(instrument) => write!(f, "instrument {instrument}"),
+        }
+    }
+}
+
+/// Breached pre-trade limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum RiskViolation {
+    /// Order USD notional above the limit
+    OrderNotional {
+        /// Scope of the breached limit
+        scope: RiskScope,
+        /// Order USD notional
+        notional: f64,
+        /// Configured limit
+        limit: f64,
+    },
+    /// Gross USD position notional after the order above the limit
+    Position {
+        /// Scope of the breached limit
+        scope: RiskScope,
+        /// Projected gross USD notional
+        notional: f64,
+        /// Configured limit
+        limit: f64,
+    },
+    /// Too many open orders
+    OpenOrders {
+        /// Scope of the breached limit
+        scope: RiskScope,
+        /// Open orders including the new one
+        open_orders: usize,
+        /// Configured limit
+        limit: u32,
+    },
+    /// Session loss at or above the limit
+    DailyLoss {
+        /// Scope of the breached limit
+        scope: RiskScope,
+        /// Current session loss
+        loss: f64,
+        /// Configured limit
+        limit: f64,
+    },
+    /// Order price outside the price collar
+    PriceCollar {
+        /// Scope of the breached limit
+        scope: RiskScope,
+        /// Order price
+        price: f64,
+        /// Reference price
+        reference_price: f64,
+        /// Configured maximum deviation
+        max_deviation: f64,
+    },
+    /// A limit could not be checked because no usable price is known
+    MissingReferencePrice {
+        /// Instrument of the order
+        instrument_name: String,
+    },
+}
+
+impl RiskViolation {
+    /// Get the scope of the breached limit, if any
+    #[must_use]
+    pub fn scope(&self) -> Option<&RiskScope> {
+        match self {
+            Self::OrderNotional { scope, .. }
+            | Self::Position { scope, .. }
+            | Self::OpenOrders { scope, .. }
+            | Self::DailyLoss { scope, .. }
+            | Self::PriceCollar { scope, .. } => Some(scope),
+            Self::MissingReferencePrice { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OrderNotional {
+                scope,
+                notional,
+                limit,
+            } => write!(f, "{scope}: order notional {notional} exceeds {limit}"),
+            Self::Position {
+                scope,
+                notional,
+                limit,
+            } => write!(f, "{scope}: position notional {notional} exceeds {limit}"),
+            Self::OpenOrders {
+                scope,
+                open_orders,
+                limit,
+            } => write!(f, "{scope}: {open_orders} open orders exceeds {limit}"),
+            Self::DailyLoss { scope, loss, limit } => {
+                write!(f, "{scope}: daily loss {loss} reached limit {limit}")
+            }
+            Self::PriceCollar {
+                scope,
+                price,
+                reference_price,
+                max_deviation,
+            } => write!(
+                f,
+                "{scope}: price {price} deviates more than {max_deviation} from {reference_price}"
+            ),
+            Self::MissingReferencePrice { instrument_name } => {
+                write!(f, "no reference price for {instrument_name}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RiskViolation {}
+
+/// Order normalized for risk checks
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskOrder {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Order side
+    pub side: OrderSide,
+    /// Order amount in the instrument's native unit
+    pub amount: f64,
+    /// Limit price, `None` for market orders
+    pub price: Option<f64>,
+    /// Whether the order can only reduce the position
+    pub reduce_only: bool,
+}
+
+impl RiskOrder {
+    /// Create a new risk order
+    #[must_use]
+    pub fn new(instrument_name: String, side: OrderSide, amount: f64, price: Option<f64>) -> Self {
+        Self {
+            instrument_name,
+            side,
+            amount,
+            price,
+            reduce_only: false,
+        }
+    }
+
+    /// Set the reduce-only flag
+    #[must_use]
+    pub fn with_reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = reduce_only;
+        self
+    }
+
+    /// Get the signed amount (positive for buys)
+    #[must_use]
+    pub fn signed_amount(&self) -> f64 {
+        match self.side {
+            OrderSide::Buy => self.amount,
+            OrderSide::Sell => -self.amount,
+        }
+    }
+}
+
+impl From<&NewOrderRequest> for RiskOrder {
+    fn from(order: &NewOrderRequest) -> Self {
+        Self {
+            instrument_name: order.instrument_name.clone(),
+            side: order.side,
+            amount: order.amount,
+            price: order.price,
+            reduce_only: order.reduce_only.unwrap_or(false),
+        }
+    }
+}
+
+impl RiskOrder {
+    /// Normalize a FIX order
+    ///
+    /// Deribit reads `OrderQty(38)` as a number of contracts, so the quantity
+    /// is converted into the native amount with the instrument's contract size.
+    pub fn from_fix(
+        order: &fix::NewOrderRequest,
+        instrument: &Instrument,
+    ) -> Result<Self, AmountError> {
+        let amount = instrument.contracts_to_amount(order.quantity)?;
+        Ok(
+            Self::new(order.symbol.clone(), order.side, amount, order.price)
+                .with_reduce_only(order.reduce_only.unwrap_or(false)),
+        )
+    }
+}
+
+/// Reference prices for the instrument being traded
+#[derive(DebugPretty, DisplaySimple, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceReference {
+    /// Mark price of the instrument
+    pub mark_price: Option<f64>,
+    /// Index price of the underlying
+    pub index_price: Option<f64>,
+}
+
+impl PriceReference {
+    /// Create a new price reference
+    #[must_use]
+    pub fn new(mark_price: Option<f64>, index_price: Option<f64>) -> Self {
+        Self {
+            mark_price,
+            index_price,
+        }
+    }
+}
+
+/// Account state the order is evaluated against
+#[derive(DebugPretty, DisplaySimple, Clone, Default, Serialize, Deserialize)]
+pub struct RiskState {
+    /// Current positions
+    pub positions: Vec<Position>,
+    /// Account summaries, one per currency
+    pub account_summaries: Vec<AccountSummary>,
+    /// Currently open orders
+    pub open_orders: Vec<OrderInfo>,
+    /// USD index price per currency, overriding the prices carried by positions
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub index_prices: HashMap<String, f64>,
+}
+
+impl RiskState {
+    /// Create an empty state
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the positions
+    #[must_use]
+    pub fn with_positions(mut self, positions: Vec<Position>) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    /// Set the account summaries
+    #[must_use]
+    pub fn with_account_summaries(mut self, account_summaries: Vec<AccountSummary>) -> Self {
+        self.account_summaries = account_summaries;
+        self
+    }
+
+    /// Set the open orders
+    #[must_use]
+    pub fn with_open_orders(mut self, open_orders: Vec<OrderInfo>) -> Self {
+        self.open_orders = open_orders;
+        self
+    }
+
+    /// Set the USD index price of a currency
+    #[must_use]
+    pub fn with_index_price(mut self, currency: &str, price: f64) -> Self {
+        self.index_prices.insert(currency.to_uppercase(), price);
+        self
+    }
+
+    /// Get the USD index price of a currency
+    ///
+    /// Uses the explicit prices first, then 1.0 for USD stablecoins, then the
+    /// index price of any position on that currency.
+    #[must_use]
+    pub fn index_price(&self, currency: &str) -> Option<f64> {
+        let currency = currency.to_uppercase();
+        let price = self.index_prices.get(&currency).copied().or_else(|| {
+            if USD_STABLECOINS.contains(&currency.as_str()) {
+                Some(1.0)
+            } else {
+                self.positions
+                    .iter()
+                    .filter(|p| base_currency_of(&p.instrument_name) == currency)
+                    .find_map(|p| p.index_price)
+            }
+        });
+        price.filter(|p| p.is_finite() && *p > 0.0)
+    }
+}
+
+/// Currencies valued at one USD
+const USD_STABLECOINS: &[&str] = &["USD", "USDC", "USDT"];
+
+/// Pre-trade risk configuration
+///
+/// Loadable from the same serde source as [`DeribitConfig`](crate::model::config::DeribitConfig).
+#[derive(DebugPretty, DisplaySimple, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskConfig {
+    /// Limits for the whole account
+    pub account: RiskLimits,
+    /// Limits per settlement currency
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub currencies: HashMap<String, RiskLimits>,
+    /// Limits per instrument
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub instruments: HashMap<String, RiskLimits>,
+}
+
+impl RiskConfig {
+    /// Create a configuration with no limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the account limits
+    #[must_use]
+    pub fn with_account_limits(mut self, limits: RiskLimits) -> Self {
+        self.account = limits;
+        self
+    }
+
+    /// Set the limits for a settlement currency
+    #[must_use]
+    pub fn with_currency_limits(mut self, currency: String, limits: RiskLimits) -> Self {
+        self.currencies.insert(currency, limits);
+        self
+    }
+
+    /// Set the limits for an instrument
+    #[must_use]
+    pub fn with_instrument_limits(mut self, instrument_name: String, limits: RiskLimits) -> Self {
+        self.instruments.insert(instrument_name, limits);
+        self
+    }
+
+    /// Evaluate an order and return every breached limit
+    ///
+    /// An empty result means the order passes. Reduce-only orders and orders
+    /// that shrink the position are never rejected for position size.
+    pub fn evaluate(
+        &self,
+        order: &RiskOrder,
+        instrument: &Instrument,
+        prices: &PriceReference,
+        state: &RiskState,
+    ) -> Vec<RiskViolation> {
+        let currency = instrument
+            .settlement_currency
+            .clone()
+            .unwrap_or_else(|| settlement_currency_of(&instrument.instrument_name).to_string());
+        let scopes = self.scopes(&instrument.instrument_name, &currency);
+        let mut violations = Vec::new();
+        if scopes.is_empty() {
+            return violations;
+        }
+
+        let usd_price = usd_reference_price(order, instrument, prices);
+        let collar_price = collar_reference_price(instrument, prices);
+        let needs_usd = scopes
+            .iter()
+            .any(|(_, l)| l.max_order_notional.is_some() || l.max_position.is_some());
+        let needs_collar =
+            order.price.is_some() && scopes.iter().any(|(_, l)| l.max_price_deviation.is_some());
+        let mut missing_price =
+            (needs_usd && usd_price.is_none()) || (needs_collar && collar_price.is_none());
+
+        for (scope, limits) in &scopes {
+            if let (Some(limit), Some(price)) = (limits.max_order_notional, usd_price) {
+                let notional = to_usd(instrument, order.amount, price);
+                if notional > limit {
+                    violations.push(RiskViolation::OrderNotional {
+                        scope: scope.clone(),
+                        notional,
+                        limit,
+                    });
+                }
+            }
+            if let (Some(limit), Some(price)) = (limits.max_position, usd_price)
+                && !order.reduce_only
+            {
+                match position_notionals(scope, order, instrument, price, &currency, state) {
+                    Some((current, projected)) if projected > limit && projected > current => {
+                        violations.push(RiskViolation::Position {
+                            scope: scope.clone(),
+                            notional: projected,
+                            limit,
+                        });
+                    }
+                    Some(_) => {}
+                    None => missing_price = true,
+                }
+            }
+            if let Some(limit) = limits.max_open_orders {
+                let open_orders = state
+                    .open_orders
+                    .iter()
+                    .filter(|o| is_open(o) && in_scope(scope, &o.instrument_name, &currency))
+                    .count()
+                    + 1;
+                if open_orders > limit as usize {
+                    violations.push(RiskViolation::OpenOrders {
+                        scope: scope.clone(),
+                        open_orders,
+                        limit,
+                    });
+                }
+            }
+            if let Some(limit) = limits.max_daily_loss {
+                match session_loss(scope, state) {
+                    Some(loss) if loss >= limit => {
+                        violations.push(RiskViolation::DailyLoss {
+                            scope: scope.clone(),
+                            loss,
+                            limit,
+                        });
+                    }
+                    Some(_) => {}
+                    None => missing_price = true,
+                }
+            }
+            if let (Some(max_deviation), Some(price), Some(reference_price)) =
+                (limits.max_price_deviation, order.price, collar_price)
+                && ((price - reference_price) / reference_price).abs() > max_deviation
+            {
+                violations.push(RiskViolation::PriceCollar {
+                    scope: scope.clone(),
+                    price,
+                    reference_price,
+                    max_deviation,
+                });
+            }
+        }
+        if missing_price {
+            violations.insert(
+                0,
+                RiskViolation::MissingReferencePrice {
+                    instrument_name: order.instrument_name.clone(),
+                },
+            );
+        }
+        violations
+    }
+
+    /// Evaluate an order, failing with the first breached limit
+    pub fn check(
+        &self,
+        order: &RiskOrder,
+        instrument: &Instrument,
+        prices: &PriceReference,
+        state: &RiskState,
+    ) -> Result<(), RiskViolation> {
+        match self
+            .evaluate(order, instrument, prices, state)
+            .into_iter()
+            .next()
+        {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the non-empty limits that apply to an instrument, most specific first
+    fn scopes(&self, instrument_name: &str, currency: &str) -> Vec<(RiskScope, &RiskLimits)> {
+        let candidates = [
+            (
+                RiskScope::Instrument(instrument_name.to_string()),
+                self.instruments.get(instrument_name),
+            ),
+            (
+                RiskScope::Currency(currency.to_string()),
+                self.currencies.get(currency),
+            ),
+            (RiskScope::Account, Some(&self.account)),
+        ];
+        candidates
+            .into_iter()
+            .filter_map(|(scope, limits)| {
+                limits
+                    .filter(|l| !l.is_empty())
+                    .map(|limits| (scope, limits))
+            })
+            .collect()
+    }
+}
+
+/// Derive the settlement currency from an instrument name
+///
+/// `BTC-PERPETUAL` settles in `BTC`, `BTC_USDC-PERPETUAL` and `ETH_USDC` in `USDC`.
+fn settlement_currency_of(instrument_name: &str) -> &str {
+    let head = instrument_name.split('-').next().unwrap_or(instrument_name);
+    head.split_once('_').map_or(head, |(_, quote)| quote)
+}
+
+/// Derive the base currency from an instrument name
+///
+/// `BTC-PERPETUAL` and `BTC_USDC-PERPETUAL` are both on `BTC`.
+fn base_currency_of(instrument_name: &str) -> &str {
+    let head = instrument_name.split('-').next().unwrap_or(instrument_name);
+    head.split_once('_').map_or(head, |(base, _)| base)
+}
+
+/// Check if an instrument belongs to a scope
+fn in_scope(scope: &RiskScope, instrument_name: &str, currency: &str) -> bool {
+    match scope {
+        RiskScope::Account => true,
+        RiskScope::Currency(_) => settlement_currency_of(instrument_name) == currency,
+        RiskScope::Instrument(name) => name == instrument_name,
+    }
+}
+
+/// Check if an order still rests on the book
+fn is_open(order: &OrderInfo) -> bool {
+    order.order_state.is_open()
+}
+
+/// USD price of one unit of base currency for the traded instrument
+fn usd_reference_price(
+    order: &RiskOrder,
+    instrument: &Instrument,
+    prices: &PriceReference,
+) -> Option<f64> {
+    let price = if instrument.is_option() {
+        prices.index_price
+    } else {
+        order.price.or(prices.mark_price).or(prices.index_price)
+    };
+    price.filter(|p| p.is_finite() && *p > 0.0)
+}
+
+/// Price the collar is measured against, in the instrument's price unit
+fn collar_reference_price(instrument: &Instrument, prices: &PriceReference) -> Option<f64> {
+    let price = if instrument.is_option() {
+        prices.mark_price
+    } else {
+        prices.mark_price.or(prices.index_price)
+    };
+    price.filter(|p| p.is_finite() && *p > 0.0)
+}
+
+/// Convert an absolute native amount into USD for the traded instrument
+fn to_usd(instrument: &Instrument, amount: f64, price: f64) -> f64 {
+    instrument
+        .amount_to_usd(amount.abs(), price)
+        .unwrap_or(f64::INFINITY)
+}
+
+/// Gross USD notional of a position held in another instrument
+///
+/// Without `size_currency`, inverse futures are sized in USD already while
+/// options and linear instruments are sized in base currency. Returns `None`
+/// when the value needs a price the position does not carry.
+fn position_usd(position: &Position) -> Option<f64> {
+    let is_option = position.kind.as_deref() == Some("option");
+    let is_inverse = !is_option && !position.instrument_name.contains('_');
+    let coin = match position.size_currency {
+        Some(size_currency) => size_currency,
+        None if is_inverse => return Some(position.size.abs()),
+        None => position.size,
+    };
+    if coin == 0.0 {
+        return Some(0.0);
+    }
+    let price = if is_option {
+        position.index_price
+    } else {
+        position.mark_price.or(position.index_price)
+    };
+    price
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .map(|price| (coin * price).abs())
+}
+
+/// Gross USD position notional in a scope, before and after the order fills
+///
+/// Returns `None` when another position in the scope cannot be valued.
+fn position_notionals(
+    scope: &RiskScope,
+    order: &RiskOrder,
+    instrument: &Instrument,
+    price: f64,
+    currency: &str,
+    state: &RiskState,
+) -> Option<(f64, f64)> {
+    let current_size = state
+        .positions
+        .iter()
+        .filter(|p| p.instrument_name == order.instrument_name)
+        .map(|p| p.size)
+        .sum::<f64>();
+    let others = state
+        .positions
+        .iter()
+        .filter(|p| {
+            p.instrument_name != order.instrument_name
+                && in_scope(scope, &p.instrument_name, currency)
+        })
+        .map(position_usd)
+        .sum::<Option<f64>>()?;
+    let current = others + to_usd(instrument, current_size, price);
+    let projected = others + to_usd(instrument, current_size + order.signed_amount(), price);
+    Some((current, projected))
+}
+
+/// Session loss in a scope, positive when losing
+///
+/// Instruments use the position P&L; currencies their account summary; the
+/// account the sum over currencies in USD. Returns `None` when a currency
+/// with a non-zero session P&L has no index price.
+fn session_loss(scope: &RiskScope, state: &RiskState) -> Option<f64> {
+    let summary_loss = |s: &AccountSummary| -(s.session_rpl + s.session_upl);
+    let loss = match scope {
+        RiskScope::Instrument(name) => -state
+            .positions
+            .iter()
+            .filter(|p| &p.instrument_name == name)
+            .map(|p| p.realized_profit_loss.unwrap_or(0.0) + p.floating_profit_loss.unwrap_or(0.0))
+            .sum::<f64>(),
+        RiskScope::Currency(currency) => state
+            .account_summaries
+            .iter()
+            .filter(|s| s.currency.eq_ignore_ascii_case(currency))
+            .map(summary_loss)
+            .sum(),
+        RiskScope::Account => {
+            return state
+                .account_summaries
+                .iter()
+                .map(|s| match summary_loss(s) {
+                    0.0 => Some(0.0),
+                    loss => state.index_price(&s.currency).map(|price| loss * price),
+                })
+                .sum();
+        }
+    };
+    Some(loss)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::account::AccountLimits;
+    use crate::model::instrument::{InstrumentKind, InstrumentType};
+    use crate::model::order::{OrderPrice, OrderStatus, OrderType, TimeInForce};
+    use crate::model::position::Direction;
+
+    fn create_test_instrument() -> Instrument {
+        Instrument {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            price_index: Some("btc_usd".to_string()),
+            kind: Some(InstrumentKind::Future),
+            currency: Some("BTC".to_string()),
+            is_active: Some(true),
+            expiration_timestamp: None,
+            strike: None,
+            option_type: None,
+            tick_size: Some(0.5),
+            tick_size_steps: None,
+            min_trade_amount: Some(10.0),
+            contract_size: Some(10.0),
+            settlement_period: Some("perpetual".to_string()),
+            instrument_type: Some(InstrumentType::Reversed),
+            quote_currency: Some("USD".to_string()),
+            settlement_currency: Some("BTC".to_string()),
+            creation_timestamp: None,
+            max_leverage: None,
+            maker_commission: None,
+            taker_commission: None,
+            instrument_id: None,
+            base_currency: Some("BTC".to_string()),
+            counter_currency: Some("USD".to_string()),
+            block_trade_commission: None,
+            block_trade_min_trade_amount: None,
+            block_trade_tick_size: None,
+            max_liquidation_commission: None,
+            rfq: None,
+        }
+    }
+
+    fn create_test_position(instrument_name: &str, size: f64, size_currency: f64) -> Position {
+        Position {
+            average_price: 50000.0,
+            average_price_usd: None,
+            delta: None,
+            direction: Direction::Buy,
+            estimated_liquidation_price: None,
+            floating_profit_loss: Some(-0.01),
+            floating_profit_loss_usd: None,
+            gamma: None,
+            index_price: Some(50000.0),
+            initial_margin: None,
+            instrument_name: instrument_name.to_string(),
+            interest_value: None,
+            kind: Some("future".to_string()),
+            leverage: None,
+            maintenance_margin: None,
+            mark_price: Some(50000.0),
+            open_orders_margin: None,
+            realized_funding: None,
+            realized_profit_loss: Some(-0.02),
+            settlement_price: None,
+            size,
+            size_currency: Some(size_currency),
+            theta: None,
+            total_profit_loss: None,
+            vega: None,
+            unrealized_profit_loss: None,
+        }
+    }
+
+    fn create_open_order(instrument_name: &str) -> OrderInfo {
+        OrderInfo {
+            amount: 10.0,
+            api: true,
+            average_price: 0.0,
+            creation_timestamp: 0,
+            direction: OrderSide::Buy,
+            filled_amount: 0.0,
+            instrument_name: instrument_name.to_string(),
+            is_liquidation: false,
+            label: String::new(),
+            last_update_timestamp: 0,
+            max_show: None,
+            order_id: "1".to_string(),
+            order_state: OrderStatus::New,
+            order_type: OrderType::Limit,
+            original_order_type: None,
+            post_only: false,
+            price: Some(OrderPrice::Value(49000.0)),
+            profit_loss: None,
+            reduce_only: false,
+            replaced: false,
+            risk_reducing: false,
+            time_in_force: TimeInForce::GoodTilCancelled,
+            triggered: None,
+            trigger: None,
+            usd: None,
+            web: false,
+            trigger_price: None,
+            implv: None,
+            advanced: None,
+            contracts: None,
+            is_rebalance: None,
+            cancel_reason: None,
+            quote: None,
+            quote_id: None,
+            quote_set_id: None,
+            mmp: None,
+            mmp_group: None,
+            mmp_cancelled: None,
+        }
+    }
+
+    fn create_account_summary(currency: &str, session_rpl: f64) -> AccountSummary {
+        AccountSummary {
+            currency: currency.to_string(),
+            balance: 0.0,
+            equity: 0.0,
+            available_funds: 0.0,
+            margin_balance: 0.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            total_pl: 0.0,
+            session_funding: 0.0,
+            session_rpl,
+            session_upl: 0.0,
+            maintenance_margin: 0.0,
+            initial_margin: 0.0,
+            available_withdrawal_funds: None,
+            cross_collateral_enabled: None,
+            delta_total: None,
+            futures_pl: None,
+            futures_session_rpl: None,
+            futures_session_upl: None,
+            options_delta: None,
+            options_gamma: None,
+            options_pl: None,
+            options_session_rpl: None,
+            options_session_upl: None,
+            options_theta: None,
+            options_vega: None,
+            options_value: None,
+            portfolio_margining_enabled: None,
+            projected_delta_total: None,
+            projected_initial_margin: None,
+            projected_maintenance_margin: None,
+            estimated_liquidation_ratio: None,
+            additional_reserve: None,
+            spot_reserve: None,
+            fee_balance: None,
+            margin_model: None,
+            has_non_block_chain_equity: None,
+            total_equity_usd: None,
+            total_margin_balance_usd: None,
+            total_delta_total_usd: None,
+            total_initial_margin_usd: None,
+            total_maintenance_margin_usd: None,
+            id: None,
+            email: None,
+            username: None,
+            system_name: None,
+            account_type: "main".to_string(),
+            creation_timestamp: None,
+            referrer_id: None,
+            login_enabled: None,
+            security_keys_enabled: None,
+            mmp_enabled: None,
+            interuser_transfers_enabled: None,
+            self_trading_reject_mode: None,
+            self_trading_extended_to_subaccounts: None,
+            block_rfq_self_match_prevention: None,
+            delta_total_map: HashMap::new(),
+            estimated_liquidation_ratio_map: HashMap::new(),
+            options_gamma_map: HashMap::new(),
+            options_theta_map: HashMap::new(),
+            options_vega_map: HashMap::new(),
+            deposit_address: String::new(),
+            fees: Vec::new(),
+            limits: AccountLimits::default(),
+        }
+    }
+
+    fn prices() -> PriceReference {
+        PriceReference::new(Some(50000.0), Some(50000.0))
+    }
+
+    #[test]
+    fn test_order_passes_without_limits() {
+        let order = RiskOrder::new(
+            "BTC-PERPETUAL".to_string(),
+            OrderSide::Buy,
+            1_000_000.0,
+            None,
+        );
+        let violations = RiskConfig::new().evaluate(
+            &order,
+            &create_test_instrument(),
+            &prices(),
+            &RiskState::new(),
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_order_notional_and_position_limits() {
+        let config = RiskConfig::new()
+            .with_instrument_limits(
+                "BTC-PERPETUAL".to_string(),
+                RiskLimits::new().with_max_order_notional(10_000.0),
+            )
+            .with_currency_limits(
+                "BTC".to_string(),
+                RiskLimits::new().with_max_position(30_000.0),
+            );
+        let state = RiskState::new().with_positions(vec![
+            create_test_position("BTC-PERPETUAL", 20_000.0, 0.4),
+            create_test_position("BTC-27DEC24", 5_000.0, 0.1),
+        ]);
+        let instrument = create_test_instrument();
+
+        let order = RiskOrder::new(
+            "BTC-PERPETUAL".to_string(),
+            OrderSide::Buy,
+            20_000.0,
+            Some(50000.0),
+        );
+        let violations = config.evaluate(&order, &instrument, &prices(), &state);
+        assert_eq!(
+            violations,
+            vec![
+                RiskViolation::OrderNotional {
+                    scope: RiskScope::Instrument("BTC-PERPETUAL".to_string()),
+                    notional: 20_000.0,
+                    limit: 10_000.0,
+                },
+                RiskViolation::Position {
+                    scope: RiskScope::Currency("BTC".to_string()),
+                    notional: 45_000.0,
+                    limit: 30_000.0,
+                },
+            ]
+        );
+
+        let reducing = RiskOrder::new("BTC-PERPETUAL".to_string(), OrderSide::Sell, 5_000.0, None);
+        assert!(
+            config
+                .evaluate(&reducing, &instrument, &prices(), &state)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_open_orders_and_daily_loss() {
+        let config = RiskConfig::new()
+            .with_account_limits(RiskLimits::new().with_max_open_orders(2))
+            .with_instrument_limits(
+                "BTC-PERPETUAL".to_string(),
+                RiskLimits::new().with_max_daily_loss(0.02),
+            );
+        let mut cancelled = create_open_order("ETH-PERPETUAL");
+        cancelled.order_state = OrderStatus::Canceled;
+        let state = RiskState::new()
+            .with_positions(vec![create_test_position("BTC-PERPETUAL", 100.0, 0.002)])
+            .with_open_orders(vec![
+                create_open_order("BTC-PERPETUAL"),
+                create_open_order("ETH_USDC-PERPETUAL"),
+                cancelled,
+            ]);
+        let order = RiskOrder::new("BTC-PERPETUAL".to_string(), OrderSide::Buy, 10.0, None);
+        let violations = config.evaluate(&order, &create_test_instrument(), &prices(), &state);
+        assert_eq!(violations.len(), 2);
+        assert!(matches!(violations[0], RiskViolation::DailyLoss { .. }));
+        assert_eq!(
+            violations[0].scope(),
+            Some(&RiskScope::Instrument("BTC-PERPETUAL".to_string()))
+        );
+        assert!(matches!(
+            violations[1],
+            RiskViolation::OpenOrders {
+                open_orders: 3,
+                limit: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_price_collar_and_missing_price() {
+        let config =
+            RiskConfig::new().with_account_limits(RiskLimits::new().with_max_price_deviation(0.05));
+        let instrument = create_test_instrument();
+        let far = RiskOrder::new(
+            "BTC-PERPETUAL".to_string(),
+            OrderSide::Buy,
+            10.0,
+            Some(56000.0),
+        );
+        let violations = config.evaluate(&far, &instrument, &prices(), &RiskState::new());
+        assert!(matches!(violations[0], RiskViolation::PriceCollar { .. }));
+
+        let near = RiskOrder::new(
+            "BTC-PERPETUAL".to_string(),
+            OrderSide::Buy,
+            10.0,
+            Some(51000.0),
+        );
+        assert!(
+            config
+                .check(&near, &instrument, &prices(), &RiskState::new())
+                .is_ok()
+        );
+        assert_eq!(
+            config.check(
+                &near,
+                &instrument,
+                &PriceReference::default(),
+                &RiskState::new()
+            ),
+            Err(RiskViolation::MissingReferencePrice {
+                instrument_name: "BTC-PERPETUAL".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_fix_and_json_rpc_orders_match() {
+        let instrument = create_test_instrument();
+        let rest = NewOrderRequest::limit_sell("BTC-PERPETUAL".to_string(), 100.0, 50000.0)
+            .with_reduce_only(true);
+        let fix_order =
+            fix::NewOrderRequest::limit_sell("BTC-PERPETUAL".to_string(), 10.0, 50000.0)
+                .with_reduce_only(true);
+        let risk_order = RiskOrder::from_fix(&fix_order, &instrument).unwrap();
+        assert_eq!(RiskOrder::from(&rest), risk_order);
+        assert_eq!(risk_order.amount, 100.0);
+        assert!(risk_order.reduce_only);
+
+        let mut no_contract_size = instrument;
+        no_contract_size.contract_size = None;
+        assert!(RiskOrder::from_fix(&fix_order, &no_contract_size).is_err());
+    }
+
+    #[test]
+    fn test_account_daily_loss_sums_currencies_in_usd() {
+        let config =
+            RiskConfig::new().with_account_limits(RiskLimits::new().with_max_daily_loss(1_000.0));
+        let order = RiskOrder::new("BTC-PERPETUAL".to_string(), OrderSide::Buy, 10.0, None);
+        let instrument = create_test_instrument();
+
+        // 0.01 BTC at the 50000 position index price plus 300 USDC
+        let state = RiskState::new()
+            .with_account_summaries(vec![
+                create_account_summary("BTC", -0.01),
+                create_account_summary("USDC", -300.0),
+            ])
+            .with_positions(vec![create_test_position("BTC-PERPETUAL", 100.0, 0.002)]);
+        assert!(
+            config
+                .evaluate(&order, &instrument, &prices(), &state)
+                .is_empty()
+        );
+        let state = state.with_index_price("btc", 80_000.0);
+        assert_eq!(
+            config.evaluate(&order, &instrument, &prices(), &state),
+            vec![RiskViolation::DailyLoss {
+                scope: RiskScope::Account,
+                loss: 1_100.0,
+                limit: 1_000.0,
+            }]
+        );
+
+        let unpriced =
+            RiskState::new().with_account_summaries(vec![create_account_summary("ETH", -1.0)]);
+        assert_eq!(
+            config.check(&order, &instrument, &prices(), &unpriced),
+            Err(RiskViolation::MissingReferencePrice {
+                instrument_name: "BTC-PERPETUAL".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_position_without_size_currency() {
+        let config =
+            RiskConfig::new().with_account_limits(RiskLimits::new().with_max_position(100_000.0));
+        let instrument = create_test_instrument();
+        let order = RiskOrder::new("BTC-PERPETUAL".to_string(), OrderSide::Buy, 10.0, None);
+
+        let mut linear = create_test_position("ETH_USDC-PERPETUAL", 50.0, 0.0);
+        linear.size_currency = None;
+        linear.mark_price = Some(3_000.0);
+        let state = RiskState::new().with_positions(vec![linear.clone()]);
+        assert!(matches!(
+            config.evaluate(&order, &instrument, &prices(), &state)[..],
+            [RiskViolation::Position { notional, .. }] if notional == 150_010.0
+        ));
+
+        linear.mark_price = None;
+        linear.index_price = None;
+        let state = RiskState::new().with_positions(vec![linear]);
+        assert_eq!(
+            config.check(&order, &instrument, &prices(), &state),
+            Err(RiskViolation::MissingReferencePrice {
+                instrument_name: "BTC-PERPETUAL".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_config_deserialization() {
+        let json = r#"{
+            "account": {"max_open_orders": 50},
+            "currencies": {"BTC": {"max_daily_loss": 1.5}},
+            "instruments": {"BTC-PERPETUAL": {"max_order_notional": 100000.0}}
+        }"#;
+        let config: RiskConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.account.max_open_orders, Some(50));
+        assert_eq!(config.currencies["BTC"].max_daily_loss, Some(1.5));
+        assert_eq!(settlement_currency_of("ETH_USDC-PERPETUAL"), "USDC");
+        assert_eq!(settlement_currency_of("BTC-PERPETUAL"), "BTC");
+    }
+}
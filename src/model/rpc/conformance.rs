@@ -0,0 +1,421 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Conformance of the models with the bundled API documentation
+//!
+//! The fixture corpus is every example response and notification in
+//! `doc/DeribitAPI.md`. Each response is decoded with the result model of
+//! its method and each notification with the payload model of its channel,
+//! then encoded back and compared with the example. Fields missing from the
+//! re-encoded value are ignored by the model; fields whose type, value or
+//! container shape changed are mis-typed. Any error, ignored or mis-typed
+//! field fails the test of the model: the catalog declares one test per
+//! method, and the notifications are checked by a single test.
+
+use crate::model::channel::Channel;
+use crate::model::subscription::SubscriptionData;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Documentation the corpus is extracted from
+const API_DOC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/doc/DeribitAPI.md");
+
+/// Examples shared by every conformance test
+static CORPUS: LazyLock<Corpus> = LazyLock::new(Corpus::load);
+
+/// Lines introducing an example response
+const RESPONSE_MARKER: &str = "> The above command returns JSON structured like this";
+
+/// Lines introducing an example notification
+const NOTIFICATION_MARKER: &str = "> This subscription will send";
+
+/// Kind of documented example
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixtureKind {
+    /// Response of a method
+    Response,
+    /// Subscription notification
+    Notification,
+}
+
+/// Example extracted from the documentation
+#[derive(Debug, Clone)]
+struct Fixture {
+    /// Method name or channel template of the section
+    section: String,
+    /// Line of the example in the documentation
+    line: usize,
+    /// Kind of example
+    kind: FixtureKind,
+    /// Parsed example
+    json: Value,
+}
+
+/// Examples extracted from the documentation
+#[derive(Debug, Default)]
+struct Corpus {
+    /// Parsed examples, in documentation order
+    fixtures: Vec<Fixture>,
+    /// Examples that are not valid JSON, with the parser error
+    unparsed: Vec<(String, usize, String)>,
+}
+
+impl Corpus {
+    /// Extract every example response and notification
+    fn load() -> Self {
+        let doc = std::fs::read_to_string(API_DOC).unwrap();
+        let lines: Vec<&str> = doc.lines().collect();
+        let mut corpus = Self::default();
+        let mut section = String::new();
+        let mut index = 0;
+        while index < lines.len() {
+            let line = lines[index];
+            index += 1;
+            if let Some(heading) = line.strip_prefix("## ") {
+                section = heading.trim().replace("\\_", "_");
+                continue;
+            }
+            let kind = if line.starts_with(RESPONSE_MARKER) && section.starts_with('/') {
+                FixtureKind::Response
+            } else if line.starts_with(NOTIFICATION_MARKER) {
+                FixtureKind::Notification
+            } else {
+                continue;
+            };
+            let Some(open) = (index..lines.len()).find(|i| lines[*i].starts_with("```")) else {
+                break;
+            };
+            let close = (open + 1..lines.len())
+                .find(|i| lines[*i].starts_with("```"))
+                .unwrap_or(lines.len());
+            let block = lines[open + 1..close].join("\n");
+            index = close + 1;
+
+            let line = open + 2;
+            let section = section.trim_start_matches('/').to_string();
+            for json in serde_json::Deserializer::from_str(&block).into_iter::<Value>() {
+                match json {
+                    Ok(json) => corpus.fixtures.push(Fixture {
+                        section: section.clone(),
+                        line,
+                        kind,
+                        json,
+                    }),
+                    Err(err) => {
+                        corpus
+                            .unparsed
+                            .push((section.clone(), line, err.to_string()));
+                        break;
+                    }
+                }
+            }
+        }
+        corpus
+    }
+
+    /// Get the examples of one kind
+    fn of_kind(&self, kind: FixtureKind) -> impl Iterator<Item = &Fixture> {
+        self.fixtures
+            .iter()
+            .filter(move |fixture| fixture.kind == kind)
+    }
+}
+
+/// Outcome of decoding one example with its model
+#[derive(Debug, Default)]
+struct Finding {
+    /// Example label
+    example: String,
+    /// Model the example was decoded with
+    model: String,
+    /// Decoding error
+    error: Option<String>,
+    /// Documented fields missing from the re-encoded value
+    ignored: BTreeSet<String>,
+    /// Documented fields whose type or value changed
+    mistyped: BTreeSet<String>,
+}
+
+impl Finding {
+    fn new(
+        example: String,
+        model: String,
+        encoded: Result<Value, String>,
+        expected: &Value,
+    ) -> Self {
+        let mut finding = Self {
+            example,
+            model,
+            ..Self::default()
+        };
+        match encoded {
+            Ok(actual) => finding.compare(expected, &actual, ""),
+            Err(error) => finding.error = Some(error),
+        }
+        finding
+    }
+
+    fn is_clean(&self) -> bool {
+        self.error.is_none() && self.ignored.is_empty() && self.mistyped.is_empty()
+    }
+
+    /// Record the differences between the example and the re-encoded value
+    fn compare(&mut self, expected: &Value, actual: &Value, path: &str) {
+        let here = || {
+            if path.is_empty() {
+                "(root)".to_string()
+            } else {
+                path.to_string()
+            }
+        };
+        match (expected, actual) {
+            (Value::Null, Value::Null) => {}
+            (Value::Object(expected), Value::Object(actual)) => {
+                for (key, value) in expected {
+                    let child = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    match actual.get(key) {
+                        Some(actual) => self.compare(value, actual, &child),
+                        None if value.is_null() => {}
+                        None => {
+                            self.ignored.insert(child);
+                        }
+                    }
+                }
+            }
+            (Value::Array(expected), Value::Array(actual)) => {
+                let child = format!("{path}[]");
+                if expected.len() != actual.len() {
+                    self.mistyped.insert(child.clone());
+                }
+                for (expected, actual) in expected.iter().zip(actual) {
+                    self.compare(expected, actual, &child);
+                }
+            }
+            (Value::Number(expected), Value::Number(actual)) => {
+                if expected.as_f64() != actual.as_f64() {
+                    self.mistyped.insert(here());
+                }
+            }
+            (expected, actual) => {
+                if expected != actual {
+                    self.mistyped.insert(here());
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields =
+            |fields: &BTreeSet<String>| fields.iter().cloned().collect::<Vec<_>>().join(", ");
+        write!(f, "{} as {}", self.example, self.model)?;
+        if let Some(error) = &self.error {
+            write!(f, ": {error}")?;
+        }
+        if !self.ignored.is_empty() {
+            write!(f, "; ignored: {}", fields(&self.ignored))?;
+        }
+        if !self.mistyped.is_empty() {
+            write!(f, "; mis-typed: {}", fields(&self.mistyped))?;
+        }
+        Ok(())
+    }
+}
+
+/// Strip module paths from a type name (e.g., `Vec<Instrument>`)
+fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+    short
+}
+
+/// Label an example with its section and documentation line
+fn label(fixture: &Fixture) -> String {
+    format!("{} (DeribitAPI.md:{})", fixture.section, fixture.line)
+}
+
+/// Fail unless the findings are all clean
+fn assert_clean(findings: impl Iterator<Item = Finding>) {
+    let failures: Vec<String> = findings
+        .filter(|finding| !finding.is_clean())
+        .map(|finding| finding.to_string())
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "documented examples do not round-trip:\n{}",
+        failures.join("\n")
+    );
+}
+
+/// Round-trip every example response of `method` through its result model
+///
+/// Fails on a decoding error and on any ignored or mis-typed field.
+pub(crate) fn assert_documented_responses<T>(method: &str)
+where
+    T: DeserializeOwned + Serialize,
+{
+    let model = short_type_name(std::any::type_name::<T>());
+    assert_clean(
+        CORPUS
+            .of_kind(FixtureKind::Response)
+            .filter(|fixture| fixture.section == method)
+            .filter_map(|fixture| {
+                let result = fixture.json.get("result")?;
+                let encoded = serde_json::from_value::<T>(result.clone())
+                    .and_then(serde_json::to_value)
+                    .map_err(|err| err.to_string());
+                Some(Finding::new(label(fixture), model.clone(), encoded, result))
+            }),
+    );
+}
+
+/// Round-trip every example notification through its channel's payload model
+fn notification_findings(corpus: &Corpus) -> impl Iterator<Item = Finding> + '_ {
+    corpus.of_kind(FixtureKind::Notification).map(|fixture| {
+        let params = &fixture.json["params"];
+        let data = &params["data"];
+        let channel = params["channel"]
+            .as_str()
+            .ok_or_else(|| "missing channel".to_string())
+            .and_then(|channel| channel.parse::<Channel>().map_err(|err| err.to_string()));
+        let decoded = channel.and_then(|channel| {
+            SubscriptionData::decode(&channel, data).map_err(|err| err.to_string())
+        });
+        let model = match &decoded {
+            Ok(decoded) => {
+                let debug = format!("{decoded:?}");
+                let variant = debug.split('(').next().unwrap_or_default();
+                format!("SubscriptionData::{variant}")
+            }
+            Err(_) => "SubscriptionData".to_string(),
+        };
+        let encoded = decoded
+            .and_then(|decoded| serde_json::to_value(decoded).map_err(|err| err.to_string()));
+        Finding::new(label(fixture), model, encoded, data)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::rpc::methods::ALL_METHODS;
+
+    #[test]
+    fn test_corpus_covers_documented_methods() {
+        let sections: BTreeSet<&str> = CORPUS
+            .of_kind(FixtureKind::Response)
+            .map(|fixture| fixture.section.as_str())
+            .collect();
+        assert!(sections.len() > 150, "only {} methods", sections.len());
+        assert!(sections.contains("public/ticker"));
+        assert!(CORPUS.of_kind(FixtureKind::Notification).count() >= 40);
+        assert!(CORPUS.fixtures.iter().all(|fixture| fixture.line > 0));
+        assert_eq!(CORPUS.unparsed, Vec::new());
+        let uncatalogued: Vec<&str> = CORPUS
+            .of_kind(FixtureKind::Response)
+            .filter(|fixture| fixture.json.get("result").is_some())
+            .map(|fixture| fixture.section.as_str())
+            .filter(|section| !ALL_METHODS.contains(section))
+            .collect();
+        assert_eq!(uncatalogued, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_finding_compare() {
+        let expected = serde_json::json!({
+            "price": 10,
+            "amount": 1.5,
+            "label": "a",
+            "extra": "x",
+            "missing": null,
+            "legs": [{"side": "buy"}]
+        });
+        let actual = serde_json::json!({
+            "price": 10.0,
+            "amount": "1.5",
+            "label": "a",
+            "legs": [{}]
+        });
+        let finding = Finding::new("m".to_string(), "M".to_string(), Ok(actual), &expected);
+        assert!(finding.error.is_none());
+        assert_eq!(
+            finding.ignored,
+            BTreeSet::from(["extra".to_string(), "legs[].side".to_string()])
+        );
+        assert_eq!(finding.mistyped, BTreeSet::from(["amount".to_string()]));
+
+        let list = serde_json::json!([{"id": 1}]);
+        let wrapped = serde_json::json!({"trades": [{"id": 1}]});
+        let finding = Finding::new("m".to_string(), "M".to_string(), Ok(wrapped), &list);
+        assert_eq!(finding.mistyped, BTreeSet::from(["(root)".to_string()]));
+        let single = serde_json::json!({"id": 1});
+        let finding = Finding::new("m".to_string(), "M".to_string(), Ok(list), &single);
+        assert_eq!(finding.mistyped, BTreeSet::from(["(root)".to_string()]));
+        let nested = serde_json::json!({"legs": {"side": "buy"}});
+        let finding = Finding::new(
+            "m".to_string(),
+            "M".to_string(),
+            Ok(serde_json::json!({"legs": [{"side": "buy"}]})),
+            &nested,
+        );
+        assert_eq!(finding.mistyped, BTreeSet::from(["legs".to_string()]));
+        assert_eq!(finding.to_string(), "m as M; mis-typed: legs");
+        let finding = Finding::new(
+            "m".to_string(),
+            "M".to_string(),
+            Ok(serde_json::json!({"label": ""})),
+            &serde_json::json!({"label": null}),
+        );
+        assert_eq!(finding.mistyped, BTreeSet::from(["label".to_string()]));
+
+        let failed = Finding::new(
+            "m".to_string(),
+            "M".to_string(),
+            Err("x".to_string()),
+            &single,
+        );
+        assert!(!failed.is_clean());
+        assert_eq!(failed.to_string(), "m as M: x");
+        assert_eq!(
+            short_type_name("alloc::vec::Vec<deribit_base::model::Instrument>"),
+            "Vec<Instrument>"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "documented examples do not round-trip")]
+    fn test_documented_responses_reject_drift() {
+        #[derive(serde::Deserialize, Serialize)]
+        struct Partial {
+            instrument_name: String,
+        }
+        assert_documented_responses::<Partial>("public/get_instrument");
+    }
+
+    #[test]
+    fn test_documented_notifications_round_trip() {
+        assert_clean(notification_findings(&CORPUS));
+    }
+}
@@ -0,0 +1,492 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Marker types for every JSON-RPC method of the Deribit API
+//!
+//! Each type implements [`RpcMethod`] with the method name, its parameters
+//! and its result. The only result without a dedicated model,
+//! `private/logout`, is left as [`serde_json::Value`] and listed in
+//! [`UNTYPED_METHODS`].
+
+use super::RpcMethod;
+use super::params::*;
+use crate::model::account::{
+    AccessLogResponse, AccountSummaries, AccountSummary, AffiliateProgramInfo, MarginModelChange,
+    Portfolio, Subaccount, SubaccountDetails, ToggleSubaccountLoginRequest,
+};
+use crate::model::api_key::{
+    ApiKey, ChangeApiKeyScopeRequest, CreateApiKeyRequest, EditApiKeyRequest,
+};
+use crate::model::block_rfq::{
+    AcceptBlockRfqRequest, AddBlockRfqQuoteRequest, BlockRfq, BlockRfqLegPrices, BlockRfqQuote,
+    BlockRfqUserInfo, CreateBlockRfqRequest, EditBlockRfqQuoteRequest, GetBlockRfqQuotesResponse,
+    GetBlockRfqTradesResponse, GetBlockRfqsRequest, GetBlockRfqsResponse,
+};
+use crate::model::block_trade::{
+    BlockTrade, BlockTradeApprovalRequest, BlockTradeSignature, BrokerTradeRequest,
+    BrokerTradesResponse, ExecuteBlockTradeRequest, PendingBlockTrade, SimulateBlockTradeRequest,
+    VerifyBlockTradeRequest,
+};
+use crate::model::book_summary::BookSummary;
+use crate::model::combo::{ComboDetails, CreateComboRequest};
+use crate::model::extended_market_data::CurrencyInfo;
+use crate::model::funding::{FundingChartData, FundingRateData, TradingViewChartData};
+use crate::model::instrument::{IndexData, IndexPriceData, Instrument};
+use crate::model::market_data::{ActiveRfq, OrderBook, TradeVolume, VolatilityIndexData};
+use crate::model::mmp::{MmpConfigs, MmpStatus, MmpTarget, SetMmpConfigRequest};
+use crate::model::move_positions::{MovePositionsRequest, MovePositionsResponse};
+use crate::model::order::{OrderInfo, TriggerOrderHistory};
+use crate::model::order_management::{
+    BuyOrderRequest, EditOrderRequest, MassQuoteRequest, SellOrderRequest,
+};
+use crate::model::pme::PmeSimulation;
+use crate::model::position::Position;
+use crate::model::request::{
+    AuthRequest, CancelAllOrdersRequest, CancelOrderRequest, ClosePositionRequest,
+};
+use crate::model::response::{
+    AprHistoryResponse, AuthResponse, CancelOnDisconnectResponse, ContractSizeResponse,
+    DeliveryPricesResponse, ExpirationsResponse, HelloResponse, LastTradesResponse,
+    MarginsResponse, MassQuoteResponse, OrderMarginResponse, OrderResponse, SettlementsResponse,
+    StatusResponse, TestResponse, UserTradesResponse,
+};
+use crate::model::subscription::{Announcement, UserLock};
+use crate::model::ticker::TickerData;
+use crate::model::trade::UserTrade;
+use crate::model::transaction::{
+    Deposit, DepositsResponse, TransactionLog, Withdrawal, WithdrawalsResponse,
+};
+use crate::model::transfer::{
+    AccountTransfer, AddressBeneficiariesResponse, AddressBeneficiary, AddressBookEntry,
+    TransfersResponse,
+};
+use crate::model::wallet::{CustodyAccount, DepositAddress, WithdrawalRequest};
+use serde_json::Value;
+
+/// Methods whose result is left as [`serde_json::Value`]
+///
+/// `private/logout` closes the connection and returns no documented body.
+/// Every other method in [`ALL_METHODS`] decodes into a typed model.
+pub const UNTYPED_METHODS: &[&str] = &["private/logout"];
+
+/// Declare a marker type per method and the list of all method names
+macro_rules! rpc_methods {
+    ($($(#[$meta:meta])* $method:ident => $name:literal, $params:ty, $result:ty;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            pub struct $method;
+
+            impl RpcMethod for $method {
+                const NAME: &'static str = $name;
+                type Params = $params;
+                type Result = $result;
+            }
+        )*
+
+        /// Names of every method in the catalog, in documentation order
+        pub const ALL_METHODS: &[&str] = &[$($name),*];
+
+        /// Get the result type name of a method, or `None` outside the catalog
+        #[cfg(test)]
+        pub(crate) fn result_type_name(method: &str) -> Option<&'static str> {
+            match method {
+                $($name => Some(std::any::type_name::<$result>()),)*
+                _ => None,
+            }
+        }
+
+        /// Round-trip of the documented example responses, one test per method
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod documented_responses {
+            use super::*;
+
+            $(
+                #[test]
+                fn $method() {
+                    crate::model::rpc::conformance::assert_documented_responses::<$result>($name);
+                }
+            )*
+        }
+    };
+}
+
+rpc_methods! {
+    // Authentication
+    /// Authenticate the session
+    Auth => "public/auth", AuthRequest, AuthResponse;
+    /// Exchange a refresh token for a subaccount token
+    ExchangeToken => "public/exchange_token", ExchangeTokenParams, AuthResponse;
+    /// Create a new named session from a refresh token
+    ForkToken => "public/fork_token", ForkTokenParams, AuthResponse;
+    /// Close the session
+    Logout => "private/logout", LogoutParams, Value;
+
+    // Session management
+    /// Enable heartbeat test requests
+    SetHeartbeat => "public/set_heartbeat", SetHeartbeatParams, String;
+    /// Disable heartbeat test requests
+    DisableHeartbeat => "public/disable_heartbeat", EmptyParams, String;
+    /// Enable cancel-on-disconnect
+    EnableCancelOnDisconnect => "private/enable_cancel_on_disconnect", CancelOnDisconnectParams, String;
+    /// Disable cancel-on-disconnect
+    DisableCancelOnDisconnect => "private/disable_cancel_on_disconnect", CancelOnDisconnectParams, String;
+    /// Read the cancel-on-disconnect configuration
+    GetCancelOnDisconnect => "private/get_cancel_on_disconnect", CancelOnDisconnectParams, CancelOnDisconnectResponse;
+
+    // Supporting
+    /// Get the server time in milliseconds
+    GetTime => "public/get_time", EmptyParams, i64;
+    /// Introduce the client software
+    Hello => "public/hello", HelloParams, HelloResponse;
+    /// Get the platform lock status
+    Status => "public/status", EmptyParams, StatusResponse;
+    /// Test the connection
+    Test => "public/test", TestParams, TestResponse;
+
+    // Subscription management
+    /// Subscribe to public channels
+    PublicSubscribe => "public/subscribe", ChannelsParams, Vec<String>;
+    /// Unsubscribe from public channels
+    PublicUnsubscribe => "public/unsubscribe", ChannelsParams, Vec<String>;
+    /// Unsubscribe from every channel
+    PublicUnsubscribeAll => "public/unsubscribe_all", EmptyParams, String;
+    /// Subscribe to public and private channels
+    PrivateSubscribe => "private/subscribe", ChannelsParams, Vec<String>;
+    /// Unsubscribe from public and private channels
+    PrivateUnsubscribe => "private/unsubscribe", ChannelsParams, Vec<String>;
+    /// Unsubscribe from every channel
+    PrivateUnsubscribeAll => "private/unsubscribe_all", EmptyParams, String;
+
+    // Market data
+    /// Get the APR history of a yield-generating token
+    GetAprHistory => "public/get_apr_history", AprHistoryParams, AprHistoryResponse;
+    /// Get book summaries of every instrument of a currency
+    GetBookSummaryByCurrency => "public/get_book_summary_by_currency", CurrencyKindParams, Vec<BookSummary>;
+    /// Get the book summary of an instrument
+    GetBookSummaryByInstrument => "public/get_book_summary_by_instrument", InstrumentParams, Vec<BookSummary>;
+    /// Get the contract size of an instrument
+    GetContractSize => "public/get_contract_size", InstrumentParams, ContractSizeResponse;
+    /// Get every supported currency
+    GetCurrencies => "public/get_currencies", EmptyParams, Vec<CurrencyInfo>;
+    /// Get delivery prices of an index
+    GetDeliveryPrices => "public/get_delivery_prices", DeliveryPricesParams, DeliveryPricesResponse;
+    /// Get instrument expirations
+    GetExpirations => "public/get_expirations", ExpirationsParams, ExpirationsResponse;
+    /// Get perpetual funding chart data
+    GetFundingChartData => "public/get_funding_chart_data", FundingChartParams, FundingChartData;
+    /// Get the hourly funding rate history of a perpetual
+    GetFundingRateHistory => "public/get_funding_rate_history", InstrumentTimeRangeParams, Vec<FundingRateData>;
+    /// Get the funding rate of a perpetual over a period
+    GetFundingRateValue => "public/get_funding_rate_value", InstrumentTimeRangeParams, f64;
+    /// Get `[timestamp, volatility]` pairs of a currency
+    GetHistoricalVolatility => "public/get_historical_volatility", CurrencyParams, Vec<(i64, f64)>;
+    /// Get the index price of a currency
+    GetIndex => "public/get_index", CurrencyParams, IndexData;
+    /// Get the price of an index
+    GetIndexPrice => "public/get_index_price", IndexNameParams, IndexPriceData;
+    /// Get the names of every price index
+    GetIndexPriceNames => "public/get_index_price_names", EmptyParams, Vec<String>;
+    /// Get an instrument
+    GetInstrument => "public/get_instrument", InstrumentParams, Instrument;
+    /// Get the instruments of a currency
+    GetInstruments => "public/get_instruments", GetInstrumentsParams, Vec<Instrument>;
+    /// Get recent settlements of a currency
+    GetLastSettlementsByCurrency => "public/get_last_settlements_by_currency", SettlementsByCurrencyParams, SettlementsResponse;
+    /// Get recent settlements of an instrument
+    GetLastSettlementsByInstrument => "public/get_last_settlements_by_instrument", SettlementsByInstrumentParams, SettlementsResponse;
+    /// Get recent trades of a currency
+    GetLastTradesByCurrency => "public/get_last_trades_by_currency", TradesByCurrencyParams, LastTradesResponse;
+    /// Get trades of a currency in a time range
+    GetLastTradesByCurrencyAndTime => "public/get_last_trades_by_currency_and_time", TradesByCurrencyAndTimeParams, LastTradesResponse;
+    /// Get recent trades of an instrument
+    GetLastTradesByInstrument => "public/get_last_trades_by_instrument", TradesByInstrumentParams, LastTradesResponse;
+    /// Get trades of an instrument in a time range
+    GetLastTradesByInstrumentAndTime => "public/get_last_trades_by_instrument_and_time", TradesByInstrumentAndTimeParams, LastTradesResponse;
+    /// Get `[timestamp, mark_price]` pairs of an instrument
+    GetMarkPriceHistory => "public/get_mark_price_history", InstrumentTimeRangeParams, Vec<(i64, f64)>;
+    /// Get the order book of an instrument
+    GetOrderBook => "public/get_order_book", OrderBookParams, OrderBook;
+    /// Get the order book of an instrument by id
+    GetOrderBookByInstrumentId => "public/get_order_book_by_instrument_id", OrderBookByIdParams, OrderBook;
+    /// Get active RFQs of a currency
+    GetRfqs => "public/get_rfqs", CurrencyKindParams, Vec<ActiveRfq>;
+    /// Get the names of supported indices
+    GetSupportedIndexNames => "public/get_supported_index_names", SupportedIndexNamesParams, Vec<String>;
+    /// Get aggregated trade volumes
+    GetTradeVolumes => "public/get_trade_volumes", TradeVolumesParams, Vec<TradeVolume>;
+    /// Get candle chart data of an instrument
+    GetTradingViewChartData => "public/get_tradingview_chart_data", ChartDataParams, TradingViewChartData;
+    /// Get volatility index candles
+    GetVolatilityIndexData => "public/get_volatility_index_data", VolatilityIndexParams, VolatilityIndexData;
+    /// Get the ticker of an instrument
+    GetTicker => "public/ticker", InstrumentParams, TickerData;
+
+    // Trading
+    /// Place a buy order
+    Buy => "private/buy", BuyOrderRequest, OrderResponse;
+    /// Place a sell order
+    Sell => "private/sell", SellOrderRequest, OrderResponse;
+    /// Edit an order
+    Edit => "private/edit", EditOrderRequest, OrderResponse;
+    /// Edit an order by label
+    EditByLabel => "private/edit_by_label", EditByLabelParams, OrderResponse;
+    /// Cancel an order
+    Cancel => "private/cancel", CancelOrderRequest, OrderInfo;
+    /// Cancel every order
+    CancelAll => "private/cancel_all", CancelAllParams, u32;
+    /// Cancel every order of a currency
+    CancelAllByCurrency => "private/cancel_all_by_currency", CancelAllOrdersRequest, u32;
+    /// Cancel every order of a currency pair
+    CancelAllByCurrencyPair => "private/cancel_all_by_currency_pair", CancelAllByCurrencyPairParams, u32;
+    /// Cancel every order of an instrument
+    CancelAllByInstrument => "private/cancel_all_by_instrument", CancelAllByInstrumentParams, u32;
+    /// Cancel every order of some currencies by kind or type
+    CancelAllByKindOrType => "private/cancel_all_by_kind_or_type", CancelAllByKindOrTypeParams, u32;
+    /// Cancel every order with a label
+    CancelByLabel => "private/cancel_by_label", CancelByLabelParams, u32;
+    /// Cancel quotes
+    CancelQuotes => "private/cancel_quotes", CancelQuotesParams, u32;
+    /// Close a position
+    ClosePosition => "private/close_position", ClosePositionRequest, OrderResponse;
+    /// Get the margins of a hypothetical order
+    GetMargins => "private/get_margins", MarginsParams, MarginsResponse;
+    /// Get MMP configurations
+    GetMmpConfig => "private/get_mmp_config", MmpTarget, MmpConfigs;
+    /// Get triggered MMP limits
+    GetMmpStatus => "private/get_mmp_status", MmpTarget, Vec<MmpStatus>;
+    /// Get every open order
+    GetOpenOrders => "private/get_open_orders", OpenOrdersParams, Vec<OrderInfo>;
+    /// Get the open orders of a currency
+    GetOpenOrdersByCurrency => "private/get_open_orders_by_currency", OpenOrdersByCurrencyParams, Vec<OrderInfo>;
+    /// Get the open orders of an instrument
+    GetOpenOrdersByInstrument => "private/get_open_orders_by_instrument", OpenOrdersByInstrumentParams, Vec<OrderInfo>;
+    /// Get the open orders with a label
+    GetOpenOrdersByLabel => "private/get_open_orders_by_label", OrdersByLabelParams, Vec<OrderInfo>;
+    /// Get the order history of a currency
+    GetOrderHistoryByCurrency => "private/get_order_history_by_currency", OrderHistoryByCurrencyParams, Vec<OrderInfo>;
+    /// Get the order history of an instrument
+    GetOrderHistoryByInstrument => "private/get_order_history_by_instrument", OrderHistoryByInstrumentParams, Vec<OrderInfo>;
+    /// Get the initial margins of orders
+    GetOrderMarginByIds => "private/get_order_margin_by_ids", OrderIdsParams, Vec<OrderMarginResponse>;
+    /// Get the state of an order
+    GetOrderState => "private/get_order_state", OrderIdParams, OrderInfo;
+    /// Get the state of the orders with a label
+    GetOrderStateByLabel => "private/get_order_state_by_label", OrdersByLabelParams, Vec<OrderInfo>;
+    /// Get the trigger order history
+    GetTriggerOrderHistory => "private/get_trigger_order_history", TriggerOrderHistoryParams, TriggerOrderHistory;
+    /// Get user trades of a currency
+    GetUserTradesByCurrency => "private/get_user_trades_by_currency", TradesByCurrencyParams, UserTradesResponse;
+    /// Get user trades of a currency in a time range
+    GetUserTradesByCurrencyAndTime => "private/get_user_trades_by_currency_and_time", TradesByCurrencyAndTimeParams, UserTradesResponse;
+    /// Get user trades of an instrument
+    GetUserTradesByInstrument => "private/get_user_trades_by_instrument", TradesByInstrumentParams, UserTradesResponse;
+    /// Get user trades of an instrument in a time range
+    GetUserTradesByInstrumentAndTime => "private/get_user_trades_by_instrument_and_time", TradesByInstrumentAndTimeParams, UserTradesResponse;
+    /// Get the trades of an order
+    GetUserTradesByOrder => "private/get_user_trades_by_order", UserTradesByOrderParams, Vec<UserTrade>;
+    /// Place quotes on many instruments
+    MassQuote => "private/mass_quote", MassQuoteRequest, MassQuoteResponse;
+    /// Move positions between subaccounts
+    MovePositions => "private/move_positions", MovePositionsRequest, MovePositionsResponse;
+    /// Reset triggered MMP limits
+    ResetMmp => "private/reset_mmp", MmpTarget, String;
+    /// Send a request for quote
+    SendRfq => "private/send_rfq", SendRfqParams, String;
+    /// Set an MMP configuration
+    SetMmpConfig => "private/set_mmp_config", SetMmpConfigRequest, MmpConfigs;
+    /// Get the settlement history of an instrument
+    GetSettlementHistoryByInstrument => "private/get_settlement_history_by_instrument", SettlementsByInstrumentParams, SettlementsResponse;
+    /// Get the settlement history of a currency
+    GetSettlementHistoryByCurrency => "private/get_settlement_history_by_currency", SettlementsByCurrencyParams, SettlementsResponse;
+
+    // Combo Books
+    /// Get a combo
+    GetComboDetails => "public/get_combo_details", ComboIdParams, ComboDetails;
+    /// Get the combo ids of a currency
+    GetComboIds => "public/get_combo_ids", ComboIdsParams, Vec<String>;
+    /// Get the active combos of a currency
+    GetCombos => "public/get_combos", CurrencyParams, Vec<ComboDetails>;
+    /// Create a combo
+    CreateCombo => "private/create_combo", CreateComboRequest, ComboDetails;
+    /// Get leg prices of a structure
+    GetLegPrices => "private/get_leg_prices", LegPricesParams, BlockRfqLegPrices;
+
+    // Block Trade
+    /// Approve a pending block trade
+    ApproveBlockTrade => "private/approve_block_trade", BlockTradeApprovalRequest, String;
+    /// Execute a block trade
+    ExecuteBlockTrade => "private/execute_block_trade", ExecuteBlockTradeRequest, BlockTrade;
+    /// Get a block trade
+    GetBlockTrade => "private/get_block_trade", BlockTradeIdParams, BlockTrade;
+    /// Get block trade requests
+    GetBlockTradeRequests => "private/get_block_trade_requests", BlockTradeRequestsParams, Vec<PendingBlockTrade>;
+    /// Get block trades
+    GetBlockTrades => "private/get_block_trades", GetBlockTradesParams, Vec<BlockTrade>;
+    /// Get broker trade requests
+    GetBrokerTradeRequests => "private/get_broker_trade_requests", EmptyParams, Vec<BrokerTradeRequest>;
+    /// Get broker trades
+    GetBrokerTrades => "private/get_broker_trades", BrokerTradesParams, BrokerTradesResponse;
+    /// Get block trades waiting for approval
+    GetPendingBlockTrades => "private/get_pending_block_trades", EmptyParams, Vec<PendingBlockTrade>;
+    /// Invalidate a block trade signature
+    InvalidateBlockTradeSignature => "private/invalidate_block_trade_signature", BlockTradeSignature, String;
+    /// Reject a pending block trade
+    RejectBlockTrade => "private/reject_block_trade", BlockTradeApprovalRequest, String;
+    /// Check if a block trade can be executed
+    SimulateBlockTrade => "private/simulate_block_trade", SimulateBlockTradeRequest, bool;
+    /// Sign a block trade
+    VerifyBlockTrade => "private/verify_block_trade", VerifyBlockTradeRequest, BlockTradeSignature;
+
+    // Block RFQ
+    /// Get recent Block RFQ trades
+    GetBlockRfqTrades => "public/get_block_rfq_trades", BlockRfqTradesParams, GetBlockRfqTradesResponse;
+    /// Accept a Block RFQ quote as taker
+    AcceptBlockRfq => "private/accept_block_rfq", AcceptBlockRfqRequest, Vec<BlockTrade>;
+    /// Add a Block RFQ quote as maker
+    AddBlockRfqQuote => "private/add_block_rfq_quote", AddBlockRfqQuoteRequest, BlockRfqQuote;
+    /// Cancel every Block RFQ quote
+    CancelAllBlockRfqQuotes => "private/cancel_all_block_rfq_quotes", CancelAllBlockRfqQuotesParams, u32;
+    /// Cancel a Block RFQ
+    CancelBlockRfq => "private/cancel_block_rfq", BlockRfqIdParams, BlockRfq;
+    /// Cancel a Block RFQ quote
+    CancelBlockRfqQuote => "private/cancel_block_rfq_quote", BlockRfqQuoteSelector, BlockRfqQuote;
+    /// Cancel the trigger order of a Block RFQ
+    CancelBlockRfqTrigger => "private/cancel_block_rfq_trigger", BlockRfqIdParams, BlockRfq;
+    /// Create a Block RFQ
+    CreateBlockRfq => "private/create_block_rfq", CreateBlockRfqRequest, BlockRfq;
+    /// Edit a Block RFQ quote
+    EditBlockRfqQuote => "private/edit_block_rfq_quote", EditBlockRfqQuoteRequest, BlockRfqQuote;
+    /// Get the available Block RFQ makers
+    GetBlockRfqMakers => "private/get_block_rfq_makers", EmptyParams, Vec<String>;
+    /// Get open Block RFQ quotes
+    GetBlockRfqQuotes => "private/get_block_rfq_quotes", BlockRfqQuoteSelector, GetBlockRfqQuotesResponse;
+    /// Get Block RFQ identities of the account
+    GetBlockRfqUserInfo => "private/get_block_rfq_user_info", EmptyParams, BlockRfqUserInfo;
+    /// Get Block RFQs
+    GetBlockRfqs => "private/get_block_rfqs", GetBlockRfqsRequest, GetBlockRfqsResponse;
+    /// Trade a Block RFQ at the best quotes
+    TradeBlockRfq => "private/trade_block_rfq", TradeBlockRfqParams, Vec<BlockTrade>;
+
+    // Wallet
+    /// Add an address to the address book
+    AddToAddressBook => "private/add_to_address_book", AddressBookParams, AddressBookEntry;
+    /// Cancel a transfer
+    CancelTransferById => "private/cancel_transfer_by_id", CurrencyIdParams, AccountTransfer;
+    /// Cancel a withdrawal
+    CancelWithdrawal => "private/cancel_withdrawal", CurrencyIdParams, Withdrawal;
+    /// Create a deposit address
+    CreateDepositAddress => "private/create_deposit_address", CurrencyParams, DepositAddress;
+    /// Delete beneficiary information of an address
+    DeleteAddressBeneficiary => "private/delete_address_beneficiary", AddressBeneficiaryParams, String;
+    /// Get beneficiary information of an address
+    GetAddressBeneficiary => "private/get_address_beneficiary", AddressBeneficiaryParams, AddressBeneficiary;
+    /// Get the address book
+    GetAddressBook => "private/get_address_book", AddressBookTypeParams, Vec<AddressBookEntry>;
+    /// Get the current deposit address
+    GetCurrentDepositAddress => "private/get_current_deposit_address", CurrencyParams, Option<DepositAddress>;
+    /// Get deposits
+    GetDeposits => "private/get_deposits", CurrencyPageParams, DepositsResponse;
+    /// Get transfers
+    GetTransfers => "private/get_transfers", CurrencyPageParams, TransfersResponse;
+    /// Get withdrawals
+    GetWithdrawals => "private/get_withdrawals", CurrencyPageParams, WithdrawalsResponse;
+    /// List address beneficiaries
+    ListAddressBeneficiaries => "private/list_address_beneficiaries", ListAddressBeneficiariesParams, AddressBeneficiariesResponse;
+    /// Remove an address from the address book
+    RemoveFromAddressBook => "private/remove_from_address_book", RemoveFromAddressBookParams, String;
+    /// Save beneficiary information of an address
+    SaveAddressBeneficiary => "private/save_address_beneficiary", SaveAddressBeneficiaryParams, AddressBeneficiary;
+    /// Set the originator of a deposit
+    SetClearanceOriginator => "private/set_clearance_originator", SetClearanceOriginatorParams, Deposit;
+    /// Transfer funds between subaccounts
+    SubmitTransferBetweenSubaccounts => "private/submit_transfer_between_subaccounts", TransferBetweenSubaccountsParams, AccountTransfer;
+    /// Transfer funds to a subaccount
+    SubmitTransferToSubaccount => "private/submit_transfer_to_subaccount", TransferToSubaccountParams, AccountTransfer;
+    /// Transfer funds to another user
+    SubmitTransferToUser => "private/submit_transfer_to_user", TransferToUserParams, AccountTransfer;
+    /// Update an address book entry
+    UpdateInAddressBook => "private/update_in_address_book", AddressBookParams, String;
+    /// Withdraw funds
+    Withdraw => "private/withdraw", WithdrawalRequest, Withdrawal;
+
+    // Account management
+    /// Get platform announcements
+    GetAnnouncements => "public/get_announcements", AnnouncementsParams, Vec<Announcement>;
+    /// Rename an API key
+    ChangeApiKeyName => "private/change_api_key_name", ApiKeyNameParams, ApiKey;
+    /// Change the margin model
+    ChangeMarginModel => "private/change_margin_model", ChangeMarginModelParams, Vec<MarginModelChange>;
+    /// Change the maximum scope of an API key
+    ChangeScopeInApiKey => "private/change_scope_in_api_key", ChangeApiKeyScopeRequest, ApiKey;
+    /// Rename a subaccount
+    ChangeSubaccountName => "private/change_subaccount_name", SubaccountNameParams, String;
+    /// Create an API key
+    CreateApiKey => "private/create_api_key", CreateApiKeyRequest, ApiKey;
+    /// Create a subaccount
+    CreateSubaccount => "private/create_subaccount", EmptyParams, Subaccount;
+    /// Disable an API key
+    DisableApiKey => "private/disable_api_key", ApiKeyIdParams, ApiKey;
+    /// Edit an API key
+    EditApiKey => "private/edit_api_key", EditApiKeyRequest, ApiKey;
+    /// Enable the affiliate program
+    EnableAffiliateProgram => "private/enable_affiliate_program", EmptyParams, String;
+    /// Enable an API key
+    EnableApiKey => "private/enable_api_key", ApiKeyIdParams, ApiKey;
+    /// Get the account access log
+    GetAccessLog => "private/get_access_log", AccessLogParams, AccessLogResponse;
+    /// Get account summaries of every currency
+    GetAccountSummaries => "private/get_account_summaries", AccountSummariesParams, AccountSummaries;
+    /// Get the account summary of a currency
+    GetAccountSummary => "private/get_account_summary", AccountSummaryParams, AccountSummary;
+    /// Get affiliate program information
+    GetAffiliateProgramInfo => "private/get_affiliate_program_info", EmptyParams, AffiliateProgramInfo;
+    /// Get the email language
+    GetEmailLanguage => "private/get_email_language", EmptyParams, String;
+    /// Get unread announcements
+    GetNewAnnouncements => "private/get_new_announcements", EmptyParams, Vec<Announcement>;
+    /// Get the position in an instrument
+    GetPosition => "private/get_position", InstrumentParams, Position;
+    /// Get positions
+    GetPositions => "private/get_positions", PositionsParams, Vec<Position>;
+    /// Get subaccounts
+    GetSubaccounts => "private/get_subaccounts", SubaccountsParams, Vec<Subaccount>;
+    /// Get positions and open orders of subaccounts
+    GetSubaccountsDetails => "private/get_subaccounts_details", SubaccountsDetailsParams, Vec<SubaccountDetails>;
+    /// Get the transaction log
+    GetTransactionLog => "private/get_transaction_log", TransactionLogParams, TransactionLog;
+    /// Get currency locks of the account
+    GetUserLocks => "private/get_user_locks", EmptyParams, Vec<UserLock>;
+    /// List API keys
+    ListApiKeys => "private/list_api_keys", EmptyParams, Vec<ApiKey>;
+    /// List custody accounts
+    ListCustodyAccounts => "private/list_custody_accounts", CurrencyParams, Vec<CustodyAccount>;
+    /// Simulate portfolio margin
+    PmeSimulate => "private/pme/simulate", SimulatePortfolioParams, PmeSimulation;
+    /// Remove an API key
+    RemoveApiKey => "private/remove_api_key", ApiKeyIdParams, String;
+    /// Remove a subaccount
+    RemoveSubaccount => "private/remove_subaccount", RemoveSubaccountParams, String;
+    /// Reset the secret of an API key
+    ResetApiKey => "private/reset_api_key", ApiKeyIdParams, ApiKey;
+    /// Mark an announcement as read
+    SetAnnouncementAsRead => "private/set_announcement_as_read", AnnouncementIdParams, String;
+    /// Disable trading products of a user
+    SetDisabledTradingProducts => "private/set_disabled_trading_products", DisabledTradingProductsParams, String;
+    /// Set the email of a subaccount
+    SetEmailForSubaccount => "private/set_email_for_subaccount", SubaccountEmailParams, String;
+    /// Set the email language
+    SetEmailLanguage => "private/set_email_language", EmailLanguageParams, String;
+    /// Configure self-trading prevention
+    SetSelfTradingConfig => "private/set_self_trading_config", SelfTradingConfigParams, String;
+    /// Simulate portfolio margin
+    SimulatePortfolio => "private/simulate_portfolio", SimulatePortfolioParams, Portfolio;
+    /// Toggle notifications from a subaccount
+    ToggleNotificationsFromSubaccount => "private/toggle_notifications_from_subaccount", SubaccountNotificationsParams, String;
+    /// Toggle login of a subaccount
+    ToggleSubaccountLogin => "private/toggle_subaccount_login", ToggleSubaccountLoginRequest, String;
+}
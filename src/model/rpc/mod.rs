@@ -0,0 +1,215 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Typed JSON-RPC method catalog
+//!
+//! Every method of the Deribit API is a marker type implementing
+//! [`RpcMethod`], which ties the method name to its parameter and result
+//! types. Transport crates can then expose a single generic call:
+//!
+//! ```rust
+//! use deribit_base::model::rpc::{RpcMethod, methods::GetInstrument, params::InstrumentParams};
+//!
+//! fn describe<M: RpcMethod>(params: &M::Params) -> (String, serde_json::Value) {
+//!     (M::NAME.to_string(), serde_json::to_value(params).unwrap())
+//! }
+//!
+//! let (method, params) = describe::<GetInstrument>(&InstrumentParams {
+//!     instrument_name: "BTC-PERPETUAL".to_string(),
+//! });
+//! assert_eq!(method, "public/get_instrument");
+//! assert_eq!(params["instrument_name"], "BTC-PERPETUAL");
+//! assert!(!GetInstrument::IS_PRIVATE);
+//! ```
+
+#[cfg(test)]
+mod conformance;
+pub mod methods;
+pub mod params;
+pub mod pending;
+pub mod request;
+
+pub use pending::{CompletedRequest, PendingRequest, PendingRequests};
+pub use request::{JsonRpcRequest, RequestIdAllocator};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Prefix of methods that require authentication
+pub const PRIVATE_METHOD_PREFIX: &str = "private/";
+
+/// A JSON-RPC method with typed parameters and result
+pub trait RpcMethod {
+    /// Method name without leading slash (e.g., `public/get_time`)
+    const NAME: &'static str;
+    /// Whether the method requires an authenticated session
+    const IS_PRIVATE: bool = is_private_method(Self::NAME);
+    /// Parameters sent with the request
+    type Params: Serialize;
+    /// Result returned on success
+    type Result: DeserializeOwned;
+}
+
+/// Check if a method name requires authentication
+///
+/// Accepts names with or without the leading slash.
+#[must_use]
+pub const fn is_private_method(name: &str) -> bool {
+    let name = name.as_bytes();
+    let prefix = PRIVATE_METHOD_PREFIX.as_bytes();
+    let offset = if !name.is_empty() && name[0] == b'/' {
+        1
+    } else {
+        0
+    };
+    if name.len() < offset + prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if name[offset + i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Check if a method name is part of the catalog
+///
+/// Accepts names with or without the leading slash.
+#[must_use]
+pub fn is_known_method(name: &str) -> bool {
+    let name = name.strip_prefix('/').unwrap_or(name);
+    methods::ALL_METHODS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::methods::*;
+    use super::params::*;
+    use super::*;
+    use crate::model::response::OrderResponse;
+    use std::collections::HashSet;
+
+    fn documented_methods() -> Vec<String> {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/doc/DeribitAPI.md");
+        let doc = std::fs::read_to_string(path).unwrap();
+        doc.lines()
+            .filter_map(|line| line.strip_prefix("## /"))
+            .map(|name| name.trim().replace("\\_", "_"))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalog_covers_documented_methods() {
+        let documented = documented_methods();
+        assert!(!documented.is_empty());
+        for name in &documented {
+            assert!(is_known_method(name), "missing method {name}");
+        }
+        let unique: HashSet<_> = ALL_METHODS.iter().collect();
+        assert_eq!(unique.len(), ALL_METHODS.len());
+        assert_eq!(ALL_METHODS.len(), documented.len());
+    }
+
+    #[test]
+    fn test_untyped_methods_are_listed() {
+        for name in ALL_METHODS {
+            let type_name = result_type_name(name).unwrap();
+            assert_eq!(
+                type_name.contains("serde_json::value::Value"),
+                UNTYPED_METHODS.contains(name),
+                "{name} decodes into {type_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_private_method() {
+        assert!(is_private_method("private/buy"));
+        assert!(is_private_method("/private/buy"));
+        assert!(!is_private_method("public/get_time"));
+        assert!(!is_private_method("private"));
+        assert!(!is_private_method(""));
+        const {
+            assert!(Buy::IS_PRIVATE);
+            assert!(PmeSimulate::IS_PRIVATE);
+            assert!(!GetTime::IS_PRIVATE);
+            assert!(!Auth::IS_PRIVATE);
+        }
+    }
+
+    #[test]
+    fn test_method_names() {
+        assert_eq!(GetTicker::NAME, "public/ticker");
+        assert_eq!(PublicSubscribe::NAME, "public/subscribe");
+        assert_eq!(PrivateSubscribe::NAME, "private/subscribe");
+        assert!(is_known_method("/public/get_time"));
+        assert!(!is_known_method("public/unknown"));
+    }
+
+    #[test]
+    fn test_params_serialization() {
+        let empty = serde_json::to_value(EmptyParams::default()).unwrap();
+        assert_eq!(empty, serde_json::json!({}));
+
+        let params = TradesByInstrumentParams {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            count: Some(10),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(params).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"instrument_name": "BTC-PERPETUAL", "count": 10})
+        );
+
+        let cancel = CancelAllByInstrumentParams {
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            order_type: Some("limit".to_string()),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(cancel).unwrap();
+        assert_eq!(value["type"], "limit");
+    }
+
+    #[test]
+    fn test_result_deserialization() {
+        fn parse<M: RpcMethod>(json: &str) -> M::Result {
+            serde_json::from_str(json).unwrap()
+        }
+
+        assert_eq!(parse::<GetTime>("1550147385946"), 1_550_147_385_946);
+        assert_eq!(
+            parse::<PublicSubscribe>(r#"["deribit_price_index.btc_usd"]"#),
+            vec!["deribit_price_index.btc_usd".to_string()]
+        );
+        assert!(parse::<SimulateBlockTrade>("true"));
+        assert_eq!(parse::<CancelAll>("4"), 4);
+        let volatility = parse::<GetHistoricalVolatility>("[[1549720800000, 14.747743607344217]]");
+        assert_eq!(volatility[0].0, 1_549_720_800_000);
+    }
+
+    #[test]
+    fn test_generic_dispatch() {
+        fn request<M: RpcMethod>(params: &M::Params) -> serde_json::Value {
+            serde_json::json!({"method": M::NAME, "params": params})
+        }
+
+        let value = request::<GetOrderState>(&OrderIdParams {
+            order_id: "ETH-100234".to_string(),
+        });
+        assert_eq!(value["method"], "private/get_order_state");
+        assert_eq!(value["params"]["order_id"], "ETH-100234");
+
+        fn result_type<M: RpcMethod<Result = OrderResponse>>() -> &'static str {
+            M::NAME
+        }
+        assert_eq!(result_type::<Buy>(), "private/buy");
+        assert_eq!(result_type::<ClosePosition>(), "private/close_position");
+    }
+}
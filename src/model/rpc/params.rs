@@ -0,0 +1,1314 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Parameter structures for JSON-RPC methods without a dedicated request model
+//!
+//! Methods sharing the same parameter shape share one structure. Optional
+//! parameters are omitted from the serialized request when `None`; use
+//! `..Default::default()` to leave them unset.
+
+use crate::model::order::OrderSide;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Parameters of methods that take none
+///
+/// Serializes to an empty object.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct EmptyParams {}
+
+/// Parameters addressing a currency
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CurrencyParams {
+    /// Currency symbol
+    pub currency: String,
+}
+
+/// Parameters addressing a currency with an optional instrument kind filter
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CurrencyKindParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+/// Parameters addressing an instrument
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct InstrumentParams {
+    /// Instrument name
+    pub instrument_name: String,
+}
+
+/// Parameters addressing a price index
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct IndexNameParams {
+    /// Index identifier (e.g., `btc_usd`)
+    pub index_name: String,
+}
+
+/// Parameters addressing an instrument over a time range
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct InstrumentTimeRangeParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Start of the range in milliseconds
+    pub start_timestamp: i64,
+    /// End of the range in milliseconds
+    pub end_timestamp: i64,
+}
+
+/// Parameters addressing an order
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrderIdParams {
+    /// Order identifier
+    pub order_id: String,
+}
+
+/// Parameters addressing several orders
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrderIdsParams {
+    /// Order identifiers
+    pub ids: Vec<String>,
+}
+
+/// Parameters of `private/logout`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct LogoutParams {
+    /// Invalidate all tokens of the session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalidate_token: Option<bool>,
+}
+
+/// Parameters of `public/exchange_token`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ExchangeTokenParams {
+    /// Refresh token
+    pub refresh_token: String,
+    /// Subaccount to switch to
+    pub subject_id: u64,
+    /// Optional session scope
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// Parameters of `public/fork_token`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ForkTokenParams {
+    /// Refresh token
+    pub refresh_token: String,
+    /// Name of the new session
+    pub session_name: String,
+}
+
+/// Parameters of `public/set_heartbeat`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SetHeartbeatParams {
+    /// Heartbeat interval in seconds (at least 10)
+    pub interval: u32,
+}
+
+/// Parameters of the cancel-on-disconnect methods
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelOnDisconnectParams {
+    /// `connection` or `account`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// Parameters of `public/hello`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct HelloParams {
+    /// Client software name
+    pub client_name: String,
+    /// Client software version
+    pub client_version: String,
+}
+
+/// Parameters of `public/test`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TestParams {
+    /// Set to `exception` to trigger an error response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_result: Option<String>,
+}
+
+/// Parameters of the subscribe and unsubscribe methods
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ChannelsParams {
+    /// Channel names
+    pub channels: Vec<String>,
+    /// Optional label, only used by `private/subscribe`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Parameters of `public/get_apr_history`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AprHistoryParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Number of days to retrieve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Latest day to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<i64>,
+}
+
+/// Parameters of `public/get_delivery_prices`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct DeliveryPricesParams {
+    /// Index identifier
+    pub index_name: String,
+    /// Offset for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+/// Parameters of `public/get_expirations`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ExpirationsParams {
+    /// Currency symbol or `any`
+    pub currency: String,
+    /// Instrument kind or `any`
+    pub kind: String,
+    /// Currency pair or `any`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_pair: Option<String>,
+}
+
+/// Parameters of `public/get_funding_chart_data`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct FundingChartParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Period (`8h`, `24h` or `1m`)
+    pub length: String,
+}
+
+/// Parameters of `public/get_instruments`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct GetInstrumentsParams {
+    /// Currency symbol or `any`
+    pub currency: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Return expired instruments instead of active ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expired: Option<bool>,
+}
+
+/// Parameters of the settlement methods filtered by currency
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SettlementsByCurrencyParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Settlement type (`settlement`, `delivery` or `bankruptcy`)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub settlement_type: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+    /// Latest timestamp to return results from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_start_timestamp: Option<i64>,
+}
+
+/// Parameters of the settlement methods filtered by instrument
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SettlementsByInstrumentParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Settlement type (`settlement`, `delivery` or `bankruptcy`)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub settlement_type: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+    /// Latest timestamp to return results from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_start_timestamp: Option<i64>,
+}
+
+/// Parameters of the public and user trade methods filtered by currency
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TradesByCurrencyParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// First trade id to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_id: Option<String>,
+    /// Last trade id to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_id: Option<String>,
+    /// Earliest timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_timestamp: Option<i64>,
+    /// Latest timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_timestamp: Option<i64>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Sorting direction (`asc`, `desc` or `default`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting: Option<String>,
+    /// Query historical trades (user trades only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+    /// Subaccount to query (user trades only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subaccount_id: Option<u64>,
+}
+
+/// Parameters of the public and user trade methods filtered by currency and time
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TradesByCurrencyAndTimeParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Earliest timestamp in milliseconds
+    pub start_timestamp: i64,
+    /// Latest timestamp in milliseconds
+    pub end_timestamp: i64,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Sorting direction (`asc`, `desc` or `default`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting: Option<String>,
+    /// Query historical trades (user trades only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+}
+
+/// Parameters of the public and user trade methods filtered by instrument
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TradesByInstrumentParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// First trade sequence number to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_seq: Option<u64>,
+    /// Last trade sequence number to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_seq: Option<u64>,
+    /// Earliest timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_timestamp: Option<i64>,
+    /// Latest timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_timestamp: Option<i64>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Sorting direction (`asc`, `desc` or `default`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting: Option<String>,
+    /// Query historical trades (user trades only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+}
+
+/// Parameters of the public and user trade methods filtered by instrument and time
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TradesByInstrumentAndTimeParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Earliest timestamp in milliseconds
+    pub start_timestamp: i64,
+    /// Latest timestamp in milliseconds
+    pub end_timestamp: i64,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Sorting direction (`asc`, `desc` or `default`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting: Option<String>,
+    /// Query historical trades (user trades only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+}
+
+/// Parameters of `private/get_user_trades_by_order`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct UserTradesByOrderParams {
+    /// Order identifier
+    pub order_id: String,
+    /// Sorting direction (`asc`, `desc` or `default`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting: Option<String>,
+    /// Query historical trades
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+}
+
+/// Parameters of `public/get_order_book`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrderBookParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Number of levels per side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+}
+
+/// Parameters of `public/get_order_book_by_instrument_id`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrderBookByIdParams {
+    /// Instrument identifier
+    pub instrument_id: u64,
+    /// Number of levels per side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+}
+
+/// Parameters of `public/get_supported_index_names`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SupportedIndexNamesParams {
+    /// Index type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub index_type: Option<crate::model::market_data::IndexType>,
+}
+
+/// Parameters of `public/get_trade_volumes`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TradeVolumesParams {
+    /// Include 7 and 30 day volumes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended: Option<bool>,
+}
+
+/// Parameters of `public/get_tradingview_chart_data`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ChartDataParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Start of the range in milliseconds
+    pub start_timestamp: i64,
+    /// End of the range in milliseconds
+    pub end_timestamp: i64,
+    /// Candle resolution in minutes or `1D`
+    pub resolution: String,
+}
+
+/// Parameters of `public/get_volatility_index_data`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct VolatilityIndexParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Start of the range in milliseconds
+    pub start_timestamp: i64,
+    /// End of the range in milliseconds
+    pub end_timestamp: i64,
+    /// Candle resolution in seconds or `1D`
+    pub resolution: String,
+}
+
+/// Parameters of `private/edit_by_label`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct EditByLabelParams {
+    /// Label of the order to edit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Instrument name
+    pub instrument_name: String,
+    /// New amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// New amount in contracts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contracts: Option<f64>,
+    /// New price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Post-only flag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_only: Option<bool>,
+    /// Reduce-only flag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    /// Reject instead of repricing post-only orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject_post_only: Option<bool>,
+    /// Advanced option order type (`usd` or `implv`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advanced: Option<String>,
+    /// New trigger price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<f64>,
+    /// Market maker protection flag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mmp: Option<bool>,
+    /// Order validity timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<i64>,
+}
+
+/// Parameters of `private/cancel_all`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelAllParams {
+    /// Return a detailed report instead of a count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+    /// Also reject incoming quotes for one second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_quotes: Option<bool>,
+}
+
+/// Parameters of `private/cancel_all_by_currency_pair`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelAllByCurrencyPairParams {
+    /// Currency pair (e.g., `btc_usd`)
+    pub currency_pair: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Order type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+    /// Return a detailed report instead of a count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+    /// Also reject incoming quotes for one second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_quotes: Option<bool>,
+}
+
+/// Parameters of `private/cancel_all_by_instrument`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelAllByInstrumentParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Order type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+    /// Return a detailed report instead of a count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+    /// Also cancel combo orders containing the instrument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_combos: Option<bool>,
+    /// Also reject incoming quotes for one second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_quotes: Option<bool>,
+}
+
+/// Parameters of `private/cancel_all_by_kind_or_type`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelAllByKindOrTypeParams {
+    /// Currency symbols, or `any`
+    pub currency: Vec<String>,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Order type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+    /// Return a detailed report instead of a count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+    /// Also reject incoming quotes for one second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_quotes: Option<bool>,
+}
+
+/// Parameters of `private/cancel_by_label`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelByLabelParams {
+    /// Order label
+    pub label: String,
+    /// Currency symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+/// Parameters of `private/cancel_quotes`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CancelQuotesParams {
+    /// Cancel scope (`delta`, `quote_set_id`, `instrument`, `kind`, `currency`, ...)
+    pub cancel_type: String,
+    /// Return a detailed report instead of a count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+    /// Also reject incoming quotes for one second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_quotes: Option<bool>,
+    /// Minimum delta of quotes to cancel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_delta: Option<f64>,
+    /// Maximum delta of quotes to cancel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_delta: Option<f64>,
+    /// Quote set to cancel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_set_id: Option<String>,
+    /// Instrument to cancel quotes on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instrument_name: Option<String>,
+    /// Instrument kind to cancel quotes on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Currency to cancel quotes on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Currency pair to cancel quotes on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_pair: Option<String>,
+}
+
+/// Parameters of `private/get_margins`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct MarginsParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Order amount
+    pub amount: f64,
+    /// Order price
+    pub price: f64,
+}
+
+/// Parameters of `private/get_open_orders`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OpenOrdersParams {
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Order type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+}
+
+/// Parameters of `private/get_open_orders_by_currency`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OpenOrdersByCurrencyParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Order type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+}
+
+/// Parameters of `private/get_open_orders_by_instrument`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OpenOrdersByInstrumentParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Order type filter
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+}
+
+/// Parameters of the order methods filtered by label
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrdersByLabelParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Order label
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Parameters of `private/get_order_history_by_currency`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrderHistoryByCurrencyParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Offset for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Include orders older than two days
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_old: Option<bool>,
+    /// Include fully unfilled closed orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_unfilled: Option<bool>,
+    /// Return a continuation token with the results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_continuation: Option<bool>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+    /// Query historical orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+}
+
+/// Parameters of `private/get_order_history_by_instrument`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct OrderHistoryByInstrumentParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Offset for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Include orders older than two days
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_old: Option<bool>,
+    /// Include fully unfilled closed orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_unfilled: Option<bool>,
+    /// Return a continuation token with the results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_continuation: Option<bool>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+    /// Query historical orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub historical: Option<bool>,
+}
+
+/// Parameters of `private/get_trigger_order_history`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TriggerOrderHistoryParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Instrument name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instrument_name: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+}
+
+/// Parameters of `private/send_rfq`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct SendRfqParams {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Amount requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// Side requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<OrderSide>,
+}
+
+/// Parameters of `public/get_combo_details`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ComboIdParams {
+    /// Combo identifier
+    pub combo_id: String,
+}
+
+/// Parameters of `public/get_combo_ids`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ComboIdsParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Combo state filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+/// Leg of `private/get_leg_prices`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegPriceLeg {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Leg amount
+    pub amount: f64,
+    /// Leg direction
+    pub direction: OrderSide,
+}
+
+/// Parameters of `private/get_leg_prices`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct LegPricesParams {
+    /// Legs of the structure
+    pub legs: Vec<LegPriceLeg>,
+    /// Price of the whole structure
+    pub price: f64,
+}
+
+/// Parameters of `private/get_block_trade`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BlockTradeIdParams {
+    /// Block trade identifier
+    pub id: String,
+}
+
+/// Parameters of `private/get_block_trade_requests`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BlockTradeRequestsParams {
+    /// Broker code filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broker_code: Option<String>,
+}
+
+/// Parameters of `private/get_block_trades`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct GetBlockTradesParams {
+    /// Currency symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// First block trade id to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_id: Option<String>,
+    /// Last block trade id to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_id: Option<String>,
+    /// Block RFQ filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_id: Option<u64>,
+    /// Broker code filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broker_code: Option<String>,
+}
+
+/// Parameters of `private/get_broker_trades`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BrokerTradesParams {
+    /// Currency symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// First block trade id to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_id: Option<String>,
+    /// Last block trade id to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_id: Option<String>,
+}
+
+/// Parameters addressing a Block RFQ
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BlockRfqIdParams {
+    /// Block RFQ identifier
+    pub block_rfq_id: u64,
+}
+
+/// Parameters of `public/get_block_rfq_trades`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BlockRfqTradesParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<u64>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+/// Parameters of `private/cancel_all_block_rfq_quotes`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CancelAllBlockRfqQuotesParams {
+    /// Block RFQ filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_id: Option<u64>,
+    /// Return a detailed report instead of a count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<bool>,
+}
+
+/// Parameters selecting Block RFQ quotes by id, label or RFQ
+///
+/// Used by `private/cancel_block_rfq_quote` and `private/get_block_rfq_quotes`.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BlockRfqQuoteSelector {
+    /// Quote identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_quote_id: Option<u64>,
+    /// Quote label
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Block RFQ identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_id: Option<u64>,
+}
+
+/// Parameters of `private/trade_block_rfq`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeBlockRfqParams {
+    /// Block RFQ identifier
+    pub block_rfq_id: u64,
+    /// Price of the structure
+    pub price: f64,
+    /// Amount of the structure
+    pub amount: f64,
+    /// Direction from the taker's perspective
+    pub direction: OrderSide,
+    /// Legs of the structure
+    pub legs: Vec<crate::model::block_rfq::BlockRfqLeg>,
+    /// Optional hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<crate::model::block_rfq::BlockRfqHedge>,
+}
+
+/// Parameters of `private/add_to_address_book` and `private/update_in_address_book`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AddressBookParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Address book type (`transfer`, `withdrawal` or `deposit_source`)
+    #[serde(rename = "type")]
+    pub address_type: String,
+    /// Address
+    pub address: String,
+    /// Label of the address
+    pub label: String,
+    /// Beneficiary VASP name
+    pub beneficiary_vasp_name: String,
+    /// Beneficiary VASP DID
+    pub beneficiary_vasp_did: String,
+    /// Beneficiary VASP website
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_website: Option<String>,
+    /// Beneficiary first name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_first_name: Option<String>,
+    /// Beneficiary last name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_last_name: Option<String>,
+    /// Beneficiary company name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_company_name: Option<String>,
+    /// Beneficiary postal address
+    pub beneficiary_address: String,
+    /// Whether the user agreed to share data with third parties
+    pub agreed: bool,
+    /// Whether the address belongs to the user
+    pub personal: bool,
+    /// Additional currencies for the address (add only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_currencies: Option<Vec<String>>,
+}
+
+/// Parameters of `private/get_address_book`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AddressBookTypeParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Address book type (`transfer`, `withdrawal` or `deposit_source`)
+    #[serde(rename = "type")]
+    pub address_type: String,
+}
+
+/// Parameters of `private/remove_from_address_book`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct RemoveFromAddressBookParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Address book type (`transfer`, `withdrawal` or `deposit_source`)
+    #[serde(rename = "type")]
+    pub address_type: String,
+    /// Address
+    pub address: String,
+}
+
+/// Parameters addressing an address beneficiary
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AddressBeneficiaryParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Address
+    pub address: String,
+    /// Address tag or memo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// Parameters of `private/save_address_beneficiary`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SaveAddressBeneficiaryParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Address
+    pub address: String,
+    /// Address tag or memo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Whether the user agreed to share data with third parties
+    pub agreed: bool,
+    /// Whether the address belongs to the user
+    pub personal: bool,
+    /// Whether the address is unhosted
+    pub unhosted: bool,
+    /// Beneficiary VASP name
+    pub beneficiary_vasp_name: String,
+    /// Beneficiary VASP DID
+    pub beneficiary_vasp_did: String,
+    /// Beneficiary VASP website
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_website: Option<String>,
+    /// Beneficiary first name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_first_name: Option<String>,
+    /// Beneficiary last name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_last_name: Option<String>,
+    /// Beneficiary company name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_company_name: Option<String>,
+    /// Beneficiary postal address
+    pub beneficiary_address: String,
+}
+
+/// Parameters of `private/list_address_beneficiaries`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ListAddressBeneficiariesParams {
+    /// Currency symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Address tag or memo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Created before this timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<i64>,
+    /// Created after this timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<i64>,
+    /// Updated before this timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_before: Option<i64>,
+    /// Updated after this timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<i64>,
+    /// Personal address filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub personal: Option<bool>,
+    /// Unhosted address filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unhosted: Option<bool>,
+    /// Beneficiary VASP name filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_name: Option<String>,
+    /// Beneficiary VASP DID filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_did: Option<String>,
+    /// Beneficiary VASP website filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_website: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+}
+
+/// Parameters of `private/set_clearance_originator`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetClearanceOriginatorParams {
+    /// Deposit to clear
+    pub deposit_id: crate::model::wallet::DepositId,
+    /// Originator of the deposit
+    pub originator: crate::model::wallet::ClearanceOriginator,
+}
+
+/// Parameters of `private/cancel_transfer_by_id` and `private/cancel_withdrawal`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CurrencyIdParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Transfer or withdrawal identifier
+    pub id: i64,
+}
+
+/// Parameters of the paginated wallet history methods
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct CurrencyPageParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Offset for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+/// Parameters of `private/submit_transfer_between_subaccounts`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct TransferBetweenSubaccountsParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Amount to transfer
+    pub amount: f64,
+    /// Destination subaccount id
+    pub destination: u64,
+    /// Source subaccount id, defaults to the current one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<u64>,
+}
+
+/// Parameters of `private/submit_transfer_to_subaccount`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct TransferToSubaccountParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Amount to transfer
+    pub amount: f64,
+    /// Destination subaccount id
+    pub destination: u64,
+}
+
+/// Parameters of `private/submit_transfer_to_user`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct TransferToUserParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Amount to transfer
+    pub amount: f64,
+    /// Destination wallet address from the address book
+    pub destination: String,
+}
+
+/// Parameters of `public/get_announcements`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AnnouncementsParams {
+    /// Latest timestamp to return announcements from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_timestamp: Option<i64>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+/// Parameters addressing an API key
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ApiKeyIdParams {
+    /// API key identifier
+    pub id: u64,
+}
+
+/// Parameters of `private/change_api_key_name`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ApiKeyNameParams {
+    /// API key identifier
+    pub id: u64,
+    /// New name
+    pub name: String,
+}
+
+/// Parameters of `private/change_margin_model`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ChangeMarginModelParams {
+    /// User id, defaults to the current one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u64>,
+    /// New margin model
+    pub margin_model: String,
+    /// Only simulate the change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+}
+
+/// Parameters of `private/change_subaccount_name`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SubaccountNameParams {
+    /// Subaccount id
+    pub sid: u64,
+    /// New username
+    pub name: String,
+}
+
+/// Parameters of `private/set_email_for_subaccount`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SubaccountEmailParams {
+    /// Subaccount id
+    pub sid: u64,
+    /// Email address
+    pub email: String,
+}
+
+/// Parameters of `private/toggle_notifications_from_subaccount`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SubaccountNotificationsParams {
+    /// Subaccount id
+    pub sid: u64,
+    /// Enable or disable notifications
+    pub state: bool,
+}
+
+/// Parameters of `private/remove_subaccount`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct RemoveSubaccountParams {
+    /// Subaccount id
+    pub subaccount_id: u64,
+}
+
+/// Parameters of `private/get_access_log`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AccessLogParams {
+    /// Offset for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+/// Parameters of `private/get_account_summaries`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AccountSummariesParams {
+    /// Subaccount to query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subaccount_id: Option<u64>,
+    /// Include additional account fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended: Option<bool>,
+}
+
+/// Parameters of `private/get_account_summary`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AccountSummaryParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Subaccount to query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subaccount_id: Option<u64>,
+    /// Include additional account fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended: Option<bool>,
+}
+
+/// Parameters of `private/get_positions`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct PositionsParams {
+    /// Currency symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Instrument kind filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Subaccount to query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subaccount_id: Option<u64>,
+}
+
+/// Parameters of `private/get_subaccounts`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SubaccountsParams {
+    /// Include portfolio information
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_portfolio: Option<bool>,
+}
+
+/// Parameters of `private/get_subaccounts_details`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SubaccountsDetailsParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Include open orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_open_orders: Option<bool>,
+}
+
+/// Parameters of `private/get_transaction_log`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TransactionLogParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Start of the range in milliseconds
+    pub start_timestamp: i64,
+    /// End of the range in milliseconds
+    pub end_timestamp: i64,
+    /// Transaction type or free text filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Number of items to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Subaccount to query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subaccount_id: Option<u64>,
+    /// Continuation token for pagination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<u64>,
+}
+
+/// Parameters of `private/simulate_portfolio` and `private/pme/simulate`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct SimulatePortfolioParams {
+    /// Currency symbol
+    pub currency: String,
+    /// Add simulated positions to the current ones instead of replacing them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_positions: Option<bool>,
+    /// Simulated position sizes by instrument name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulated_positions: Option<HashMap<String, f64>>,
+}
+
+/// Parameters of `private/set_announcement_as_read`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AnnouncementIdParams {
+    /// Announcement identifier
+    pub announcement_id: u64,
+}
+
+/// Parameters of `private/set_disabled_trading_products`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct DisabledTradingProductsParams {
+    /// User id
+    pub user_id: u64,
+    /// Disabled trading products
+    pub trading_products: Vec<String>,
+}
+
+/// Parameters of `private/set_email_language`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct EmailLanguageParams {
+    /// Language code (e.g., `en`)
+    pub language: String,
+}
+
+/// Parameters of `private/set_self_trading_config`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SelfTradingConfigParams {
+    /// Self-trading prevention mode (`reject_taker` or `cancel_maker`)
+    pub mode: String,
+    /// Apply the configuration to subaccounts
+    pub extended_to_subaccounts: bool,
+    /// Apply self-match prevention to Block RFQs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_rfq_self_match_prevention: Option<bool>,
+}
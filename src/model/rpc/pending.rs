@@ -0,0 +1,356 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Sans-IO table of outstanding JSON-RPC requests
+//!
+//! The table performs no I/O and reads no clock: callers register a request
+//! when they send it, feed every incoming response frame to
+//! [`PendingRequests::resolve`], and periodically call
+//! [`PendingRequests::expire`] with the current time. This makes it usable
+//! from both WebSocket clients (many requests in flight on one socket) and
+//! HTTP clients (one request per call).
+
+use super::request::JsonRpcRequest;
+use crate::constants::DEFAULT_REQUEST_TIMEOUT_MS;
+use crate::error::types::{ApiError, DeribitError, DeribitResult};
+use crate::model::response::JsonRpcResponse;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// Outstanding request tracked by [`PendingRequests`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingRequest<C> {
+    /// Request ID
+    pub id: u64,
+    /// Method name
+    pub method: String,
+    /// Time the request was sent in milliseconds
+    pub sent_at_ms: u64,
+    /// Time after which the request times out in milliseconds
+    pub deadline_ms: u64,
+    /// Caller context (e.g., a channel to wake the waiting task)
+    pub context: C,
+}
+
+/// Request completed by a response or a timeout
+#[derive(Debug, Clone)]
+pub struct CompletedRequest<C> {
+    /// The request that completed
+    pub request: PendingRequest<C>,
+    /// Raw result, or the error returned by the server or the timeout
+    pub result: DeribitResult<serde_json::Value>,
+}
+
+impl<C> CompletedRequest<C> {
+    /// Decode the result into the expected type
+    ///
+    /// Use `RpcMethod::Result` as `T` for catalog methods.
+    pub fn decode<T: DeserializeOwned>(self) -> DeribitResult<T> {
+        let value = self.result?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Get the round-trip latency in milliseconds, given the completion time
+    #[must_use]
+    pub fn latency_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.request.sent_at_ms)
+    }
+}
+
+/// Table matching JSON-RPC responses to outstanding requests by id
+#[derive(Debug, Clone)]
+pub struct PendingRequests<C = ()> {
+    timeout_ms: u64,
+    requests: HashMap<u64, PendingRequest<C>>,
+}
+
+impl<C> PendingRequests<C> {
+    /// Create a table with the given default timeout
+    #[must_use]
+    pub fn new(timeout_ms: u64) -> Self {
+        Self {
+            timeout_ms,
+            requests: HashMap::new(),
+        }
+    }
+
+    /// Get the default timeout in milliseconds
+    #[must_use]
+    pub fn timeout_ms(&self) -> u64 {
+        self.timeout_ms
+    }
+
+    /// Track a request sent at `now_ms` with the default timeout
+    pub fn register<P>(
+        &mut self,
+        request: &JsonRpcRequest<P>,
+        now_ms: u64,
+        context: C,
+    ) -> DeribitResult<()> {
+        self.insert(
+            request.id,
+            &request.method,
+            now_ms,
+            self.timeout_ms,
+            context,
+        )
+    }
+
+    /// Track a request by id and method with an explicit timeout
+    ///
+    /// Fails if a request with the same id is already outstanding.
+    pub fn insert(
+        &mut self,
+        id: u64,
+        method: &str,
+        now_ms: u64,
+        timeout_ms: u64,
+        context: C,
+    ) -> DeribitResult<()> {
+        if self.requests.contains_key(&id) {
+            return Err(DeribitError::Other(format!(
+                "request id {id} is already pending"
+            )));
+        }
+        self.requests.insert(
+            id,
+            PendingRequest {
+                id,
+                method: method.to_string(),
+                sent_at_ms: now_ms,
+                deadline_ms: now_ms.saturating_add(timeout_ms),
+                context,
+            },
+        );
+        Ok(())
+    }
+
+    /// Match a response to its request
+    ///
+    /// Returns `None` when the response has no numeric id or the id is not
+    /// pending (e.g., it already timed out). A response arriving after the
+    /// deadline completes the request with [`DeribitError::Timeout`]; error
+    /// responses carry the request method and ID in their [`ApiError`].
+    pub fn resolve(
+        &mut self,
+        response: JsonRpcResponse<serde_json::Value>,
+        now_ms: u64,
+    ) -> Option<CompletedRequest<C>> {
+        let id = response.id.as_ref().and_then(serde_json::Value::as_u64)?;
+        let request = self.requests.remove(&id)?;
+        let result = if now_ms > request.deadline_ms {
+            Err(DeribitError::Timeout)
+        } else {
+            response.into_result().map_err(|error| {
+                ApiError::from(error)
+                    .with_method(request.method.as_str())
+                    .with_request_id(id)
+                    .into()
+            })
+        };
+        Some(CompletedRequest { request, result })
+    }
+
+    /// Parse a response frame and match it to its request
+    ///
+    /// Frames that are not responses (e.g., subscription notifications)
+    /// yield `Ok(None)`.
+    pub fn resolve_frame(
+        &mut self,
+        frame: &str,
+        now_ms: u64,
+    ) -> DeribitResult<Option<CompletedRequest<C>>> {
+        let value: serde_json::Value = serde_json::from_str(frame)?;
+        if value.get("id").is_none() {
+            return Ok(None);
+        }
+        let response: JsonRpcResponse<serde_json::Value> = serde_json::from_value(value)?;
+        Ok(self.resolve(response, now_ms))
+    }
+
+    /// Complete every request past its deadline with [`DeribitError::Timeout`]
+    pub fn expire(&mut self, now_ms: u64) -> Vec<CompletedRequest<C>> {
+        let expired: Vec<u64> = self
+            .requests
+            .values()
+            .filter(|request| now_ms > request.deadline_ms)
+            .map(|request| request.id)
+            .collect();
+        let mut completed: Vec<CompletedRequest<C>> = expired
+            .into_iter()
+            .filter_map(|id| self.requests.remove(&id))
+            .map(|request| CompletedRequest {
+                request,
+                result: Err(DeribitError::Timeout),
+            })
+            .collect();
+        completed.sort_by_key(|completed| completed.request.id);
+        completed
+    }
+
+    /// Stop tracking a request, returning it if it was pending
+    pub fn cancel(&mut self, id: u64) -> Option<PendingRequest<C>> {
+        self.requests.remove(&id)
+    }
+
+    /// Stop tracking every request, e.g., when the connection drops
+    pub fn drain(&mut self) -> Vec<PendingRequest<C>> {
+        let mut drained: Vec<_> = self.requests.drain().map(|(_, request)| request).collect();
+        drained.sort_by_key(|request| request.id);
+        drained
+    }
+
+    /// Get the earliest deadline, to schedule the next [`Self::expire`] call
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<u64> {
+        self.requests
+            .values()
+            .map(|request| request.deadline_ms)
+            .min()
+    }
+
+    /// Get a pending request by id
+    #[must_use]
+    pub fn get(&self, id: u64) -> Option<&PendingRequest<C>> {
+        self.requests.get(&id)
+    }
+
+    /// Check if a request is pending
+    #[must_use]
+    pub fn contains(&self, id: u64) -> bool {
+        self.requests.contains_key(&id)
+    }
+
+    /// Get the number of pending requests
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Check if no request is pending
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+}
+
+impl<C> Default for PendingRequests<C> {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUEST_TIMEOUT_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::rpc::RpcMethod;
+    use crate::model::rpc::methods::GetTime;
+    use crate::model::rpc::params::EmptyParams;
+    use crate::model::rpc::request::RequestIdAllocator;
+
+    fn get_time_request(allocator: &RequestIdAllocator) -> JsonRpcRequest<EmptyParams> {
+        allocator.request::<GetTime>(EmptyParams::default())
+    }
+
+    #[test]
+    fn test_resolve_success() {
+        let allocator = RequestIdAllocator::new();
+        let mut pending = PendingRequests::default();
+        let request = get_time_request(&allocator);
+        pending.register(&request, 1_000, "ctx").unwrap();
+        assert_eq!(pending.len(), 1);
+
+        let frame =
+            r#"{"jsonrpc":"2.0","id":1,"result":1550147385946,"usIn":1,"usOut":2,"usDiff":1}"#;
+        let completed = pending.resolve_frame(frame, 1_050).unwrap().unwrap();
+        assert_eq!(completed.request.context, "ctx");
+        assert_eq!(completed.request.method, "public/get_time");
+        assert_eq!(completed.latency_ms(1_050), 50);
+        let time: <GetTime as RpcMethod>::Result = completed.decode().unwrap();
+        assert_eq!(time, 1_550_147_385_946);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_error() {
+        let mut pending = PendingRequests::default();
+        pending.insert(5, "private/buy", 0, 1_000, ()).unwrap();
+        let frame =
+            r#"{"jsonrpc":"2.0","id":5,"error":{"code":10009,"message":"not_enough_funds"}}"#;
+        let completed = pending.resolve_frame(frame, 10).unwrap().unwrap();
+        match completed.result {
+            Err(DeribitError::Api(error)) => {
+                assert_eq!(error.code(), 10009);
+                assert_eq!(error.message, "not_enough_funds");
+                assert_eq!(error.method.as_deref(), Some("private/buy"));
+                assert_eq!(error.request_id, Some(5));
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_and_notification_frames() {
+        let mut pending: PendingRequests = PendingRequests::default();
+        pending.insert(1, "public/test", 0, 1_000, ()).unwrap();
+        let unknown = r#"{"jsonrpc":"2.0","id":99,"result":"ok"}"#;
+        assert!(pending.resolve_frame(unknown, 0).unwrap().is_none());
+        let notification =
+            r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"x","data":1}}"#;
+        assert!(pending.resolve_frame(notification, 0).unwrap().is_none());
+        assert!(pending.resolve_frame("not json", 0).is_err());
+        assert!(pending.contains(1));
+    }
+
+    #[test]
+    fn test_duplicate_id_rejected() {
+        let mut pending: PendingRequests = PendingRequests::default();
+        pending.insert(1, "public/test", 0, 1_000, ()).unwrap();
+        assert!(pending.insert(1, "public/test", 0, 1_000, ()).is_err());
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut pending: PendingRequests = PendingRequests::new(100);
+        let allocator = RequestIdAllocator::new();
+        let first = get_time_request(&allocator);
+        let second = get_time_request(&allocator);
+        pending.register(&first, 0, ()).unwrap();
+        pending.register(&second, 50, ()).unwrap();
+        assert_eq!(pending.next_deadline(), Some(100));
+
+        assert!(pending.expire(100).is_empty());
+        let expired = pending.expire(101);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].request.id, first.id);
+        assert!(matches!(expired[0].result, Err(DeribitError::Timeout)));
+        assert_eq!(pending.next_deadline(), Some(150));
+    }
+
+    #[test]
+    fn test_late_response_times_out() {
+        let mut pending: PendingRequests = PendingRequests::new(100);
+        pending.insert(1, "public/test", 0, 100, ()).unwrap();
+        let frame = r#"{"jsonrpc":"2.0","id":1,"result":{"version":"1.2.26"}}"#;
+        let completed = pending.resolve_frame(frame, 200).unwrap().unwrap();
+        assert!(matches!(
+            completed.decode::<serde_json::Value>(),
+            Err(DeribitError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn test_cancel_and_drain() {
+        let mut pending: PendingRequests = PendingRequests::default();
+        pending.insert(2, "public/test", 0, 1_000, ()).unwrap();
+        pending.insert(1, "public/test", 0, 1_000, ()).unwrap();
+        pending.insert(3, "public/test", 0, 1_000, ()).unwrap();
+        assert_eq!(pending.cancel(3).map(|request| request.id), Some(3));
+        let drained: Vec<u64> = pending.drain().into_iter().map(|r| r.id).collect();
+        assert_eq!(drained, vec![1, 2]);
+        assert!(pending.is_empty());
+    }
+}
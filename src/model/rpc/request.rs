@@ -0,0 +1,151 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! JSON-RPC request envelope and request id allocation
+
+use super::RpcMethod;
+use crate::constants::{DEFAULT_REQUEST_ID, JSONRPC_VERSION};
+use crate::error::types::DeribitResult;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Generic JSON-RPC 2.0 request envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcRequest<P> {
+    /// JSON-RPC version
+    pub jsonrpc: String,
+    /// Request ID, echoed back in the response
+    pub id: u64,
+    /// Method name (e.g., `public/get_time`)
+    pub method: String,
+    /// Method parameters
+    pub params: P,
+}
+
+impl<P> JsonRpcRequest<P> {
+    /// Create a request for a method given by name
+    pub fn new(id: u64, method: impl Into<String>, params: P) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            method: method.into(),
+            params,
+        }
+    }
+
+    /// Check if the method requires an authenticated session
+    #[must_use]
+    pub fn is_private(&self) -> bool {
+        super::is_private_method(&self.method)
+    }
+
+    /// Get the HTTP path of the method (e.g., `/api/v2/public/get_time`)
+    #[must_use]
+    pub fn http_path(&self) -> String {
+        format!("/api/v2/{}", self.method.trim_start_matches('/'))
+    }
+}
+
+impl<P: Serialize> JsonRpcRequest<P> {
+    /// Create a request for a catalog method
+    pub fn for_method<M>(id: u64, params: P) -> Self
+    where
+        M: RpcMethod<Params = P>,
+    {
+        Self::new(id, M::NAME, params)
+    }
+
+    /// Serialize the request to a JSON frame
+    pub fn to_json(&self) -> DeribitResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Thread-safe allocator of sequential JSON-RPC request ids
+///
+/// Ids start at [`DEFAULT_REQUEST_ID`] and never repeat for the lifetime
+/// of the allocator, so a connection should own one allocator.
+#[derive(Debug)]
+pub struct RequestIdAllocator {
+    next: AtomicU64,
+}
+
+impl RequestIdAllocator {
+    /// Create an allocator starting at [`DEFAULT_REQUEST_ID`]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::starting_at(DEFAULT_REQUEST_ID)
+    }
+
+    /// Create an allocator starting at the given id
+    #[must_use]
+    pub const fn starting_at(first: u64) -> Self {
+        Self {
+            next: AtomicU64::new(first),
+        }
+    }
+
+    /// Allocate the next request id
+    pub fn next_id(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Allocate an id and wrap a catalog method call in a request
+    pub fn request<M: RpcMethod>(&self, params: M::Params) -> JsonRpcRequest<M::Params> {
+        JsonRpcRequest::for_method::<M>(self.next_id(), params)
+    }
+}
+
+impl Default for RequestIdAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::rpc::methods::{Buy, GetTime};
+    use crate::model::rpc::params::EmptyParams;
+
+    #[test]
+    fn test_request_serialization() {
+        let request = JsonRpcRequest::for_method::<GetTime>(7, EmptyParams::default());
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "method": "public/get_time",
+                "params": {}
+            })
+        );
+        assert_eq!(request.http_path(), "/api/v2/public/get_time");
+        assert!(!request.is_private());
+        assert!(request.to_json().unwrap().contains("\"id\":7"));
+    }
+
+    #[test]
+    fn test_id_allocator() {
+        let allocator = RequestIdAllocator::new();
+        assert_eq!(allocator.next_id(), DEFAULT_REQUEST_ID);
+        assert_eq!(allocator.next_id(), DEFAULT_REQUEST_ID + 1);
+
+        let request = allocator.request::<GetTime>(EmptyParams::default());
+        assert_eq!(request.id, DEFAULT_REQUEST_ID + 2);
+        assert_eq!(request.method, "public/get_time");
+
+        let allocator = RequestIdAllocator::starting_at(100);
+        assert_eq!(allocator.next_id(), 100);
+    }
+
+    #[test]
+    fn test_private_request() {
+        let request = JsonRpcRequest::new(1, Buy::NAME, serde_json::json!({}));
+        assert!(request.is_private());
+    }
+}
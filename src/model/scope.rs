@@ -0,0 +1,1369 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Typed access scopes
+//!
+//! Deribit scopes are space separated items such as
+//! `account:read trade:read_write wallet:none session:mybot expires:3600`.
+//! [`AccessScope`] parses and validates them, renders them back in the order
+//! they were given, and checks whether a scope grants the access a private
+//! method requires before the request is sent, e.g. to keep a monitoring
+//! key read-only.
+//!
+//! Items this version does not know, such as an area added by Deribit, are
+//! kept verbatim in [`AccessScope::unknown`] so scopes returned by the server
+//! always decode. Areas missing from a scope are treated as not granted. The server may
+//! narrow a requested scope, so check the scope returned in `AuthResponse`.
+
+use crate::model::rpc::is_private_method;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// Functional area covered by a scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeArea {
+    /// Account methods
+    Account,
+    /// Trading methods
+    Trade,
+    /// Wallet methods
+    Wallet,
+    /// Block trade methods
+    BlockTrade,
+    /// Block RFQ methods
+    BlockRfq,
+    /// Custody platform funds
+    Custody,
+}
+
+impl ScopeArea {
+    /// Returns the area as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Account => "account",
+            Self::Trade => "trade",
+            Self::Wallet => "wallet",
+            Self::BlockTrade => "block_trade",
+            Self::BlockRfq => "block_rfq",
+            Self::Custody => "custody",
+        }
+    }
+}
+
+impl fmt::Display for ScopeArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ScopeArea {
+    type Err = ScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "account" => Ok(Self::Account),
+            "trade" => Ok(Self::Trade),
+            "wallet" => Ok(Self::Wallet),
+            "block_trade" => Ok(Self::BlockTrade),
+            "block_rfq" => Ok(Self::BlockRfq),
+            "custody" => Ok(Self::Custody),
+            _ => Err(ScopeError::UnknownScope(s.to_string())),
+        }
+    }
+}
+
+/// Access level granted on an area, ordered from `None` to `ReadWrite`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessLevel {
+    /// Access blocked
+    None,
+    /// Read only access
+    Read,
+    /// Read and write access
+    ReadWrite,
+}
+
+impl AccessLevel {
+    /// Returns the level as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Read => "read",
+            Self::ReadWrite => "read_write",
+        }
+    }
+}
+
+impl fmt::Display for AccessLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AccessLevel {
+    type Err = ScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "read" => Ok(Self::Read),
+            "read_write" => Ok(Self::ReadWrite),
+            _ => Err(ScopeError::InvalidLevel(s.to_string())),
+        }
+    }
+}
+
+/// Error raised while parsing or checking an [`AccessScope`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopeError {
+    /// Scope item is not known
+    UnknownScope(String),
+    /// Access level is not `none`, `read` or `read_write`
+    InvalidLevel(String),
+    /// `expires` is not a positive number of seconds
+    InvalidExpires(String),
+    /// `ip` is neither an IPv4 address nor `*`
+    InvalidIp(String),
+    /// `session` has an empty name
+    EmptySessionName,
+    /// Scope item is given twice
+    Duplicate(String),
+    /// Both `connection` and `session` are given
+    ConflictingLifetime,
+    /// Scope does not grant the access a method requires
+    Insufficient {
+        /// Method name
+        method: String,
+        /// Required access
+        required: ScopeRequirement,
+    },
+}
+
+impl fmt::Display for ScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownScope(item) => write!(f, "unknown scope: '{item}'"),
+            Self::InvalidLevel(level) => write!(f, "invalid access level: '{level}'"),
+            Self::InvalidExpires(value) => write!(f, "invalid expires value: '{value}'"),
+            Self::InvalidIp(value) => write!(f, "invalid ip value: '{value}'"),
+            Self::EmptySessionName => write!(f, "session name must not be empty"),
+            Self::Duplicate(item) => write!(f, "duplicate scope: '{item}'"),
+            Self::ConflictingLifetime => {
+                write!(f, "connection and session scopes are mutually exclusive")
+            }
+            Self::Insufficient { method, required } => {
+                write!(f, "{method} requires {required}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScopeError {}
+
+/// Access a private method requires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScopeRequirement {
+    /// Area of the method
+    pub area: ScopeArea,
+    /// Minimum access level
+    pub level: AccessLevel,
+    /// Area and level accepted instead of `area` and `level`
+    pub alternative: Option<(ScopeArea, AccessLevel)>,
+    /// Whether the method is restricted to the main account
+    pub mainaccount: bool,
+}
+
+impl ScopeRequirement {
+    /// Get the requirement of a method, if any
+    ///
+    /// Accepts names with or without the leading slash. Public methods and
+    /// the private methods in [`UNSCOPED_METHODS`] return `None`.
+    #[must_use]
+    pub fn for_method(method: &str) -> Option<Self> {
+        let method = method.trim_start_matches('/');
+        if !is_private_method(method) {
+            return None;
+        }
+        let (_, area, level) = METHOD_SCOPES.iter().find(|(name, _, _)| *name == method)?;
+        let alternative = ALTERNATIVE_METHOD_SCOPES
+            .iter()
+            .find(|(name, _, _)| *name == method)
+            .map(|(_, area, level)| (*area, *level));
+        Some(Self {
+            area: *area,
+            level: *level,
+            alternative,
+            mainaccount: MAINACCOUNT_METHODS.contains(&method),
+        })
+    }
+
+    /// Check whether a scope grants the area access, ignoring `mainaccount`
+    #[must_use]
+    pub fn is_granted_by(&self, scope: &AccessScope) -> bool {
+        scope.allows(self.area, self.level)
+            || self
+                .alternative
+                .is_some_and(|(area, level)| scope.allows(area, level))
+    }
+}
+
+impl fmt::Display for ScopeRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.area, self.level)?;
+        if let Some((area, level)) = self.alternative {
+            write!(f, " or {area}:{level}")?;
+        }
+        if self.mainaccount {
+            write!(f, " and mainaccount")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parsed access scope
+///
+/// Serializes to and displays as the scope string. Parsed items are rendered
+/// in their original order, followed by items added with the builders.
+/// Equality ignores the order of items.
+#[derive(Debug, Clone, Default)]
+pub struct AccessScope {
+    /// Access level per area
+    pub areas: BTreeMap<ScopeArea, AccessLevel>,
+    /// Name of the session the token is bound to
+    pub session: Option<String>,
+    /// Whether the token is bound to the connection
+    pub connection: bool,
+    /// Whether the user is the main account, set by the server
+    pub mainaccount: bool,
+    /// Access token lifetime in seconds
+    pub expires: Option<u64>,
+    /// Allowed IPv4 address, `*` for any
+    pub ip: Option<String>,
+    /// Items not known to this version, kept verbatim
+    pub unknown: Vec<String>,
+    /// Order in which the items were parsed
+    order: Vec<ScopeItem>,
+}
+
+/// Kind of a scope item, used to keep the parsed order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScopeItem {
+    Connection,
+    Session,
+    Mainaccount,
+    Area(ScopeArea),
+    Expires,
+    Ip,
+    Unknown(usize),
+}
+
+impl PartialEq for AccessScope {
+    fn eq(&self, other: &Self) -> bool {
+        self.areas == other.areas
+            && self.session == other.session
+            && self.connection == other.connection
+            && self.mainaccount == other.mainaccount
+            && self.expires == other.expires
+            && self.ip == other.ip
+            && self.unknown == other.unknown
+    }
+}
+
+impl Eq for AccessScope {}
+
+impl AccessScope {
+    /// Create an empty scope
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a scope with read access to account, trade and wallet methods
+    #[must_use]
+    pub fn read_only() -> Self {
+        Self::new()
+            .with_access(ScopeArea::Account, AccessLevel::Read)
+            .with_access(ScopeArea::Trade, AccessLevel::Read)
+            .with_access(ScopeArea::Wallet, AccessLevel::Read)
+    }
+
+    /// Parse a space separated scope string
+    pub fn parse(scope: &str) -> Result<Self, ScopeError> {
+        let mut parsed = Self::new();
+        for item in scope.split_whitespace() {
+            parsed.parse_item(item)?;
+        }
+        if parsed.connection && parsed.session.is_some() {
+            return Err(ScopeError::ConflictingLifetime);
+        }
+        Ok(parsed)
+    }
+
+    /// Set the access level of an area
+    #[must_use]
+    pub fn with_access(mut self, area: ScopeArea, level: AccessLevel) -> Self {
+        self.areas.insert(area, level);
+        self
+    }
+
+    /// Bind the token to a named session
+    #[must_use]
+    pub fn with_session(mut self, name: impl Into<String>) -> Self {
+        self.session = Some(name.into());
+        self.connection = false;
+        self
+    }
+
+    /// Set the access token lifetime in seconds
+    #[must_use]
+    pub fn with_expires(mut self, seconds: u64) -> Self {
+        self.expires = Some(seconds);
+        self
+    }
+
+    /// Restrict the token to an IPv4 address
+    #[must_use]
+    pub fn with_ip(mut self, ip: Ipv4Addr) -> Self {
+        self.ip = Some(ip.to_string());
+        self
+    }
+
+    /// Get the access level of an area, `None` when the area is absent
+    #[must_use]
+    pub fn level(&self, area: ScopeArea) -> AccessLevel {
+        self.areas.get(&area).copied().unwrap_or(AccessLevel::None)
+    }
+
+    /// Check whether the scope grants `level` on `area`
+    #[must_use]
+    pub fn allows(&self, area: ScopeArea, level: AccessLevel) -> bool {
+        self.level(area) >= level
+    }
+
+    /// Check whether the scope permits calling `method`
+    #[must_use]
+    pub fn permits(&self, method: &str) -> bool {
+        self.check(method).is_ok()
+    }
+
+    /// Check whether the scope permits calling `method`
+    ///
+    /// `mainaccount` requirements are only checked when the scope comes
+    /// from the server, i.e. when it has `mainaccount` or a lifetime set.
+    pub fn check(&self, method: &str) -> Result<(), ScopeError> {
+        let Some(required) = ScopeRequirement::for_method(method) else {
+            return Ok(());
+        };
+        let granted = required.is_granted_by(self)
+            && (!required.mainaccount || self.mainaccount || !self.is_granted());
+        if granted {
+            Ok(())
+        } else {
+            Err(ScopeError::Insufficient {
+                method: method.trim_start_matches('/').to_string(),
+                required,
+            })
+        }
+    }
+
+    /// Render the scope string sent to or returned by Deribit
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut items: Vec<ScopeItem> = self.order.clone();
+        let canonical = [
+            ScopeItem::Connection,
+            ScopeItem::Session,
+            ScopeItem::Mainaccount,
+        ]
+        .into_iter()
+        .chain(self.areas.keys().copied().map(ScopeItem::Area))
+        .chain([ScopeItem::Expires, ScopeItem::Ip])
+        .chain((0..self.unknown.len()).map(ScopeItem::Unknown));
+        for item in canonical {
+            if !items.contains(&item) {
+                items.push(item);
+            }
+        }
+        items
+            .into_iter()
+            .filter_map(|item| self.render_item(item))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Render a single item, `None` when it is not set
+    fn render_item(&self, item: ScopeItem) -> Option<String> {
+        match item {
+            ScopeItem::Connection => self.connection.then(|| "connection".to_string()),
+            ScopeItem::Session => self.session.as_ref().map(|name| format!("session:{name}")),
+            ScopeItem::Mainaccount => self.mainaccount.then(|| "mainaccount".to_string()),
+            ScopeItem::Area(area) => self.areas.get(&area).map(|level| format!("{area}:{level}")),
+            ScopeItem::Expires => self.expires.map(|expires| format!("expires:{expires}")),
+            ScopeItem::Ip => self.ip.as_ref().map(|ip| format!("ip:{ip}")),
+            ScopeItem::Unknown(index) => self.unknown.get(index).cloned(),
+        }
+    }
+
+    /// Whether the scope looks like one granted by the server
+    fn is_granted(&self) -> bool {
+        self.mainaccount || self.connection || self.session.is_some()
+    }
+
+    fn parse_item(&mut self, item: &str) -> Result<(), ScopeError> {
+        let duplicate = || ScopeError::Duplicate(item.to_string());
+        let kind = match item.split_once(':') {
+            None => {
+                let (flag, kind) = match item {
+                    "connection" => (&mut self.connection, ScopeItem::Connection),
+                    "mainaccount" => (&mut self.mainaccount, ScopeItem::Mainaccount),
+                    _ => {
+                        self.push_unknown(item);
+                        return Ok(());
+                    }
+                };
+                if *flag {
+                    return Err(duplicate());
+                }
+                *flag = true;
+                kind
+            }
+            Some(("session", name)) => {
+                if name.is_empty() {
+                    return Err(ScopeError::EmptySessionName);
+                }
+                if self.session.replace(name.to_string()).is_some() {
+                    return Err(duplicate());
+                }
+                ScopeItem::Session
+            }
+            Some(("expires", value)) => {
+                let seconds = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|seconds| *seconds > 0)
+                    .ok_or_else(|| ScopeError::InvalidExpires(value.to_string()))?;
+                if self.expires.replace(seconds).is_some() {
+                    return Err(duplicate());
+                }
+                ScopeItem::Expires
+            }
+            Some(("ip", value)) => {
+                if value != "*" && value.parse::<Ipv4Addr>().is_err() {
+                    return Err(ScopeError::InvalidIp(value.to_string()));
+                }
+                if self.ip.replace(value.to_string()).is_some() {
+                    return Err(duplicate());
+                }
+                ScopeItem::Ip
+            }
+            Some((area, level)) => {
+                let Ok(area) = area.parse::<ScopeArea>() else {
+                    self.push_unknown(item);
+                    return Ok(());
+                };
+                if self.areas.insert(area, level.parse()?).is_some() {
+                    return Err(duplicate());
+                }
+                ScopeItem::Area(area)
+            }
+        };
+        self.order.push(kind);
+        Ok(())
+    }
+
+    fn push_unknown(&mut self, item: &str) {
+        self.order.push(ScopeItem::Unknown(self.unknown.len()));
+        self.unknown.push(item.to_string());
+    }
+}
+
+impl FromStr for AccessScope {
+    type Err = ScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for AccessScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+impl Serialize for AccessScope {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.render())
+    }
+}
+
+impl<'de> Deserialize<'de> for AccessScope {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let scope = String::deserialize(deserializer)?;
+        Self::parse(&scope).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<AccessScope> for String {
+    fn from(scope: AccessScope) -> Self {
+        scope.render()
+    }
+}
+
+/// Methods restricted to the main account
+pub const MAINACCOUNT_METHODS: &[&str] = &[
+    "private/change_subaccount_name",
+    "private/create_subaccount",
+    "private/submit_transfer_to_user",
+    "private/withdraw",
+];
+
+/// Private methods the documentation gives no scope for
+///
+/// They only need an authenticated session, so [`AccessScope::check`]
+/// allows them under any scope.
+pub const UNSCOPED_METHODS: &[&str] = &[
+    "private/get_leg_prices",
+    "private/list_custody_accounts",
+    "private/logout",
+    "private/subscribe",
+    "private/unsubscribe",
+    "private/unsubscribe_all",
+];
+
+/// Scopes documented as accepted instead of the [`METHOD_SCOPES`] entry
+///
+/// The MMP methods also accept `block_rfq` access when they target the
+/// Block RFQ MMP configuration.
+pub const ALTERNATIVE_METHOD_SCOPES: &[(&str, ScopeArea, AccessLevel)] = &[
+    (
+        "private/get_mmp_config",
+        ScopeArea::BlockRfq,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_mmp_status",
+        ScopeArea::BlockRfq,
+        AccessLevel::Read,
+    ),
+    (
+        "private/reset_mmp",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_mmp_config",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+];
+
+/// Documented scope of every private method not in [`UNSCOPED_METHODS`]
+pub const METHOD_SCOPES: &[(&str, ScopeArea, AccessLevel)] = &[
+    (
+        "private/accept_block_rfq",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/add_block_rfq_quote",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/add_to_address_book",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/approve_block_trade",
+        ScopeArea::BlockTrade,
+        AccessLevel::ReadWrite,
+    ),
+    ("private/buy", ScopeArea::Trade, AccessLevel::ReadWrite),
+    ("private/cancel", ScopeArea::Trade, AccessLevel::ReadWrite),
+    (
+        "private/cancel_all",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_all_block_rfq_quotes",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_all_by_currency",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_all_by_currency_pair",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_all_by_instrument",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_all_by_kind_or_type",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_block_rfq",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_block_rfq_quote",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_block_rfq_trigger",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_by_label",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_quotes",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_transfer_by_id",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/cancel_withdrawal",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/change_api_key_name",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/change_margin_model",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/change_scope_in_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/change_subaccount_name",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/close_position",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/create_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/create_block_rfq",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/create_combo",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/create_deposit_address",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/create_subaccount",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/delete_address_beneficiary",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/disable_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/disable_cancel_on_disconnect",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    ("private/edit", ScopeArea::Trade, AccessLevel::ReadWrite),
+    (
+        "private/edit_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/edit_block_rfq_quote",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/edit_by_label",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/enable_affiliate_program",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/enable_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/enable_cancel_on_disconnect",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/execute_block_trade",
+        ScopeArea::BlockTrade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/get_access_log",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_account_summaries",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_account_summary",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_address_beneficiary",
+        ScopeArea::Wallet,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_address_book",
+        ScopeArea::Wallet,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_affiliate_program_info",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_rfq_makers",
+        ScopeArea::BlockRfq,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_rfq_quotes",
+        ScopeArea::BlockRfq,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_rfq_user_info",
+        ScopeArea::BlockRfq,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_rfqs",
+        ScopeArea::BlockRfq,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_trade",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_trade_requests",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_block_trades",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_broker_trade_requests",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_broker_trades",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_cancel_on_disconnect",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_current_deposit_address",
+        ScopeArea::Wallet,
+        AccessLevel::Read,
+    ),
+    ("private/get_deposits", ScopeArea::Wallet, AccessLevel::Read),
+    (
+        "private/get_email_language",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    ("private/get_margins", ScopeArea::Trade, AccessLevel::Read),
+    (
+        "private/get_mmp_config",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_mmp_status",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_new_announcements",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_open_orders",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_open_orders_by_currency",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_open_orders_by_instrument",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_open_orders_by_label",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_order_history_by_currency",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_order_history_by_instrument",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_order_margin_by_ids",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_order_state",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_order_state_by_label",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_pending_block_trades",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    ("private/get_position", ScopeArea::Trade, AccessLevel::Read),
+    ("private/get_positions", ScopeArea::Trade, AccessLevel::Read),
+    (
+        "private/get_settlement_history_by_currency",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_settlement_history_by_instrument",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_subaccounts",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_subaccounts_details",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_transaction_log",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_transfers",
+        ScopeArea::Wallet,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_trigger_order_history",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_user_locks",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_user_trades_by_currency",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_user_trades_by_currency_and_time",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_user_trades_by_instrument",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_user_trades_by_instrument_and_time",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_user_trades_by_order",
+        ScopeArea::Trade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/get_withdrawals",
+        ScopeArea::Wallet,
+        AccessLevel::Read,
+    ),
+    (
+        "private/invalidate_block_trade_signature",
+        ScopeArea::BlockTrade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/list_address_beneficiaries",
+        ScopeArea::Wallet,
+        AccessLevel::Read,
+    ),
+    (
+        "private/list_api_keys",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/mass_quote",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/move_positions",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/pme/simulate",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/reject_block_trade",
+        ScopeArea::BlockTrade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/remove_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/remove_from_address_book",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/remove_subaccount",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/reset_api_key",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/reset_mmp",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/save_address_beneficiary",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    ("private/sell", ScopeArea::Trade, AccessLevel::ReadWrite),
+    ("private/send_rfq", ScopeArea::Trade, AccessLevel::ReadWrite),
+    (
+        "private/set_announcement_as_read",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_clearance_originator",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_disabled_trading_products",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_email_for_subaccount",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_email_language",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_mmp_config",
+        ScopeArea::Trade,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/set_self_trading_config",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/simulate_block_trade",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/simulate_portfolio",
+        ScopeArea::Account,
+        AccessLevel::Read,
+    ),
+    (
+        "private/submit_transfer_between_subaccounts",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/submit_transfer_to_subaccount",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/submit_transfer_to_user",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/toggle_notifications_from_subaccount",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/toggle_subaccount_login",
+        ScopeArea::Account,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/trade_block_rfq",
+        ScopeArea::BlockRfq,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/update_in_address_book",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+    (
+        "private/verify_block_trade",
+        ScopeArea::BlockTrade,
+        AccessLevel::Read,
+    ),
+    (
+        "private/withdraw",
+        ScopeArea::Wallet,
+        AccessLevel::ReadWrite,
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::rpc::methods::ALL_METHODS;
+
+    #[test]
+    fn test_parse_and_render() {
+        let scope = AccessScope::parse(
+            "account:read trade:read_write wallet:none session:mybot expires:3600 ip:1.2.3.4 mainaccount",
+        )
+        .unwrap();
+        assert_eq!(scope.level(ScopeArea::Account), AccessLevel::Read);
+        assert_eq!(scope.level(ScopeArea::Trade), AccessLevel::ReadWrite);
+        assert_eq!(scope.level(ScopeArea::Wallet), AccessLevel::None);
+        assert_eq!(scope.level(ScopeArea::BlockRfq), AccessLevel::None);
+        assert_eq!(scope.session.as_deref(), Some("mybot"));
+        assert_eq!(scope.expires, Some(3600));
+        assert_eq!(scope.ip.as_deref(), Some("1.2.3.4"));
+        assert!(scope.mainaccount);
+        assert!(!scope.connection);
+
+        let rendered = scope.render();
+        assert_eq!(
+            rendered,
+            "account:read trade:read_write wallet:none session:mybot expires:3600 ip:1.2.3.4 mainaccount"
+        );
+        assert_eq!(rendered.parse::<AccessScope>().unwrap(), scope);
+        assert_eq!(scope.to_string(), rendered);
+        assert_eq!(
+            serde_json::to_value(&scope).unwrap(),
+            serde_json::json!(rendered)
+        );
+        assert_eq!(
+            serde_json::from_str::<AccessScope>(&format!("\"{rendered}\"")).unwrap(),
+            scope
+        );
+        assert!(serde_json::from_str::<AccessScope>("\"trade:write\"").is_err());
+        assert_eq!(String::from(scope), rendered);
+
+        let granted = AccessScope::parse("connection mainaccount").unwrap();
+        assert!(granted.connection);
+        assert!(granted.areas.is_empty());
+        assert_eq!(AccessScope::parse("").unwrap(), AccessScope::new());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("trade:write", ScopeError::InvalidLevel("write".to_string())),
+            ("expires:0", ScopeError::InvalidExpires("0".to_string())),
+            (
+                "expires:soon",
+                ScopeError::InvalidExpires("soon".to_string()),
+            ),
+            ("ip:::1", ScopeError::InvalidIp("::1".to_string())),
+            ("session:", ScopeError::EmptySessionName),
+            (
+                "trade:read trade:read_write",
+                ScopeError::Duplicate("trade:read_write".to_string()),
+            ),
+            ("connection session:bot", ScopeError::ConflictingLifetime),
+        ];
+        for (scope, expected) in cases {
+            assert_eq!(AccessScope::parse(scope), Err(expected), "{scope}");
+        }
+        assert!(AccessScope::parse("ip:*").is_ok());
+    }
+
+    #[test]
+    fn test_unknown_items_and_order_are_kept() {
+        let documented = "account:read_write block_trade:read trade:read_write wallet:read_write";
+        let scope = AccessScope::parse(documented).unwrap();
+        assert_eq!(scope.render(), documented);
+        assert_eq!(
+            scope,
+            AccessScope::parse(
+                "trade:read_write wallet:read_write account:read_write block_trade:read"
+            )
+            .unwrap()
+        );
+
+        let scope = AccessScope::parse("custodian:read trade:read admin").unwrap();
+        assert_eq!(scope.unknown, ["custodian:read", "admin"]);
+        assert_eq!(scope.level(ScopeArea::Trade), AccessLevel::Read);
+        assert_eq!(scope.render(), "custodian:read trade:read admin");
+        assert!(scope.permits("private/get_positions"));
+
+        let extended = scope
+            .with_access(ScopeArea::Trade, AccessLevel::ReadWrite)
+            .with_access(ScopeArea::Account, AccessLevel::Read)
+            .with_expires(60);
+        assert_eq!(
+            extended.render(),
+            "custodian:read trade:read_write admin account:read expires:60"
+        );
+        assert_eq!(
+            serde_json::from_str::<AccessScope>("\"future_area:read wallet:none\"")
+                .unwrap()
+                .render(),
+            "future_area:read wallet:none"
+        );
+    }
+
+    #[test]
+    fn test_method_requirements() {
+        let buy = ScopeRequirement::for_method("/private/buy").unwrap();
+        assert_eq!(buy.area, ScopeArea::Trade);
+        assert_eq!(buy.level, AccessLevel::ReadWrite);
+        assert!(!buy.mainaccount);
+
+        let withdraw = ScopeRequirement::for_method("private/withdraw").unwrap();
+        assert_eq!(withdraw.to_string(), "wallet:read_write and mainaccount");
+        let transfer = ScopeRequirement::for_method("private/submit_transfer_to_subaccount");
+        assert_eq!(transfer.unwrap().area, ScopeArea::Wallet);
+
+        assert_eq!(ScopeRequirement::for_method("public/get_time"), None);
+        assert_eq!(ScopeRequirement::for_method("private/logout"), None);
+        assert!(
+            METHOD_SCOPES
+                .iter()
+                .all(|(method, _, _)| is_private_method(method))
+        );
+    }
+
+    #[test]
+    fn test_every_private_method_is_scoped_or_listed() {
+        for method in ALL_METHODS
+            .iter()
+            .filter(|method| is_private_method(method))
+        {
+            let scoped = METHOD_SCOPES.iter().any(|(name, _, _)| name == method);
+            assert_ne!(scoped, UNSCOPED_METHODS.contains(method), "{method}");
+        }
+        assert!(AccessScope::default().permits("private/logout"));
+    }
+
+    #[test]
+    fn test_read_only_scope_permits() {
+        let scope = AccessScope::read_only().with_session("monitor");
+        assert!(scope.permits("private/get_positions"));
+        assert!(scope.permits("private/get_account_summary"));
+        assert!(scope.permits("private/get_deposits"));
+        assert!(scope.permits("public/ticker"));
+        assert!(!scope.permits("private/get_block_trades"));
+        assert_eq!(
+            scope.check("/private/buy"),
+            Err(ScopeError::Insufficient {
+                method: "private/buy".to_string(),
+                required: ScopeRequirement {
+                    area: ScopeArea::Trade,
+                    level: AccessLevel::ReadWrite,
+                    alternative: None,
+                    mainaccount: false,
+                },
+            })
+        );
+        assert!(!scope.permits("private/withdraw"));
+    }
+
+    #[test]
+    fn test_mmp_methods_accept_block_rfq_scope() {
+        let set = ScopeRequirement::for_method("private/set_mmp_config").unwrap();
+        assert_eq!(set.to_string(), "trade:read_write or block_rfq:read_write");
+
+        let block_rfq = AccessScope::new().with_access(ScopeArea::BlockRfq, AccessLevel::ReadWrite);
+        for method in [
+            "private/get_mmp_config",
+            "private/get_mmp_status",
+            "private/reset_mmp",
+            "private/set_mmp_config",
+        ] {
+            assert!(block_rfq.permits(method), "{method}");
+            assert!(!AccessScope::new().permits(method), "{method}");
+        }
+        assert!(!block_rfq.permits("private/mass_quote"));
+
+        let read = AccessScope::new().with_access(ScopeArea::BlockRfq, AccessLevel::Read);
+        assert!(read.permits("private/get_mmp_config"));
+        assert!(!read.permits("private/reset_mmp"));
+        assert!(
+            ALTERNATIVE_METHOD_SCOPES
+                .iter()
+                .all(|(method, _, _)| METHOD_SCOPES.iter().any(|(name, _, _)| name == method))
+        );
+    }
+
+    #[test]
+    fn test_mainaccount_requirement() {
+        let requested = AccessScope::new().with_access(ScopeArea::Wallet, AccessLevel::ReadWrite);
+        assert!(requested.permits("private/withdraw"));
+
+        let subaccount = AccessScope::parse("connection wallet:read_write").unwrap();
+        assert!(!subaccount.permits("private/withdraw"));
+        assert!(subaccount.permits("private/cancel_withdrawal"));
+
+        let main = AccessScope::parse("connection mainaccount wallet:read_write").unwrap();
+        assert!(main.permits("private/withdraw"));
+    }
+
+    #[test]
+    fn test_builders() {
+        let scope = AccessScope::new()
+            .with_access(ScopeArea::BlockRfq, AccessLevel::Read)
+            .with_expires(600)
+            .with_ip(Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(scope.render(), "block_rfq:read expires:600 ip:10.0.0.1");
+        assert!(scope.allows(ScopeArea::BlockRfq, AccessLevel::Read));
+        assert!(!scope.allows(ScopeArea::BlockRfq, AccessLevel::ReadWrite));
+        assert_eq!(ScopeArea::BlockTrade.to_string(), "block_trade");
+        assert_eq!(
+            AccessScope::parse("custody:read_write")
+                .unwrap()
+                .level(ScopeArea::Custody),
+            AccessLevel::ReadWrite
+        );
+        assert_eq!(
+            "read_write".parse::<AccessLevel>(),
+            Ok(AccessLevel::ReadWrite)
+        );
+    }
+}
@@ -24,7 +24,7 @@ pub enum SettlementType {
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Settlement {
     /// Type of settlement event
-    #[serde(alias = "type")]
+    #[serde(rename = "type", alias = "settlement_type")]
     pub settlement_type: SettlementType,
     /// Timestamp of the settlement event (milliseconds since Unix epoch)
     pub timestamp: i64,
@@ -32,7 +32,11 @@ pub struct Settlement {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instrument_name: Option<String>,
     /// Position size in quote currency (settlement and delivery only)
-    #[serde(alias = "position", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "position",
+        alias = "position_size",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub position_size: Option<f64>,
     /// Mark price at settlement time in quote currency (settlement and delivery only)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -46,12 +50,20 @@ pub struct Settlement {
     /// Funding in base currency (settlement for perpetual product only)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub funding: Option<f64>,
+    /// Funded amount in base currency (bankruptcy only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funded: Option<f64>,
     // Additional fields from deribit-http types.rs
     /// Session profit and loss (optional)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_profit_loss: Option<f64>,
     /// Session bankruptcy (optional)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        rename = "session_bankruptcy",
+        alias = "session_bankrupt_cy",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub session_bankrupt_cy: Option<f64>,
     /// Session tax (optional)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -60,7 +72,12 @@ pub struct Settlement {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_tax_rate: Option<f64>,
     /// Socialized losses (optional)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        rename = "socialized",
+        alias = "socialized_losses",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub socialized_losses: Option<f64>,
     /// Additional fields that might be present in the API response
     #[serde(flatten)]
@@ -79,6 +96,7 @@ impl Settlement {
             index_price: None,
             profit_loss: None,
             funding: None,
+            funded: None,
             session_profit_loss: None,
             session_bankrupt_cy: None,
             session_tax: None,
@@ -103,6 +121,7 @@ impl Settlement {
             index_price: None,
             profit_loss: None,
             funding: None,
+            funded: None,
             session_profit_loss: None,
             session_bankrupt_cy: None,
             session_tax: None,
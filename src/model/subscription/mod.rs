@@ -0,0 +1,541 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Typed decoding of subscription notifications
+//!
+//! [`Notification<T>`](crate::model::response::Notification) requires the
+//! payload type up front. [`NotificationDecoder`] instead reads the
+//! `channel` field of a `subscription` notification, parses it into a
+//! [`Channel`] and decodes `data` into the matching [`SubscriptionData`]
+//! variant. Channels that fail to parse, payloads that fail to decode and
+//! fields the models do not cover are recorded in a [`ShapeReport`], so
+//! API changes surface without breaking the stream.
+
+pub mod payload;
+
+pub use payload::{
+    AccessLogEntry, Announcement, BlockRfqPublicTrade, BookAction, BookLevelChange, BookUpdate,
+    ChartTradesUpdate, EstimatedExpirationPrice, GroupedBookUpdate, IncrementalTickerUpdate,
+    InstrumentStateUpdate, MarkPriceOption, PerpetualUpdate, PlatformStateUpdate,
+    PriceRankingEntry, PriceStatistics, PublicMethodsState, QuoteUpdate, RfqUpdate, UpdateType,
+    UserChanges, UserLock, VolatilityIndexUpdate,
+};
+
+use crate::model::account::Portfolio;
+use crate::model::block_rfq::{BlockRfq, BlockRfqQuote};
+use crate::model::block_trade::PendingBlockTrade;
+use crate::model::channel::{Channel, ChannelError, ChannelInterval};
+use crate::model::extended_market_data::IndexPrice;
+use crate::model::mmp::MmpTrigger;
+use crate::model::order::OrderInfo;
+use crate::model::ticker::TickerData;
+use crate::model::trade::{Trade, UserTrade};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// JSON-RPC method of subscription notifications
+pub const SUBSCRIPTION_METHOD: &str = "subscription";
+
+/// Error raised when decoding a subscription notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationError {
+    /// Frame is not valid JSON
+    Json(String),
+    /// Frame is not a `subscription` notification
+    NotASubscription,
+    /// Channel name is unknown or invalid
+    Channel(ChannelError),
+    /// Payload does not match the model of its channel
+    Payload {
+        /// Channel name
+        channel: String,
+        /// Decoding error
+        message: String,
+    },
+}
+
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(message) => write!(f, "invalid notification JSON: {message}"),
+            Self::NotASubscription => write!(f, "frame is not a subscription notification"),
+            Self::Channel(err) => write!(f, "{err}"),
+            Self::Payload { channel, message } => {
+                write!(f, "unexpected payload on channel {channel}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotificationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Channel(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ChannelError> for NotificationError {
+    fn from(err: ChannelError) -> Self {
+        Self::Channel(err)
+    }
+}
+
+/// Decoded payload of a subscription notification, one variant per channel family
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SubscriptionData {
+    /// `announcements`
+    Announcement(Announcement),
+    /// `block_rfq.maker.quotes.{currency}`
+    BlockRfqQuotes(Vec<BlockRfqQuote>),
+    /// `block_rfq.maker.{currency}` and `block_rfq.taker.{currency}`
+    BlockRfq(Box<BlockRfq>),
+    /// `block_rfq.trades.{currency}`
+    BlockRfqTrade(BlockRfqPublicTrade),
+    /// `block_trade_confirmations` and `block_trade_confirmations.{currency}`
+    BlockTradeConfirmation(PendingBlockTrade),
+    /// `book.{instrument_name}.{interval}`
+    Book(BookUpdate),
+    /// `book.{instrument_name}.{group}.{depth}.{interval}`
+    GroupedBook(GroupedBookUpdate),
+    /// `chart.trades.{instrument_name}.{resolution}`
+    ChartTrades(ChartTradesUpdate),
+    /// `deribit_price_index.{index_name}`
+    IndexPrice(IndexPrice),
+    /// `deribit_price_ranking.{index_name}`
+    PriceRanking(Vec<PriceRankingEntry>),
+    /// `deribit_price_statistics.{index_name}`
+    PriceStatistics(PriceStatistics),
+    /// `deribit_volatility_index.{index_name}`
+    VolatilityIndex(VolatilityIndexUpdate),
+    /// `estimated_expiration_price.{index_name}`
+    EstimatedExpirationPrice(EstimatedExpirationPrice),
+    /// `incremental_ticker.{instrument_name}`
+    IncrementalTicker(Box<IncrementalTickerUpdate>),
+    /// `instrument.state.{kind}.{currency}`
+    InstrumentState(InstrumentStateUpdate),
+    /// `markprice.options.{index_name}`
+    MarkPriceOptions(Vec<MarkPriceOption>),
+    /// `perpetual.{instrument_name}.{interval}`
+    Perpetual(PerpetualUpdate),
+    /// `platform_state`
+    PlatformState(PlatformStateUpdate),
+    /// `platform_state.public_methods_state`
+    PublicMethodsState(PublicMethodsState),
+    /// `quote.{instrument_name}`
+    Quote(QuoteUpdate),
+    /// `rfq.{currency}`
+    Rfq(RfqUpdate),
+    /// `ticker.{instrument_name}.{interval}`
+    Ticker(Box<TickerData>),
+    /// `trades.{instrument_name}.{interval}` and `trades.{kind}.{currency}.{interval}`
+    Trades(Vec<Trade>),
+    /// `user.access_log`
+    AccessLog(AccessLogEntry),
+    /// `user.changes.*`
+    UserChanges(UserChanges),
+    /// `user.combo_trades.*`
+    UserComboTrades(Vec<UserTrade>),
+    /// `user.lock`
+    UserLock(UserLock),
+    /// `user.mmp_trigger.{index_name}`
+    MmpTrigger(MmpTrigger),
+    /// `user.orders.*.raw`, one order per notification
+    UserOrder(Box<OrderInfo>),
+    /// `user.orders.*` aggregated over an interval
+    UserOrders(Vec<OrderInfo>),
+    /// `user.portfolio.{currency}`
+    Portfolio(Box<Portfolio>),
+    /// `user.trades.*`
+    UserTrades(Vec<UserTrade>),
+}
+
+impl SubscriptionData {
+    /// Decode the `data` field of a notification on the given channel
+    pub fn decode(channel: &Channel, data: &Value) -> Result<Self, serde_json::Error> {
+        Ok(match channel {
+            Channel::Announcements => Self::Announcement(decode(data)?),
+            Channel::BlockRfqMakerQuotes { .. } => Self::BlockRfqQuotes(decode(data)?),
+            Channel::BlockRfqMaker { .. } | Channel::BlockRfqTaker { .. } => {
+                Self::BlockRfq(decode(data)?)
+            }
+            Channel::BlockRfqTrades { .. } => Self::BlockRfqTrade(decode(data)?),
+            Channel::BlockTradeConfirmations { .. } => Self::BlockTradeConfirmation(decode(data)?),
+            Channel::Book { .. } => Self::Book(decode(data)?),
+            Channel::GroupedBook { .. } => Self::GroupedBook(decode(data)?),
+            Channel::ChartTrades { .. } => Self::ChartTrades(decode(data)?),
+            Channel::PriceIndex { .. } => Self::IndexPrice(decode(data)?),
+            Channel::PriceRanking { .. } => Self::PriceRanking(decode(data)?),
+            Channel::PriceStatistics { .. } => Self::PriceStatistics(decode(data)?),
+            Channel::VolatilityIndex { .. } => Self::VolatilityIndex(decode(data)?),
+            Channel::EstimatedExpirationPrice { .. } => {
+                Self::EstimatedExpirationPrice(decode(data)?)
+            }
+            Channel::IncrementalTicker { .. } => Self::IncrementalTicker(decode(data)?),
+            Channel::InstrumentState { .. } => Self::InstrumentState(decode(data)?),
+            Channel::MarkPriceOptions { .. } => Self::MarkPriceOptions(decode(data)?),
+            Channel::Perpetual { .. } => Self::Perpetual(decode(data)?),
+            Channel::PlatformState => Self::PlatformState(decode(data)?),
+            Channel::PlatformStatePublicMethods => Self::PublicMethodsState(decode(data)?),
+            Channel::Quote { .. } => Self::Quote(decode(data)?),
+            Channel::Rfq { .. } => Self::Rfq(decode(data)?),
+            Channel::Ticker { .. } => Self::Ticker(decode(data)?),
+            Channel::Trades { .. } | Channel::TradesByKind { .. } => Self::Trades(decode(data)?),
+            Channel::UserAccessLog => Self::AccessLog(decode(data)?),
+            Channel::UserChanges { .. } | Channel::UserChangesByKind { .. } => {
+                Self::UserChanges(decode(data)?)
+            }
+            Channel::UserComboTrades { .. } | Channel::UserComboTradesByKind { .. } => {
+                Self::UserComboTrades(decode(data)?)
+            }
+            Channel::UserLock => Self::UserLock(decode(data)?),
+            Channel::UserMmpTrigger { .. } => Self::MmpTrigger(decode(data)?),
+            Channel::UserOrders {
+                interval: ChannelInterval::Raw,
+                ..
+            }
+            | Channel::UserOrdersByKind {
+                interval: ChannelInterval::Raw,
+                ..
+            } => Self::UserOrder(decode(data)?),
+            Channel::UserOrders { .. } | Channel::UserOrdersByKind { .. } => {
+                Self::UserOrders(decode(data)?)
+            }
+            Channel::UserPortfolio { .. } => Self::Portfolio(decode(data)?),
+            Channel::UserTrades { .. } | Channel::UserTradesByKind { .. } => {
+                Self::UserTrades(decode(data)?)
+            }
+        })
+    }
+}
+
+/// Subscription notification with its parsed channel and typed payload
+#[derive(Debug, Clone)]
+pub struct SubscriptionNotification {
+    /// Channel the notification was published on
+    pub channel: Channel,
+    /// Decoded payload
+    pub data: SubscriptionData,
+}
+
+/// `params` of a raw subscription notification
+#[derive(Deserialize)]
+struct RawParams {
+    channel: String,
+    data: Value,
+}
+
+/// Raw subscription notification
+#[derive(Deserialize)]
+struct RawNotification {
+    method: Option<String>,
+    params: Option<Value>,
+}
+
+/// Decode a subscription notification frame without tracking payload shapes
+pub fn decode_notification(frame: &str) -> Result<SubscriptionNotification, NotificationError> {
+    let (channel, data) = split_frame(frame)?;
+    let channel: Channel = channel.parse()?;
+    let data =
+        SubscriptionData::decode(&channel, &data).map_err(|err| NotificationError::Payload {
+            channel: channel.to_string(),
+            message: err.to_string(),
+        })?;
+    Ok(SubscriptionNotification { channel, data })
+}
+
+/// Payload decoding failures recorded for one channel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PayloadErrorStats {
+    /// Number of notifications that failed to decode
+    pub count: u64,
+    /// Most recent decoding error
+    pub last_error: String,
+}
+
+/// Channels whose notifications did not match the models
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapeReport {
+    /// Channel names that failed to parse, with occurrence counts
+    pub unknown_channels: BTreeMap<String, u64>,
+    /// Channels whose payload failed to decode
+    pub payload_errors: BTreeMap<String, PayloadErrorStats>,
+    /// Fields received on a channel that the model does not cover
+    pub unknown_fields: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ShapeReport {
+    /// Check if every notification matched its model
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.unknown_channels.is_empty()
+            && self.payload_errors.is_empty()
+            && self.unknown_fields.is_empty()
+    }
+
+    /// Get every channel with an unknown name, a failed payload or unknown fields
+    #[must_use]
+    pub fn affected_channels(&self) -> BTreeSet<&str> {
+        self.unknown_channels
+            .keys()
+            .chain(self.payload_errors.keys())
+            .chain(self.unknown_fields.keys())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Stateful subscription decoder that records unexpected payload shapes
+#[derive(Debug, Clone, Default)]
+pub struct NotificationDecoder {
+    report: ShapeReport,
+}
+
+impl NotificationDecoder {
+    /// Create a decoder with an empty report
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode a subscription notification frame
+    pub fn decode(&mut self, frame: &str) -> Result<SubscriptionNotification, NotificationError> {
+        let (channel_name, data) = split_frame(frame)?;
+        let channel: Channel = match channel_name.parse() {
+            Ok(channel) => channel,
+            Err(err) => {
+                *self
+                    .report
+                    .unknown_channels
+                    .entry(channel_name)
+                    .or_default() += 1;
+                return Err(err.into());
+            }
+        };
+        let decoded = match SubscriptionData::decode(&channel, &data) {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                let stats = self
+                    .report
+                    .payload_errors
+                    .entry(channel_name.clone())
+                    .or_default();
+                stats.count += 1;
+                stats.last_error = err.to_string();
+                return Err(NotificationError::Payload {
+                    channel: channel_name,
+                    message: err.to_string(),
+                });
+            }
+        };
+        let mut unknown = BTreeSet::new();
+        if let Ok(modelled) = serde_json::to_value(&decoded) {
+            collect_unknown_fields(&data, &modelled, &mut unknown);
+        }
+        if !unknown.is_empty() {
+            self.report
+                .unknown_fields
+                .entry(channel_name)
+                .or_default()
+                .extend(unknown);
+        }
+        Ok(SubscriptionNotification {
+            channel,
+            data: decoded,
+        })
+    }
+
+    /// Get the shape report accumulated so far
+    #[must_use]
+    pub fn report(&self) -> &ShapeReport {
+        &self.report
+    }
+
+    /// Take the shape report, leaving an empty one
+    pub fn take_report(&mut self) -> ShapeReport {
+        std::mem::take(&mut self.report)
+    }
+}
+
+/// Extract the channel name and payload of a subscription frame
+fn split_frame(frame: &str) -> Result<(String, Value), NotificationError> {
+    let raw: RawNotification =
+        serde_json::from_str(frame).map_err(|err| NotificationError::Json(err.to_string()))?;
+    if raw.method.as_deref() != Some(SUBSCRIPTION_METHOD) {
+        return Err(NotificationError::NotASubscription);
+    }
+    let params = raw.params.ok_or(NotificationError::NotASubscription)?;
+    let params =
+        RawParams::deserialize(params).map_err(|err| NotificationError::Json(err.to_string()))?;
+    Ok((params.channel, params.data))
+}
+
+fn decode<T: DeserializeOwned>(data: &Value) -> Result<T, serde_json::Error> {
+    T::deserialize(data)
+}
+
+/// Collect top-level fields present in the raw payload but not in the model
+fn collect_unknown_fields(raw: &Value, modelled: &Value, unknown: &mut BTreeSet<String>) {
+    match (raw, modelled) {
+        (Value::Object(raw), Value::Object(modelled)) => unknown.extend(
+            raw.iter()
+                .filter(|(key, value)| !value.is_null() && !modelled.contains_key(*key))
+                .map(|(key, _)| key.clone()),
+        ),
+        (Value::Array(raw), Value::Array(modelled)) => {
+            for (raw, modelled) in raw.iter().zip(modelled) {
+                collect_unknown_fields(raw, modelled, unknown);
+            }
+        }
+        (Value::Object(_), Value::Array(modelled)) => {
+            if let Some(modelled) = modelled.first() {
+                collect_unknown_fields(raw, modelled, unknown);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every notification example of the bundled API documentation
+    fn documented_examples() -> Vec<String> {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/doc/DeribitAPI.md");
+        let doc = std::fs::read_to_string(path).unwrap();
+        doc.lines()
+            .filter(|line| line.starts_with(r#"{"jsonrpc":"2.0","method":"subscription""#))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_every_documented_example() {
+        let examples = documented_examples();
+        assert!(examples.len() >= 40);
+        let mut decoder = NotificationDecoder::new();
+        for frame in &examples {
+            if let Err(err) = decoder.decode(frame) {
+                panic!("{err}\n{frame}");
+            }
+        }
+        let report = decoder.report();
+        assert!(report.unknown_channels.is_empty());
+        assert!(report.payload_errors.is_empty());
+        assert!(report.is_clean(), "{report:?}");
+    }
+
+    #[test]
+    fn test_decode_book_update() {
+        let frame = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"book.BTC-PERPETUAL.100ms","data":{"asks":[],"bids":[["delete",5041.94,0],["new",5042.34,10]],"change_id":297218,"instrument_name":"BTC-PERPETUAL","prev_change_id":297217,"timestamp":1554373911330,"type":"change"}}}"#;
+        let notification = decode_notification(frame).unwrap();
+        assert_eq!(
+            notification.channel,
+            Channel::book(
+                "BTC-PERPETUAL",
+                crate::model::channel::ChannelInterval::Ms100
+            )
+        );
+        let SubscriptionData::Book(book) = notification.data else {
+            panic!("expected a book update");
+        };
+        assert_eq!(book.type_, UpdateType::Change);
+        assert!(book.follows(297217));
+        assert_eq!(book.bids[0].action(), BookAction::Delete);
+        assert_eq!(book.bids[1].price(), 5042.34);
+        assert_eq!(book.bids[1].amount(), 10.0);
+    }
+
+    #[test]
+    fn test_user_orders_raw_and_aggregated() {
+        let order = r#"{"amount":200,"average_price":0,"creation_timestamp":1,"direction":"buy","filled_amount":50,"instrument_name":"BTC-PERPETUAL","label":"","last_update_timestamp":2,"order_id":"5","order_state":"open","order_type":"market","price":"market_price"}"#;
+        let raw = format!(
+            r#"{{"jsonrpc":"2.0","method":"subscription","params":{{"channel":"user.orders.BTC-PERPETUAL.raw","data":{order}}}}}"#
+        );
+        let aggregated = format!(
+            r#"{{"jsonrpc":"2.0","method":"subscription","params":{{"channel":"user.orders.BTC-PERPETUAL.100ms","data":[{order},{order}]}}}}"#
+        );
+        let SubscriptionData::UserOrder(order) = decode_notification(&raw).unwrap().data else {
+            panic!("expected an order");
+        };
+        assert_eq!(order.remaining_amount(), 150.0);
+        assert_eq!(order.limit_price(), None);
+        let SubscriptionData::UserOrders(orders) = decode_notification(&aggregated).unwrap().data
+        else {
+            panic!("expected orders");
+        };
+        assert_eq!(orders.len(), 2);
+    }
+
+    #[test]
+    fn test_report_unknown_channel() {
+        let mut decoder = NotificationDecoder::new();
+        let frame = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"new_channel.btc","data":{}}}"#;
+        assert!(matches!(
+            decoder.decode(frame),
+            Err(NotificationError::Channel(ChannelError::UnknownChannel(_)))
+        ));
+        decoder.decode(frame).unwrap_err();
+        assert_eq!(decoder.report().unknown_channels["new_channel.btc"], 2);
+        assert!(!decoder.report().is_clean());
+    }
+
+    #[test]
+    fn test_report_changed_payload() {
+        let mut decoder = NotificationDecoder::new();
+        let frame = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"deribit_price_index.btc_usd","data":{"index_name":"btc_usd","value":3937.89,"timestamp":1550588002899}}}"#;
+        let err = decoder.decode(frame).unwrap_err();
+        assert!(matches!(err, NotificationError::Payload { .. }));
+        let stats = &decoder.report().payload_errors["deribit_price_index.btc_usd"];
+        assert_eq!(stats.count, 1);
+        assert!(stats.last_error.contains("price"));
+        assert_eq!(
+            decoder.report().affected_channels(),
+            BTreeSet::from(["deribit_price_index.btc_usd"])
+        );
+    }
+
+    #[test]
+    fn test_report_unknown_fields() {
+        let mut decoder = NotificationDecoder::new();
+        let frame = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"trades.BTC-PERPETUAL.raw","data":[{"amount":10,"direction":"sell","index_price":8955.88,"instrument_name":"BTC-PERPETUAL","mark_price":8948.9,"price":8950,"tick_direction":2,"timestamp":1590484512188,"trade_id":"48079269","trade_seq":30289442,"venue":"x","iv":null}]}}"#;
+        let notification = decoder.decode(frame).unwrap();
+        assert!(
+            matches!(notification.data, SubscriptionData::Trades(ref trades) if trades.len() == 1)
+        );
+        let report = decoder.take_report();
+        assert_eq!(
+            report.unknown_fields["trades.BTC-PERPETUAL.raw"],
+            BTreeSet::from(["venue".to_string()])
+        );
+        assert!(decoder.report().is_clean());
+    }
+
+    #[test]
+    fn test_not_a_subscription() {
+        let response = r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#;
+        assert_eq!(
+            decode_notification(response).unwrap_err(),
+            NotificationError::NotASubscription
+        );
+        let heartbeat =
+            r#"{"jsonrpc":"2.0","method":"heartbeat","params":{"type":"test_request"}}"#;
+        assert_eq!(
+            decode_notification(heartbeat).unwrap_err(),
+            NotificationError::NotASubscription
+        );
+        assert!(matches!(
+            decode_notification("{").unwrap_err(),
+            NotificationError::Json(_)
+        ));
+    }
+}
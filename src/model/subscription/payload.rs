@@ -0,0 +1,496 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Payloads of subscription notifications
+//!
+//! Channels whose payload already has a model (e.g., `ticker`, `trades`,
+//! `user.orders`, `block_rfq.*`) reuse it; the types here cover the remaining
+//! ones.
+//! Fields the API omits in some notifications are optional so that a
+//! missing field does not fail the whole notification.
+
+use crate::model::block_rfq::{BlockRfqHedge, BlockRfqQuoteLeg, BlockRfqTrade};
+use crate::model::order::{OrderInfo, OrderSide};
+use crate::model::position::Position;
+use crate::model::ticker::{Greeks, TickerStats};
+use crate::model::trade::UserTrade;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+
+/// Payload of the `announcements` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Announcement {
+    /// Announcement identifier
+    pub id: u64,
+    /// Action taken by the platform (`new` or `delete`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Announcement title
+    pub title: String,
+    /// HTML-formatted announcement body
+    pub body: String,
+    /// Publication timestamp in milliseconds
+    pub publication_timestamp: u64,
+    /// Whether the announcement is marked as important
+    #[serde(default)]
+    pub important: bool,
+    /// Whether user confirmation is required
+    #[serde(default)]
+    pub confirmation: bool,
+    /// Number of previous unread announcements (authorized users only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unread: Option<u64>,
+}
+
+/// Public trade of the `block_rfq.trades` channel and `public/get_block_rfq_trades`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRfqPublicTrade {
+    /// Block RFQ identifier
+    pub id: u64,
+    /// Trade timestamp in milliseconds
+    pub timestamp: u64,
+    /// Traded amount
+    pub amount: f64,
+    /// Direction of the taker
+    pub direction: OrderSide,
+    /// Combo instrument, if the legs form a combo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combo_id: Option<String>,
+    /// Mark price of the structure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
+    /// Legs with their execution prices
+    pub legs: Vec<BlockRfqQuoteLeg>,
+    /// Hedge leg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<BlockRfqHedge>,
+    /// Individual executions
+    #[serde(default)]
+    pub trades: Vec<BlockRfqTrade>,
+}
+
+/// Order book change action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookAction {
+    /// New price level
+    New,
+    /// Amount changed at an existing price level
+    Change,
+    /// Price level removed
+    Delete,
+}
+
+/// `[action, price, amount]` entry of a `book` notification
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevelChange(pub BookAction, pub f64, pub f64);
+
+impl BookLevelChange {
+    /// Get the change action
+    #[must_use]
+    pub fn action(&self) -> BookAction {
+        self.0
+    }
+
+    /// Get the price level
+    #[must_use]
+    pub fn price(&self) -> f64 {
+        self.1
+    }
+
+    /// Get the new amount at the price level (0 for deletions)
+    #[must_use]
+    pub fn amount(&self) -> f64 {
+        self.2
+    }
+}
+
+/// Kind of an incremental notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateType {
+    /// Full state
+    Snapshot,
+    /// Changes since the previous notification
+    Change,
+}
+
+/// Payload of the `book.{instrument_name}.{interval}` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookUpdate {
+    /// Snapshot for the first notification, change afterwards
+    #[serde(rename = "type")]
+    pub type_: UpdateType,
+    /// Instrument name
+    pub instrument_name: String,
+    /// Timestamp of the last change in milliseconds
+    pub timestamp: u64,
+    /// Identifier of this notification
+    pub change_id: u64,
+    /// Identifier of the previous notification (absent on snapshots)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_change_id: Option<u64>,
+    /// Bid changes
+    pub bids: Vec<BookLevelChange>,
+    /// Ask changes
+    pub asks: Vec<BookLevelChange>,
+}
+
+impl BookUpdate {
+    /// Check if this update directly follows the given change id
+    ///
+    /// A gap means notifications were missed and the book must be
+    /// resubscribed.
+    #[must_use]
+    pub fn follows(&self, change_id: u64) -> bool {
+        self.prev_change_id == Some(change_id)
+    }
+}
+
+/// Payload of the `book.{instrument_name}.{group}.{depth}.{interval}` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupedBookUpdate {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Timestamp of the last change in milliseconds
+    pub timestamp: u64,
+    /// Identifier of this notification
+    pub change_id: u64,
+    /// `[price, amount]` bid levels
+    pub bids: Vec<(f64, f64)>,
+    /// `[price, amount]` ask levels
+    pub asks: Vec<(f64, f64)>,
+}
+
+/// Payload of the `chart.trades` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartTradesUpdate {
+    /// Bar start timestamp in milliseconds
+    pub tick: u64,
+    /// Open price
+    pub open: f64,
+    /// Highest price
+    pub high: f64,
+    /// Lowest price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+    /// Volume in base currency
+    pub volume: f64,
+    /// Volume in quote currency
+    pub cost: f64,
+}
+
+/// Entry of the `deribit_price_ranking` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceRankingEntry {
+    /// Exchange identifier
+    pub identifier: String,
+    /// Whether the exchange is used in the index
+    pub enabled: bool,
+    /// Price reported by the exchange
+    pub original_price: f64,
+    /// Price adjusted for the index
+    pub price: f64,
+    /// Weight of the exchange in the index
+    pub weight: f64,
+    /// Timestamp of the last price in milliseconds
+    pub timestamp: u64,
+}
+
+/// Payload of the `deribit_price_statistics` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceStatistics {
+    /// Index identifier
+    pub index_name: String,
+    /// Highest price in the last 24 hours
+    pub high24h: f64,
+    /// Lowest price in the last 24 hours
+    pub low24h: f64,
+    /// Price change in the last 24 hours
+    pub change24h: f64,
+    /// Whether the index is in a high volatility period
+    #[serde(default)]
+    pub high_volatility: bool,
+}
+
+/// Payload of the `deribit_volatility_index` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityIndexUpdate {
+    /// Index identifier
+    pub index_name: String,
+    /// Volatility index value
+    pub volatility: f64,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+/// Payload of the `estimated_expiration_price` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EstimatedExpirationPrice {
+    /// Estimated or final expiration price
+    pub price: f64,
+    /// Whether the price is still an estimate
+    pub is_estimated: bool,
+    /// Seconds until expiration
+    pub seconds: u64,
+    /// Remaining ticks of the averaging period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left_ticks: Option<f64>,
+    /// Total ticks of the averaging period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_ticks: Option<f64>,
+}
+
+/// Payload of the `incremental_ticker` channel
+///
+/// The first notification is a full snapshot; later ones only carry the
+/// fields that changed.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncrementalTickerUpdate {
+    /// Snapshot for the first notification, change afterwards
+    #[serde(rename = "type")]
+    pub type_: UpdateType,
+    /// Instrument name
+    pub instrument_name: String,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+    /// Instrument state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Best bid price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_bid_price: Option<f64>,
+    /// Amount at the best bid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_bid_amount: Option<f64>,
+    /// Best ask price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_ask_price: Option<f64>,
+    /// Amount at the best ask
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_ask_amount: Option<f64>,
+    /// Last traded price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_price: Option<f64>,
+    /// Mark price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
+    /// Index price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_price: Option<f64>,
+    /// Estimated delivery price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_delivery_price: Option<f64>,
+    /// Delivery price (closed instruments only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_price: Option<f64>,
+    /// Settlement price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_price: Option<f64>,
+    /// Minimum allowed order price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_price: Option<f64>,
+    /// Maximum allowed order price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<f64>,
+    /// Open interest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_interest: Option<f64>,
+    /// Current funding (perpetuals only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_funding: Option<f64>,
+    /// Funding over the last 8 hours (perpetuals only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_8h: Option<f64>,
+    /// Interest rate used in implied volatility calculations (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interest_rate: Option<f64>,
+    /// Implied volatility at the best bid (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bid_iv: Option<f64>,
+    /// Implied volatility at the best ask (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ask_iv: Option<f64>,
+    /// Mark implied volatility (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_iv: Option<f64>,
+    /// Underlying price (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_price: Option<f64>,
+    /// Underlying index name (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_index: Option<String>,
+    /// Greeks (options only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub greeks: Option<Greeks>,
+    /// 24 hour statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<TickerStats>,
+}
+
+/// Payload of the `instrument.state` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstrumentStateUpdate {
+    /// Instrument name
+    pub instrument_name: String,
+    /// New state (`created`, `started`, `settled`, `closed`, `deactivated`, `terminated`)
+    pub state: String,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+/// Entry of the `markprice.options` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkPriceOption {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Mark price
+    pub mark_price: f64,
+    /// Implied volatility of the mark price
+    pub iv: f64,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+/// Payload of the `perpetual` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerpetualUpdate {
+    /// Current index price
+    pub index_price: f64,
+    /// Current interest rate
+    pub interest: f64,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+/// Payload of the `platform_state` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformStateUpdate {
+    /// Whether the platform or index is locked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    /// Whether the platform is in maintenance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<bool>,
+    /// Index the lock applies to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_index: Option<String>,
+}
+
+/// Payload of the `platform_state.public_methods_state` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicMethodsState {
+    /// Whether public methods are allowed without authentication
+    pub allow_unauthenticated_public_requests: bool,
+}
+
+/// Payload of the `quote` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteUpdate {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+    /// Best bid price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_bid_price: Option<f64>,
+    /// Amount at the best bid
+    pub best_bid_amount: f64,
+    /// Best ask price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_ask_price: Option<f64>,
+    /// Amount at the best ask
+    pub best_ask_amount: f64,
+}
+
+/// Payload of the `rfq` channel
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RfqUpdate {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Whether an RFQ is active
+    pub state: bool,
+    /// Requested side
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<OrderSide>,
+    /// Requested amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// Timestamp of the last RFQ in milliseconds
+    #[serde(
+        rename = "last_rfq_tstamp",
+        alias = "last_rfq_timestamp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_rfq_timestamp: Option<u64>,
+    /// Whether the instrument was created recently
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_new_instrument: Option<bool>,
+}
+
+/// Payload of the `user.access_log` channel and entry of `private/get_access_log`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessLogEntry {
+    /// Entry identifier
+    pub id: u64,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+    /// Action (e.g., `success`, `failure`, `enabled_tfa`), as sent by `user.access_log`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub log: String,
+    /// Action, as returned by `private/get_access_log`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// IP address
+    pub ip: String,
+    /// City
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Country
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Free-form details, an object or a string depending on the action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl AccessLogEntry {
+    /// Action of the entry, whichever field carries it
+    #[must_use]
+    pub fn action(&self) -> &str {
+        self.result.as_deref().unwrap_or(&self.log)
+    }
+}
+
+/// Payload of the `user.changes` channels
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+pub struct UserChanges {
+    /// Instrument name
+    pub instrument_name: String,
+    /// Orders that changed
+    #[serde(default)]
+    pub orders: Vec<OrderInfo>,
+    /// Positions after the change
+    #[serde(default, alias = "position")]
+    pub positions: Vec<Position>,
+    /// Trades that caused the change
+    #[serde(default)]
+    pub trades: Vec<UserTrade>,
+}
+
+/// Payload of the `user.lock` channel and entry of `private/get_user_locks`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserLock {
+    /// Locked currency, or `ALL`
+    pub currency: String,
+    /// Whether the account is locked
+    #[serde(alias = "enabled")]
+    pub locked: bool,
+    /// Reason the account is locked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
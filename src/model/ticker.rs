@@ -2,7 +2,7 @@ use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
 /// Greeks sub-structure for options
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Greeks {
     /// Delta value
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -22,7 +22,7 @@ pub struct Greeks {
 }
 
 /// Ticker stats sub-structure
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TickerStats {
     /// Trading volume
     pub volume: f64,
@@ -123,6 +123,15 @@ pub struct TickerData {
     /// Estimated delivery price
     #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_delivery_price: Option<f64>,
+    /// Current funding (perpetuals only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_funding: Option<f64>,
+    /// Funding over the last 8 hours (perpetuals only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funding_8h: Option<f64>,
+    /// Value used to calculate realized funding (perpetuals only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interest_value: Option<f64>,
 }
 
 #[cfg(test)]
@@ -3,9 +3,14 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
-use crate::model::{instrument::InstrumentKind, order::OrderSide};
+use crate::model::{
+    instrument::InstrumentKind,
+    label::OrderLabel,
+    order::{OrderSide, OrderStatus, OrderType},
+    request::AdvancedOrderType,
+};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Liquidity type enumeration
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,13 +27,18 @@ pub enum Liquidity {
 }
 
 /// Trade execution information
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+///
+/// Also sent by the public `trades` channels, whose trades carry no order,
+/// fee or liquidity: those default to empty, zero and taker, the side the
+/// trade direction is reported from.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trade {
     /// Unique trade identifier
     pub trade_id: String,
     /// Instrument name
     pub instrument_name: String,
     /// Order ID that generated this trade
+    #[serde(default)]
     pub order_id: String,
     /// Trade direction (buy/sell)
     pub direction: OrderSide,
@@ -39,10 +49,13 @@ pub struct Trade {
     /// Trade timestamp
     pub timestamp: i64,
     /// Fee amount
+    #[serde(default)]
     pub fee: f64,
     /// Fee currency
+    #[serde(default)]
     pub fee_currency: String,
     /// Liquidity type (maker/taker)
+    #[serde(default = "taker")]
     pub liquidity: Liquidity,
     /// Mark price at time of trade
     pub mark_price: f64,
@@ -68,6 +81,25 @@ pub struct Trade {
     pub tick_direction: Option<i32>,
     /// Whether this trade was self-traded
     pub self_trade: Option<bool>,
+    /// Trade size in contracts
+    pub contracts: Option<f64>,
+    /// Liquidated side, if the trade was a liquidation
+    pub liquidation: Option<Liquidity>,
+    /// Block trade identifier
+    pub block_trade_id: Option<String>,
+    /// Number of legs of the block trade
+    pub block_trade_leg_count: Option<u32>,
+    /// Block RFQ identifier
+    pub block_rfq_id: Option<u64>,
+    /// Combo instrument, if the trade is a combo leg
+    pub combo_id: Option<String>,
+    /// Combo trade identifier
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub combo_trade_id: Option<String>,
+}
+
+fn taker() -> Liquidity {
+    Liquidity::Taker
 }
 
 impl Trade {
@@ -104,6 +136,11 @@ impl Trade {
             0.0
         }
     }
+
+    /// Decode the structured order label, if any
+    pub fn order_label(&self) -> Option<OrderLabel> {
+        self.label.as_deref().and_then(OrderLabel::try_decode)
+    }
 }
 
 /// Trade statistics
@@ -173,6 +210,7 @@ pub struct TradeExecution {
     /// Implied volatility (for options)
     pub iv: Option<f64>,
     /// User-defined label for the trade
+    #[serde(default)]
     pub label: String,
     /// Liquidity type (maker/taker)
     pub liquidity: String,
@@ -186,9 +224,14 @@ pub struct TradeExecution {
     pub order_type: String,
     /// Original order type before modifications
     pub original_order_type: Option<String>,
+    /// Whether the order was post-only
+    pub post_only: Option<bool>,
     /// Execution price
     pub price: f64,
+    /// Whether the order was reduce-only
+    pub reduce_only: Option<bool>,
     /// Whether this was a self trade
+    #[serde(default)]
     pub self_trade: bool,
     /// Current state of the trade
     pub state: String,
@@ -204,13 +247,23 @@ pub struct TradeExecution {
     pub underlying_price: Option<f64>,
 }
 
+impl TradeExecution {
+    /// Decode the structured order label, if any
+    pub fn order_label(&self) -> Option<OrderLabel> {
+        OrderLabel::try_decode(&self.label)
+    }
+}
+
 /// User trade information
-#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+///
+/// Returned by the user trade methods and sent by the `user.trades`,
+/// `user.combo_trades` and `user.changes` channels.
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserTrade {
     /// Trade amount
     pub amount: f64,
     /// Trade direction (buy/sell)
-    pub direction: String,
+    pub direction: OrderSide,
     /// Trading fee paid
     pub fee: f64,
     /// Currency of the trading fee
@@ -222,9 +275,10 @@ pub struct UserTrade {
     /// Implied volatility (for options)
     pub iv: Option<f64>,
     /// User-defined label for the trade
+    #[serde(default)]
     pub label: String,
     /// Liquidity type (maker/taker)
-    pub liquidity: String,
+    pub liquidity: Liquidity,
     /// Mark price at execution time
     pub mark_price: f64,
     /// Matching engine identifier
@@ -232,15 +286,16 @@ pub struct UserTrade {
     /// Order ID that generated this trade
     pub order_id: String,
     /// Type of the order that generated this trade
-    pub order_type: String,
+    pub order_type: OrderType,
     /// Original order type before modifications
-    pub original_order_type: Option<String>,
+    pub original_order_type: Option<OrderType>,
     /// Execution price
     pub price: f64,
     /// Whether this was a self trade
+    #[serde(default)]
     pub self_trade: bool,
-    /// Current state of the trade
-    pub state: String,
+    /// State of the order after the trade
+    pub state: OrderStatus,
     /// Price tick direction (1=up, -1=down, 0=no change)
     pub tick_direction: i32,
     /// Execution timestamp
@@ -251,6 +306,49 @@ pub struct UserTrade {
     pub trade_seq: u64,
     /// Underlying asset price (for derivatives)
     pub underlying_price: Option<f64>,
+    /// Trade size in contracts
+    pub contracts: Option<f64>,
+    /// Profit or loss of the trade
+    pub profit_loss: Option<f64>,
+    /// Whether the order was post-only
+    pub post_only: Option<bool>,
+    /// Whether the order was reduce-only
+    pub reduce_only: Option<bool>,
+    /// Whether the trade only reduced risk
+    pub risk_reducing: Option<bool>,
+    /// Whether the order was placed through the API
+    pub api: Option<bool>,
+    /// Whether the order was protected by MMP
+    pub mmp: Option<bool>,
+    /// Mass quote identifier
+    pub quote_id: Option<String>,
+    /// Mass quote set identifier
+    pub quote_set_id: Option<String>,
+    /// Liquidated side, if the trade was a liquidation
+    pub liquidation: Option<Liquidity>,
+    /// Advanced option order type
+    pub advanced: Option<AdvancedOrderType>,
+    /// Block trade identifier
+    pub block_trade_id: Option<String>,
+    /// Block RFQ identifier
+    pub block_rfq_id: Option<u64>,
+    /// Block RFQ quote identifier
+    pub block_rfq_quote_id: Option<u64>,
+    /// Combo instrument, if the trade is a combo leg
+    pub combo_id: Option<String>,
+    /// Combo trade identifier
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub combo_trade_id: Option<String>,
+    /// Leg trades of a combo trade
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legs: Vec<UserTrade>,
+}
+
+impl UserTrade {
+    /// Decode the structured order label, if any
+    pub fn order_label(&self) -> Option<OrderLabel> {
+        OrderLabel::try_decode(&self.label)
+    }
 }
 
 /// Last trade
@@ -268,6 +366,8 @@ pub struct LastTrade {
     pub iv: Option<f64>,
     /// Liquidity information
     pub liquid: Option<String>,
+    /// Mark price at execution time
+    pub mark_price: Option<f64>,
     /// Execution price
     pub price: f64,
     /// Price tick direction (1=up, -1=down, 0=no change)
@@ -280,6 +380,21 @@ pub struct LastTrade {
     pub trade_seq: u64,
 }
 
+/// Deserialize an identifier documented as a number but sent as a string
+fn string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(value)) => Ok(Some(value)),
+        Some(serde_json::Value::Number(value)) => Ok(Some(value.to_string())),
+        Some(other) => Err(serde::de::Error::custom(format!(
+            "expected a string or number, got {other}"
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,6 +460,13 @@ mod tests {
             profit_loss: Some(100.0),
             tick_direction: Some(1),
             self_trade: Some(false),
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         assert_eq!(trade.trade_id, "12345");
@@ -354,6 +476,46 @@ mod tests {
         assert_eq!(trade.price, 50000.0);
         assert_eq!(trade.fee, 25.0);
         assert_eq!(trade.liquidity, Liquidity::Maker);
+        assert_eq!(trade.order_label(), None);
+    }
+
+    #[test]
+    fn test_trade_order_label() {
+        let trade = Trade {
+            trade_id: "12345".to_string(),
+            instrument_name: "BTC-PERPETUAL".to_string(),
+            order_id: "order_123".to_string(),
+            direction: OrderSide::Sell,
+            amount: 10.0,
+            price: 50000.0,
+            timestamp: 1640995200000,
+            fee: 0.0,
+            fee_currency: "BTC".to_string(),
+            liquidity: Liquidity::Taker,
+            mark_price: 50010.0,
+            index_price: 50005.0,
+            instrument_kind: Some(InstrumentKind::Future),
+            trade_seq: None,
+            user_role: None,
+            block_trade: None,
+            underlying_price: None,
+            iv: None,
+            label: Some("spread-1:2:1a".to_string()),
+            profit_loss: None,
+            tick_direction: None,
+            self_trade: None,
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
+        };
+        let label = trade.order_label().unwrap();
+        assert_eq!(label.strategy_id, "spread-1");
+        assert_eq!(label.leg, 2);
+        assert_eq!(label.sequence, 46);
     }
 
     #[test]
@@ -381,6 +543,13 @@ mod tests {
             profit_loss: None,
             tick_direction: None,
             self_trade: None,
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         assert_eq!(trade.notional_value(), 100000.0);
@@ -411,6 +580,13 @@ mod tests {
             profit_loss: None,
             tick_direction: None,
             self_trade: None,
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         let taker_trade = Trade {
@@ -458,6 +634,13 @@ mod tests {
             profit_loss: None,
             tick_direction: None,
             self_trade: None,
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         let sell_trade = Trade {
@@ -497,6 +680,13 @@ mod tests {
             profit_loss: None,
             tick_direction: None,
             self_trade: None,
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         assert_eq!(trade.fee_percentage(), 0.05); // 25 / 50000 * 100
@@ -564,7 +754,9 @@ mod tests {
             order_id: "order_123".to_string(),
             order_type: "limit".to_string(),
             original_order_type: Some("limit".to_string()),
+            post_only: None,
             price: 50000.0,
+            reduce_only: None,
             self_trade: false,
             state: "filled".to_string(),
             tick_direction: 1,
@@ -587,31 +779,48 @@ mod tests {
     fn test_user_trade_creation() {
         let user_trade = UserTrade {
             amount: 2.0,
-            direction: "sell".to_string(),
+            direction: OrderSide::Sell,
             fee: 50.0,
             fee_currency: "USD".to_string(),
             index_price: 49995.0,
             instrument_name: "ETH-PERPETUAL".to_string(),
             iv: None,
             label: "user_label".to_string(),
-            liquidity: "T".to_string(),
+            liquidity: Liquidity::Taker,
             mark_price: 49990.0,
             matching_id: None,
             order_id: "user_order_456".to_string(),
-            order_type: "market".to_string(),
+            order_type: OrderType::Market,
             original_order_type: None,
             price: 49985.0,
             self_trade: true,
-            state: "filled".to_string(),
+            state: OrderStatus::Filled,
             tick_direction: -1,
             timestamp: 1640995300000,
             trade_id: "user_trade_456".to_string(),
             trade_seq: 12346,
             underlying_price: None,
+            contracts: None,
+            profit_loss: None,
+            post_only: None,
+            reduce_only: None,
+            risk_reducing: None,
+            api: None,
+            mmp: None,
+            quote_id: None,
+            quote_set_id: None,
+            liquidation: None,
+            advanced: None,
+            block_trade_id: None,
+            block_rfq_id: None,
+            block_rfq_quote_id: None,
+            combo_id: None,
+            combo_trade_id: None,
+            legs: Vec::new(),
         };
 
         assert_eq!(user_trade.amount, 2.0);
-        assert_eq!(user_trade.direction, "sell");
+        assert_eq!(user_trade.direction, OrderSide::Sell);
         assert_eq!(user_trade.fee, 50.0);
         assert_eq!(user_trade.instrument_name, "ETH-PERPETUAL");
         assert_eq!(user_trade.price, 49985.0);
@@ -629,6 +838,7 @@ mod tests {
             instrument_name: "BTC-25DEC24-50000-C".to_string(),
             iv: Some(0.75),
             liquid: Some("liquid".to_string()),
+            mark_price: Some(2501.0),
             price: 2500.0,
             tick_direction: 0,
             timestamp: 1640995400000,
@@ -672,6 +882,13 @@ mod tests {
             profit_loss: Some(100.0),
             tick_direction: Some(1),
             self_trade: Some(false),
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         let json = serde_json::to_string(&trade).unwrap();
@@ -727,6 +944,13 @@ mod tests {
             profit_loss: None,
             tick_direction: None,
             self_trade: None,
+            contracts: None,
+            liquidation: None,
+            block_trade_id: None,
+            block_trade_leg_count: None,
+            block_rfq_id: None,
+            combo_id: None,
+            combo_trade_id: None,
         };
 
         let cloned_trade = trade.clone();
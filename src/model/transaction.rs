@@ -3,11 +3,13 @@
    Email: jb@taunais.com
    Date: 21/7/25
 ******************************************************************************/
+use crate::model::wallet::ClearanceState;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 
 /// Transaction type enumeration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
 pub enum TransactionType {
     /// Deposit transaction
     Deposit,
@@ -30,6 +32,16 @@ pub enum TransactionType {
     Liquidation,
     /// Insurance transaction
     Insurance,
+    /// Settlement of a position
+    Settlement,
+    /// Delivery at expiration
+    Delivery,
+    /// Balance correction
+    Correction,
+    /// Currency swap
+    Swap,
+    /// Block trade
+    BlockTrade,
 }
 
 /// Generic transaction log entry
@@ -37,29 +49,112 @@ pub enum TransactionType {
 pub struct TransactionLogEntry {
     /// Unique transaction identifier
     pub id: u64,
+    /// Id of the user
+    pub user_id: Option<u64>,
+    /// User name
+    pub username: Option<String>,
+    /// Sequential number of the user transaction
+    pub user_seq: Option<u64>,
     /// Currency of the transaction
     pub currency: String,
-    /// Transaction amount
+    /// Transaction amount (zero when not reported)
+    #[serde(default)]
     pub amount: f64,
     /// Account balance after transaction
     pub balance: f64,
+    /// Account equity after transaction
+    pub equity: Option<f64>,
+    /// Change of the balance
+    pub change: Option<f64>,
+    /// Cash flow of the transaction
+    pub cashflow: Option<f64>,
+    /// Commission paid
+    pub commission: Option<f64>,
     /// Transaction timestamp
     pub timestamp: u64,
     /// Type of transaction
+    #[serde(rename = "type")]
     pub transaction_type: TransactionType,
-    /// Additional transaction information
-    pub info: Option<String>,
+    /// Side of the transaction (e.g., "open buy", "long", "-")
+    pub side: Option<String>,
+    /// Instrument name
+    pub instrument_name: Option<String>,
+    /// Trade identifier
+    pub trade_id: Option<String>,
+    /// Order identifier
+    pub order_id: Option<String>,
+    /// Block RFQ identifier
+    pub block_rfq_id: Option<u64>,
+    /// Price of the trade or settlement
+    pub price: Option<f64>,
+    /// Currency of the price
+    pub price_currency: Option<String>,
+    /// Mark price at the time of the transaction
+    pub mark_price: Option<f64>,
+    /// Index price at the time of the transaction
+    pub index_price: Option<f64>,
+    /// Settlement price
+    pub settlement_price: Option<f64>,
+    /// Position size after the transaction
+    pub position: Option<f64>,
+    /// Trade size in contracts
+    pub contracts: Option<f64>,
+    /// Interest profit and loss
+    pub interest_pl: Option<f64>,
+    /// Total interest profit and loss
+    pub total_interest_pl: Option<f64>,
+    /// Session unrealized profit and loss
+    pub session_upl: Option<f64>,
+    /// Session realized profit and loss
+    pub session_rpl: Option<f64>,
+    /// Whether the profit is settled as cash flow
+    pub profit_as_cashflow: Option<bool>,
+    /// Role of the user in the trade (maker/taker)
+    pub user_role: Option<String>,
+    /// Fee role of the user (maker/taker)
+    pub fee_role: Option<String>,
+    /// IP address the transaction originated from
+    pub ip: Option<String>,
+    /// Additional transaction information, as text or an object
+    pub info: Option<serde_json::Value>,
 }
 
 impl Default for TransactionLogEntry {
     fn default() -> Self {
         Self {
             id: 0,
+            user_id: None,
+            username: None,
+            user_seq: None,
             currency: String::new(),
             amount: 0.0,
             balance: 0.0,
+            equity: None,
+            change: None,
+            cashflow: None,
+            commission: None,
             timestamp: 0,
             transaction_type: TransactionType::default(),
+            side: None,
+            instrument_name: None,
+            trade_id: None,
+            order_id: None,
+            block_rfq_id: None,
+            price: None,
+            price_currency: None,
+            mark_price: None,
+            index_price: None,
+            settlement_price: None,
+            position: None,
+            contracts: None,
+            interest_pl: None,
+            total_interest_pl: None,
+            session_upl: None,
+            session_rpl: None,
+            profit_as_cashflow: None,
+            user_role: None,
+            fee_role: None,
+            ip: None,
             info: None,
         }
     }
@@ -68,8 +163,8 @@ impl Default for TransactionLogEntry {
 /// Paginated transaction log response
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize, Default)]
 pub struct TransactionLog {
-    /// Continuation token for pagination
-    pub continuation: Option<String>,
+    /// Id to pass as `continuation` to fetch the next page
+    pub continuation: Option<u64>,
     /// List of transaction log entries
     pub logs: Vec<TransactionLogEntry>,
 }
@@ -92,6 +187,21 @@ pub struct Deposit {
     /// Timestamp when deposit was last updated
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_timestamp: Option<u64>,
+    /// Id of the (sub)account that received the deposit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u64>,
+    /// Address the funds were sent from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_address: Option<String>,
+    /// Note attached to the deposit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Clearance state of the deposit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clearance_state: Option<ClearanceState>,
+    /// Transaction ID of the refund, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_transaction_id: Option<String>,
 }
 
 /// Deposits response wrapper
@@ -116,14 +226,16 @@ pub struct Withdrawal {
     pub fee: f64,
     /// Unique withdrawal identifier
     pub id: u64,
-    /// Withdrawal priority level
-    pub priority: String,
+    /// Withdrawal priority, as the fee multiplier
+    pub priority: f64,
     /// Current state of the withdrawal
     pub state: String,
     /// Timestamp when withdrawal was created
     pub created_timestamp: u64,
     /// Timestamp when withdrawal was last updated
     pub updated_timestamp: Option<u64>,
+    /// Timestamp when withdrawal was confirmed
+    pub confirmed_timestamp: Option<u64>,
     /// Transaction ID on the blockchain
     pub transaction_id: Option<String>,
 }
@@ -147,4 +259,27 @@ mod tests {
         assert_eq!(tx.id, 0);
         assert_eq!(tx.amount, 0.0);
     }
+
+    #[test]
+    fn test_deposit_with_clearance_deserialization() {
+        let json = r#"{
+            "currency": "BTC",
+            "user_id": 123,
+            "address": "2NBqqD5GRJ8wHy1PYyCXTe9ke5226FhavBz",
+            "amount": 0.4,
+            "state": "completed",
+            "transaction_id": "230669110fdaf0a0dbcdc079b6b8b43d5af29cc73683835b9bc6b3406c065fda",
+            "source_address": "A3BqqD5GRJ8wHy1PYyCXTe9ke5226Fha123",
+            "received_timestamp": 1550574558607,
+            "updated_timestamp": 1550574558807,
+            "note": "Note",
+            "clearance_state": "in_progress"
+        }"#;
+
+        let deposit: Deposit = serde_json::from_str(json).unwrap();
+        assert_eq!(deposit.user_id, Some(123));
+        assert_eq!(deposit.clearance_state, Some(ClearanceState::InProgress));
+        assert_eq!(deposit.note.as_deref(), Some("Note"));
+        assert_eq!(deposit.refund_transaction_id, None);
+    }
 }
@@ -163,9 +163,25 @@ pub struct AddressBookEntry {
     #[serde(rename = "type")]
     pub address_type: AddressType,
     /// Whether this address requires email confirmation for withdrawals
+    #[serde(default)]
     pub requires_confirmation: bool,
+    /// Whether a change of the confirmation requirement is pending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_confirmation_change: Option<bool>,
     /// Creation timestamp (milliseconds since Unix epoch)
     pub creation_timestamp: i64,
+    /// Address status (e.g., "waiting", "confirmed")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Timestamp until which the address is waiting (milliseconds since Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_timestamp: Option<i64>,
+    /// Whether the user agreed to the address terms
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agreed: Option<bool>,
+    /// Whether beneficiary information is still required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info_required: Option<bool>,
     /// Whether this is a personal address
     #[serde(skip_serializing_if = "Option::is_none")]
     pub personal: Option<bool>,
@@ -175,6 +191,9 @@ pub struct AddressBookEntry {
     /// Beneficiary last name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_last_name: Option<String>,
+    /// Beneficiary company name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_company_name: Option<String>,
     /// Beneficiary address for compliance
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_address: Option<String>,
@@ -184,6 +203,9 @@ pub struct AddressBookEntry {
     /// Beneficiary VASP name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_vasp_name: Option<String>,
+    /// Beneficiary VASP website
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_website: Option<String>,
 }
 
 impl AddressBookEntry {
@@ -201,13 +223,20 @@ impl AddressBookEntry {
             label,
             address_type,
             requires_confirmation: false,
+            requires_confirmation_change: None,
             creation_timestamp,
+            status: None,
+            waiting_timestamp: None,
+            agreed: None,
+            info_required: None,
             personal: None,
             beneficiary_first_name: None,
             beneficiary_last_name: None,
+            beneficiary_company_name: None,
             beneficiary_address: None,
             beneficiary_vasp_did: None,
             beneficiary_vasp_name: None,
+            beneficiary_vasp_website: None,
         }
     }
 
@@ -259,6 +288,78 @@ impl AddressBookEntry {
     }
 }
 
+/// Travel-rule beneficiary of a withdrawal address
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressBeneficiary {
+    /// Currency of the address
+    pub currency: String,
+    /// Address in currency format
+    pub address: String,
+    /// Tag for XRP addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Id of the user owning the address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u64>,
+    /// Whether the user agreed to share the information with third parties
+    #[serde(default)]
+    pub agreed: bool,
+    /// Whether the address belongs to the user
+    #[serde(default)]
+    pub personal: bool,
+    /// Whether the address belongs to an unhosted wallet
+    #[serde(default)]
+    pub unhosted: bool,
+    /// Beneficiary VASP name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_name: Option<String>,
+    /// Beneficiary VASP DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_did: Option<String>,
+    /// Beneficiary VASP website
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp_website: Option<String>,
+    /// Beneficiary first name (persons only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_first_name: Option<String>,
+    /// Beneficiary last name (persons only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_last_name: Option<String>,
+    /// Beneficiary company name (companies only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_company_name: Option<String>,
+    /// Geographical address of the beneficiary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_address: Option<String>,
+    /// Creation timestamp (milliseconds since Unix epoch)
+    #[serde(
+        rename = "created",
+        alias = "creation_timestamp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub creation_timestamp: Option<i64>,
+    /// Last update timestamp (milliseconds since Unix epoch)
+    #[serde(
+        rename = "updated",
+        alias = "update_timestamp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub update_timestamp: Option<i64>,
+}
+
+/// Page of address beneficiaries returned by `private/list_address_beneficiaries`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressBeneficiariesResponse {
+    /// Beneficiaries in this page
+    pub data: Vec<AddressBeneficiary>,
+    /// Continuation token for the next page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+    /// Total number of beneficiaries
+    #[serde(default)]
+    pub count: u32,
+}
+
 /// Subaccount transfer information
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SubaccountTransfer {
@@ -325,6 +426,39 @@ impl SubaccountTransfer {
     }
 }
 
+/// Transfer between Deribit accounts, as listed by `private/get_transfers`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountTransfer {
+    /// Transfer ID
+    pub id: i64,
+    /// Currency being transferred
+    pub currency: String,
+    /// Transfer amount
+    pub amount: f64,
+    /// Transfer direction: `payment` or `income`
+    pub direction: String,
+    /// Counterparty account name or address
+    pub other_side: String,
+    /// Current transfer state
+    pub state: TransferState,
+    /// Transfer type: `user` or `subaccount`
+    #[serde(rename = "type")]
+    pub transfer_type: String,
+    /// Creation timestamp (milliseconds since Unix epoch)
+    pub created_timestamp: i64,
+    /// Last update timestamp (milliseconds since Unix epoch)
+    pub updated_timestamp: i64,
+}
+
+/// Page of account transfers returned by `private/get_transfers`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransfersResponse {
+    /// Total number of transfers available
+    pub count: u32,
+    /// Transfers in this page
+    pub data: Vec<AccountTransfer>,
+}
+
 /// Collection of transfers
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Transfers {
@@ -521,4 +655,66 @@ mod tests {
         let deserialized: Transfer = serde_json::from_str(&json).unwrap();
         assert_eq!(transfer, deserialized);
     }
+
+    #[test]
+    fn test_transfers_response_deserialization() {
+        let json = r#"{
+            "count": 1,
+            "data": [{
+                "amount": 0.3,
+                "created_timestamp": 1550579255800,
+                "currency": "BTC",
+                "direction": "payment",
+                "id": 1,
+                "other_side": "new_user_1_1",
+                "state": "confirmed",
+                "type": "subaccount",
+                "updated_timestamp": 1550579255800
+            }]
+        }"#;
+        let response: TransfersResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.count, 1);
+        assert_eq!(response.data[0].state, TransferState::Confirmed);
+        assert_eq!(response.data[0].transfer_type, "subaccount");
+    }
+
+    #[test]
+    fn test_address_beneficiaries_deserialization() {
+        let json = r#"{
+            "data": [{
+                "currency": "BTC",
+                "address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf0uyj",
+                "user_id": 1026,
+                "agreed": true,
+                "personal": false,
+                "unhosted": false,
+                "beneficiary_vasp_name": "Money's Gone",
+                "beneficiary_vasp_did": "did:example:123456789abcdefghi",
+                "beneficiary_vasp_website": "https://example.com",
+                "beneficiary_first_name": "John",
+                "beneficiary_last_name": "Doe",
+                "beneficiary_company_name": "Example Corp",
+                "beneficiary_address": "NL, Amsterdam, Street, 1",
+                "created": 1536569522277,
+                "updated": 1536569522277
+            }],
+            "continuation": "xY7T6cutS3t2B9YtaDkE6TS379oKnkzTvmEDUnEUP2Msa9xKWNNaT",
+            "count": 1
+        }"#;
+
+        let response: AddressBeneficiariesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.count, 1);
+        let beneficiary = &response.data[0];
+        assert!(beneficiary.agreed);
+        assert_eq!(beneficiary.user_id, Some(1026));
+        assert_eq!(beneficiary.creation_timestamp, Some(1536569522277));
+        assert_eq!(
+            beneficiary.beneficiary_company_name.as_deref(),
+            Some("Example Corp")
+        );
+
+        let value = serde_json::to_value(beneficiary).unwrap();
+        assert_eq!(value["created"], 1536569522277_i64);
+        assert!(value.get("tag").is_none());
+    }
 }
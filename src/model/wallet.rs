@@ -300,8 +300,20 @@ pub enum ClearanceState {
     InProgress,
     /// Clearance has been completed
     Completed,
+    /// Clearance succeeded
+    Success,
+    /// Clearance waits for a decision by Deribit
+    PendingAdminDecision,
+    /// Clearance waits for information from the user
+    PendingUserInput,
     /// Clearance failed
     Failed,
+    /// Clearance was cancelled
+    Cancelled,
+    /// A refund of the deposit was initiated
+    RefundInitiated,
+    /// The deposit was refunded
+    Refunded,
 }
 
 impl ClearanceState {
@@ -312,14 +324,20 @@ impl ClearanceState {
             Self::NotRequired => "not_required",
             Self::InProgress => "in_progress",
             Self::Completed => "completed",
+            Self::Success => "success",
+            Self::PendingAdminDecision => "pending_admin_decision",
+            Self::PendingUserInput => "pending_user_input",
             Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+            Self::RefundInitiated => "refund_initiated",
+            Self::Refunded => "refunded",
         }
     }
 
     /// Check if clearance is complete
     #[must_use]
     pub fn is_cleared(&self) -> bool {
-        matches!(self, Self::NotRequired | Self::Completed)
+        matches!(self, Self::NotRequired | Self::Completed | Self::Success)
     }
 }
 
@@ -329,6 +347,40 @@ impl std::fmt::Display for ClearanceState {
     }
 }
 
+/// Custody account returned by `private/list_custody_accounts`
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustodyAccount {
+    /// Custody name
+    pub name: String,
+    /// Currency of the account
+    pub currency: String,
+    /// API key client id used to reserve/release funds on the custody platform
+    pub client_id: String,
+    /// User ID in the external system
+    pub external_id: String,
+    /// Funds held in custody
+    pub balance: f64,
+    /// Funds pending withdrawal
+    pub pending_withdrawal_balance: f64,
+    /// Whether new custody funds are moved to the trading balance automatically
+    pub auto_deposit: bool,
+    /// Whether withdrawals require a security key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub withdrawals_require_security_key: Option<bool>,
+    /// Address that can be used for deposits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_address: Option<String>,
+    /// Address used for withdrawals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub withdrawal_address: Option<String>,
+    /// Withdrawal address that takes effect after the pending change
+    #[serde(
+        alias = "pending_withdrawal_addres",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pending_withdrawal_address: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -539,6 +591,8 @@ mod tests {
         assert!(!ClearanceState::InProgress.is_cleared());
         assert!(ClearanceState::Completed.is_cleared());
         assert!(!ClearanceState::Failed.is_cleared());
+        assert!(ClearanceState::Success.is_cleared());
+        assert!(!ClearanceState::PendingUserInput.is_cleared());
     }
 
     #[test]
@@ -550,4 +604,22 @@ mod tests {
         let deserialized: ClearanceState = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, ClearanceState::InProgress);
     }
+
+    #[test]
+    fn test_custody_account_deserialization() {
+        let json = r#"{
+            "name": "copper",
+            "currency": "BTC",
+            "client_id": "4KVcFrrzmXBR",
+            "external_id": "24f97d44-1d72-4641-8527-811268a0bdd3",
+            "balance": 0.5,
+            "withdrawals_require_security_key": false,
+            "pending_withdrawal_balance": 0.1,
+            "auto_deposit": false
+        }"#;
+        let account: CustodyAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.name, "copper");
+        assert_eq!(account.pending_withdrawal_balance, 0.1);
+        assert_eq!(account.deposit_address, None);
+    }
 }
@@ -9,12 +9,22 @@
 // Re-export commonly used types
 pub use crate::error::{
     codes::DeribitErrorCode,
-    types::{DeribitError, DeribitResult},
+    types::{ApiError, DeribitError, DeribitResult},
 };
 
 // Model exports
 pub use crate::model::{
-    account::{AccountSummary, Portfolio, Subaccount},
+    account::{
+        AccessLogResponse, AccountFee, AccountFeeValue, AccountLimits, AccountSummaries,
+        AccountSummary, AffiliateProgramInfo, FeeRates, MarginModelChange, MarginState,
+        MatchingEngineLimits, Portfolio, RateLimit, Subaccount, SubaccountBalance,
+        SubaccountDetails, SubaccountLoginState, ToggleSubaccountLoginRequest,
+    },
+    amount::{AmountError, AmountUnit, OrderSize},
+    api_key::{
+        ApiKey, ApiKeyError, ApiKeyFeature, ChangeApiKeyScopeRequest, CreateApiKeyRequest,
+        EditApiKeyRequest,
+    },
     book_summary::{BookSummaries, BookSummary},
     config::{DeribitConfig, DeribitUrls, HttpConfig, WebSocketConfig},
     currency::Currency,
@@ -25,13 +35,28 @@ pub use crate::model::{
     funding::{FundingChartData, FundingDataPoint, FundingRateData, TradingViewChartData},
     instrument::{
         IndexData, IndexPriceData, Instrument, InstrumentKind, InstrumentType, OptionType,
+        TickSizeStep,
+    },
+    label::{LabelError, OrderLabel},
+    market_data::{ActiveRfq, Candle, Greeks, MarketStats, OrderBook, OrderBookEntry, Ticker},
+    mmp::{MmpConfig, MmpConfigs, MmpStatus, MmpTarget, MmpTrigger, SetMmpConfigRequest},
+    move_positions::{
+        MovePositionTrade, MovePositionTradeResult, MovePositionsError, MovePositionsPreview,
+        MovePositionsRequest, MovePositionsResponse, ProjectedPosition,
     },
-    market_data::{Candle, Greeks, MarketStats, OrderBook, OrderBookEntry, Ticker},
     options::{OptionInstrument, OptionInstrumentPair},
-    order::{OrderInfo, OrderSide, OrderStatus, OrderType, TimeInForce},
+    order::{
+        OrderInfo, OrderPrice, OrderSide, OrderStatus, OrderType, TimeInForce, TriggerOrderHistory,
+        TriggerOrderHistoryEntry,
+    },
     order_management::{
-        BuyOrderRequest, EditOrderRequest, MassQuoteItem, MassQuoteRequest, SellOrderRequest,
-        TransferResult,
+        BuyOrderRequest, EditOrderRequest, MassQuoteBuilder, MassQuoteError, MassQuoteItem,
+        MassQuoteRequest, QuoteError, QuoteLevel, QuoteSide, SellOrderRequest, TransferResult,
+    },
+    pme::{
+        PmeCurrencyPairParams, PmeCurrencyParams, PmeGeneralParams, PmeInitialMarginDetails,
+        PmeMargin, PmeModelParams, PmePortfolio, PmeRiskMatrixMarginDetails, PmeRiskVector,
+        PmeSimulation, PmeTicker, PmeWorstCaseBucket,
     },
     position::Position,
     request::{
@@ -39,12 +64,17 @@ pub use crate::model::{
         ClosePositionRequest, ModifyOrderRequest, NewOrderRequest, TriggerType,
     },
     response::{
-        AprHistoryResponse, AuthResponse, ContractSizeResponse, DeliveryPricesResponse,
-        ExpirationsResponse, HeartbeatResponse, HelloResponse, JsonRpcError, JsonRpcResponse,
-        LastTradesResponse, MassQuoteResponse, Notification, OrderResponse, PaginatedResponse,
-        Pagination, ServerTimeResponse, SettlementsResponse, StatusResponse, SubscriptionResponse,
-        TestResponse,
+        AprHistoryResponse, AuthResponse, CancelOnDisconnectResponse, ContractSizeResponse,
+        DeliveryPricesResponse, ExpirationsResponse, HeartbeatResponse, HelloResponse,
+        JsonRpcError, JsonRpcResponse, LastTradesResponse, MarginsResponse, MassQuoteResponse,
+        Notification, OrderMarginResponse, OrderResponse, PaginatedResponse, Pagination,
+        ServerTimeResponse, SettlementsResponse, StatusResponse, SubscriptionResponse,
+        TestResponse, UserTradesResponse,
+    },
+    risk::{
+        PriceReference, RiskConfig, RiskLimits, RiskOrder, RiskScope, RiskState, RiskViolation,
     },
+    scope::{AccessLevel, AccessScope, ScopeArea, ScopeError, ScopeRequirement},
     settlement::{Settlement, SettlementType, Settlements},
     ticker::{TickerData, TickerStats},
     trade::{Liquidity, Trade, TradeStats, UserTrade},
@@ -53,14 +83,62 @@ pub use crate::model::{
         Withdrawal, WithdrawalsResponse,
     },
     transfer::{
-        AddressBookEntry, AddressType, SubaccountTransfer, Transfer, TransferState, Transfers,
+        AccountTransfer, AddressBeneficiariesResponse, AddressBeneficiary, AddressBookEntry,
+        AddressType, SubaccountTransfer, Transfer, TransferState, Transfers, TransfersResponse,
     },
 };
 
 // Block trade exports
 pub use crate::model::block_trade::{
-    BlockTrade, BlockTradeExecution, BlockTradeLeg, BlockTradeRole, BlockTradeSignature,
-    ExecuteBlockTradeRequest, VerifyBlockTradeRequest,
+    BLOCK_TRADE_SIGNATURE_VALIDITY_MS, BlockTrade, BlockTradeApprovalRequest,
+    BlockTradeConfirmation, BlockTradeError, BlockTradeExecution, BlockTradeLeg,
+    BlockTradeNonceTracker, BlockTradeProposal, BlockTradeRequestState, BlockTradeRole,
+    BlockTradeSignature, BrokerClient, BrokerTrade, BrokerTradeRequest, BrokerTradesResponse,
+    ExecuteBlockTradeRequest, PendingBlockTrade, SimulateBlockTradeRequest,
+    VerifyBlockTradeRequest, generate_block_trade_nonce, is_block_trade_timestamp_valid,
+    validate_block_trade_legs,
+};
+
+// Block RFQ exports
+pub use crate::model::block_rfq::{
+    AcceptBlockRfqRequest, AddBlockRfqQuoteRequest, BlockRfq, BlockRfqError, BlockRfqHedge,
+    BlockRfqIdentity, BlockRfqLeg, BlockRfqLegPrices, BlockRfqMaker, BlockRfqQuote,
+    BlockRfqQuoteLeg, BlockRfqQuoteLevel, BlockRfqQuoteState, BlockRfqRoleFilter, BlockRfqState,
+    BlockRfqTaker, BlockRfqTimeInForce, BlockRfqTrade, BlockRfqTradeTrigger, BlockRfqUser,
+    BlockRfqUserInfo, CreateBlockRfqLeg, CreateBlockRfqRequest, EditBlockRfqQuoteRequest,
+    ExecutionInstruction, GetBlockRfqQuotesResponse, GetBlockRfqTradesResponse,
+    GetBlockRfqsRequest, GetBlockRfqsResponse,
+};
+
+// JSON-RPC method catalog exports
+pub use crate::model::rpc::{
+    CompletedRequest, JsonRpcRequest, PendingRequest, PendingRequests, RequestIdAllocator,
+    RpcMethod, is_known_method, is_private_method,
+};
+
+// Subscription channel exports
+pub use crate::model::channel::{
+    BookGroup, Channel, ChannelError, ChannelInterval, ChannelKind, ChartResolution,
+};
+
+// Subscription notification exports
+pub use crate::model::subscription::{
+    AccessLogEntry, Announcement, BlockRfqPublicTrade, BookAction, BookLevelChange, BookUpdate,
+    ChartTradesUpdate, EstimatedExpirationPrice, GroupedBookUpdate, IncrementalTickerUpdate,
+    InstrumentStateUpdate, MarkPriceOption, NotificationDecoder, NotificationError,
+    PayloadErrorStats, PerpetualUpdate, PlatformStateUpdate, PriceRankingEntry, PriceStatistics,
+    PublicMethodsState, QuoteUpdate, RfqUpdate, ShapeReport, SubscriptionData,
+    SubscriptionNotification, UpdateType, UserChanges, UserLock, VolatilityIndexUpdate,
+    decode_notification,
+};
+
+// FIX exports
+pub use crate::fix::{
+    ExecutionReport, FieldMap, FixDecoder, FixError, FixField, FixFrame, FixMessage, FixSession,
+    GroupSpec, LogonBuilder, LogonError, LogonVerifier, MarketDataIncrement, MarketDataRequest,
+    MarketDataSnapshot, MemorySequenceStore, MsgType, OrderCancelReject, RawField, SecurityList,
+    SecurityListRequest, SequenceStore, SessionAction, SessionConfig, SessionError,
+    SessionRejectReason, SessionState, VerifiedLogon,
 };
 
 // Combo exports
@@ -70,13 +148,18 @@ pub use crate::model::combo::{
 
 // Wallet exports
 pub use crate::model::wallet::{
-    ClearanceOriginator, ClearanceState, DepositAddress, DepositId, WithdrawalRequest,
-    WithdrawalState,
+    ClearanceOriginator, ClearanceState, CustodyAccount, DepositAddress, DepositId,
+    WithdrawalRequest, WithdrawalState,
 };
 
 // Utility exports - macros and functions
 pub use crate::constants::*;
 pub use crate::utils::{
+    ids::{MonotonicIdGenerator, next_monotonic_id},
     logger::setup_logger,
+    rate_limit::{CreditPool, CreditRateLimiter, RateLimitBucket, RateLimitConfig},
+    retry::{Idempotency, RetryAction, RetryDecision, RetryPolicy},
+    signature::{HmacAuthBuilder, HmacAuthHeader, client_signature, request_signature},
+    token::{IssuedToken, TokenDerivation, TokenManager, TokenStatus},
     tools::{generate_nonce, generate_timestamp},
 };
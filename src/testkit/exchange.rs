@@ -0,0 +1,1216 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! In-process fake exchange speaking Deribit JSON-RPC
+
+use super::fixtures;
+use crate::constants::{ACCESS_TOKEN_EXPIRATION_SEC, JSONRPC_VERSION};
+use crate::error::codes::DeribitErrorCode;
+use crate::model::channel::{Channel, ChannelInterval, ChannelKind};
+use crate::model::instrument::Instrument;
+use crate::model::order::{OrderInfo, OrderPrice, OrderSide, OrderStatus, OrderType, TimeInForce};
+use crate::model::position::{Direction, Position};
+use crate::model::request::{AuthRequest, CancelOrderRequest};
+use crate::model::response::{AuthResponse, JsonRpcError, JsonRpcResponse, OrderResponse};
+use crate::model::rpc::methods::{
+    Auth, Buy, Cancel, GetInstrument, GetInstruments, GetPosition, GetPositions, GetTicker,
+    GetTime, PrivateSubscribe, PrivateUnsubscribe, PublicSubscribe, PublicUnsubscribe, Sell,
+};
+use crate::model::rpc::params::{
+    ChannelsParams, GetInstrumentsParams, InstrumentParams, PositionsParams,
+};
+use crate::model::rpc::{JsonRpcRequest, RpcMethod, is_private_method};
+use crate::model::ticker::TickerData;
+use crate::model::trade::TradeExecution;
+use crate::utils::signature::client_signature;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Time of a new exchange in milliseconds
+pub const MOCK_START_TIME_MS: u64 = 1_760_000_000_000;
+
+/// Scope granted when `public/auth` does not request one
+pub const MOCK_DEFAULT_SCOPE: &str =
+    "connection mainaccount account:read_write trade:read_write wallet:read_write";
+
+/// Error injected into the responses of one method
+#[derive(Debug, Clone)]
+struct InjectedError {
+    method: String,
+    error: JsonRpcError,
+    /// Remaining responses to fail, `None` for every response
+    remaining: Option<usize>,
+}
+
+/// Signed position of one instrument
+#[derive(Debug, Clone, Copy, Default)]
+struct PositionState {
+    size: f64,
+    average_price: f64,
+    realized_profit_loss: f64,
+}
+
+impl PositionState {
+    /// Apply a fill of a signed amount
+    fn apply(&mut self, amount: f64, price: f64) {
+        let size = self.size + amount;
+        if self.size == 0.0 || self.size.signum() == amount.signum() {
+            self.average_price =
+                (self.size.abs() * self.average_price + amount.abs() * price) / size.abs();
+        } else {
+            let closed = amount.abs().min(self.size.abs());
+            self.realized_profit_loss += closed * (price - self.average_price) * self.size.signum();
+            if size == 0.0 {
+                self.average_price = 0.0;
+            } else if size.signum() != self.size.signum() {
+                self.average_price = price;
+            }
+        }
+        self.size = size;
+    }
+}
+
+/// `params` of `private/buy` and `private/sell` used by the exchange
+#[derive(Deserialize)]
+struct OrderParams {
+    instrument_name: String,
+    amount: Option<f64>,
+    contracts: Option<f64>,
+    #[serde(rename = "type")]
+    order_type: Option<OrderType>,
+    price: Option<f64>,
+    label: Option<String>,
+    time_in_force: Option<TimeInForce>,
+    post_only: Option<bool>,
+    reduce_only: Option<bool>,
+}
+
+/// Request frame whose `params` may be omitted
+#[derive(Deserialize)]
+struct RawRequest {
+    id: u64,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// In-process fake exchange answering Deribit JSON-RPC requests
+///
+/// The exchange performs no I/O and reads no clock: requests are passed to
+/// [`MockExchange::handle_frame`] or [`MockExchange::call`], time moves with
+/// [`MockExchange::advance`], and subscription notifications queue up until
+/// [`MockExchange::take_notifications`]. It answers `public/auth`,
+/// `public/get_time`, `public/get_instrument`, `public/get_instruments`,
+/// `public/ticker`, `private/buy`, `private/sell`, `private/cancel`,
+/// `private/get_position`, `private/get_positions` and the subscribe and
+/// unsubscribe methods; any other method fails with `Method not found`.
+///
+/// Market orders, and limit orders crossing the ticker's best price, fill
+/// in full at that price without fees. Other limit orders rest until
+/// cancelled.
+#[derive(Debug, Clone)]
+pub struct MockExchange {
+    now_ms: u64,
+    credentials: HashMap<String, String>,
+    refresh_tokens: HashSet<String>,
+    token_expires_at_ms: Option<u64>,
+    issued_tokens: u64,
+    instruments: BTreeMap<String, Instrument>,
+    tickers: HashMap<String, TickerData>,
+    orders: BTreeMap<String, OrderInfo>,
+    positions: BTreeMap<String, PositionState>,
+    subscriptions: BTreeMap<String, Channel>,
+    scripts: BTreeMap<String, (Channel, VecDeque<Value>)>,
+    injected: Vec<InjectedError>,
+    notifications: VecDeque<String>,
+    received: Vec<String>,
+    next_order_id: u64,
+    next_trade_seq: u64,
+    next_request_id: u64,
+}
+
+impl MockExchange {
+    /// Create an exchange without instruments that accepts any credentials
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            now_ms: MOCK_START_TIME_MS,
+            credentials: HashMap::new(),
+            refresh_tokens: HashSet::new(),
+            token_expires_at_ms: None,
+            issued_tokens: 0,
+            instruments: BTreeMap::new(),
+            tickers: HashMap::new(),
+            orders: BTreeMap::new(),
+            positions: BTreeMap::new(),
+            subscriptions: BTreeMap::new(),
+            scripts: BTreeMap::new(),
+            injected: Vec::new(),
+            notifications: VecDeque::new(),
+            received: Vec::new(),
+            next_order_id: 1,
+            next_trade_seq: 1,
+            next_request_id: 1,
+        }
+    }
+
+    /// Create an exchange listing `BTC-PERPETUAL` and `ETH-PERPETUAL`
+    #[must_use]
+    pub fn with_default_market() -> Self {
+        let now_ms = MOCK_START_TIME_MS;
+        Self::new()
+            .with_instrument(
+                fixtures::perpetual("BTC"),
+                fixtures::ticker("BTC-PERPETUAL", 60_000.0, 60_000.5, now_ms),
+            )
+            .with_instrument(
+                fixtures::perpetual("ETH"),
+                fixtures::ticker("ETH-PERPETUAL", 3_000.0, 3_000.05, now_ms),
+            )
+    }
+
+    /// Only accept `public/auth` with the given credentials
+    ///
+    /// Can be called several times to register more than one key.
+    #[must_use]
+    pub fn with_credentials(
+        mut self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        self.credentials
+            .insert(client_id.into(), client_secret.into());
+        self
+    }
+
+    /// List an instrument with its ticker
+    #[must_use]
+    pub fn with_instrument(mut self, instrument: Instrument, ticker: TickerData) -> Self {
+        self.tickers
+            .insert(instrument.instrument_name.clone(), ticker);
+        self.instruments
+            .insert(instrument.instrument_name.clone(), instrument);
+        self
+    }
+
+    /// Get the current time in milliseconds
+    #[must_use]
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// Move the clock forward
+    pub fn advance(&mut self, ms: u64) {
+        self.now_ms = self.now_ms.saturating_add(ms);
+    }
+
+    /// Check if the connection holds a valid access token
+    #[must_use]
+    pub fn is_authenticated(&self) -> bool {
+        self.token_expires_at_ms
+            .is_some_and(|expires_at| self.now_ms < expires_at)
+    }
+
+    /// Drop the session and its subscriptions, as a closed connection would
+    pub fn disconnect(&mut self) {
+        self.token_expires_at_ms = None;
+        self.subscriptions.clear();
+        self.notifications.clear();
+    }
+
+    /// Fail the next request of a method with a Deribit error code
+    pub fn fail_next(&mut self, method: &str, code: DeribitErrorCode) {
+        self.fail_next_with(method, code_error(code));
+    }
+
+    /// Fail the next request of a method with a JSON-RPC error
+    ///
+    /// Use this to attach `data`, such as the `wait` hint of rate limit errors.
+    pub fn fail_next_with(&mut self, method: &str, error: JsonRpcError) {
+        self.inject(method, error, Some(1));
+    }
+
+    /// Fail every request of a method with a Deribit error code
+    pub fn fail_always(&mut self, method: &str, code: DeribitErrorCode) {
+        self.inject(method, code_error(code), None);
+    }
+
+    /// Remove every injected error
+    pub fn clear_failures(&mut self) {
+        self.injected.clear();
+    }
+
+    /// Replace the ticker of an instrument
+    ///
+    /// Subscribers of the instrument's `ticker` channels are notified.
+    pub fn set_ticker(&mut self, ticker: TickerData) {
+        let name = ticker.instrument_name.clone();
+        let data = serde_json::to_value(&ticker).unwrap_or(Value::Null);
+        self.tickers.insert(name.clone(), ticker);
+        let channels: Vec<Channel> = self
+            .subscriptions
+            .values()
+            .filter(|channel| channel.instrument_name() == Some(name.as_str()))
+            .filter(|channel| matches!(channel, Channel::Ticker { .. }))
+            .cloned()
+            .collect();
+        for channel in channels {
+            self.publish(&channel, data.clone());
+        }
+    }
+
+    /// Publish a notification if the channel is subscribed
+    ///
+    /// Returns whether a notification was queued.
+    pub fn publish(&mut self, channel: &Channel, data: Value) -> bool {
+        let name = channel.to_string();
+        if !self.subscriptions.contains_key(&name) {
+            return false;
+        }
+        let frame = json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "method": "subscription",
+            "params": {
+                "channel": name,
+                "data": data,
+            }
+        });
+        self.notifications.push_back(frame.to_string());
+        true
+    }
+
+    /// Queue payloads to publish on a channel, one per [`MockExchange::step`]
+    ///
+    /// Payloads of `ticker` channels also replace the instrument's ticker,
+    /// so scripted prices drive order fills.
+    pub fn script(&mut self, channel: Channel, payloads: impl IntoIterator<Item = Value>) {
+        let entry = self
+            .scripts
+            .entry(channel.to_string())
+            .or_insert_with(|| (channel, VecDeque::new()));
+        entry.1.extend(payloads);
+    }
+
+    /// Publish the next scripted payload of every channel
+    ///
+    /// Payloads of unsubscribed channels are consumed without a
+    /// notification. Returns the number of notifications queued.
+    pub fn step(&mut self) -> usize {
+        let due: Vec<(Channel, Value)> = self
+            .scripts
+            .values_mut()
+            .filter_map(|(channel, payloads)| {
+                payloads.pop_front().map(|data| (channel.clone(), data))
+            })
+            .collect();
+        self.scripts.retain(|_, (_, payloads)| !payloads.is_empty());
+        let mut queued = 0;
+        for (channel, data) in due {
+            if matches!(channel, Channel::Ticker { .. })
+                && let Ok(ticker) = serde_json::from_value::<TickerData>(data.clone())
+            {
+                self.tickers.insert(ticker.instrument_name.clone(), ticker);
+            }
+            if self.publish(&channel, data) {
+                queued += 1;
+            }
+        }
+        queued
+    }
+
+    /// Check if scripted payloads remain
+    #[must_use]
+    pub fn has_script(&self) -> bool {
+        !self.scripts.is_empty()
+    }
+
+    /// Take the queued notification frames, oldest first
+    pub fn take_notifications(&mut self) -> Vec<String> {
+        self.notifications.drain(..).collect()
+    }
+
+    /// Check if a channel is subscribed
+    #[must_use]
+    pub fn is_subscribed(&self, channel: &Channel) -> bool {
+        self.subscriptions.contains_key(&channel.to_string())
+    }
+
+    /// Get the subscribed channel names, sorted
+    #[must_use]
+    pub fn subscriptions(&self) -> Vec<&str> {
+        self.subscriptions.keys().map(String::as_str).collect()
+    }
+
+    /// Get an order by id
+    #[must_use]
+    pub fn order(&self, order_id: &str) -> Option<&OrderInfo> {
+        self.orders.get(order_id)
+    }
+
+    /// Get the orders resting on the book
+    #[must_use]
+    pub fn open_orders(&self) -> Vec<&OrderInfo> {
+        self.orders
+            .values()
+            .filter(|order| order.order_state == OrderStatus::New)
+            .collect()
+    }
+
+    /// Get the methods received so far, in order
+    #[must_use]
+    pub fn received_methods(&self) -> &[String] {
+        &self.received
+    }
+
+    /// Answer a request frame with a response frame
+    ///
+    /// Malformed frames are answered with a `Parse error` without id.
+    pub fn handle_frame(&mut self, frame: &str) -> String {
+        let response = match serde_json::from_str::<RawRequest>(frame) {
+            Ok(raw) => self.handle(&JsonRpcRequest::new(raw.id, raw.method, raw.params)),
+            Err(_) => self.envelope(None, Err(JsonRpcError::parse_error())),
+        };
+        serde_json::to_string(&response).expect("JSON-RPC response serializes")
+    }
+
+    /// Answer a request
+    pub fn handle(&mut self, request: &JsonRpcRequest<Value>) -> JsonRpcResponse<Value> {
+        let method = request.method.trim_start_matches('/').to_string();
+        self.received.push(method.clone());
+        let outcome = match self.take_injected(&method) {
+            Some(error) => Err(error),
+            None => self.dispatch(&method, request.params.clone()),
+        };
+        self.envelope(Some(Value::from(request.id)), outcome)
+    }
+
+    /// Call a catalog method and decode its result
+    pub fn call<M: RpcMethod>(&mut self, params: &M::Params) -> Result<M::Result, JsonRpcError> {
+        let params = serde_json::to_value(params)
+            .map_err(|err| invalid_params("params", &err.to_string()))?;
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        let result = self
+            .handle(&JsonRpcRequest::new(id, M::NAME, params))
+            .into_result()?;
+        serde_json::from_value(result).map_err(|err| {
+            JsonRpcError::with_data(
+                -32603,
+                "Internal error".to_string(),
+                json!({ "reason": err.to_string() }),
+            )
+        })
+    }
+
+    fn inject(&mut self, method: &str, error: JsonRpcError, remaining: Option<usize>) {
+        self.injected.push(InjectedError {
+            method: method.trim_start_matches('/').to_string(),
+            error,
+            remaining,
+        });
+    }
+
+    fn take_injected(&mut self, method: &str) -> Option<JsonRpcError> {
+        let index = self
+            .injected
+            .iter()
+            .position(|injected| injected.method == method)?;
+        let injected = &mut self.injected[index];
+        let error = injected.error.clone();
+        if let Some(remaining) = injected.remaining.as_mut() {
+            *remaining -= 1;
+            if *remaining == 0 {
+                self.injected.remove(index);
+            }
+        }
+        Some(error)
+    }
+
+    fn envelope(
+        &self,
+        id: Option<Value>,
+        outcome: Result<Value, JsonRpcError>,
+    ) -> JsonRpcResponse<Value> {
+        let mut response = match outcome {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(error) => JsonRpcResponse::error(id, error),
+        };
+        let now_us = i64::try_from(self.now_ms.saturating_mul(1000)).unwrap_or(i64::MAX);
+        response.testnet = Some(true);
+        response.us_in = Some(now_us);
+        response.us_out = Some(now_us);
+        response.us_diff = Some(0);
+        response
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, JsonRpcError> {
+        if is_private_method(method) && !self.is_authenticated() {
+            return Err(code_error(DeribitErrorCode::Unauthorized));
+        }
+        match method {
+            Auth::NAME => self.auth(decode(params)?),
+            GetTime::NAME => Ok(Value::from(self.now_ms)),
+            GetInstrument::NAME => {
+                let params: InstrumentParams = decode(params)?;
+                to_value(self.instrument(&params.instrument_name)?)
+            }
+            GetInstruments::NAME => self.instruments(decode(params)?),
+            GetTicker::NAME => {
+                let params: InstrumentParams = decode(params)?;
+                self.instrument(&params.instrument_name)?;
+                to_value(
+                    self.tickers.get(&params.instrument_name).ok_or_else(|| {
+                        code_error(DeribitErrorCode::InvalidOrUnsupportedInstrument)
+                    })?,
+                )
+            }
+            Buy::NAME => self.place(OrderSide::Buy, decode(params)?),
+            Sell::NAME => self.place(OrderSide::Sell, decode(params)?),
+            Cancel::NAME => self.cancel(decode(params)?),
+            GetPosition::NAME => {
+                let params: InstrumentParams = decode(params)?;
+                let instrument = self.instrument(&params.instrument_name)?;
+                to_value(self.position(instrument))
+            }
+            GetPositions::NAME => self.positions(decode(params)?),
+            PublicSubscribe::NAME | PrivateSubscribe::NAME => self.subscribe(decode(params)?),
+            PublicUnsubscribe::NAME | PrivateUnsubscribe::NAME => self.unsubscribe(decode(params)?),
+            _ => Err(JsonRpcError::method_not_found()),
+        }
+    }
+
+    fn auth(&mut self, request: AuthRequest) -> Result<Value, JsonRpcError> {
+        let secret = self.credentials.get(&request.client_id);
+        let open = self.credentials.is_empty();
+        let valid = match request.grant_type.as_str() {
+            "client_credentials" => {
+                open || secret.is_some_and(|secret| *secret == request.client_secret)
+            }
+            "client_signature" => match (&request.timestamp, &request.signature, &request.nonce) {
+                (Some(timestamp), Some(signature), Some(nonce)) => {
+                    let data = request.data.as_deref().unwrap_or_default();
+                    open || secret.is_some_and(|secret| {
+                        client_signature(secret, *timestamp, nonce, data) == *signature
+                    })
+                }
+                _ => return Err(invalid_params("signature", "must be present")),
+            },
+            "refresh_token" => request
+                .refresh_token
+                .as_ref()
+                .is_some_and(|token| self.refresh_tokens.contains(token)),
+            _ => return Err(invalid_params("grant_type", "not supported")),
+        };
+        if !valid {
+            return Err(code_error(DeribitErrorCode::InvalidCredentials));
+        }
+
+        self.issued_tokens += 1;
+        let refresh_token = format!("mock_refresh_{}", self.issued_tokens);
+        self.refresh_tokens.insert(refresh_token.clone());
+        self.token_expires_at_ms = Some(
+            self.now_ms
+                .saturating_add(ACCESS_TOKEN_EXPIRATION_SEC * 1000),
+        );
+        to_value(&AuthResponse {
+            access_token: format!("mock_access_{}", self.issued_tokens),
+            token_type: "bearer".to_string(),
+            expires_in: ACCESS_TOKEN_EXPIRATION_SEC as i64,
+            refresh_token,
+            scope: request
+                .scope
+                .unwrap_or_else(|| MOCK_DEFAULT_SCOPE.to_string()),
+            enabled_features: Vec::new(),
+        })
+    }
+
+    fn instrument(&self, instrument_name: &str) -> Result<&Instrument, JsonRpcError> {
+        self.instruments
+            .get(instrument_name)
+            .ok_or_else(|| code_error(DeribitErrorCode::InvalidOrUnsupportedInstrument))
+    }
+
+    fn instruments(&self, params: GetInstrumentsParams) -> Result<Value, JsonRpcError> {
+        let expired = params.expired.unwrap_or(false);
+        let listed: Vec<&Instrument> = self
+            .instruments
+            .values()
+            .filter(|instrument| matches_currency(instrument, Some(&params.currency)))
+            .filter(|instrument| matches_kind(instrument, params.kind.as_deref()))
+            .filter(|instrument| (instrument.is_active == Some(false)) == expired)
+            .collect();
+        to_value(listed)
+    }
+
+    fn place(&mut self, side: OrderSide, params: OrderParams) -> Result<Value, JsonRpcError> {
+        let instrument = self.instrument(&params.instrument_name)?.clone();
+        let contract_size = instrument.contract_size.unwrap_or(1.0);
+        let amount = params
+            .amount
+            .or(params.contracts.map(|contracts| contracts * contract_size))
+            .filter(|amount| *amount > 0.0)
+            .ok_or_else(|| code_error(DeribitErrorCode::InvalidAmount))?;
+        let order_type = params.order_type.unwrap_or(OrderType::Limit);
+        let ticker = self.tickers.get(&instrument.instrument_name);
+        let opposite = ticker.and_then(|ticker| match side {
+            OrderSide::Buy => ticker.best_ask_price,
+            OrderSide::Sell => ticker.best_bid_price,
+        });
+        let fill_price = match order_type {
+            OrderType::Market => opposite.or(ticker.map(|ticker| ticker.mark_price)),
+            OrderType::Limit => {
+                let price = params
+                    .price
+                    .ok_or_else(|| invalid_params("price", "must be present"))?;
+                opposite.filter(|best| match side {
+                    OrderSide::Buy => price >= *best,
+                    OrderSide::Sell => price <= *best,
+                })
+            }
+            _ => return Err(invalid_params("type", "not supported by the mock exchange")),
+        };
+        let post_only = params.post_only.unwrap_or(false);
+        if post_only && fill_price.is_some() {
+            return Err(code_error(DeribitErrorCode::PostOnlyReject));
+        }
+
+        let order_id = format!("MOCK-{}", self.next_order_id);
+        self.next_order_id += 1;
+        let label = params.label.unwrap_or_default();
+        let mut order = OrderInfo {
+            amount,
+            api: true,
+            average_price: 0.0,
+            creation_timestamp: self.now_ms,
+            direction: side,
+            filled_amount: 0.0,
+            instrument_name: instrument.instrument_name.clone(),
+            is_liquidation: false,
+            label: label.clone(),
+            last_update_timestamp: self.now_ms,
+            max_show: Some(amount),
+            order_id: order_id.clone(),
+            order_state: OrderStatus::New,
+            order_type,
+            original_order_type: None,
+            post_only,
+            price: match order_type {
+                OrderType::Market => Some(OrderPrice::Text("market_price".to_string())),
+                _ => params.price.map(OrderPrice::Value),
+            },
+            profit_loss: Some(0.0),
+            reduce_only: params.reduce_only.unwrap_or(false),
+            replaced: false,
+            risk_reducing: false,
+            time_in_force: params
+                .time_in_force
+                .unwrap_or(TimeInForce::GoodTilCancelled),
+            triggered: None,
+            trigger: None,
+            usd: None,
+            web: false,
+            trigger_price: None,
+            implv: None,
+            advanced: None,
+            contracts: None,
+            is_rebalance: None,
+            cancel_reason: None,
+            quote: None,
+            quote_id: None,
+            quote_set_id: None,
+            mmp: None,
+            mmp_group: None,
+            mmp_cancelled: None,
+        };
+
+        let mut trades = Vec::new();
+        if let Some(price) = fill_price {
+            order.order_state = OrderStatus::Filled;
+            order.filled_amount = amount;
+            order.average_price = price;
+            let signed = match side {
+                OrderSide::Buy => amount,
+                OrderSide::Sell => -amount,
+            };
+            self.positions
+                .entry(instrument.instrument_name.clone())
+                .or_default()
+                .apply(signed, price);
+            let trade_seq = self.next_trade_seq;
+            self.next_trade_seq += 1;
+            trades.push(TradeExecution {
+                amount,
+                direction: side.as_str().to_string(),
+                fee: 0.0,
+                fee_currency: instrument.currency.clone().unwrap_or_default(),
+                index_price: ticker
+                    .and_then(|ticker| ticker.index_price)
+                    .unwrap_or(price),
+                instrument_name: instrument.instrument_name.clone(),
+                iv: None,
+                label,
+                liquidity: "T".to_string(),
+                mark_price: ticker.map_or(price, |ticker| ticker.mark_price),
+                matching_id: None,
+                order_id: order_id.clone(),
+                order_type: order.order_type.as_str().to_string(),
+                original_order_type: None,
+                post_only: None,
+                price,
+                reduce_only: None,
+                self_trade: false,
+                state: "filled".to_string(),
+                tick_direction: 0,
+                timestamp: self.now_ms,
+                trade_id: format!("MOCK-T{trade_seq}"),
+                trade_seq,
+                underlying_price: None,
+            });
+        }
+
+        self.orders.insert(order_id, order.clone());
+        self.publish_user(&order, &trades);
+        to_value(&OrderResponse { order, trades })
+    }
+
+    fn cancel(&mut self, request: CancelOrderRequest) -> Result<Value, JsonRpcError> {
+        let order = self
+            .orders
+            .get_mut(&request.order_id)
+            .ok_or_else(|| code_error(DeribitErrorCode::OrderNotFound))?;
+        if order.order_state != OrderStatus::New {
+            return Err(code_error(DeribitErrorCode::NotOpenOrder));
+        }
+        order.order_state = OrderStatus::Canceled;
+        order.last_update_timestamp = self.now_ms;
+        let order = order.clone();
+        self.publish_user(&order, &[]);
+        to_value(&order)
+    }
+
+    fn position(&self, instrument: &Instrument) -> Position {
+        let state = self
+            .positions
+            .get(&instrument.instrument_name)
+            .copied()
+            .unwrap_or_default();
+        let ticker = self.tickers.get(&instrument.instrument_name);
+        let direction = if state.size > 0.0 {
+            Direction::Buy
+        } else if state.size < 0.0 {
+            Direction::Sell
+        } else {
+            Direction::Zero
+        };
+        Position {
+            average_price: state.average_price,
+            average_price_usd: None,
+            delta: None,
+            direction,
+            estimated_liquidation_price: None,
+            floating_profit_loss: None,
+            floating_profit_loss_usd: None,
+            gamma: None,
+            index_price: ticker.and_then(|ticker| ticker.index_price),
+            initial_margin: None,
+            instrument_name: instrument.instrument_name.clone(),
+            interest_value: None,
+            kind: instrument.kind.as_ref().and_then(|kind| {
+                serde_json::to_value(kind)
+                    .ok()
+                    .and_then(|kind| kind.as_str().map(str::to_string))
+            }),
+            leverage: None,
+            maintenance_margin: None,
+            mark_price: ticker.map(|ticker| ticker.mark_price),
+            open_orders_margin: None,
+            realized_funding: None,
+            realized_profit_loss: Some(state.realized_profit_loss),
+            settlement_price: None,
+            size: state.size,
+            size_currency: None,
+            theta: None,
+            total_profit_loss: None,
+            vega: None,
+            unrealized_profit_loss: None,
+        }
+    }
+
+    fn positions(&self, params: PositionsParams) -> Result<Value, JsonRpcError> {
+        let positions: Vec<Position> = self
+            .positions
+            .keys()
+            .filter_map(|name| self.instruments.get(name))
+            .filter(|instrument| matches_currency(instrument, params.currency.as_deref()))
+            .filter(|instrument| matches_kind(instrument, params.kind.as_deref()))
+            .map(|instrument| self.position(instrument))
+            .collect();
+        to_value(positions)
+    }
+
+    fn subscribe(&mut self, params: ChannelsParams) -> Result<Value, JsonRpcError> {
+        let mut channels = Vec::with_capacity(params.channels.len());
+        for name in &params.channels {
+            let channel: Channel =
+                name.parse()
+                    .map_err(|err: crate::model::channel::ChannelError| {
+                        invalid_params("channels", &err.to_string())
+                    })?;
+            if !self.is_authenticated() {
+                if channel.is_private() {
+                    return Err(code_error(DeribitErrorCode::Unauthorized));
+                }
+                if channel.interval() == Some(ChannelInterval::Raw) {
+                    return Err(code_error(
+                        DeribitErrorCode::RawSubscriptionsNotAvailableForUnauthorized,
+                    ));
+                }
+            }
+            channels.push(channel);
+        }
+        let names: Vec<String> = channels.iter().map(Channel::to_string).collect();
+        for (name, channel) in names.iter().zip(channels) {
+            self.subscriptions.insert(name.clone(), channel);
+        }
+        to_value(names)
+    }
+
+    fn unsubscribe(&mut self, params: ChannelsParams) -> Result<Value, JsonRpcError> {
+        let removed: Vec<String> = params
+            .channels
+            .iter()
+            .filter(|name| self.subscriptions.remove(name.as_str()).is_some())
+            .cloned()
+            .collect();
+        to_value(removed)
+    }
+
+    /// Publish an order change on its `user.orders`, `user.trades` and `user.changes` channels
+    fn publish_user(&mut self, order: &OrderInfo, trades: &[TradeExecution]) {
+        let Some(instrument) = self.instruments.get(&order.instrument_name) else {
+            return;
+        };
+        let orders = serde_json::to_value(order).unwrap_or(Value::Null);
+        let orders_list = Value::Array(vec![orders.clone()]);
+        let trades_data = serde_json::to_value(trades).unwrap_or(Value::Null);
+        let changes = json!({
+            "instrument_name": order.instrument_name,
+            "orders": [orders],
+            "trades": trades_data,
+            "positions": [self.position(instrument)],
+        });
+        let notifications: Vec<(Channel, Value)> = self
+            .subscriptions
+            .values()
+            .filter_map(|channel| {
+                let data = match channel {
+                    Channel::UserOrders {
+                        interval: ChannelInterval::Raw,
+                        ..
+                    }
+                    | Channel::UserOrdersByKind {
+                        interval: ChannelInterval::Raw,
+                        ..
+                    } => &orders,
+                    Channel::UserOrders { .. } | Channel::UserOrdersByKind { .. } => &orders_list,
+                    Channel::UserTrades { .. } | Channel::UserTradesByKind { .. }
+                        if !trades.is_empty() =>
+                    {
+                        &trades_data
+                    }
+                    Channel::UserChanges { .. } | Channel::UserChangesByKind { .. } => &changes,
+                    _ => return None,
+                };
+                let matches = match channel {
+                    Channel::UserOrders {
+                        instrument_name, ..
+                    }
+                    | Channel::UserTrades {
+                        instrument_name, ..
+                    }
+                    | Channel::UserChanges {
+                        instrument_name, ..
+                    } => *instrument_name == order.instrument_name,
+                    Channel::UserOrdersByKind { kind, currency, .. }
+                    | Channel::UserTradesByKind { kind, currency, .. }
+                    | Channel::UserChangesByKind { kind, currency, .. } => {
+                        matches_channel_kind(instrument, *kind)
+                            && matches_currency(instrument, Some(currency))
+                    }
+                    _ => false,
+                };
+                matches.then(|| (channel.clone(), data.clone()))
+            })
+            .collect();
+        for (channel, data) in notifications {
+            self.publish(&channel, data);
+        }
+    }
+}
+
+impl Default for MockExchange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// JSON-RPC error of a Deribit error code
+fn code_error(code: DeribitErrorCode) -> JsonRpcError {
+    JsonRpcError::new(code.code(), code.message().to_string())
+}
+
+/// `Invalid params` error naming the offending parameter
+fn invalid_params(param: &str, reason: &str) -> JsonRpcError {
+    JsonRpcError::with_data(
+        -32602,
+        "Invalid params".to_string(),
+        json!({ "param": param, "reason": reason }),
+    )
+}
+
+fn decode<T: DeserializeOwned>(params: Value) -> Result<T, JsonRpcError> {
+    serde_json::from_value(params).map_err(|err| invalid_params("params", &err.to_string()))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, JsonRpcError> {
+    serde_json::to_value(value).map_err(|_| JsonRpcError::internal_error())
+}
+
+/// Check an instrument against a currency filter, where `any` matches all
+fn matches_currency(instrument: &Instrument, currency: Option<&str>) -> bool {
+    match currency {
+        None => true,
+        Some(currency) if currency.eq_ignore_ascii_case("any") => true,
+        Some(currency) => instrument
+            .currency
+            .as_deref()
+            .or(instrument.base_currency.as_deref())
+            .is_some_and(|own| own.eq_ignore_ascii_case(currency)),
+    }
+}
+
+/// Check an instrument against the kind of a channel
+fn matches_channel_kind(instrument: &Instrument, kind: ChannelKind) -> bool {
+    match kind {
+        ChannelKind::Any => true,
+        ChannelKind::Combo => instrument
+            .kind
+            .as_ref()
+            .and_then(|own| serde_json::to_value(own).ok())
+            .is_some_and(|own| own.as_str().is_some_and(|own| own.ends_with("_combo"))),
+        kind => matches_kind(instrument, Some(kind.as_str())),
+    }
+}
+
+/// Check an instrument against a kind filter, where `any` matches all
+fn matches_kind(instrument: &Instrument, kind: Option<&str>) -> bool {
+    match kind {
+        None | Some("any") => true,
+        Some(kind) => instrument
+            .kind
+            .as_ref()
+            .and_then(|own| serde_json::to_value(own).ok())
+            .is_some_and(|own| own == kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::channel::ChannelInterval;
+    use crate::model::order_management::BuyOrderRequest;
+    use crate::model::rpc::methods::Buy;
+    use crate::model::subscription::{SubscriptionData, decode_notification};
+    use std::collections::BTreeSet;
+
+    fn authenticated() -> MockExchange {
+        let mut exchange = MockExchange::with_default_market().with_credentials("client", "secret");
+        exchange
+            .call::<Auth>(&AuthRequest::client_credentials(
+                "client".to_string(),
+                "secret".to_string(),
+            ))
+            .unwrap();
+        exchange
+    }
+
+    fn buy(instrument_name: &str, amount: f64, price: Option<f64>) -> BuyOrderRequest {
+        serde_json::from_value(json!({
+            "instrument_name": instrument_name,
+            "amount": amount,
+            "price": price,
+            "type": if price.is_some() { "limit" } else { "market" },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_auth_and_private_access() {
+        let mut exchange = MockExchange::with_default_market().with_credentials("client", "secret");
+        let frame = exchange.handle_frame(
+            r#"{"jsonrpc":"2.0","id":3,"method":"private/get_positions","params":{}}"#,
+        );
+        let response: JsonRpcResponse<Value> = serde_json::from_str(&frame).unwrap();
+        assert_eq!(response.id, Some(json!(3)));
+        assert_eq!(response.error.unwrap().code, 13009);
+        assert_eq!(response.us_in, Some((MOCK_START_TIME_MS * 1000) as i64));
+
+        let wrong = AuthRequest::client_credentials("client".to_string(), "other".to_string());
+        assert_eq!(exchange.call::<Auth>(&wrong).unwrap_err().code, 13004);
+
+        let signed =
+            AuthRequest::client_signature("client".to_string(), "secret", 1, "n".to_string(), None);
+        let token = exchange.call::<Auth>(&signed).unwrap();
+        assert!(exchange.is_authenticated());
+        assert_eq!(token.scope, MOCK_DEFAULT_SCOPE);
+
+        exchange.advance(ACCESS_TOKEN_EXPIRATION_SEC * 1000);
+        assert!(!exchange.is_authenticated());
+        let refresh =
+            AuthRequest::refresh_token("client".to_string(), String::new(), token.refresh_token);
+        exchange.call::<Auth>(&refresh).unwrap();
+        assert!(exchange.is_authenticated());
+    }
+
+    #[test]
+    fn test_market_data_methods() {
+        let mut exchange = MockExchange::with_default_market();
+        let instruments = exchange
+            .call::<GetInstruments>(&GetInstrumentsParams {
+                currency: "BTC".to_string(),
+                kind: Some("future".to_string()),
+                expired: None,
+            })
+            .unwrap();
+        assert_eq!(instruments.len(), 1);
+        assert!(instruments[0].is_perpetual());
+
+        let ticker = exchange
+            .call::<GetTicker>(&InstrumentParams {
+                instrument_name: "ETH-PERPETUAL".to_string(),
+            })
+            .unwrap();
+        assert_eq!(ticker.best_bid_price, Some(3_000.0));
+
+        let unknown = exchange.call::<GetTicker>(&InstrumentParams {
+            instrument_name: "XRP-PERPETUAL".to_string(),
+        });
+        assert_eq!(unknown.unwrap_err().code, 10020);
+        let missing = exchange.handle_frame(r#"{"jsonrpc":"2.0","id":1,"method":"public/nope"}"#);
+        assert!(missing.contains("-32601"));
+    }
+
+    #[test]
+    fn test_orders_and_positions() {
+        let mut exchange = authenticated();
+        let filled = exchange
+            .call::<Buy>(&buy("BTC-PERPETUAL", 100.0, None))
+            .unwrap();
+        assert_eq!(filled.order.order_state, OrderStatus::Filled);
+        assert_eq!(filled.trades[0].price, 60_000.5);
+
+        let resting = exchange
+            .call::<Buy>(&buy("BTC-PERPETUAL", 50.0, Some(59_000.0)))
+            .unwrap();
+        assert_eq!(resting.order.order_state, OrderStatus::New);
+        assert!(resting.trades.is_empty());
+        assert_eq!(exchange.open_orders().len(), 1);
+
+        let cancelled = exchange
+            .call::<Cancel>(&CancelOrderRequest {
+                order_id: resting.order.order_id.clone(),
+            })
+            .unwrap();
+        assert_eq!(cancelled.order_state, OrderStatus::Canceled);
+        let again = exchange.call::<Cancel>(&CancelOrderRequest {
+            order_id: resting.order.order_id,
+        });
+        assert_eq!(again.unwrap_err().code, 11044);
+
+        let sell: crate::model::order_management::SellOrderRequest =
+            serde_json::from_value(json!({
+                "instrument_name": "BTC-PERPETUAL",
+                "amount": 40.0,
+                "type": "market"
+            }))
+            .unwrap();
+        exchange.call::<Sell>(&sell).unwrap();
+        let positions = exchange
+            .call::<GetPositions>(&PositionsParams {
+                currency: Some("BTC".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].size, 60.0);
+        assert_eq!(positions[0].average_price, 60_000.5);
+        assert_eq!(positions[0].realized_profit_loss, Some(-20.0));
+    }
+
+    #[test]
+    fn test_injected_errors() {
+        let mut exchange = authenticated();
+        exchange.fail_next(Buy::NAME, DeribitErrorCode::TooManyRequests);
+        let error = exchange
+            .call::<Buy>(&buy("BTC-PERPETUAL", 10.0, None))
+            .unwrap_err();
+        assert_eq!(error.code, 10028);
+        assert_eq!(error.message, "too_many_requests");
+        assert!(
+            exchange
+                .call::<Buy>(&buy("BTC-PERPETUAL", 10.0, None))
+                .is_ok()
+        );
+
+        exchange.fail_always("/public/get_time", DeribitErrorCode::Error);
+        assert!(exchange.call::<GetTime>(&Default::default()).is_err());
+        assert!(exchange.call::<GetTime>(&Default::default()).is_err());
+        exchange.clear_failures();
+        assert_eq!(
+            exchange.call::<GetTime>(&Default::default()).unwrap(),
+            MOCK_START_TIME_MS as i64
+        );
+        assert_eq!(exchange.received_methods().len(), 6);
+    }
+
+    #[test]
+    fn test_subscriptions_and_notifications() {
+        let mut exchange = MockExchange::with_default_market();
+        let raw = ChannelsParams {
+            channels: vec!["ticker.BTC-PERPETUAL.raw".to_string()],
+            label: None,
+        };
+        assert_eq!(
+            exchange.call::<PublicSubscribe>(&raw).unwrap_err().code,
+            13778
+        );
+        for channel in [
+            "user.orders.BTC-PERPETUAL.100ms",
+            "user.changes.future.BTC.raw",
+        ] {
+            let private = ChannelsParams {
+                channels: vec![channel.to_string()],
+                label: None,
+            };
+            assert_eq!(
+                exchange
+                    .call::<PrivateSubscribe>(&private)
+                    .unwrap_err()
+                    .code,
+                13009,
+                "{channel}"
+            );
+        }
+
+        let ticker_channel = Channel::ticker("BTC-PERPETUAL", ChannelInterval::Ms100);
+        let subscribed = exchange
+            .call::<PublicSubscribe>(&ChannelsParams {
+                channels: vec![ticker_channel.to_string()],
+                label: None,
+            })
+            .unwrap();
+        assert_eq!(subscribed, vec!["ticker.BTC-PERPETUAL.100ms"]);
+
+        let prices = [61_000.0, 62_000.0].map(|bid| {
+            serde_json::to_value(fixtures::ticker("BTC-PERPETUAL", bid, bid + 0.5, 0)).unwrap()
+        });
+        exchange.script(ticker_channel.clone(), prices);
+        assert_eq!(exchange.step(), 1);
+        assert_eq!(exchange.step(), 1);
+        assert!(!exchange.has_script());
+        let frames = exchange.take_notifications();
+        assert_eq!(frames.len(), 2);
+        let notification = decode_notification(&frames[1]).unwrap();
+        assert_eq!(notification.channel, ticker_channel);
+        assert!(matches!(
+            notification.data,
+            SubscriptionData::Ticker(ticker) if ticker.best_bid_price == Some(62_000.0)
+        ));
+
+        exchange
+            .call::<Auth>(&AuthRequest::client_credentials(
+                "any".to_string(),
+                "any".to_string(),
+            ))
+            .unwrap();
+        exchange
+            .call::<PrivateSubscribe>(&ChannelsParams {
+                channels: vec![
+                    "user.orders.BTC-PERPETUAL.raw".to_string(),
+                    "user.trades.BTC-PERPETUAL.raw".to_string(),
+                ],
+                label: None,
+            })
+            .unwrap();
+        let order = exchange
+            .call::<Buy>(&buy("BTC-PERPETUAL", 10.0, None))
+            .unwrap();
+        assert_eq!(order.trades[0].price, 62_000.5);
+        let frames = exchange.take_notifications();
+        assert_eq!(frames.len(), 2);
+        for frame in &frames {
+            let notification = decode_notification(frame).unwrap();
+            assert!(matches!(
+                notification.data,
+                SubscriptionData::UserOrder(_) | SubscriptionData::UserTrades(_)
+            ));
+        }
+
+        exchange.disconnect();
+        assert!(exchange.subscriptions().is_empty());
+        assert!(!exchange.publish(&ticker_channel, Value::Null));
+    }
+
+    #[test]
+    fn test_user_channels_by_kind_and_changes() {
+        let mut exchange = authenticated();
+        exchange
+            .call::<PrivateSubscribe>(&ChannelsParams {
+                channels: vec![
+                    "user.orders.future.BTC.raw".to_string(),
+                    "user.orders.option.any.raw".to_string(),
+                    "user.trades.any.any.raw".to_string(),
+                    "user.changes.future.any.raw".to_string(),
+                    "user.changes.ETH-PERPETUAL.raw".to_string(),
+                ],
+                label: None,
+            })
+            .unwrap();
+        exchange
+            .call::<Buy>(&buy("BTC-PERPETUAL", 10.0, None))
+            .unwrap();
+        let notifications: Vec<_> = exchange
+            .take_notifications()
+            .iter()
+            .map(|frame| decode_notification(frame).unwrap())
+            .collect();
+        let channels: BTreeSet<String> = notifications
+            .iter()
+            .map(|notification| notification.channel.to_string())
+            .collect();
+        assert_eq!(
+            channels,
+            BTreeSet::from([
+                "user.orders.future.BTC.raw".to_string(),
+                "user.trades.any.any.raw".to_string(),
+                "user.changes.future.any.raw".to_string(),
+            ])
+        );
+        let changes = notifications
+            .iter()
+            .find_map(|notification| match &notification.data {
+                SubscriptionData::UserChanges(changes) => Some(changes),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(changes.instrument_name, "BTC-PERPETUAL");
+        assert_eq!(changes.orders.len(), 1);
+        assert_eq!(changes.trades.len(), 1);
+        assert_eq!(changes.positions[0].size, 10.0);
+    }
+}
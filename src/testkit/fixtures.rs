@@ -0,0 +1,95 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Instruments and tickers to seed a [`MockExchange`](super::MockExchange)
+
+use crate::model::instrument::{Instrument, InstrumentKind, InstrumentType};
+use crate::model::ticker::{TickerData, TickerStats};
+
+/// Inverse perpetual of a currency (e.g., `BTC-PERPETUAL`)
+///
+/// Amounts are in USD with a contract size of 10, like the BTC and ETH
+/// perpetuals on Deribit.
+#[must_use]
+pub fn perpetual(currency: &str) -> Instrument {
+    let currency = currency.to_uppercase();
+    Instrument {
+        instrument_name: format!("{currency}-PERPETUAL"),
+        price_index: Some(format!("{}_usd", currency.to_lowercase())),
+        kind: Some(InstrumentKind::Future),
+        currency: Some(currency.clone()),
+        is_active: Some(true),
+        expiration_timestamp: None,
+        strike: None,
+        option_type: None,
+        tick_size: Some(0.5),
+        tick_size_steps: None,
+        min_trade_amount: Some(10.0),
+        contract_size: Some(10.0),
+        settlement_period: Some("perpetual".to_string()),
+        instrument_type: Some(InstrumentType::Reversed),
+        quote_currency: Some("USD".to_string()),
+        settlement_currency: Some(currency.clone()),
+        creation_timestamp: Some(1_534_242_287_000),
+        max_leverage: Some(50.0),
+        maker_commission: Some(0.0),
+        taker_commission: Some(0.0005),
+        instrument_id: None,
+        base_currency: Some(currency),
+        counter_currency: Some("USD".to_string()),
+        block_trade_commission: None,
+        block_trade_min_trade_amount: None,
+        block_trade_tick_size: None,
+        max_liquidation_commission: None,
+        rfq: None,
+    }
+}
+
+/// Open ticker quoting `best_bid` and `best_ask`, marked at the mid price
+#[must_use]
+pub fn ticker(instrument_name: &str, best_bid: f64, best_ask: f64, timestamp: u64) -> TickerData {
+    let mark_price = (best_bid + best_ask) / 2.0;
+    TickerData {
+        instrument_name: instrument_name.to_string(),
+        last_price: Some(mark_price),
+        mark_price,
+        best_bid_price: Some(best_bid),
+        best_ask_price: Some(best_ask),
+        best_bid_amount: 10_000.0,
+        best_ask_amount: 10_000.0,
+        volume: None,
+        volume_usd: None,
+        open_interest: None,
+        high: None,
+        low: None,
+        price_change: None,
+        price_change_percentage: None,
+        bid_iv: None,
+        ask_iv: None,
+        mark_iv: None,
+        timestamp,
+        state: "open".to_string(),
+        settlement_price: None,
+        stats: TickerStats {
+            volume: 0.0,
+            volume_usd: None,
+            price_change: None,
+            high: None,
+            low: None,
+        },
+        greeks: None,
+        index_price: Some(mark_price),
+        min_price: Some(best_bid * 0.95),
+        max_price: Some(best_ask * 1.05),
+        interest_rate: None,
+        underlying_price: None,
+        underlying_index: None,
+        estimated_delivery_price: Some(mark_price),
+        current_funding: None,
+        funding_8h: None,
+        interest_value: None,
+    }
+}
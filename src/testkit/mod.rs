@@ -0,0 +1,26 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Offline test kit for Deribit clients
+//!
+//! Enabled with the `testkit` feature. [`MockExchange`] answers JSON-RPC
+//! frames the way Deribit does, so client crates can run integration tests
+//! without a network connection:
+//!
+//! ```rust
+//! use deribit_base::testkit::MockExchange;
+//!
+//! let mut exchange = MockExchange::with_default_market();
+//! let response = exchange.handle_frame(
+//!     r#"{"jsonrpc":"2.0","id":1,"method":"public/ticker","params":{"instrument_name":"BTC-PERPETUAL"}}"#,
+//! );
+//! assert!(response.contains("\"best_bid_price\":60000.0"));
+//! ```
+
+pub mod exchange;
+pub mod fixtures;
+
+pub use exchange::{MOCK_DEFAULT_SCOPE, MOCK_START_TIME_MS, MockExchange};
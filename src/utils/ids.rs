@@ -0,0 +1,129 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Monotonic identifier generation
+//!
+//! Client order ids, request ids and label sequence numbers must never
+//! repeat, even when several are created within the same millisecond.
+//! [`MonotonicIdGenerator`] hands out strictly increasing `u64` values that
+//! stay close to the wall clock (microsecond resolution), so ids also sort
+//! by creation time and survive process restarts without a persisted counter.
+
+use crate::utils::tools::generate_timestamp;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of ids available per millisecond before the generator runs ahead
+/// of the wall clock
+pub const IDS_PER_MILLISECOND: u64 = 1000;
+
+/// Thread-safe generator of strictly increasing identifiers
+///
+/// Each id is at least `now_ms * IDS_PER_MILLISECOND` and always greater
+/// than the previously issued id.
+#[derive(Debug, Default)]
+pub struct MonotonicIdGenerator {
+    last: AtomicU64,
+}
+
+impl MonotonicIdGenerator {
+    /// Create a new generator
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the next identifier based on the current time
+    pub fn next_id(&self) -> u64 {
+        self.next_id_at(generate_timestamp())
+    }
+
+    /// Get the next identifier for the given time in milliseconds
+    ///
+    /// Useful for deterministic tests with an injected clock.
+    pub fn next_id_at(&self, now_ms: u64) -> u64 {
+        let floor = now_ms.saturating_mul(IDS_PER_MILLISECOND);
+        let previous = self
+            .last
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| {
+                Some(floor.max(last.saturating_add(1)))
+            })
+            .unwrap_or_else(|last| last);
+        floor.max(previous.saturating_add(1))
+    }
+
+    /// Get the last issued identifier (0 if none)
+    pub fn last_id(&self) -> u64 {
+        self.last.load(Ordering::Acquire)
+    }
+}
+
+/// Process-wide generator used by the free functions in this module
+static GLOBAL_ID_GENERATOR: MonotonicIdGenerator = MonotonicIdGenerator::new();
+
+/// Get the next process-wide monotonic identifier
+pub fn next_monotonic_id() -> u64 {
+    GLOBAL_ID_GENERATOR.next_id()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_ids_strictly_increase_within_same_millisecond() {
+        let generator = MonotonicIdGenerator::new();
+        let first = generator.next_id_at(1_700_000_000_000);
+        let second = generator.next_id_at(1_700_000_000_000);
+        let third = generator.next_id_at(1_700_000_000_000);
+        assert_eq!(first, 1_700_000_000_000 * IDS_PER_MILLISECOND);
+        assert_eq!(second, first + 1);
+        assert_eq!(third, first + 2);
+        assert_eq!(generator.last_id(), third);
+    }
+
+    #[test]
+    fn test_ids_follow_clock_and_ignore_clock_going_back() {
+        let generator = MonotonicIdGenerator::new();
+        let first = generator.next_id_at(2_000);
+        let later = generator.next_id_at(3_000);
+        assert_eq!(later, 3_000 * IDS_PER_MILLISECOND);
+
+        let after_clock_jump_back = generator.next_id_at(1_000);
+        assert!(after_clock_jump_back > later);
+        assert!(later > first);
+    }
+
+    #[test]
+    fn test_ids_unique_across_threads() {
+        let generator = Arc::new(MonotonicIdGenerator::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                std::thread::spawn(move || {
+                    (0..500).map(|_| generator.next_id()).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut ids = HashSet::new();
+        for handle in handles {
+            for id in handle.join().unwrap() {
+                assert!(ids.insert(id));
+            }
+        }
+        assert_eq!(ids.len(), 2000);
+    }
+
+    #[test]
+    fn test_global_generator() {
+        let a = next_monotonic_id();
+        let b = next_monotonic_id();
+        assert!(b > a);
+    }
+}
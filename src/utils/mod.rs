@@ -3,7 +3,13 @@
 //! This module contains various utility functions and helpers used across
 //! all Deribit API client implementations (FIX, HTTP REST, WebSocket).
 
+pub mod ids;
 /// Logging configuration and setup
 pub mod logger;
+pub mod rate_limit;
+pub mod retry;
+/// Deribit signature credentials
+pub mod signature;
+pub mod token;
 /// Common utility functions and constants
 pub(crate) mod tools;
@@ -0,0 +1,485 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Credit-based rate limiting
+//!
+//! Deribit charges every request a number of credits from a pool that refills
+//! at a constant rate; the pool size sets the burst capacity. Order entry
+//! methods handled by the matching engine draw from their own pool, separate
+//! from every other method.
+//!
+//! [`CreditRateLimiter`] mirrors that model on the client side. It does no I/O
+//! and never sleeps: callers ask it how long to wait before sending, which
+//! makes it usable from any transport (HTTP, WebSocket, FIX) and any runtime.
+//! All methods have an `_at` variant taking the current time in milliseconds
+//! for deterministic tests.
+
+use crate::error::codes::DeribitErrorCode;
+use crate::utils::tools::generate_timestamp;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default credit cost of a request
+pub const DEFAULT_REQUEST_COST: u64 = 500;
+
+/// Default non-matching engine pool size (burst of 100 requests)
+pub const DEFAULT_NON_MATCHING_ENGINE_MAX_CREDITS: u64 = 50_000;
+
+/// Default non-matching engine refill rate (20 requests per second)
+pub const DEFAULT_NON_MATCHING_ENGINE_REFILL_PER_SECOND: u64 = 10_000;
+
+/// Default matching engine pool size (burst of 20 requests)
+pub const DEFAULT_MATCHING_ENGINE_MAX_CREDITS: u64 = 10_000;
+
+/// Default matching engine refill rate (5 requests per second)
+pub const DEFAULT_MATCHING_ENGINE_REFILL_PER_SECOND: u64 = 2_500;
+
+/// Methods handled by the matching engine
+pub const MATCHING_ENGINE_METHODS: &[&str] = &[
+    "private/buy",
+    "private/sell",
+    "private/edit",
+    "private/edit_by_label",
+    "private/cancel",
+    "private/cancel_by_label",
+    "private/cancel_all",
+    "private/cancel_all_by_instrument",
+    "private/cancel_all_by_currency",
+    "private/cancel_all_by_currency_pair",
+    "private/cancel_all_by_kind_or_type",
+    "private/cancel_quotes",
+    "private/close_position",
+    "private/mass_quote",
+    "private/verify_block_trade",
+    "private/execute_block_trade",
+    "private/move_positions",
+];
+
+/// Credit pool a method draws from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitBucket {
+    /// Order entry methods processed by the matching engine
+    MatchingEngine,
+    /// Every other method
+    NonMatchingEngine,
+}
+
+impl RateLimitBucket {
+    /// Get the bucket for a method name, with or without leading slash
+    #[must_use]
+    pub fn for_method(method: &str) -> Self {
+        let method = method.trim_start_matches('/');
+        if MATCHING_ENGINE_METHODS.contains(&method) {
+            Self::MatchingEngine
+        } else {
+            Self::NonMatchingEngine
+        }
+    }
+
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MatchingEngine => "matching_engine",
+            Self::NonMatchingEngine => "non_matching_engine",
+        }
+    }
+}
+
+impl std::fmt::Display for RateLimitBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Size and refill rate of a credit pool
+#[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreditPool {
+    /// Maximum credits, i.e. the burst capacity
+    pub max_credits: u64,
+    /// Credits added per second
+    pub refill_per_second: u64,
+}
+
+impl CreditPool {
+    /// Create a new credit pool
+    #[must_use]
+    pub fn new(max_credits: u64, refill_per_second: u64) -> Self {
+        Self {
+            max_credits,
+            refill_per_second,
+        }
+    }
+}
+
+/// Rate limiter configuration
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Pool for matching engine methods
+    pub matching_engine: CreditPool,
+    /// Pool for all other methods
+    pub non_matching_engine: CreditPool,
+    /// Cost of methods without an entry in `method_costs`
+    pub default_cost: u64,
+    /// Per-method cost overrides, keyed by method name without leading slash
+    pub method_costs: HashMap<String, u64>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            matching_engine: CreditPool::new(
+                DEFAULT_MATCHING_ENGINE_MAX_CREDITS,
+                DEFAULT_MATCHING_ENGINE_REFILL_PER_SECOND,
+            ),
+            non_matching_engine: CreditPool::new(
+                DEFAULT_NON_MATCHING_ENGINE_MAX_CREDITS,
+                DEFAULT_NON_MATCHING_ENGINE_REFILL_PER_SECOND,
+            ),
+            default_cost: DEFAULT_REQUEST_COST,
+            method_costs: HashMap::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Create a configuration with Deribit's default limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a configuration from a plain requests-per-second limit
+    ///
+    /// Bridges `DeribitConfig::rate_limit`: the non-matching engine pool
+    /// refills `requests_per_second` requests per second with a one second
+    /// burst; the matching engine pool keeps its default.
+    #[must_use]
+    pub fn from_requests_per_second(requests_per_second: u32) -> Self {
+        let credits = u64::from(requests_per_second) * DEFAULT_REQUEST_COST;
+        Self {
+            non_matching_engine: CreditPool::new(credits, credits),
+            ..Self::default()
+        }
+    }
+
+    /// Set the matching engine pool
+    #[must_use]
+    pub fn with_matching_engine(mut self, pool: CreditPool) -> Self {
+        self.matching_engine = pool;
+        self
+    }
+
+    /// Set the non-matching engine pool
+    #[must_use]
+    pub fn with_non_matching_engine(mut self, pool: CreditPool) -> Self {
+        self.non_matching_engine = pool;
+        self
+    }
+
+    /// Set the cost of a method
+    #[must_use]
+    pub fn with_method_cost(mut self, method: &str, cost: u64) -> Self {
+        self.method_costs
+            .insert(method.trim_start_matches('/').to_string(), cost);
+        self
+    }
+
+    /// Get the credit cost of a method
+    #[must_use]
+    pub fn method_cost(&self, method: &str) -> u64 {
+        self.method_costs
+            .get(method.trim_start_matches('/'))
+            .copied()
+            .unwrap_or(self.default_cost)
+    }
+
+    /// Get the cost charged to a bucket, capped at the pool size
+    ///
+    /// A method costing more than a full pool would otherwise never be sent.
+    fn charged_cost(&self, method: &str, bucket: RateLimitBucket) -> f64 {
+        self.method_cost(method).min(self.pool(bucket).max_credits) as f64
+    }
+
+    /// Get the pool of a bucket
+    #[must_use]
+    pub fn pool(&self, bucket: RateLimitBucket) -> CreditPool {
+        match bucket {
+            RateLimitBucket::MatchingEngine => self.matching_engine,
+            RateLimitBucket::NonMatchingEngine => self.non_matching_engine,
+        }
+    }
+}
+
+/// Credits available in one pool at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PoolState {
+    credits: f64,
+    updated_ms: Option<u64>,
+}
+
+impl PoolState {
+    /// Refill the pool up to `now_ms`
+    fn refill(&mut self, pool: CreditPool, now_ms: u64) {
+        let elapsed_ms = match self.updated_ms {
+            Some(updated_ms) => now_ms.saturating_sub(updated_ms),
+            None => {
+                self.credits = pool.max_credits as f64;
+                0
+            }
+        };
+        let refilled = elapsed_ms as f64 * pool.refill_per_second as f64 / 1000.0;
+        self.credits = (self.credits + refilled).min(pool.max_credits as f64);
+        self.updated_ms = Some(self.updated_ms.map_or(now_ms, |u| u.max(now_ms)));
+    }
+}
+
+/// Client-side credit rate limiter
+///
+/// Not synchronized: share it behind a `Mutex` when several tasks send
+/// requests over the same connection.
+#[derive(Debug, Clone)]
+pub struct CreditRateLimiter {
+    config: RateLimitConfig,
+    matching_engine: PoolState,
+    non_matching_engine: PoolState,
+}
+
+impl Default for CreditRateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+impl CreditRateLimiter {
+    /// Create a limiter with full pools
+    #[must_use]
+    pub fn new(config: RateLimitConfig) -> Self {
+        let empty = PoolState {
+            credits: 0.0,
+            updated_ms: None,
+        };
+        Self {
+            config,
+            matching_engine: empty,
+            non_matching_engine: empty,
+        }
+    }
+
+    /// Get the configuration
+    #[must_use]
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Reserve credits for a method, or get how long to wait first
+    pub fn try_acquire(&mut self, method: &str) -> Result<(), Duration> {
+        self.try_acquire_at(method, generate_timestamp())
+    }
+
+    /// Reserve credits for a method at the given time in milliseconds
+    ///
+    /// Nothing is reserved when a wait is returned.
+    pub fn try_acquire_at(&mut self, method: &str, now_ms: u64) -> Result<(), Duration> {
+        let bucket = RateLimitBucket::for_method(method);
+        let cost = self.config.charged_cost(method, bucket);
+        let pool = self.config.pool(bucket);
+        let state = self.state_mut(bucket);
+        state.refill(pool, now_ms);
+        if state.credits >= cost {
+            state.credits -= cost;
+            Ok(())
+        } else {
+            Err(wait_for(cost - state.credits, pool))
+        }
+    }
+
+    /// Get how long to wait before a method can be sent
+    pub fn wait_time(&mut self, method: &str) -> Duration {
+        self.wait_time_at(method, generate_timestamp())
+    }
+
+    /// Get how long to wait before a method can be sent, at the given time
+    pub fn wait_time_at(&mut self, method: &str, now_ms: u64) -> Duration {
+        let bucket = RateLimitBucket::for_method(method);
+        let cost = self.config.charged_cost(method, bucket);
+        let pool = self.config.pool(bucket);
+        let state = self.state_mut(bucket);
+        state.refill(pool, now_ms);
+        wait_for(cost - state.credits, pool)
+    }
+
+    /// Get the credits currently available in a bucket
+    pub fn available_credits_at(&mut self, bucket: RateLimitBucket, now_ms: u64) -> u64 {
+        let pool = self.config.pool(bucket);
+        let state = self.state_mut(bucket);
+        state.refill(pool, now_ms);
+        state.credits as u64
+    }
+
+    /// Drain the bucket of a method after the server rejected it for rate limiting
+    ///
+    /// The server's view of our credits is authoritative, so the next request
+    /// waits for a full refill of its cost.
+    pub fn record_too_many_requests(&mut self, method: &str) {
+        self.record_too_many_requests_at(method, generate_timestamp());
+    }
+
+    /// Drain the bucket of a method at the given time
+    pub fn record_too_many_requests_at(&mut self, method: &str, now_ms: u64) {
+        let bucket = RateLimitBucket::for_method(method);
+        let pool = self.config.pool(bucket);
+        let state = self.state_mut(bucket);
+        state.refill(pool, now_ms);
+        state.credits = 0.0;
+    }
+
+    /// React to an error code returned for a method
+    ///
+    /// Returns `true` if the error was a rate limit error and the bucket was drained.
+    pub fn record_error_at(&mut self, method: &str, code: &DeribitErrorCode, now_ms: u64) -> bool {
+        if code.is_rate_limit_error() {
+            self.record_too_many_requests_at(method, now_ms);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn state_mut(&mut self, bucket: RateLimitBucket) -> &mut PoolState {
+        match bucket {
+            RateLimitBucket::MatchingEngine => &mut self.matching_engine,
+            RateLimitBucket::NonMatchingEngine => &mut self.non_matching_engine,
+        }
+    }
+}
+
+/// Time needed to refill `missing` credits
+fn wait_for(missing: f64, pool: CreditPool) -> Duration {
+    if missing <= 0.0 {
+        return Duration::ZERO;
+    }
+    if pool.refill_per_second == 0 {
+        return Duration::MAX;
+    }
+    let ms = (missing * 1000.0 / pool.refill_per_second as f64).ceil();
+    Duration::from_millis(ms as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_buckets_and_costs() {
+        assert_eq!(
+            RateLimitBucket::for_method("/private/buy"),
+            RateLimitBucket::MatchingEngine
+        );
+        assert_eq!(
+            RateLimitBucket::for_method("public/get_instruments"),
+            RateLimitBucket::NonMatchingEngine
+        );
+        let config = RateLimitConfig::new().with_method_cost("/public/get_instruments", 10_000);
+        assert_eq!(config.method_cost("public/get_instruments"), 10_000);
+        assert_eq!(config.method_cost("public/ticker"), DEFAULT_REQUEST_COST);
+    }
+
+    #[test]
+    fn test_method_cost_overrides() {
+        let config = RateLimitConfig::new();
+        assert_eq!(config.method_cost("private/buy"), DEFAULT_REQUEST_COST);
+        assert_eq!(config.method_cost("/private/cancel"), DEFAULT_REQUEST_COST);
+
+        let config = config
+            .with_method_cost("private/cancel", 250)
+            .with_method_cost("public/get_instruments", 10_000);
+        let mut limiter = CreditRateLimiter::new(config);
+        // 10_000 matching engine credits: 40 cancels at the overridden cost
+        for _ in 0..40 {
+            assert!(limiter.try_acquire_at("private/cancel", 0).is_ok());
+        }
+        assert!(limiter.try_acquire_at("private/cancel", 0).is_err());
+        assert_eq!(
+            limiter.wait_time_at("private/buy", 0),
+            Duration::from_millis(200)
+        );
+
+        // Costs above the pool size are capped so the method can still be sent
+        let config = RateLimitConfig::from_requests_per_second(2)
+            .with_method_cost("public/get_instruments", 10_000);
+        let mut small = CreditRateLimiter::new(config);
+        assert!(small.try_acquire_at("public/get_instruments", 0).is_ok());
+        assert_eq!(
+            small.wait_time_at("public/get_instruments", 0),
+            Duration::from_millis(1_000)
+        );
+    }
+
+    #[test]
+    fn test_burst_then_refill() {
+        let mut limiter = CreditRateLimiter::default();
+        for _ in 0..20 {
+            assert!(limiter.try_acquire_at("private/buy", 1_000).is_ok());
+        }
+        assert_eq!(
+            limiter.try_acquire_at("private/buy", 1_000),
+            Err(Duration::from_millis(200))
+        );
+        // Separate pool is untouched
+        assert!(limiter.try_acquire_at("public/ticker", 1_000).is_ok());
+
+        assert_eq!(
+            limiter.wait_time_at("private/buy", 1_100),
+            Duration::from_millis(100)
+        );
+        assert!(limiter.try_acquire_at("private/buy", 1_200).is_ok());
+        assert!(limiter.try_acquire_at("private/buy", 1_200).is_err());
+    }
+
+    #[test]
+    fn test_refill_is_capped_and_clock_going_back_is_ignored() {
+        let mut limiter = CreditRateLimiter::default();
+        assert!(limiter.try_acquire_at("public/ticker", 1_000).is_ok());
+        assert_eq!(
+            limiter.available_credits_at(RateLimitBucket::NonMatchingEngine, 60_000),
+            DEFAULT_NON_MATCHING_ENGINE_MAX_CREDITS
+        );
+        assert!(limiter.try_acquire_at("public/ticker", 60_000).is_ok());
+        assert_eq!(
+            limiter.available_credits_at(RateLimitBucket::NonMatchingEngine, 10_000),
+            DEFAULT_NON_MATCHING_ENGINE_MAX_CREDITS - DEFAULT_REQUEST_COST
+        );
+    }
+
+    #[test]
+    fn test_too_many_requests_drains_bucket() {
+        let mut limiter = CreditRateLimiter::default();
+        assert!(!limiter.record_error_at("public/ticker", &DeribitErrorCode::OrderNotFound, 0));
+        assert!(limiter.record_error_at("public/ticker", &DeribitErrorCode::TooManyRequests, 0));
+        assert_eq!(
+            limiter.wait_time_at("public/ticker", 0),
+            Duration::from_millis(50)
+        );
+        assert!(limiter.try_acquire_at("public/ticker", 50).is_ok());
+    }
+
+    #[test]
+    fn test_from_requests_per_second() {
+        let config = RateLimitConfig::from_requests_per_second(2);
+        let mut limiter = CreditRateLimiter::new(config);
+        assert!(limiter.try_acquire_at("public/ticker", 0).is_ok());
+        assert!(limiter.try_acquire_at("public/ticker", 0).is_ok());
+        assert_eq!(
+            limiter.try_acquire_at("public/ticker", 0),
+            Err(Duration::from_millis(500))
+        );
+    }
+}
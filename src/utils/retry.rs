@@ -0,0 +1,527 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Retry and backoff policy
+//!
+//! [`RetryAction`] classifies every [`DeribitError`] as retry now, back off,
+//! re-authenticate or fail. [`RetryPolicy`] turns that classification into a
+//! [`RetryDecision`] for one failed attempt: exponential backoff with jitter,
+//! never shorter than the `wait` the server asked for, bounded by a number of
+//! retries.
+//!
+//! Retries are idempotency-aware. When a request may have executed before
+//! it failed (a timeout, a dropped connection or an internal server error),
+//! it is only sent again if the method is idempotent. Order entry with a
+//! label yields [`RetryDecision::VerifyByLabel`], so the caller looks the
+//! order up with `private/get_order_state_by_label` first; other
+//! non-idempotent methods fail. Like the other helpers in this module, the
+//! policy does no I/O and never sleeps.
+
+use crate::constants::{MAX_RETRY_ATTEMPTS, RETRY_BASE_DELAY_MS, RETRY_MAX_DELAY_MS};
+use crate::error::codes::DeribitErrorCode;
+use crate::error::types::DeribitError;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rand::{RngExt, rng};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Default share of the backoff delay that is randomized
+pub const DEFAULT_RETRY_JITTER: f64 = 0.5;
+
+/// Order entry methods whose requests can be found again by label
+pub const LABEL_DEDUP_METHODS: &[&str] = &["private/buy", "private/sell"];
+
+/// Name prefixes of methods that can be sent twice: public methods, private
+/// reads, cancels, edits and simulations
+pub const IDEMPOTENT_METHOD_PREFIXES: &[&str] = &[
+    "public/",
+    "private/get_",
+    "private/list_",
+    "private/cancel",
+    "private/edit",
+    "private/simulate_",
+];
+
+/// Other private methods that can be sent twice
+pub const IDEMPOTENT_METHODS: &[&str] = &[
+    "private/disable_cancel_on_disconnect",
+    "private/enable_cancel_on_disconnect",
+    "private/logout",
+    "private/pme/simulate",
+    "private/subscribe",
+    "private/unsubscribe",
+    "private/unsubscribe_all",
+    "private/verify_block_trade",
+];
+
+/// Method that authenticates the session
+const AUTH_METHOD: &str = "public/auth";
+
+/// Whether sending a method twice has the same effect as sending it once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Idempotency {
+    /// Reads, cancels, edits and subscriptions
+    Idempotent,
+    /// Order entry, safe to resend once no order with its label exists
+    LabelDedup,
+    /// Every other private method, e.g. transfers, withdrawals and RFQs
+    NonIdempotent,
+}
+
+impl Idempotency {
+    /// Get the idempotency of a method name, with or without leading slash
+    ///
+    /// Private methods are non-idempotent unless listed in
+    /// [`IDEMPOTENT_METHODS`] or matching [`IDEMPOTENT_METHOD_PREFIXES`].
+    #[must_use]
+    pub fn for_method(method: &str) -> Self {
+        let method = method.trim_start_matches('/');
+        if LABEL_DEDUP_METHODS.contains(&method) {
+            Self::LabelDedup
+        } else if IDEMPOTENT_METHODS.contains(&method)
+            || IDEMPOTENT_METHOD_PREFIXES
+                .iter()
+                .any(|prefix| method.starts_with(prefix))
+        {
+            Self::Idempotent
+        } else {
+            Self::NonIdempotent
+        }
+    }
+
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idempotent => "idempotent",
+            Self::LabelDedup => "label_dedup",
+            Self::NonIdempotent => "non_idempotent",
+        }
+    }
+}
+
+impl std::fmt::Display for Idempotency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How to react to an error, regardless of the method that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryAction {
+    /// Send the request again right away
+    Retry,
+    /// Send the request again after an exponential backoff
+    Backoff,
+    /// Authenticate again, then send the request again
+    Reauthenticate,
+    /// Surface the error
+    Fail,
+}
+
+impl RetryAction {
+    /// Classify an API error code
+    #[must_use]
+    pub fn for_code(code: &DeribitErrorCode) -> Self {
+        if code.is_authorization_error() {
+            Self::Reauthenticate
+        } else if matches!(code, DeribitErrorCode::Retry) {
+            Self::Retry
+        } else if code.is_transient() {
+            Self::Backoff
+        } else {
+            Self::Fail
+        }
+    }
+
+    /// Classify an error
+    ///
+    /// Timeouts are retried right away and connection errors back off while
+    /// the transport reconnects. Authentication, configuration and
+    /// serialization errors do not go away by resending.
+    #[must_use]
+    pub fn for_error(error: &DeribitError) -> Self {
+        match error {
+            DeribitError::Api(error) => Self::for_code(&error.code),
+            DeribitError::Timeout => Self::Retry,
+            DeribitError::Connection(_) => Self::Backoff,
+            _ => Self::Fail,
+        }
+    }
+
+    /// Get the string representation
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Retry => "retry",
+            Self::Backoff => "backoff",
+            Self::Reauthenticate => "reauthenticate",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+impl std::fmt::Display for RetryAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// What to do after a failed attempt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Send the request again after `delay`
+    Retry {
+        /// Time to wait before sending
+        delay: Duration,
+    },
+    /// Authenticate again, then send the request again
+    Reauthenticate,
+    /// Look the order up by label and send it again only if none exists
+    ///
+    /// The request may have executed before it failed. Query
+    /// `private/get_order_state_by_label`: if an order with `label` is
+    /// returned, the request went through and must not be resent.
+    VerifyByLabel {
+        /// Label of the order to look up
+        label: String,
+        /// Time to wait before sending again
+        delay: Duration,
+    },
+    /// Surface the error
+    Fail,
+}
+
+impl RetryDecision {
+    /// Check if the request may be sent again
+    #[must_use]
+    pub fn is_retry(&self) -> bool {
+        !matches!(self, Self::Fail)
+    }
+
+    /// Get the delay before sending again, if any
+    #[must_use]
+    pub fn delay(&self) -> Option<Duration> {
+        match self {
+            Self::Retry { delay } | Self::VerifyByLabel { delay, .. } => Some(*delay),
+            Self::Reauthenticate => Some(Duration::ZERO),
+            Self::Fail => None,
+        }
+    }
+}
+
+/// Retry policy configuration
+#[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Backoff delay of the first retry in milliseconds
+    pub base_delay_ms: u64,
+    /// Maximum backoff delay in milliseconds, unless the server asks for more
+    pub max_delay_ms: u64,
+    /// Share of the backoff delay that is randomized, from 0.0 to 1.0
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: u32::from(MAX_RETRY_ATTEMPTS),
+            base_delay_ms: RETRY_BASE_DELAY_MS,
+            max_delay_ms: RETRY_MAX_DELAY_MS,
+            jitter: DEFAULT_RETRY_JITTER,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy with the default limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy from a number of retries
+    ///
+    /// Bridges `DeribitConfig::max_retries`, keeping the default backoff.
+    #[must_use]
+    pub fn from_max_retries(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of retries after the first attempt
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the base and maximum backoff delays in milliseconds
+    #[must_use]
+    pub fn with_backoff(mut self, base_delay_ms: u64, max_delay_ms: u64) -> Self {
+        self.base_delay_ms = base_delay_ms;
+        self.max_delay_ms = max_delay_ms;
+        self
+    }
+
+    /// Set the share of the backoff delay that is randomized
+    ///
+    /// Clamped to `0.0..=1.0`; `0.0` makes the backoff deterministic.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Get the backoff delay before retry number `attempt`, counted from 0
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff_with(attempt, rng().random::<f64>())
+    }
+
+    /// Get the backoff delay with a jitter sample from `0.0..1.0`
+    ///
+    /// The delay doubles with every attempt up to `max_delay_ms`, then the
+    /// jitter share of it is scaled down by `sample`.
+    #[must_use]
+    pub fn backoff_with(&self, attempt: u32, sample: f64) -> Duration {
+        let factor = 2u64.saturating_pow(attempt);
+        let delay_ms = self
+            .base_delay_ms
+            .saturating_mul(factor)
+            .min(self.max_delay_ms);
+        let jitter = self.jitter.clamp(0.0, 1.0) * sample.clamp(0.0, 1.0);
+        let delay_ms = delay_ms as f64 * (1.0 - jitter);
+        Duration::from_millis(delay_ms.round() as u64)
+    }
+
+    /// Decide what to do after attempt number `attempt` (counted from 0) failed
+    ///
+    /// `label` is the label sent with the request, if any.
+    pub fn decide(
+        &self,
+        method: &str,
+        label: Option<&str>,
+        attempt: u32,
+        error: &DeribitError,
+    ) -> RetryDecision {
+        self.decide_with(method, label, attempt, error, rng().random::<f64>())
+    }
+
+    /// Decide what to do after a failed attempt, with a jitter sample
+    pub fn decide_with(
+        &self,
+        method: &str,
+        label: Option<&str>,
+        attempt: u32,
+        error: &DeribitError,
+        sample: f64,
+    ) -> RetryDecision {
+        if attempt >= self.max_retries {
+            return RetryDecision::Fail;
+        }
+        let delay = match RetryAction::for_error(error) {
+            RetryAction::Fail => return RetryDecision::Fail,
+            RetryAction::Reauthenticate if method.trim_start_matches('/') == AUTH_METHOD => {
+                return RetryDecision::Fail;
+            }
+            RetryAction::Reauthenticate => return RetryDecision::Reauthenticate,
+            RetryAction::Retry => Duration::ZERO,
+            RetryAction::Backoff => self.backoff_with(attempt, sample),
+        };
+        let delay = error.retry_after().map_or(delay, |wait| wait.max(delay));
+        if !may_have_executed(error) {
+            return RetryDecision::Retry { delay };
+        }
+        match (Idempotency::for_method(method), label) {
+            (Idempotency::Idempotent, _) => RetryDecision::Retry { delay },
+            (Idempotency::LabelDedup, Some(label)) if !label.is_empty() => {
+                RetryDecision::VerifyByLabel {
+                    label: label.to_string(),
+                    delay,
+                }
+            }
+            _ => RetryDecision::Fail,
+        }
+    }
+}
+
+/// Whether a failed request may still have been executed by the server
+fn may_have_executed(error: &DeribitError) -> bool {
+    match error {
+        DeribitError::Timeout | DeribitError::Connection(_) => true,
+        DeribitError::Api(error) => matches!(
+            error.code,
+            DeribitErrorCode::InternalServerError | DeribitErrorCode::TemporarilyUnavailable
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::types::ApiError;
+
+    fn api(code: i32) -> DeribitError {
+        ApiError::from(DeribitErrorCode::from(code)).into()
+    }
+
+    #[test]
+    fn test_error_classification() {
+        assert_eq!(RetryAction::for_error(&api(10028)), RetryAction::Backoff);
+        assert_eq!(RetryAction::for_error(&api(10040)), RetryAction::Retry);
+        assert_eq!(
+            RetryAction::for_error(&api(13009)),
+            RetryAction::Reauthenticate
+        );
+        assert_eq!(RetryAction::for_error(&api(10009)), RetryAction::Fail);
+        assert_eq!(
+            RetryAction::for_error(&DeribitError::Timeout),
+            RetryAction::Retry
+        );
+        assert_eq!(
+            RetryAction::for_error(&DeribitError::Connection("reset".to_string())),
+            RetryAction::Backoff
+        );
+        assert_eq!(
+            RetryAction::for_error(&DeribitError::Serialization("bad".to_string())),
+            RetryAction::Fail
+        );
+        assert_eq!(
+            Idempotency::for_method("/private/buy"),
+            Idempotency::LabelDedup
+        );
+        assert_eq!(
+            Idempotency::for_method("private/withdraw"),
+            Idempotency::NonIdempotent
+        );
+        assert_eq!(
+            Idempotency::for_method("private/cancel"),
+            Idempotency::Idempotent
+        );
+        for method in [
+            "public/ticker",
+            "private/get_positions",
+            "private/edit_by_label",
+        ] {
+            assert_eq!(Idempotency::for_method(method), Idempotency::Idempotent);
+        }
+        for method in [
+            "private/send_rfq",
+            "private/add_to_address_book",
+            "private/unknown",
+        ] {
+            assert_eq!(Idempotency::for_method(method), Idempotency::NonIdempotent);
+        }
+    }
+
+    #[test]
+    fn test_exponential_backoff_with_jitter() {
+        let policy = RetryPolicy::new();
+        assert_eq!(policy.backoff_with(0, 0.0), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff_with(2, 0.0), Duration::from_millis(4_000));
+        assert_eq!(policy.backoff_with(2, 1.0), Duration::from_millis(2_000));
+        assert_eq!(policy.backoff_with(10, 0.0), Duration::from_millis(30_000));
+        assert_eq!(
+            policy.backoff_with(u32::MAX, 0.0),
+            Duration::from_millis(30_000)
+        );
+        for attempt in 0..5 {
+            let delay = policy.backoff(attempt);
+            let ceiling = policy.backoff_with(attempt, 0.0);
+            assert!(delay <= ceiling && delay >= ceiling / 2);
+        }
+        let fixed = RetryPolicy::new().with_jitter(0.0).with_backoff(100, 250);
+        assert_eq!(fixed.backoff(1), Duration::from_millis(200));
+        assert_eq!(fixed.backoff(2), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_decisions_respect_limits_and_wait_hints() {
+        let policy = RetryPolicy::from_max_retries(2).with_jitter(0.0);
+        let rate_limited = api(10028);
+        assert_eq!(
+            policy.decide("public/ticker", None, 1, &rate_limited),
+            RetryDecision::Retry {
+                delay: Duration::from_millis(2_000)
+            }
+        );
+        assert_eq!(
+            policy.decide("public/ticker", None, 2, &rate_limited),
+            RetryDecision::Fail
+        );
+
+        let over_limit: DeribitError = ApiError::from(DeribitErrorCode::MovePositionsOverLimit)
+            .with_data(serde_json::json!({"wait": 45}))
+            .into();
+        let decision = policy.decide("private/move_positions", None, 0, &over_limit);
+        assert_eq!(
+            decision,
+            RetryDecision::Retry {
+                delay: Duration::from_secs(45)
+            }
+        );
+        assert_eq!(decision.delay(), Some(Duration::from_secs(45)));
+
+        assert_eq!(
+            policy.decide("private/get_positions", None, 0, &api(13009)),
+            RetryDecision::Reauthenticate
+        );
+        assert_eq!(
+            policy.decide("public/auth", None, 0, &api(13009)),
+            RetryDecision::Fail
+        );
+        assert!(
+            !policy
+                .decide("private/buy", Some("a"), 0, &api(10009))
+                .is_retry()
+        );
+    }
+
+    #[test]
+    fn test_order_entry_is_not_blindly_retried() {
+        let policy = RetryPolicy::new().with_jitter(0.0);
+        let timeout = DeribitError::Timeout;
+        assert_eq!(
+            policy.decide("private/buy", Some("mm:0:1"), 0, &timeout),
+            RetryDecision::VerifyByLabel {
+                label: "mm:0:1".to_string(),
+                delay: Duration::ZERO
+            }
+        );
+        assert_eq!(
+            policy.decide("private/sell", None, 0, &timeout),
+            RetryDecision::Fail
+        );
+        assert_eq!(
+            policy.decide("private/sell", Some(""), 0, &timeout),
+            RetryDecision::Fail
+        );
+        assert_eq!(
+            policy.decide("private/withdraw", None, 0, &api(11094)),
+            RetryDecision::Fail
+        );
+        assert_eq!(
+            policy.decide("public/get_time", None, 0, &timeout),
+            RetryDecision::Retry {
+                delay: Duration::ZERO
+            }
+        );
+        // Rejected before execution, so resending is safe
+        assert_eq!(
+            policy.decide("private/buy", None, 0, &api(10028)),
+            RetryDecision::Retry {
+                delay: Duration::from_millis(1_000)
+            }
+        );
+    }
+}
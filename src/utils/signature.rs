@@ -0,0 +1,266 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Deribit signature credentials
+//!
+//! Both the `client_signature` grant of `public/auth` and the
+//! `deri-hmac-sha256` REST `Authorization` header prove knowledge of the
+//! client secret with `HEX(HMAC-SHA256(secret, StringToSign))`, so the secret
+//! itself never leaves the process:
+//!
+//! - `client_signature`: `StringToSign = Timestamp\nNonce\nData`
+//! - REST header: `StringToSign = Timestamp\nNonce\nMETHOD\nURI\nBody\n`
+//!
+//! Timestamps are in milliseconds and are accepted by the server for 60 seconds.
+
+use crate::model::config::DeribitConfig;
+use crate::utils::tools::generate_timestamp;
+use hmac::{Hmac, Mac};
+use rand::{RngExt, rng};
+use sha2::Sha256;
+use std::fmt;
+
+/// Name of the HTTP header carrying the signature
+pub const AUTHORIZATION_HEADER: &str = "Authorization";
+
+/// Scheme of the `Authorization` header value
+pub const HMAC_AUTH_SCHEME: &str = "deri-hmac-sha256";
+
+/// Length of the nonces generated for signatures
+pub const SIGNATURE_NONCE_LENGTH: usize = 8;
+
+const NONCE_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Compute `HEX(HMAC-SHA256(secret, payload))`
+#[must_use]
+pub fn hmac_sha256_hex(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Signature for the `client_signature` grant of `public/auth`
+#[must_use]
+pub fn client_signature(client_secret: &str, timestamp: u64, nonce: &str, data: &str) -> String {
+    hmac_sha256_hex(client_secret, &format!("{timestamp}\n{nonce}\n{data}"))
+}
+
+/// Signature of a REST request for the `deri-hmac-sha256` header
+///
+/// `uri` is the path including the query string, e.g.
+/// `/api/v2/private/get_account_summary?currency=BTC`.
+#[must_use]
+pub fn request_signature(
+    client_secret: &str,
+    timestamp: u64,
+    nonce: &str,
+    method: &str,
+    uri: &str,
+    body: &str,
+) -> String {
+    let method = method.to_uppercase();
+    hmac_sha256_hex(
+        client_secret,
+        &format!("{timestamp}\n{nonce}\n{method}\n{uri}\n{body}\n"),
+    )
+}
+
+/// Generate a random lowercase alphanumeric nonce
+#[must_use]
+pub fn generate_signature_nonce() -> String {
+    let mut rng = rng();
+    (0..SIGNATURE_NONCE_LENGTH)
+        .map(|_| NONCE_CHARSET[rng.random_range(0..NONCE_CHARSET.len())] as char)
+        .collect()
+}
+
+/// Builder of `deri-hmac-sha256` `Authorization` headers for REST requests
+#[derive(Clone)]
+pub struct HmacAuthBuilder {
+    client_id: String,
+    client_secret: String,
+}
+
+impl HmacAuthBuilder {
+    /// Create a builder for the given credentials
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+        }
+    }
+
+    /// Create a builder from the configured credentials
+    #[must_use]
+    pub fn from_config(config: &DeribitConfig) -> Self {
+        Self::new(config.client_id.as_str(), config.client_secret.as_str())
+    }
+
+    /// Sign a request with the current time and a fresh nonce
+    #[must_use]
+    pub fn sign(&self, method: &str, uri: &str, body: &str) -> HmacAuthHeader {
+        self.sign_with(
+            generate_timestamp(),
+            &generate_signature_nonce(),
+            method,
+            uri,
+            body,
+        )
+    }
+
+    /// Sign a request with an explicit timestamp and nonce
+    #[must_use]
+    pub fn sign_with(
+        &self,
+        timestamp: u64,
+        nonce: &str,
+        method: &str,
+        uri: &str,
+        body: &str,
+    ) -> HmacAuthHeader {
+        HmacAuthHeader {
+            client_id: self.client_id.clone(),
+            timestamp,
+            nonce: nonce.to_string(),
+            signature: request_signature(&self.client_secret, timestamp, nonce, method, uri, body),
+        }
+    }
+}
+
+impl fmt::Debug for HmacAuthBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacAuthBuilder")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"***")
+            .finish()
+    }
+}
+
+/// A signed `deri-hmac-sha256` `Authorization` header
+///
+/// Displays as the header value:
+/// `deri-hmac-sha256 id=ClientId,ts=Timestamp,sig=Signature,nonce=Nonce`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HmacAuthHeader {
+    /// Client id
+    pub client_id: String,
+    /// Signing time in milliseconds
+    pub timestamp: u64,
+    /// Single use nonce
+    pub nonce: String,
+    /// Hex encoded signature
+    pub signature: String,
+}
+
+impl HmacAuthHeader {
+    /// Get the header name
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        AUTHORIZATION_HEADER
+    }
+
+    /// Get the header value
+    #[must_use]
+    pub fn value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for HmacAuthHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{HMAC_AUTH_SCHEME} id={},ts={},sig={},nonce={}",
+            self.client_id, self.timestamp, self.signature, self.nonce
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values from the signature credentials examples of the API documentation
+    const CLIENT_ID: &str = "AMANDA";
+    const CLIENT_SECRET: &str = "AMANDASECRECT";
+    const TIMESTAMP: u64 = 1_576_074_319_000;
+    const NONCE: &str = "1iqt2wls";
+
+    #[test]
+    fn test_client_signature_doc_vector() {
+        assert_eq!(
+            client_signature(CLIENT_SECRET, TIMESTAMP, NONCE, ""),
+            "56590594f97921b09b18f166befe0d1319b198bbcdad7ca73382de2f88fe9aa1"
+        );
+    }
+
+    #[test]
+    fn test_request_signature_doc_vector() {
+        let uri = "/api/v2/private/get_account_summary?currency=BTC";
+        let expected = "9bfbc51a2bc372d72cc396cf1a213dc78d42eb74cb7dc272351833ad0de276ab";
+        assert_eq!(
+            request_signature(CLIENT_SECRET, TIMESTAMP, NONCE, "GET", uri, ""),
+            expected
+        );
+        assert_eq!(
+            request_signature(CLIENT_SECRET, TIMESTAMP, NONCE, "get", uri, ""),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_request_signature_with_body() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"private/buy","params":{"instrument_name":"BTC-PERPETUAL","amount":10}}"#;
+        assert_eq!(
+            request_signature(
+                CLIENT_SECRET,
+                TIMESTAMP,
+                NONCE,
+                "POST",
+                "/api/v2/private/buy",
+                body
+            ),
+            "43ebdabfb8e42513ff1e7a5f90f476e3eda8ba2f2893e46cf4d6d33b2fc5ecd9"
+        );
+    }
+
+    #[test]
+    fn test_hmac_auth_header() {
+        let header = HmacAuthBuilder::new(CLIENT_ID, CLIENT_SECRET).sign_with(
+            TIMESTAMP,
+            NONCE,
+            "GET",
+            "/api/v2/private/get_account_summary?currency=BTC",
+            "",
+        );
+        assert_eq!(header.name(), "Authorization");
+        assert_eq!(
+            header.value(),
+            "deri-hmac-sha256 id=AMANDA,ts=1576074319000,\
+             sig=9bfbc51a2bc372d72cc396cf1a213dc78d42eb74cb7dc272351833ad0de276ab,nonce=1iqt2wls"
+        );
+    }
+
+    #[test]
+    fn test_sign_uses_fresh_nonce() {
+        let builder = HmacAuthBuilder::from_config(&DeribitConfig::new(
+            CLIENT_ID.to_string(),
+            CLIENT_SECRET.to_string(),
+        ));
+        let first = builder.sign("GET", "/api/v2/private/get_positions", "");
+        let second = builder.sign("GET", "/api/v2/private/get_positions", "");
+        assert_ne!(first.nonce, second.nonce);
+        assert_eq!(first.nonce.len(), SIGNATURE_NONCE_LENGTH);
+        assert!(
+            first
+                .nonce
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        );
+        assert!(!format!("{builder:?}").contains(CLIENT_SECRET));
+    }
+}
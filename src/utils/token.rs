@@ -0,0 +1,599 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Access token lifecycle
+//!
+//! [`TokenManager`] performs no I/O and reads no clock: callers record every
+//! [`AuthResponse`] with the time it was received, and ask
+//! [`TokenManager::next_request`] with the current time which `public/auth`
+//! request to send next, if any. Tokens are refreshed
+//! [`TOKEN_REFRESH_BUFFER_SEC`] before they expire, and the manager falls
+//! back to a full authentication once the refresh token has expired too.
+//!
+//! Sessions derived with `public/exchange_token` or `public/fork_token`
+//! remember how they were derived. Their credentials belong to the parent
+//! account, so once their refresh token expires they request nothing: the
+//! caller authenticates [`TokenManager::parent`] and replays
+//! [`TokenManager::derivations`].
+
+use crate::constants::{
+    ACCESS_TOKEN_EXPIRATION_SEC, REFRESH_TOKEN_EXPIRATION_SEC, TOKEN_REFRESH_BUFFER_SEC,
+};
+use crate::model::config::DeribitConfig;
+use crate::model::request::AuthRequest;
+use crate::model::response::AuthResponse;
+use crate::model::rpc::params::{ExchangeTokenParams, ForkTokenParams};
+use crate::model::scope::{AccessScope, ScopeError};
+use crate::utils::signature::generate_signature_nonce;
+use std::fmt;
+
+/// State of the current access token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStatus {
+    /// No token was recorded or it was invalidated
+    Missing,
+    /// Token is valid and not due for refresh
+    Valid,
+    /// Token is valid but within the refresh buffer
+    RefreshDue,
+    /// Access token expired, the refresh token is still valid
+    Expired,
+    /// Both tokens expired
+    RefreshExpired,
+}
+
+impl TokenStatus {
+    /// Returns the status as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Valid => "valid",
+            Self::RefreshDue => "refresh_due",
+            Self::Expired => "expired",
+            Self::RefreshExpired => "refresh_expired",
+        }
+    }
+}
+
+impl fmt::Display for TokenStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Token issued by `public/auth`, `public/exchange_token` or `public/fork_token`
+#[derive(Clone, PartialEq, Eq)]
+pub struct IssuedToken {
+    /// Access token
+    pub access_token: String,
+    /// Refresh token
+    pub refresh_token: String,
+    /// Granted scope
+    pub scope: String,
+    /// Time the response was received in milliseconds
+    pub issued_at_ms: u64,
+    /// Time the access token expires in milliseconds
+    pub expires_at_ms: u64,
+    /// Time the refresh token expires in milliseconds
+    pub refresh_expires_at_ms: u64,
+}
+
+impl IssuedToken {
+    /// Record a response received at `now_ms`
+    ///
+    /// A missing or non-positive `expires_in` falls back to
+    /// [`ACCESS_TOKEN_EXPIRATION_SEC`].
+    #[must_use]
+    pub fn from_response(response: &AuthResponse, now_ms: u64) -> Self {
+        let expires_in = u64::try_from(response.expires_in)
+            .ok()
+            .filter(|seconds| *seconds > 0)
+            .unwrap_or(ACCESS_TOKEN_EXPIRATION_SEC);
+        Self {
+            access_token: response.access_token.clone(),
+            refresh_token: response.refresh_token.clone(),
+            scope: response.scope.clone(),
+            issued_at_ms: now_ms,
+            expires_at_ms: now_ms.saturating_add(expires_in * 1000),
+            refresh_expires_at_ms: now_ms.saturating_add(REFRESH_TOKEN_EXPIRATION_SEC * 1000),
+        }
+    }
+
+    /// Parse the granted scope
+    pub fn access_scope(&self) -> Result<AccessScope, ScopeError> {
+        self.scope.parse()
+    }
+
+    /// Get the session name from a `session:<name>` scope
+    #[must_use]
+    pub fn session_name(&self) -> Option<&str> {
+        self.scope
+            .split_whitespace()
+            .find_map(|scope| scope.strip_prefix("session:"))
+    }
+}
+
+impl fmt::Debug for IssuedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssuedToken")
+            .field("access_token", &"***")
+            .field("refresh_token", &"***")
+            .field("scope", &self.scope)
+            .field("issued_at_ms", &self.issued_at_ms)
+            .field("expires_at_ms", &self.expires_at_ms)
+            .field("refresh_expires_at_ms", &self.refresh_expires_at_ms)
+            .finish()
+    }
+}
+
+/// Step deriving a session from the authenticated account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenDerivation {
+    /// Subaccount session from `public/exchange_token`
+    Exchange {
+        /// Subaccount switched to
+        subject_id: u64,
+        /// Requested session scope
+        scope: Option<String>,
+    },
+    /// Named session from `public/fork_token`
+    Fork {
+        /// Name of the session
+        session_name: String,
+    },
+}
+
+/// Sans-IO tracker of the access token of one session
+#[derive(Clone)]
+pub struct TokenManager {
+    client_id: String,
+    client_secret: String,
+    use_signature: bool,
+    scope: Option<String>,
+    refresh_buffer_ms: u64,
+    token: Option<IssuedToken>,
+    derivations: Vec<TokenDerivation>,
+}
+
+impl TokenManager {
+    /// Create a manager authenticating with `client_credentials`
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            use_signature: false,
+            scope: None,
+            refresh_buffer_ms: TOKEN_REFRESH_BUFFER_SEC * 1000,
+            token: None,
+            derivations: Vec::new(),
+        }
+    }
+
+    /// Create a manager from the configured credentials
+    #[must_use]
+    pub fn from_config(config: &DeribitConfig) -> Self {
+        Self::new(config.client_id.as_str(), config.client_secret.as_str())
+    }
+
+    /// Authenticate with `client_signature` so the secret is never sent
+    #[must_use]
+    pub fn with_signature(mut self) -> Self {
+        self.use_signature = true;
+        self
+    }
+
+    /// Request a scope on authentication
+    #[must_use]
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Set how long before expiry the token is refreshed
+    #[must_use]
+    pub fn with_refresh_buffer_ms(mut self, refresh_buffer_ms: u64) -> Self {
+        self.refresh_buffer_ms = refresh_buffer_ms;
+        self
+    }
+
+    /// Get the client id
+    #[must_use]
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Get the current token, even if expired
+    #[must_use]
+    pub fn token(&self) -> Option<&IssuedToken> {
+        self.token.as_ref()
+    }
+
+    /// Get the steps deriving this session from the authenticated account
+    #[must_use]
+    pub fn derivations(&self) -> &[TokenDerivation] {
+        &self.derivations
+    }
+
+    /// Check if the session was exchanged or forked
+    #[must_use]
+    pub fn is_derived(&self) -> bool {
+        !self.derivations.is_empty()
+    }
+
+    /// Create a manager for the account this session was derived from
+    ///
+    /// The new manager has the same credentials and settings, and no token.
+    #[must_use]
+    pub fn parent(&self) -> Self {
+        Self {
+            token: None,
+            derivations: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Record a token response received at `now_ms`
+    pub fn record(&mut self, response: &AuthResponse, now_ms: u64) {
+        self.token = Some(IssuedToken::from_response(response, now_ms));
+    }
+
+    /// Forget the current token, e.g. after logout or an invalid token error
+    pub fn invalidate(&mut self) {
+        self.token = None;
+    }
+
+    /// Get the status of the token at `now_ms`
+    #[must_use]
+    pub fn status(&self, now_ms: u64) -> TokenStatus {
+        match &self.token {
+            None => TokenStatus::Missing,
+            Some(token) if now_ms >= token.refresh_expires_at_ms => TokenStatus::RefreshExpired,
+            Some(token) if now_ms >= token.expires_at_ms => TokenStatus::Expired,
+            Some(token) if now_ms >= self.refresh_due_ms(token) => TokenStatus::RefreshDue,
+            Some(_) => TokenStatus::Valid,
+        }
+    }
+
+    /// Get the access token if it has not expired at `now_ms`
+    #[must_use]
+    pub fn access_token(&self, now_ms: u64) -> Option<&str> {
+        match self.status(now_ms) {
+            TokenStatus::Valid | TokenStatus::RefreshDue => {
+                self.token.as_ref().map(|token| token.access_token.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the time a refresh should be scheduled at, in milliseconds
+    #[must_use]
+    pub fn refresh_at_ms(&self) -> Option<u64> {
+        self.token.as_ref().map(|token| self.refresh_due_ms(token))
+    }
+
+    /// Get the `public/auth` request to send at `now_ms`, if any
+    ///
+    /// Returns a `refresh_token` grant while the refresh token is valid and a
+    /// full authentication when there is no usable token. A derived session
+    /// without a usable token returns `None`; re-authenticate its
+    /// [`TokenManager::parent`] and replay [`TokenManager::derivations`].
+    #[must_use]
+    pub fn next_request(&self, now_ms: u64) -> Option<AuthRequest> {
+        match self.status(now_ms) {
+            TokenStatus::Valid => None,
+            TokenStatus::RefreshDue | TokenStatus::Expired => self.refresh_request(),
+            TokenStatus::Missing | TokenStatus::RefreshExpired if self.is_derived() => None,
+            TokenStatus::Missing | TokenStatus::RefreshExpired => {
+                Some(self.authenticate_request(now_ms))
+            }
+        }
+    }
+
+    /// Build a full authentication request signed at `now_ms` if applicable
+    ///
+    /// Always authenticates the account owning the credentials, never a
+    /// derived session.
+    #[must_use]
+    pub fn authenticate_request(&self, now_ms: u64) -> AuthRequest {
+        let request = if self.use_signature {
+            AuthRequest::client_signature(
+                self.client_id.clone(),
+                &self.client_secret,
+                now_ms,
+                generate_signature_nonce(),
+                None,
+            )
+        } else {
+            AuthRequest::client_credentials(self.client_id.clone(), self.client_secret.clone())
+        };
+        match &self.scope {
+            Some(scope) => request.with_scope(scope.as_str()),
+            None => request,
+        }
+    }
+
+    /// Build a `refresh_token` request for the current token
+    #[must_use]
+    pub fn refresh_request(&self) -> Option<AuthRequest> {
+        let token = self.token.as_ref()?;
+        let client_secret = if self.use_signature {
+            String::new()
+        } else {
+            self.client_secret.clone()
+        };
+        Some(AuthRequest::refresh_token(
+            self.client_id.clone(),
+            client_secret,
+            token.refresh_token.clone(),
+        ))
+    }
+
+    /// Build `public/exchange_token` parameters to switch to a subaccount
+    ///
+    /// Pass the response to [`TokenManager::record_exchange`], it replaces
+    /// the current session.
+    #[must_use]
+    pub fn exchange_token(
+        &self,
+        subject_id: u64,
+        scope: Option<String>,
+    ) -> Option<ExchangeTokenParams> {
+        Some(ExchangeTokenParams {
+            refresh_token: self.token.as_ref()?.refresh_token.clone(),
+            subject_id,
+            scope,
+        })
+    }
+
+    /// Build `public/fork_token` parameters to open a named session
+    ///
+    /// Pass the response to [`TokenManager::fork`] to track the new session.
+    #[must_use]
+    pub fn fork_token(&self, session_name: impl Into<String>) -> Option<ForkTokenParams> {
+        Some(ForkTokenParams {
+            refresh_token: self.token.as_ref()?.refresh_token.clone(),
+            session_name: session_name.into(),
+        })
+    }
+
+    /// Record the `public/exchange_token` response received at `now_ms`
+    pub fn record_exchange(
+        &mut self,
+        params: &ExchangeTokenParams,
+        response: &AuthResponse,
+        now_ms: u64,
+    ) {
+        self.record(response, now_ms);
+        self.derivations.push(TokenDerivation::Exchange {
+            subject_id: params.subject_id,
+            scope: params.scope.clone(),
+        });
+    }
+
+    /// Create a manager for a session forked at `now_ms`
+    #[must_use]
+    pub fn fork(&self, params: &ForkTokenParams, response: &AuthResponse, now_ms: u64) -> Self {
+        let mut forked = self.clone();
+        forked.record(response, now_ms);
+        forked.derivations.push(TokenDerivation::Fork {
+            session_name: params.session_name.clone(),
+        });
+        forked
+    }
+
+    fn refresh_due_ms(&self, token: &IssuedToken) -> u64 {
+        token
+            .expires_at_ms
+            .saturating_sub(self.refresh_buffer_ms)
+            .max(token.issued_at_ms)
+    }
+}
+
+impl fmt::Debug for TokenManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenManager")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"***")
+            .field("use_signature", &self.use_signature)
+            .field("scope", &self.scope)
+            .field("refresh_buffer_ms", &self.refresh_buffer_ms)
+            .field("token", &self.token)
+            .field("derivations", &self.derivations)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_792_317_600_000;
+
+    fn response(access_token: &str, refresh_token: &str, scope: &str) -> AuthResponse {
+        AuthResponse {
+            access_token: access_token.to_string(),
+            token_type: "bearer".to_string(),
+            expires_in: 900,
+            refresh_token: refresh_token.to_string(),
+            scope: scope.to_string(),
+            enabled_features: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_without_token() {
+        let manager = TokenManager::new("client", "secret").with_scope("session:bot");
+        assert_eq!(manager.status(NOW), TokenStatus::Missing);
+        assert_eq!(manager.access_token(NOW), None);
+        assert_eq!(manager.refresh_at_ms(), None);
+
+        let request = manager.next_request(NOW).unwrap();
+        assert_eq!(request.grant_type, "client_credentials");
+        assert_eq!(request.client_secret, "secret");
+        assert_eq!(request.scope.as_deref(), Some("session:bot"));
+    }
+
+    #[test]
+    fn test_refresh_schedule() {
+        let mut manager = TokenManager::new("client", "secret");
+        manager.record(&response("a1", "r1", "session:bot trade:read"), NOW);
+        let token = manager.token().unwrap();
+        assert_eq!(token.expires_at_ms, NOW + 900_000);
+        assert_eq!(token.session_name(), Some("bot"));
+        let scope = token.access_scope().unwrap();
+        assert!(scope.permits("private/get_positions"));
+        assert!(!scope.permits("private/buy"));
+        assert_eq!(manager.refresh_at_ms(), Some(NOW + 600_000));
+
+        assert_eq!(manager.status(NOW + 599_999), TokenStatus::Valid);
+        assert!(manager.next_request(NOW + 599_999).is_none());
+        assert_eq!(manager.access_token(NOW + 599_999), Some("a1"));
+
+        assert_eq!(manager.status(NOW + 600_000), TokenStatus::RefreshDue);
+        assert_eq!(manager.access_token(NOW + 600_000), Some("a1"));
+        let request = manager.next_request(NOW + 600_000).unwrap();
+        assert_eq!(request.grant_type, "refresh_token");
+        assert_eq!(request.refresh_token.as_deref(), Some("r1"));
+
+        assert_eq!(manager.status(NOW + 900_000), TokenStatus::Expired);
+        assert_eq!(manager.access_token(NOW + 900_000), None);
+        assert_eq!(
+            manager.next_request(NOW + 900_000).unwrap().grant_type,
+            "refresh_token"
+        );
+
+        manager.record(&response("a2", "r2", ""), NOW + 900_000);
+        assert_eq!(manager.access_token(NOW + 900_001), Some("a2"));
+    }
+
+    #[test]
+    fn test_reauthenticates_after_refresh_expiry() {
+        let mut manager = TokenManager::new("client", "secret").with_signature();
+        manager.record(&response("a1", "r1", ""), NOW);
+        let later = NOW + REFRESH_TOKEN_EXPIRATION_SEC * 1000;
+        assert_eq!(manager.status(later), TokenStatus::RefreshExpired);
+        let request = manager.next_request(later).unwrap();
+        assert_eq!(request.grant_type, "client_signature");
+        assert_eq!(request.timestamp, Some(later));
+        assert!(request.client_secret.is_empty());
+
+        let refresh = manager.refresh_request().unwrap();
+        assert!(refresh.client_secret.is_empty());
+
+        manager.invalidate();
+        assert_eq!(manager.status(NOW), TokenStatus::Missing);
+    }
+
+    #[test]
+    fn test_default_and_short_expiry() {
+        let mut manager = TokenManager::new("client", "secret");
+        let mut unknown = response("a1", "r1", "");
+        unknown.expires_in = 0;
+        manager.record(&unknown, NOW);
+        assert_eq!(
+            manager.token().unwrap().expires_at_ms,
+            NOW + ACCESS_TOKEN_EXPIRATION_SEC * 1000
+        );
+
+        let mut short = response("a1", "r1", "");
+        short.expires_in = 60;
+        manager.record(&short, NOW);
+        assert_eq!(manager.refresh_at_ms(), Some(NOW));
+        assert_eq!(manager.status(NOW), TokenStatus::RefreshDue);
+
+        let manager = manager.with_refresh_buffer_ms(10_000);
+        assert_eq!(manager.refresh_at_ms(), Some(NOW + 50_000));
+    }
+
+    #[test]
+    fn test_exchange_and_fork() {
+        let mut manager = TokenManager::from_config(&DeribitConfig::new(
+            "client".to_string(),
+            "secret".to_string(),
+        ));
+        assert!(manager.exchange_token(7, None).is_none());
+        assert!(manager.fork_token("bot").is_none());
+
+        manager.record(&response("a1", "r1", ""), NOW);
+        let exchange = manager
+            .exchange_token(7, Some("session:sub".to_string()))
+            .unwrap();
+        assert_eq!(exchange.refresh_token, "r1");
+        assert_eq!(exchange.subject_id, 7);
+
+        let fork = manager.fork_token("bot").unwrap();
+        assert_eq!(fork.refresh_token, "r1");
+        assert_eq!(fork.session_name, "bot");
+
+        let forked = manager.fork(&fork, &response("a2", "r2", "session:bot"), NOW + 1_000);
+        assert_eq!(forked.access_token(NOW + 1_000), Some("a2"));
+        assert_eq!(forked.token().unwrap().session_name(), Some("bot"));
+        assert_eq!(
+            forked.derivations(),
+            [TokenDerivation::Fork {
+                session_name: "bot".to_string()
+            }]
+        );
+        assert_eq!(manager.access_token(NOW + 1_000), Some("a1"));
+        assert!(!manager.is_derived());
+        assert!(!format!("{forked:?}").contains("\"secret\""));
+        assert!(!format!("{forked:?}").contains("a2"));
+    }
+
+    #[test]
+    fn test_derived_session_does_not_fall_back_to_parent() {
+        let mut manager = TokenManager::new("client", "secret");
+        manager.record(&response("a1", "r1", ""), NOW);
+        let exchange = manager
+            .exchange_token(7, Some("session:sub".to_string()))
+            .unwrap();
+        manager.record_exchange(&exchange, &response("a2", "r2", "session:sub"), NOW);
+        let fork = manager.fork_token("bot").unwrap();
+        assert_eq!(fork.refresh_token, "r2");
+        let forked = manager.fork(&fork, &response("a3", "r3", "session:bot"), NOW);
+
+        let later = NOW + REFRESH_TOKEN_EXPIRATION_SEC * 1000;
+        assert_eq!(forked.status(later), TokenStatus::RefreshExpired);
+        assert!(forked.next_request(later).is_none());
+        assert_eq!(
+            forked
+                .next_request(NOW + 900_000)
+                .unwrap()
+                .refresh_token
+                .as_deref(),
+            Some("r3")
+        );
+
+        let mut parent = forked.parent();
+        assert!(!parent.is_derived());
+        assert_eq!(
+            parent.next_request(later).unwrap().grant_type,
+            "client_credentials"
+        );
+        parent.record(&response("a4", "r4", ""), later);
+        let mut rederived = parent;
+        for step in forked.derivations() {
+            rederived = match step {
+                TokenDerivation::Exchange { subject_id, scope } => {
+                    let params = rederived
+                        .exchange_token(*subject_id, scope.clone())
+                        .unwrap();
+                    assert_eq!(params.refresh_token, "r4");
+                    rederived.record_exchange(&params, &response("a5", "r5", ""), later);
+                    rederived
+                }
+                TokenDerivation::Fork { session_name } => {
+                    let params = rederived.fork_token(session_name.as_str()).unwrap();
+                    assert_eq!(params.refresh_token, "r5");
+                    rederived.fork(&params, &response("a6", "r6", "session:bot"), later)
+                }
+            };
+        }
+        assert_eq!(rederived.derivations(), forked.derivations());
+        assert_eq!(rederived.access_token(later), Some("a6"));
+    }
+}
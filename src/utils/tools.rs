@@ -14,6 +14,7 @@
 //! - String escaping and parsing utilities
 
 use crate::model::order::{OrderSide, OrderType, TimeInForce};
+use crate::utils::ids::next_monotonic_id;
 use base64::prelude::*;
 use chrono::{DateTime, Utc};
 use rand::{RngExt, rng};
@@ -80,9 +81,12 @@ pub fn validate_checksum(message: &str) -> bool {
 }
 
 /// Generate a unique client order ID
+///
+/// Uses a process-wide monotonic counter, so ids never collide even when
+/// created within the same millisecond.
 #[allow(dead_code)]
 pub fn generate_client_order_id(prefix: &str) -> String {
-    format!("{}_{}", prefix, generate_timestamp())
+    format!("{}_{}", prefix, next_monotonic_id())
 }
 
 /// Convert price to FIX decimal format
@@ -118,9 +122,7 @@ pub fn unescape_fix_value(value: &str) -> String {
 /// Generate a unique request ID for JSON-RPC calls
 #[allow(dead_code)]
 pub fn generate_request_id(prefix: &str) -> String {
-    let mut rng = rng();
-    let random_part: u32 = rng.random();
-    format!("{prefix}_{random_part}")
+    format!("{prefix}_{}", next_monotonic_id())
 }
 
 /// Convert OrderSide to FIX Side field
@@ -337,6 +339,13 @@ mod tests {
         assert_ne!(order_id1, order_id2);
     }
 
+    #[test]
+    fn test_generate_client_order_id_same_millisecond() {
+        let ids: Vec<String> = (0..100).map(|_| generate_client_order_id("OMS")).collect();
+        let unique: std::collections::HashSet<&String> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+    }
+
     #[test]
     fn test_format_price() {
         assert_eq!(format_price(123.456789, 2), "123.46");